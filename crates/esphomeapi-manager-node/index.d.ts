@@ -1,7 +1,158 @@
 /* auto-generated by NAPI-RS */
 /* eslint-disable */
+export declare class AlarmControlPanel {
+  key: number
+  deviceId: number
+  name: string
+  kind: EntityKind.AlarmControlPanel
+  get state(): AlarmControlPanelEntityState
+  get alarmState(): AlarmControlPanelState
+  /** Register a callback that is called whenever the alarm control panel state changes. */
+  onStateChange(callback: ((arg: AlarmControlPanelEntityState) => void)): void
+  disarm(code?: string | undefined | null): Promise<void>
+  armHome(code?: string | undefined | null): Promise<void>
+  armAway(code?: string | undefined | null): Promise<void>
+  armNight(code?: string | undefined | null): Promise<void>
+  armVacation(code?: string | undefined | null): Promise<void>
+  armCustomBypass(code?: string | undefined | null): Promise<void>
+  trigger(code?: string | undefined | null): Promise<void>
+}
+
+export declare class BinarySensor {
+  key: number
+  deviceId: number
+  name: string
+  kind: EntityKind.BinarySensor
+  get state(): BinarySensorState
+  get isOn(): boolean
+  /** Register a callback that is called whenever the binary sensor state changes. */
+  onStateChange(callback: ((arg: BinarySensorState) => void)): void
+}
+
+export declare class Button {
+  key: number
+  deviceId: number
+  name: string
+  kind: EntityKind.Button
+  press(): Promise<void>
+}
+
+export declare class Camera {
+  key: number
+  deviceId: number
+  name: string
+  kind: EntityKind.Camera
+  /** The most recent complete image (usually JPEG). */
+  get image(): Buffer
+  /** Register a callback that is called with every new complete image. */
+  onImage(callback: ((arg: Buffer) => void)): void
+  /** Ask the device for a single image from each of its cameras. */
+  requestImage(): Promise<void>
+  requestStream(): Promise<void>
+}
+
+export declare class Climate {
+  key: number
+  deviceId: number
+  name: string
+  kind: EntityKind.Climate
+  get state(): ClimateState
+  get mode(): ClimateMode
+  get currentTemperature(): number
+  get targetTemperature(): number
+  /** Register a callback that is called whenever the climate state changes. */
+  onStateChange(callback: ((arg: ClimateState) => void)): void
+  setMode(mode: ClimateMode): Promise<void>
+  setTargetTemperature(targetTemperature: number): Promise<void>
+  sendCommand(options: ClimateCommandOptions): Promise<void>
+}
+
+export declare class Cover {
+  key: number
+  deviceId: number
+  name: string
+  kind: EntityKind.Cover
+  get state(): CoverState
+  get position(): number
+  get tilt(): number
+  get currentOperation(): CoverOperation
+  /** Register a callback that is called whenever the cover state changes. */
+  onStateChange(callback: ((arg: CoverState) => void)): void
+  open(): Promise<void>
+  close(): Promise<void>
+  stop(): Promise<void>
+  /** Position from `0` (closed) to `1` (open). */
+  setPosition(position: number): Promise<void>
+  setTilt(tilt: number): Promise<void>
+}
+
+export declare class Date {
+  key: number
+  deviceId: number
+  name: string
+  kind: EntityKind.Date
+  get state(): DateState
+  /** Register a callback that is called whenever the date state changes. */
+  onStateChange(callback: ((arg: DateState) => void)): void
+  setDate(year: number, month: number, day: number): Promise<void>
+}
+
+export declare class DateTime {
+  key: number
+  deviceId: number
+  name: string
+  kind: EntityKind.DateTime
+  get state(): DateTimeState
+  /** Register a callback that is called whenever the datetime state changes. */
+  onStateChange(callback: ((arg: DateTimeState) => void)): void
+  setEpochSeconds(epochSeconds: number): Promise<void>
+}
+
+export declare class Event {
+  key: number
+  deviceId: number
+  name: string
+  kind: EntityKind.Event
+  get state(): EventState
+  get eventTypes(): Array<string>
+  /** Register a callback that is called whenever the event state changes. */
+  onStateChange(callback: ((arg: EventState) => void)): void
+}
+
+export declare class Fan {
+  key: number
+  deviceId: number
+  name: string
+  kind: EntityKind.Fan
+  get state(): FanState
+  get isOn(): boolean
+  /** Register a callback that is called whenever the fan state changes. */
+  onStateChange(callback: ((arg: FanState) => void)): void
+  turnOn(): Promise<void>
+  turnOff(): Promise<void>
+  toggle(): Promise<void>
+  setSpeedLevel(speedLevel: number): Promise<void>
+  setOscillating(oscillating: boolean): Promise<void>
+  setDirection(direction: FanDirection): Promise<void>
+  setPresetMode(presetMode: string): Promise<void>
+}
+
+export declare class Infrared {
+  key: number
+  deviceId: number
+  name: string
+  kind: EntityKind.Infrared
+  get canTransmit(): boolean
+  get canReceive(): boolean
+  /** Transmit raw timings: positive marks and negative spaces in microseconds. */
+  transmitRaw(carrierFrequency: number, repeatCount: number, timings: Array<number>): Promise<void>
+  /** Transmit a learned Pronto hex code (`0000 006D ...`). */
+  transmitPronto(code: string, repeatCount: number): Promise<void>
+}
+
 export declare class Light {
   key: number
+  deviceId: number
   name: string
   kind: EntityKind.Light
   get isOn(): boolean
@@ -26,6 +177,20 @@ export declare class Light {
   sendCommand(options: LightCommandOptions): Promise<void>
 }
 
+export declare class Lock {
+  key: number
+  deviceId: number
+  name: string
+  kind: EntityKind.Lock
+  get state(): LockEntityState
+  get lockState(): LockState
+  /** Register a callback that is called whenever the lock state changes. */
+  onStateChange(callback: ((arg: LockEntityState) => void)): void
+  lock(code?: string | undefined | null): Promise<void>
+  unlock(code?: string | undefined | null): Promise<void>
+  open(code?: string | undefined | null): Promise<void>
+}
+
 export declare class Manager {
   static connect(options: ConnectionOptions): Promise<Manager>
   getDeviceInfo(): DeviceInfo
@@ -62,6 +227,13 @@ export declare class Manager {
    * sending a new subscription request to the device.
    */
   onHomeAssistantActionRequest(callback: ((arg: HomeassistantActionRequest) => void)): Promise<void>
+  /**
+   * Answer an action request that set `wantsResponse`.
+   *
+   * Devices wait for this reply until they time out, so send it even when
+   * the action failed.
+   */
+  sendHomeAssistantActionResponse(response: HomeassistantActionResponse): Promise<void>
   /**
    * Subscribe to ESPHome logs.
    *
@@ -95,14 +267,96 @@ export declare class Manager {
   onDeviceDisconnect(): Promise<void>
   /**
    * Returns a Promise that resolves the next time the manager successfully
-   * reconnects after a device-initiated disconnect.
+   * reconnects after a device-initiated disconnect. Rejects once the manager
+   * gives up reconnecting.
    */
   onReconnect(): Promise<void>
+  /**
+   * Move the device to a freshly generated Noise PSK and reconnect with it.
+   *
+   * Resolves to the new base64 key once connected with it. Store the key:
+   * the device accepts no other connections from then on.
+   */
+  rotateNoisePsk(): Promise<string>
   disconnect(): Promise<void>
 }
 
+export declare class MediaPlayer {
+  key: number
+  deviceId: number
+  name: string
+  kind: EntityKind.MediaPlayer
+  get state(): MediaPlayerEntityState
+  get playerState(): MediaPlayerState
+  get volume(): number
+  get isMuted(): boolean
+  /** Register a callback that is called whenever the media player state changes. */
+  onStateChange(callback: ((arg: MediaPlayerEntityState) => void)): void
+  play(): Promise<void>
+  pause(): Promise<void>
+  stop(): Promise<void>
+  mute(): Promise<void>
+  unmute(): Promise<void>
+  /** Volume from `0` to `1`. */
+  setVolume(volume: number): Promise<void>
+  playMedia(mediaUrl: string, announcement?: boolean | undefined | null): Promise<void>
+}
+
+export declare class Number {
+  key: number
+  deviceId: number
+  name: string
+  kind: EntityKind.Number
+  get state(): NumberState
+  get value(): number | null
+  /** Register a callback that is called whenever the number state changes. */
+  onStateChange(callback: ((arg: NumberState) => void)): void
+  setValue(value: number): Promise<void>
+}
+
+export declare class Select {
+  key: number
+  deviceId: number
+  name: string
+  kind: EntityKind.Select
+  get state(): SelectState
+  get value(): string | null
+  get options(): Array<string>
+  /** Register a callback that is called whenever the select state changes. */
+  onStateChange(callback: ((arg: SelectState) => void)): void
+  selectOption(option: string): Promise<void>
+}
+
+export declare class Sensor {
+  key: number
+  deviceId: number
+  name: string
+  kind: EntityKind.Sensor
+  get state(): SensorState
+  get value(): number | null
+  get unitOfMeasurement(): string
+  /** Register a callback that is called whenever the sensor state changes. */
+  onStateChange(callback: ((arg: SensorState) => void)): void
+}
+
+export declare class Siren {
+  key: number
+  deviceId: number
+  name: string
+  kind: EntityKind.Siren
+  get state(): SirenState
+  get isOn(): boolean
+  get tones(): Array<string>
+  /** Register a callback that is called whenever the siren state changes. */
+  onStateChange(callback: ((arg: SirenState) => void)): void
+  turnOn(): Promise<void>
+  turnOff(): Promise<void>
+  sendCommand(options: SirenCommandOptions): Promise<void>
+}
+
 export declare class Switch {
   key: number
+  deviceId: number
   name: string
   kind: EntityKind.Switch
   get isOn(): boolean
@@ -118,6 +372,193 @@ export declare class Switch {
   setState(state: boolean): Promise<void>
 }
 
+export declare class Text {
+  key: number
+  deviceId: number
+  name: string
+  kind: EntityKind.Text
+  get state(): TextState
+  get value(): string | null
+  /** Register a callback that is called whenever the text state changes. */
+  onStateChange(callback: ((arg: TextState) => void)): void
+  setValue(value: string): Promise<void>
+}
+
+export declare class TextSensor {
+  key: number
+  deviceId: number
+  name: string
+  kind: EntityKind.TextSensor
+  get state(): TextSensorState
+  get value(): string | null
+  /** Register a callback that is called whenever the text sensor state changes. */
+  onStateChange(callback: ((arg: TextSensorState) => void)): void
+}
+
+export declare class Time {
+  key: number
+  deviceId: number
+  name: string
+  kind: EntityKind.Time
+  get state(): TimeState
+  /** Register a callback that is called whenever the time state changes. */
+  onStateChange(callback: ((arg: TimeState) => void)): void
+  setTime(hour: number, minute: number, second: number): Promise<void>
+}
+
+export declare class Update {
+  key: number
+  deviceId: number
+  name: string
+  kind: EntityKind.Update
+  get state(): UpdateState
+  get hasUpdate(): boolean
+  /** Register a callback that is called whenever the update state changes. */
+  onStateChange(callback: ((arg: UpdateState) => void)): void
+  install(): Promise<void>
+  /** Ask the device to look for a newer version. */
+  check(): Promise<void>
+}
+
+export declare class Valve {
+  key: number
+  deviceId: number
+  name: string
+  kind: EntityKind.Valve
+  get state(): ValveState
+  get position(): number
+  get currentOperation(): ValveOperation
+  /** Register a callback that is called whenever the valve state changes. */
+  onStateChange(callback: ((arg: ValveState) => void)): void
+  open(): Promise<void>
+  close(): Promise<void>
+  stop(): Promise<void>
+  /** Position from `0` (closed) to `1` (open). */
+  setPosition(position: number): Promise<void>
+}
+
+export declare class WaterHeater {
+  key: number
+  deviceId: number
+  name: string
+  kind: EntityKind.WaterHeater
+  get state(): WaterHeaterState
+  get mode(): WaterHeaterMode
+  get currentTemperature(): number
+  get targetTemperature(): number
+  /** Register a callback that is called whenever the water heater state changes. */
+  onStateChange(callback: ((arg: WaterHeaterState) => void)): void
+  setMode(mode: WaterHeaterMode): Promise<void>
+  setTargetTemperature(targetTemperature: number): Promise<void>
+  sendCommand(options: WaterHeaterCommandOptions): Promise<void>
+}
+
+export interface AlarmControlPanelEntityState {
+  state: AlarmControlPanelState
+}
+
+export declare const enum AlarmControlPanelState {
+  Disarmed = 0,
+  ArmedHome = 1,
+  ArmedAway = 2,
+  ArmedNight = 3,
+  ArmedVacation = 4,
+  ArmedCustomBypass = 5,
+  Pending = 6,
+  Arming = 7,
+  Disarming = 8,
+  Triggered = 9
+}
+
+export interface AreaInfo {
+  areaId: number
+  name: string
+}
+
+export interface BinarySensorState {
+  isOn: boolean
+  missingState: boolean
+}
+
+export declare const enum ClimateAction {
+  Off = 0,
+  Cooling = 1,
+  Heating = 2,
+  Idle = 3,
+  Drying = 4,
+  Fan = 5
+}
+
+export interface ClimateCommandOptions {
+  mode?: ClimateMode
+  targetTemperature?: number
+  targetTemperatureLow?: number
+  targetTemperatureHigh?: number
+  fanMode?: ClimateFanMode
+  swingMode?: ClimateSwingMode
+  customFanMode?: string
+  preset?: ClimatePreset
+  customPreset?: string
+  targetHumidity?: number
+}
+
+export declare const enum ClimateFanMode {
+  On = 0,
+  Off = 1,
+  Auto = 2,
+  Low = 3,
+  Medium = 4,
+  High = 5,
+  Middle = 6,
+  Focus = 7,
+  Diffuse = 8,
+  Quiet = 9
+}
+
+export declare const enum ClimateMode {
+  Off = 0,
+  HeatCool = 1,
+  Cool = 2,
+  Heat = 3,
+  FanOnly = 4,
+  Dry = 5,
+  Auto = 6
+}
+
+export declare const enum ClimatePreset {
+  None = 0,
+  Home = 1,
+  Away = 2,
+  Boost = 3,
+  Comfort = 4,
+  Eco = 5,
+  Sleep = 6,
+  Activity = 7
+}
+
+export interface ClimateState {
+  mode: ClimateMode
+  action: ClimateAction
+  currentTemperature: number
+  targetTemperature: number
+  targetTemperatureLow: number
+  targetTemperatureHigh: number
+  fanMode: ClimateFanMode
+  swingMode: ClimateSwingMode
+  customFanMode: string
+  preset: ClimatePreset
+  customPreset: string
+  currentHumidity: number
+  targetHumidity: number
+}
+
+export declare const enum ClimateSwingMode {
+  Off = 0,
+  Both = 1,
+  Vertical = 2,
+  Horizontal = 3
+}
+
 export declare const enum ColorMode {
   Unknown = 0,
   OnOff = 1,
@@ -140,6 +581,40 @@ export interface ConnectionOptions {
   psk?: string
   clientInfo?: string
   keepAliveDuration?: number
+  /** Seconds allowed for dialing, the handshake and the hello exchange. */
+  connectTimeout?: number
+  /** Seconds to wait for a response to a single request. */
+  requestTimeout?: number
+  reconnect?: ReconnectOptions
+  /**
+   * Timezone sent to devices asking for the time, either a POSIX TZ string
+   * or an IANA zone like `Europe/Berlin`.
+   */
+  timezone?: string
+}
+
+export declare const enum CoverOperation {
+  Idle = 0,
+  Opening = 1,
+  Closing = 2
+}
+
+export interface CoverState {
+  position: number
+  tilt: number
+  currentOperation: CoverOperation
+}
+
+export interface DateState {
+  year: number
+  month: number
+  day: number
+  missingState: boolean
+}
+
+export interface DateTimeState {
+  epochSeconds: number
+  missingState: boolean
 }
 
 export interface DeviceInfo {
@@ -160,18 +635,70 @@ export interface DeviceInfo {
   legacyBluetoothProxyVersion: number
   bluetoothProxyFeatureFlags: number
   suggestedArea: string
+  bluetoothMacAddress: string
+  apiEncryptionSupported: boolean
+  /** Sub-devices whose entities are exposed through this node. */
+  devices: Array<SubDeviceInfo>
+  areas: Array<AreaInfo>
+  /** Area of the node itself. */
+  area?: AreaInfo
+  zwaveProxyFeatureFlags: number
+  zwaveHomeId: number
 }
 
 export declare function discover(seconds: number): Promise<Array<ServiceInfo>>
 
 export type Entity =
-  Light | Switch
+  AlarmControlPanel | BinarySensor | Button | Camera | Climate | Cover | Date | DateTime | Event | Fan | Infrared | Light | Lock | MediaPlayer | Number | Select | Sensor | Siren | Switch | Text | TextSensor | Time | Update | Valve | WaterHeater
 
 export declare const enum EntityKind {
+  AlarmControlPanel = 'AlarmControlPanel',
+  BinarySensor = 'BinarySensor',
+  Button = 'Button',
+  Camera = 'Camera',
+  Climate = 'Climate',
+  Cover = 'Cover',
+  Date = 'Date',
+  DateTime = 'DateTime',
+  Event = 'Event',
+  Fan = 'Fan',
+  Infrared = 'Infrared',
   Light = 'Light',
-  Switch = 'Switch'
+  Lock = 'Lock',
+  MediaPlayer = 'MediaPlayer',
+  Number = 'Number',
+  Select = 'Select',
+  Sensor = 'Sensor',
+  Siren = 'Siren',
+  Switch = 'Switch',
+  Text = 'Text',
+  TextSensor = 'TextSensor',
+  Time = 'Time',
+  Update = 'Update',
+  Valve = 'Valve',
+  WaterHeater = 'WaterHeater'
+}
+
+export interface EventState {
+  eventType: string
 }
 
+export declare const enum FanDirection {
+  Forward = 0,
+  Reverse = 1
+}
+
+export interface FanState {
+  isOn: boolean
+  oscillating: boolean
+  speedLevel: number
+  direction: FanDirection
+  presetMode: string
+}
+
+/** Generate a random base64-encoded Noise PSK. */
+export declare function generateNoisePsk(): string
+
 export interface HomeassistantActionRequest {
   service: string
   isEvent: boolean
@@ -183,6 +710,16 @@ export interface HomeassistantActionRequest {
   responseTemplate: string
 }
 
+/** Reply to an action request that set `wantsResponse`. */
+export interface HomeassistantActionResponse {
+  /** The `callId` of the request being answered. */
+  callId: number
+  success: boolean
+  errorMessage?: string
+  /** JSON result of the action. */
+  responseData?: Buffer
+}
+
 /**
  * A Home Assistant state event received from the ESPHome device.
  *
@@ -241,6 +778,19 @@ export interface LightState {
   effect: string
 }
 
+export interface LockEntityState {
+  state: LockState
+}
+
+export declare const enum LockState {
+  None = 0,
+  Locked = 1,
+  Unlocked = 2,
+  Jammed = 3,
+  Locking = 4,
+  Unlocking = 5
+}
+
 export interface LogEvent {
   level: LogLevel
   message: string
@@ -257,12 +807,154 @@ export declare const enum LogLevel {
   VeryVerbose = 7
 }
 
+export interface MediaPlayerEntityState {
+  state: MediaPlayerState
+  volume: number
+  muted: boolean
+}
+
+export declare const enum MediaPlayerState {
+  None = 0,
+  Idle = 1,
+  Playing = 2,
+  Paused = 3,
+  Announcing = 4,
+  Off = 5,
+  On = 6
+}
+
+export interface NumberState {
+  state: number
+  missingState: boolean
+}
+
+/** Automatic reconnect behaviour after the device drops the connection. */
+export interface ReconnectOptions {
+  enabled?: boolean
+  /** Seconds before the first attempt; doubles after each failure. */
+  initialDelay?: number
+  /** Upper bound in seconds for the delay between attempts. */
+  maxDelay?: number
+  /** Give up after this many failed attempts. Retries forever when unset. */
+  maxAttempts?: number
+  /** Random spread applied to each delay, as a fraction (e.g. `0.2` for ±20%). */
+  jitter?: number
+}
+
+export interface SelectState {
+  state: string
+  missingState: boolean
+}
+
+export interface SensorState {
+  state: number
+  missingState: boolean
+}
+
 export interface ServiceInfo {
   tyDomain: string
   subDomain?: string
   fullname: string
   server: string
   friendlyName?: string
+  /**
+   * IPv4 and IPv6 addresses; link-local IPv6 ones carry their interface,
+   * e.g. `fe80::1%2`
+   */
   addresses: Array<string>
+  ipv4Addresses: Array<string>
+  ipv6Addresses: Array<string>
   port: number
 }
+
+export interface SirenCommandOptions {
+  state?: boolean
+  /** One of the tones the siren lists. */
+  tone?: string
+  /** Volume from `0` to `1`. */
+  volume?: number
+  /** Seconds to sound for. */
+  duration?: number
+}
+
+export interface SirenState {
+  state: boolean
+}
+
+export interface SubDeviceInfo {
+  deviceId: number
+  name: string
+  /** 0 when the sub-device has no area. */
+  areaId: number
+}
+
+export interface TextSensorState {
+  state: string
+  missingState: boolean
+}
+
+export interface TextState {
+  state: string
+  missingState: boolean
+}
+
+export interface TimeState {
+  hour: number
+  minute: number
+  second: number
+  missingState: boolean
+}
+
+export interface UpdateState {
+  missingState: boolean
+  inProgress: boolean
+  hasProgress: boolean
+  progress: number
+  currentVersion: string
+  latestVersion: string
+  title: string
+  releaseSummary: string
+  releaseUrl: string
+}
+
+export declare const enum ValveOperation {
+  Idle = 0,
+  Opening = 1,
+  Closing = 2
+}
+
+export interface ValveState {
+  position: number
+  currentOperation: ValveOperation
+}
+
+export interface WaterHeaterCommandOptions {
+  mode?: WaterHeaterMode
+  targetTemperature?: number
+  targetTemperatureLow?: number
+  targetTemperatureHigh?: number
+  /** Left as last reported when unset. */
+  away?: boolean
+  /** Left as last reported when unset. */
+  on?: boolean
+}
+
+export declare const enum WaterHeaterMode {
+  Off = 0,
+  Eco = 1,
+  Electric = 2,
+  Performance = 3,
+  HighDemand = 4,
+  HeatPump = 5,
+  Gas = 6
+}
+
+export interface WaterHeaterState {
+  mode: WaterHeaterMode
+  currentTemperature: number
+  targetTemperature: number
+  targetTemperatureLow: number
+  targetTemperatureHigh: number
+  away: boolean
+  on: boolean
+}
@@ -577,12 +577,48 @@ if (!nativeBinding) {
 }
 
 module.exports = nativeBinding
+module.exports.AlarmControlPanel = nativeBinding.AlarmControlPanel
+module.exports.BinarySensor = nativeBinding.BinarySensor
+module.exports.Button = nativeBinding.Button
+module.exports.Camera = nativeBinding.Camera
+module.exports.Climate = nativeBinding.Climate
+module.exports.Cover = nativeBinding.Cover
+module.exports.Date = nativeBinding.Date
+module.exports.DateTime = nativeBinding.DateTime
+module.exports.Event = nativeBinding.Event
+module.exports.Fan = nativeBinding.Fan
+module.exports.Infrared = nativeBinding.Infrared
 module.exports.Light = nativeBinding.Light
+module.exports.Lock = nativeBinding.Lock
 module.exports.Manager = nativeBinding.Manager
+module.exports.MediaPlayer = nativeBinding.MediaPlayer
+module.exports.Number = nativeBinding.Number
+module.exports.Select = nativeBinding.Select
+module.exports.Sensor = nativeBinding.Sensor
+module.exports.Siren = nativeBinding.Siren
 module.exports.Switch = nativeBinding.Switch
+module.exports.Text = nativeBinding.Text
+module.exports.TextSensor = nativeBinding.TextSensor
+module.exports.Time = nativeBinding.Time
+module.exports.Update = nativeBinding.Update
+module.exports.Valve = nativeBinding.Valve
+module.exports.WaterHeater = nativeBinding.WaterHeater
+module.exports.AlarmControlPanelState = nativeBinding.AlarmControlPanelState
+module.exports.ClimateAction = nativeBinding.ClimateAction
+module.exports.ClimateFanMode = nativeBinding.ClimateFanMode
+module.exports.ClimateMode = nativeBinding.ClimateMode
+module.exports.ClimatePreset = nativeBinding.ClimatePreset
+module.exports.ClimateSwingMode = nativeBinding.ClimateSwingMode
 module.exports.ColorMode = nativeBinding.ColorMode
+module.exports.CoverOperation = nativeBinding.CoverOperation
 module.exports.discover = nativeBinding.discover
 module.exports.EntityKind = nativeBinding.EntityKind
+module.exports.FanDirection = nativeBinding.FanDirection
+module.exports.generateNoisePsk = nativeBinding.generateNoisePsk
 module.exports.HomeAssistantEventKind = nativeBinding.HomeAssistantEventKind
 module.exports.initLogger = nativeBinding.initLogger
+module.exports.LockState = nativeBinding.LockState
 module.exports.LogLevel = nativeBinding.LogLevel
+module.exports.MediaPlayerState = nativeBinding.MediaPlayerState
+module.exports.ValveOperation = nativeBinding.ValveOperation
+module.exports.WaterHeaterMode = nativeBinding.WaterHeaterMode
@@ -1,3 +1,5 @@
+use std::net::IpAddr;
+
 use esphomeapi_manager::ServiceInfo as RustServiceInfo;
 use napi_derive::napi;
 
@@ -10,19 +12,45 @@ pub struct ServiceInfo {
   pub fullname: String,
   pub server: String,
   pub friendly_name: Option<String>,
+  /// IPv4 and IPv6 addresses; link-local IPv6 ones carry their interface,
+  /// e.g. `fe80::1%2`
   pub addresses: Vec<String>,
+  pub ipv4_addresses: Vec<String>,
+  pub ipv6_addresses: Vec<String>,
   pub port: u16,
 }
 
 impl From<RustServiceInfo> for ServiceInfo {
   fn from(value: RustServiceInfo) -> Self {
+    let mut addresses: Vec<IpAddr> = value.addresses.iter().copied().collect();
+    addresses.sort();
+    let format = |address: &IpAddr| match address {
+      IpAddr::V6(v6) => match value.scope_ids.get(v6) {
+        Some(scope_id) => format!("{v6}%{scope_id}"),
+        None => v6.to_string(),
+      },
+      IpAddr::V4(v4) => v4.to_string(),
+    };
+    let ipv4_addresses = addresses
+      .iter()
+      .filter(|address| address.is_ipv4())
+      .map(format)
+      .collect();
+    let ipv6_addresses = addresses
+      .iter()
+      .filter(|address| address.is_ipv6())
+      .map(format)
+      .collect();
+
     Self {
       ty_domain: value.ty_domain,
       sub_domain: value.sub_domain,
       fullname: value.fullname,
       server: value.server,
       friendly_name: value.friendly_name,
-      addresses: value.addresses.iter().map(|a| a.to_string()).collect(),
+      addresses: addresses.iter().map(format).collect(),
+      ipv4_addresses,
+      ipv6_addresses,
       port: value.port,
     }
   }
@@ -30,7 +58,9 @@ impl From<RustServiceInfo> for ServiceInfo {
 
 #[napi]
 pub async fn discover(seconds: u32) -> Result<Vec<ServiceInfo>> {
-  let result = esphomeapi_manager::discover(seconds).await?;
+  let result = esphomeapi_manager::discover(seconds)
+    .await
+    .map_err(esphomeapi_manager::Error::from)?;
   Ok(
     result
       .iter()
@@ -0,0 +1,157 @@
+use esphomeapi_manager::entity::{
+  AlarmControlPanel as RustAlarmControlPanel,
+  AlarmControlPanelEntityState as RustAlarmControlPanelEntityState, BaseEntity as _,
+};
+use esphomeapi_manager::EntityState;
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi_derive::napi;
+
+use crate::entity::EntityKind;
+use crate::model::AlarmControlPanelState;
+
+#[napi(object)]
+pub struct AlarmControlPanelEntityState {
+  pub state: AlarmControlPanelState,
+}
+
+impl From<RustAlarmControlPanelEntityState> for AlarmControlPanelEntityState {
+  fn from(s: RustAlarmControlPanelEntityState) -> Self {
+    AlarmControlPanelEntityState {
+      state: s.state.into(),
+    }
+  }
+}
+
+#[napi]
+#[derive(Clone)]
+pub struct AlarmControlPanel {
+  inner: RustAlarmControlPanel,
+  pub key: u32,
+  pub device_id: u32,
+  pub name: String,
+  #[napi(ts_type = "EntityKind.AlarmControlPanel")]
+  pub kind: EntityKind,
+}
+
+impl AlarmControlPanel {
+  pub fn new(rust_alarm_control_panel: &RustAlarmControlPanel) -> Self {
+    AlarmControlPanel {
+      inner: rust_alarm_control_panel.clone(),
+      key: rust_alarm_control_panel.key(),
+      device_id: rust_alarm_control_panel.device_id(),
+      name: rust_alarm_control_panel.name().to_string(),
+      kind: EntityKind::AlarmControlPanel,
+    }
+  }
+}
+
+#[napi]
+impl AlarmControlPanel {
+  #[napi(getter)]
+  pub fn state(&self) -> Result<AlarmControlPanelEntityState> {
+    self
+      .inner
+      .get_state()
+      .map(Into::into)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi(getter)]
+  pub fn alarm_state(&self) -> Result<AlarmControlPanelState> {
+    self
+      .inner
+      .alarm_state()
+      .map(Into::into)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  /// Register a callback that is called whenever the alarm control panel state changes.
+  #[napi]
+  pub fn on_state_change(
+    &self,
+    callback: ThreadsafeFunction<
+      AlarmControlPanelEntityState,
+      (),
+      AlarmControlPanelEntityState,
+      Status,
+      false,
+      true,
+    >,
+  ) -> Result<()> {
+    let mut receiver = self.inner.state_receiver();
+
+    napi::bindgen_prelude::spawn(async move {
+      while receiver.changed().await.is_ok() {
+        if let Some(EntityState::AlarmControlPanel(s)) = receiver.borrow().clone() {
+          callback.call(s.into(), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+      }
+    });
+
+    Ok(())
+  }
+
+  #[napi]
+  pub async fn disarm(&self, code: Option<String>) -> Result<()> {
+    self
+      .inner
+      .disarm(code)
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi]
+  pub async fn arm_home(&self, code: Option<String>) -> Result<()> {
+    self
+      .inner
+      .arm_home(code)
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi]
+  pub async fn arm_away(&self, code: Option<String>) -> Result<()> {
+    self
+      .inner
+      .arm_away(code)
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi]
+  pub async fn arm_night(&self, code: Option<String>) -> Result<()> {
+    self
+      .inner
+      .arm_night(code)
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi]
+  pub async fn arm_vacation(&self, code: Option<String>) -> Result<()> {
+    self
+      .inner
+      .arm_vacation(code)
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi]
+  pub async fn arm_custom_bypass(&self, code: Option<String>) -> Result<()> {
+    self
+      .inner
+      .arm_custom_bypass(code)
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi]
+  pub async fn trigger(&self, code: Option<String>) -> Result<()> {
+    self
+      .inner
+      .trigger(code)
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+}
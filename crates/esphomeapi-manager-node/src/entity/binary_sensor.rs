@@ -0,0 +1,86 @@
+use esphomeapi_manager::entity::{
+  BaseEntity as _, BinarySensor as RustBinarySensor, BinarySensorState as RustBinarySensorState,
+};
+use esphomeapi_manager::EntityState;
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi_derive::napi;
+
+use crate::entity::EntityKind;
+
+#[napi(object)]
+pub struct BinarySensorState {
+  pub is_on: bool,
+  pub missing_state: bool,
+}
+
+impl From<RustBinarySensorState> for BinarySensorState {
+  fn from(s: RustBinarySensorState) -> Self {
+    BinarySensorState {
+      is_on: s.state,
+      missing_state: s.missing_state,
+    }
+  }
+}
+
+#[napi]
+#[derive(Clone)]
+pub struct BinarySensor {
+  inner: RustBinarySensor,
+  pub key: u32,
+  pub device_id: u32,
+  pub name: String,
+  #[napi(ts_type = "EntityKind.BinarySensor")]
+  pub kind: EntityKind,
+}
+
+impl BinarySensor {
+  pub fn new(rust_binary_sensor: &RustBinarySensor) -> Self {
+    BinarySensor {
+      inner: rust_binary_sensor.clone(),
+      key: rust_binary_sensor.key(),
+      device_id: rust_binary_sensor.device_id(),
+      name: rust_binary_sensor.name().to_string(),
+      kind: EntityKind::BinarySensor,
+    }
+  }
+}
+
+#[napi]
+impl BinarySensor {
+  #[napi(getter)]
+  pub fn state(&self) -> Result<BinarySensorState> {
+    self
+      .inner
+      .get_state()
+      .map(Into::into)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi(getter)]
+  pub fn is_on(&self) -> Result<bool> {
+    self
+      .inner
+      .is_on()
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  /// Register a callback that is called whenever the binary sensor state changes.
+  #[napi]
+  pub fn on_state_change(
+    &self,
+    callback: ThreadsafeFunction<BinarySensorState, (), BinarySensorState, Status, false, true>,
+  ) -> Result<()> {
+    let mut receiver = self.inner.state_receiver();
+
+    napi::bindgen_prelude::spawn(async move {
+      while receiver.changed().await.is_ok() {
+        if let Some(EntityState::BinarySensor(s)) = receiver.borrow().clone() {
+          callback.call(s.into(), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+      }
+    });
+
+    Ok(())
+  }
+}
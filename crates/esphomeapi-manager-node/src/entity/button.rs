@@ -0,0 +1,40 @@
+use esphomeapi_manager::entity::{BaseEntity as _, Button as RustButton};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+use crate::entity::EntityKind;
+
+#[napi]
+#[derive(Clone)]
+pub struct Button {
+  inner: RustButton,
+  pub key: u32,
+  pub device_id: u32,
+  pub name: String,
+  #[napi(ts_type = "EntityKind.Button")]
+  pub kind: EntityKind,
+}
+
+impl Button {
+  pub fn new(rust_button: &RustButton) -> Self {
+    Button {
+      inner: rust_button.clone(),
+      key: rust_button.key(),
+      device_id: rust_button.device_id(),
+      name: rust_button.name().to_string(),
+      kind: EntityKind::Button,
+    }
+  }
+}
+
+#[napi]
+impl Button {
+  #[napi]
+  pub async fn press(&self) -> Result<()> {
+    self
+      .inner
+      .press()
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+}
@@ -0,0 +1,80 @@
+use esphomeapi_manager::entity::{BaseEntity as _, Camera as RustCamera};
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi_derive::napi;
+
+use crate::entity::EntityKind;
+
+#[napi]
+#[derive(Clone)]
+pub struct Camera {
+  inner: RustCamera,
+  pub key: u32,
+  pub device_id: u32,
+  pub name: String,
+  #[napi(ts_type = "EntityKind.Camera")]
+  pub kind: EntityKind,
+}
+
+impl Camera {
+  pub fn new(rust_camera: &RustCamera) -> Self {
+    Camera {
+      inner: rust_camera.clone(),
+      key: rust_camera.key(),
+      device_id: rust_camera.device_id(),
+      name: rust_camera.name().to_string(),
+      kind: EntityKind::Camera,
+    }
+  }
+}
+
+#[napi]
+impl Camera {
+  /// The most recent complete image (usually JPEG).
+  #[napi(getter)]
+  pub fn image(&self) -> Result<Buffer> {
+    self
+      .inner
+      .get_state()
+      .map(|image| image.data.into())
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  /// Register a callback that is called with every new complete image.
+  #[napi]
+  pub fn on_image(
+    &self,
+    callback: ThreadsafeFunction<Buffer, (), Buffer, Status, false, true>,
+  ) -> Result<()> {
+    let mut receiver = self.inner.state_receiver();
+
+    napi::bindgen_prelude::spawn(async move {
+      while receiver.changed().await.is_ok() {
+        if let Some(image) = receiver.borrow().clone() {
+          callback.call(image.data.into(), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+      }
+    });
+
+    Ok(())
+  }
+
+  /// Ask the device for a single image from each of its cameras.
+  #[napi]
+  pub async fn request_image(&self) -> Result<()> {
+    self
+      .inner
+      .request_image()
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi]
+  pub async fn request_stream(&self) -> Result<()> {
+    self
+      .inner
+      .request_stream()
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+}
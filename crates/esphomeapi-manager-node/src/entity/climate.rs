@@ -0,0 +1,201 @@
+use esphomeapi_manager::entity::{
+  BaseEntity as _, Climate as RustClimate, ClimateState as RustClimateState,
+};
+use esphomeapi_manager::EntityState;
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi_derive::napi;
+
+use crate::entity::EntityKind;
+use crate::model::{ClimateAction, ClimateFanMode, ClimateMode, ClimatePreset, ClimateSwingMode};
+
+#[napi(object)]
+pub struct ClimateCommandOptions {
+  pub mode: Option<ClimateMode>,
+  pub target_temperature: Option<f64>,
+  pub target_temperature_low: Option<f64>,
+  pub target_temperature_high: Option<f64>,
+  pub fan_mode: Option<ClimateFanMode>,
+  pub swing_mode: Option<ClimateSwingMode>,
+  pub custom_fan_mode: Option<String>,
+  pub preset: Option<ClimatePreset>,
+  pub custom_preset: Option<String>,
+  pub target_humidity: Option<f64>,
+}
+
+#[napi(object)]
+pub struct ClimateState {
+  pub mode: ClimateMode,
+  pub action: ClimateAction,
+  pub current_temperature: f64,
+  pub target_temperature: f64,
+  pub target_temperature_low: f64,
+  pub target_temperature_high: f64,
+  pub fan_mode: ClimateFanMode,
+  pub swing_mode: ClimateSwingMode,
+  pub custom_fan_mode: String,
+  pub preset: ClimatePreset,
+  pub custom_preset: String,
+  pub current_humidity: f64,
+  pub target_humidity: f64,
+}
+
+impl From<RustClimateState> for ClimateState {
+  fn from(s: RustClimateState) -> Self {
+    ClimateState {
+      mode: s.mode.into(),
+      action: s.action.into(),
+      current_temperature: s.current_temperature as f64,
+      target_temperature: s.target_temperature as f64,
+      target_temperature_low: s.target_temperature_low as f64,
+      target_temperature_high: s.target_temperature_high as f64,
+      fan_mode: s.fan_mode.into(),
+      swing_mode: s.swing_mode.into(),
+      custom_fan_mode: s.custom_fan_mode,
+      preset: s.preset.into(),
+      custom_preset: s.custom_preset,
+      current_humidity: s.current_humidity as f64,
+      target_humidity: s.target_humidity as f64,
+    }
+  }
+}
+
+#[napi]
+#[derive(Clone)]
+pub struct Climate {
+  inner: RustClimate,
+  pub key: u32,
+  pub device_id: u32,
+  pub name: String,
+  #[napi(ts_type = "EntityKind.Climate")]
+  pub kind: EntityKind,
+}
+
+impl Climate {
+  pub fn new(rust_climate: &RustClimate) -> Self {
+    Climate {
+      inner: rust_climate.clone(),
+      key: rust_climate.key(),
+      device_id: rust_climate.device_id(),
+      name: rust_climate.name().to_string(),
+      kind: EntityKind::Climate,
+    }
+  }
+}
+
+#[napi]
+impl Climate {
+  #[napi(getter)]
+  pub fn state(&self) -> Result<ClimateState> {
+    self
+      .inner
+      .get_state()
+      .map(Into::into)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi(getter)]
+  pub fn mode(&self) -> Result<ClimateMode> {
+    self
+      .inner
+      .mode()
+      .map(Into::into)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi(getter)]
+  pub fn current_temperature(&self) -> Result<f64> {
+    self
+      .inner
+      .current_temperature()
+      .map(|v| v as f64)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi(getter)]
+  pub fn target_temperature(&self) -> Result<f64> {
+    self
+      .inner
+      .target_temperature()
+      .map(|v| v as f64)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  /// Register a callback that is called whenever the climate state changes.
+  #[napi]
+  pub fn on_state_change(
+    &self,
+    callback: ThreadsafeFunction<ClimateState, (), ClimateState, Status, false, true>,
+  ) -> Result<()> {
+    let mut receiver = self.inner.state_receiver();
+
+    napi::bindgen_prelude::spawn(async move {
+      while receiver.changed().await.is_ok() {
+        if let Some(EntityState::Climate(s)) = receiver.borrow().clone() {
+          callback.call(s.into(), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+      }
+    });
+
+    Ok(())
+  }
+
+  #[napi]
+  pub async fn set_mode(&self, mode: ClimateMode) -> Result<()> {
+    self
+      .inner
+      .set_mode(mode.into())
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi]
+  pub async fn set_target_temperature(&self, target_temperature: f64) -> Result<()> {
+    self
+      .inner
+      .set_target_temperature(target_temperature as f32)
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi]
+  pub async fn send_command(&self, options: ClimateCommandOptions) -> Result<()> {
+    let mut builder = self.inner.command();
+
+    if let Some(mode) = options.mode {
+      builder = builder.mode(mode.into());
+    }
+    if let Some(target_temperature) = options.target_temperature {
+      builder = builder.target_temperature(target_temperature as f32);
+    }
+    if let Some(target_temperature_low) = options.target_temperature_low {
+      builder = builder.target_temperature_low(target_temperature_low as f32);
+    }
+    if let Some(target_temperature_high) = options.target_temperature_high {
+      builder = builder.target_temperature_high(target_temperature_high as f32);
+    }
+    if let Some(fan_mode) = options.fan_mode {
+      builder = builder.fan_mode(fan_mode.into());
+    }
+    if let Some(swing_mode) = options.swing_mode {
+      builder = builder.swing_mode(swing_mode.into());
+    }
+    if let Some(custom_fan_mode) = options.custom_fan_mode {
+      builder = builder.custom_fan_mode(custom_fan_mode);
+    }
+    if let Some(preset) = options.preset {
+      builder = builder.preset(preset.into());
+    }
+    if let Some(custom_preset) = options.custom_preset {
+      builder = builder.custom_preset(custom_preset);
+    }
+    if let Some(target_humidity) = options.target_humidity {
+      builder = builder.target_humidity(target_humidity as f32);
+    }
+
+    builder
+      .send()
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+}
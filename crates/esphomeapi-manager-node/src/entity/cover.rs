@@ -0,0 +1,154 @@
+use esphomeapi_manager::entity::{
+  BaseEntity as _, Cover as RustCover, CoverState as RustCoverState,
+};
+use esphomeapi_manager::EntityState;
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi_derive::napi;
+
+use crate::entity::EntityKind;
+use crate::model::CoverOperation;
+
+#[napi(object)]
+pub struct CoverState {
+  pub position: f64,
+  pub tilt: f64,
+  pub current_operation: CoverOperation,
+}
+
+impl From<RustCoverState> for CoverState {
+  fn from(s: RustCoverState) -> Self {
+    CoverState {
+      position: s.position as f64,
+      tilt: s.tilt as f64,
+      current_operation: s.current_operation.into(),
+    }
+  }
+}
+
+#[napi]
+#[derive(Clone)]
+pub struct Cover {
+  inner: RustCover,
+  pub key: u32,
+  pub device_id: u32,
+  pub name: String,
+  #[napi(ts_type = "EntityKind.Cover")]
+  pub kind: EntityKind,
+}
+
+impl Cover {
+  pub fn new(rust_cover: &RustCover) -> Self {
+    Cover {
+      inner: rust_cover.clone(),
+      key: rust_cover.key(),
+      device_id: rust_cover.device_id(),
+      name: rust_cover.name().to_string(),
+      kind: EntityKind::Cover,
+    }
+  }
+}
+
+#[napi]
+impl Cover {
+  #[napi(getter)]
+  pub fn state(&self) -> Result<CoverState> {
+    self
+      .inner
+      .get_state()
+      .map(Into::into)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi(getter)]
+  pub fn position(&self) -> Result<f64> {
+    self
+      .inner
+      .position()
+      .map(|v| v as f64)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi(getter)]
+  pub fn tilt(&self) -> Result<f64> {
+    self
+      .inner
+      .tilt()
+      .map(|v| v as f64)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi(getter)]
+  pub fn current_operation(&self) -> Result<CoverOperation> {
+    self
+      .inner
+      .current_operation()
+      .map(Into::into)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  /// Register a callback that is called whenever the cover state changes.
+  #[napi]
+  pub fn on_state_change(
+    &self,
+    callback: ThreadsafeFunction<CoverState, (), CoverState, Status, false, true>,
+  ) -> Result<()> {
+    let mut receiver = self.inner.state_receiver();
+
+    napi::bindgen_prelude::spawn(async move {
+      while receiver.changed().await.is_ok() {
+        if let Some(EntityState::Cover(s)) = receiver.borrow().clone() {
+          callback.call(s.into(), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+      }
+    });
+
+    Ok(())
+  }
+
+  #[napi]
+  pub async fn open(&self) -> Result<()> {
+    self
+      .inner
+      .open()
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi]
+  pub async fn close(&self) -> Result<()> {
+    self
+      .inner
+      .close()
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi]
+  pub async fn stop(&self) -> Result<()> {
+    self
+      .inner
+      .stop()
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  /// Position from `0` (closed) to `1` (open).
+  #[napi]
+  pub async fn set_position(&self, position: f64) -> Result<()> {
+    self
+      .inner
+      .set_position(position as f32)
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi]
+  pub async fn set_tilt(&self, tilt: f64) -> Result<()> {
+    self
+      .inner
+      .set_tilt(tilt as f32)
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+}
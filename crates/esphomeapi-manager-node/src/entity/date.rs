@@ -0,0 +1,89 @@
+use esphomeapi_manager::entity::{BaseEntity as _, Date as RustDate, DateState as RustDateState};
+use esphomeapi_manager::EntityState;
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi_derive::napi;
+
+use crate::entity::EntityKind;
+
+#[napi(object)]
+pub struct DateState {
+  pub year: u32,
+  pub month: u32,
+  pub day: u32,
+  pub missing_state: bool,
+}
+
+impl From<RustDateState> for DateState {
+  fn from(s: RustDateState) -> Self {
+    DateState {
+      year: s.year,
+      month: s.month,
+      day: s.day,
+      missing_state: s.missing_state,
+    }
+  }
+}
+
+#[napi]
+#[derive(Clone)]
+pub struct Date {
+  inner: RustDate,
+  pub key: u32,
+  pub device_id: u32,
+  pub name: String,
+  #[napi(ts_type = "EntityKind.Date")]
+  pub kind: EntityKind,
+}
+
+impl Date {
+  pub fn new(rust_date: &RustDate) -> Self {
+    Date {
+      inner: rust_date.clone(),
+      key: rust_date.key(),
+      device_id: rust_date.device_id(),
+      name: rust_date.name().to_string(),
+      kind: EntityKind::Date,
+    }
+  }
+}
+
+#[napi]
+impl Date {
+  #[napi(getter)]
+  pub fn state(&self) -> Result<DateState> {
+    self
+      .inner
+      .get_state()
+      .map(Into::into)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  /// Register a callback that is called whenever the date state changes.
+  #[napi]
+  pub fn on_state_change(
+    &self,
+    callback: ThreadsafeFunction<DateState, (), DateState, Status, false, true>,
+  ) -> Result<()> {
+    let mut receiver = self.inner.state_receiver();
+
+    napi::bindgen_prelude::spawn(async move {
+      while receiver.changed().await.is_ok() {
+        if let Some(EntityState::Date(s)) = receiver.borrow().clone() {
+          callback.call(s.into(), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+      }
+    });
+
+    Ok(())
+  }
+
+  #[napi]
+  pub async fn set_date(&self, year: u32, month: u32, day: u32) -> Result<()> {
+    self
+      .inner
+      .set_date(year, month, day)
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+}
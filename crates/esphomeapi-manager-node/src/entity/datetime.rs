@@ -0,0 +1,87 @@
+use esphomeapi_manager::entity::{
+  BaseEntity as _, DateTime as RustDateTime, DateTimeState as RustDateTimeState,
+};
+use esphomeapi_manager::EntityState;
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi_derive::napi;
+
+use crate::entity::EntityKind;
+
+#[napi(object)]
+pub struct DateTimeState {
+  pub epoch_seconds: u32,
+  pub missing_state: bool,
+}
+
+impl From<RustDateTimeState> for DateTimeState {
+  fn from(s: RustDateTimeState) -> Self {
+    DateTimeState {
+      epoch_seconds: s.epoch_seconds,
+      missing_state: s.missing_state,
+    }
+  }
+}
+
+#[napi]
+#[derive(Clone)]
+pub struct DateTime {
+  inner: RustDateTime,
+  pub key: u32,
+  pub device_id: u32,
+  pub name: String,
+  #[napi(ts_type = "EntityKind.DateTime")]
+  pub kind: EntityKind,
+}
+
+impl DateTime {
+  pub fn new(rust_datetime: &RustDateTime) -> Self {
+    DateTime {
+      inner: rust_datetime.clone(),
+      key: rust_datetime.key(),
+      device_id: rust_datetime.device_id(),
+      name: rust_datetime.name().to_string(),
+      kind: EntityKind::DateTime,
+    }
+  }
+}
+
+#[napi]
+impl DateTime {
+  #[napi(getter)]
+  pub fn state(&self) -> Result<DateTimeState> {
+    self
+      .inner
+      .get_state()
+      .map(Into::into)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  /// Register a callback that is called whenever the datetime state changes.
+  #[napi]
+  pub fn on_state_change(
+    &self,
+    callback: ThreadsafeFunction<DateTimeState, (), DateTimeState, Status, false, true>,
+  ) -> Result<()> {
+    let mut receiver = self.inner.state_receiver();
+
+    napi::bindgen_prelude::spawn(async move {
+      while receiver.changed().await.is_ok() {
+        if let Some(EntityState::DateTime(s)) = receiver.borrow().clone() {
+          callback.call(s.into(), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+      }
+    });
+
+    Ok(())
+  }
+
+  #[napi]
+  pub async fn set_epoch_seconds(&self, epoch_seconds: u32) -> Result<()> {
+    self
+      .inner
+      .set_epoch_seconds(epoch_seconds)
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+}
@@ -0,0 +1,81 @@
+use esphomeapi_manager::entity::{
+  BaseEntity as _, Event as RustEvent, EventState as RustEventState,
+};
+use esphomeapi_manager::EntityState;
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi_derive::napi;
+
+use crate::entity::EntityKind;
+
+#[napi(object)]
+pub struct EventState {
+  pub event_type: String,
+}
+
+impl From<RustEventState> for EventState {
+  fn from(s: RustEventState) -> Self {
+    EventState {
+      event_type: s.event_type,
+    }
+  }
+}
+
+#[napi]
+#[derive(Clone)]
+pub struct Event {
+  inner: RustEvent,
+  pub key: u32,
+  pub device_id: u32,
+  pub name: String,
+  #[napi(ts_type = "EntityKind.Event")]
+  pub kind: EntityKind,
+}
+
+impl Event {
+  pub fn new(rust_event: &RustEvent) -> Self {
+    Event {
+      inner: rust_event.clone(),
+      key: rust_event.key(),
+      device_id: rust_event.device_id(),
+      name: rust_event.name().to_string(),
+      kind: EntityKind::Event,
+    }
+  }
+}
+
+#[napi]
+impl Event {
+  #[napi(getter)]
+  pub fn state(&self) -> Result<EventState> {
+    self
+      .inner
+      .get_state()
+      .map(Into::into)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi(getter)]
+  pub fn event_types(&self) -> Vec<String> {
+    self.inner.event_types().to_vec()
+  }
+
+  /// Register a callback that is called whenever the event state changes.
+  #[napi]
+  pub fn on_state_change(
+    &self,
+    callback: ThreadsafeFunction<EventState, (), EventState, Status, false, true>,
+  ) -> Result<()> {
+    let mut receiver = self.inner.state_receiver();
+
+    napi::bindgen_prelude::spawn(async move {
+      while receiver.changed().await.is_ok() {
+        if let Some(EntityState::Event(s)) = receiver.borrow().clone() {
+          callback.call(s.into(), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+      }
+    });
+
+    Ok(())
+  }
+}
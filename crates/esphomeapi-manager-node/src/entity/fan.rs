@@ -0,0 +1,154 @@
+use esphomeapi_manager::entity::{BaseEntity as _, Fan as RustFan, FanState as RustFanState};
+use esphomeapi_manager::EntityState;
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi_derive::napi;
+
+use crate::entity::EntityKind;
+use crate::model::FanDirection;
+
+#[napi(object)]
+pub struct FanState {
+  pub is_on: bool,
+  pub oscillating: bool,
+  pub speed_level: i32,
+  pub direction: FanDirection,
+  pub preset_mode: String,
+}
+
+impl From<RustFanState> for FanState {
+  fn from(s: RustFanState) -> Self {
+    FanState {
+      is_on: s.state,
+      oscillating: s.oscillating,
+      speed_level: s.speed_level,
+      direction: s.direction.into(),
+      preset_mode: s.preset_mode,
+    }
+  }
+}
+
+#[napi]
+#[derive(Clone)]
+pub struct Fan {
+  inner: RustFan,
+  pub key: u32,
+  pub device_id: u32,
+  pub name: String,
+  #[napi(ts_type = "EntityKind.Fan")]
+  pub kind: EntityKind,
+}
+
+impl Fan {
+  pub fn new(rust_fan: &RustFan) -> Self {
+    Fan {
+      inner: rust_fan.clone(),
+      key: rust_fan.key(),
+      device_id: rust_fan.device_id(),
+      name: rust_fan.name().to_string(),
+      kind: EntityKind::Fan,
+    }
+  }
+}
+
+#[napi]
+impl Fan {
+  #[napi(getter)]
+  pub fn state(&self) -> Result<FanState> {
+    self
+      .inner
+      .get_state()
+      .map(Into::into)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi(getter)]
+  pub fn is_on(&self) -> Result<bool> {
+    self
+      .inner
+      .is_on()
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  /// Register a callback that is called whenever the fan state changes.
+  #[napi]
+  pub fn on_state_change(
+    &self,
+    callback: ThreadsafeFunction<FanState, (), FanState, Status, false, true>,
+  ) -> Result<()> {
+    let mut receiver = self.inner.state_receiver();
+
+    napi::bindgen_prelude::spawn(async move {
+      while receiver.changed().await.is_ok() {
+        if let Some(EntityState::Fan(s)) = receiver.borrow().clone() {
+          callback.call(s.into(), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+      }
+    });
+
+    Ok(())
+  }
+
+  #[napi]
+  pub async fn turn_on(&self) -> Result<()> {
+    self
+      .inner
+      .turn_on()
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi]
+  pub async fn turn_off(&self) -> Result<()> {
+    self
+      .inner
+      .turn_off()
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi]
+  pub async fn toggle(&self) -> Result<()> {
+    self
+      .inner
+      .toggle()
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi]
+  pub async fn set_speed_level(&self, speed_level: i32) -> Result<()> {
+    self
+      .inner
+      .set_speed_level(speed_level)
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi]
+  pub async fn set_oscillating(&self, oscillating: bool) -> Result<()> {
+    self
+      .inner
+      .set_oscillating(oscillating)
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi]
+  pub async fn set_direction(&self, direction: FanDirection) -> Result<()> {
+    self
+      .inner
+      .set_direction(direction.into())
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi]
+  pub async fn set_preset_mode(&self, preset_mode: String) -> Result<()> {
+    self
+      .inner
+      .set_preset_mode(preset_mode)
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+}
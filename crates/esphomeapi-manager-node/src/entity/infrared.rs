@@ -0,0 +1,76 @@
+use esphomeapi_manager::entity::{
+  BaseEntity as _, Infrared as RustInfrared, InfraredCapability, InfraredSignal,
+};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+use crate::entity::EntityKind;
+
+#[napi]
+#[derive(Clone)]
+pub struct Infrared {
+  inner: RustInfrared,
+  pub key: u32,
+  pub device_id: u32,
+  pub name: String,
+  #[napi(ts_type = "EntityKind.Infrared")]
+  pub kind: EntityKind,
+}
+
+impl Infrared {
+  pub fn new(rust_infrared: &RustInfrared) -> Self {
+    Infrared {
+      inner: rust_infrared.clone(),
+      key: rust_infrared.key(),
+      device_id: rust_infrared.device_id(),
+      name: rust_infrared.name().to_string(),
+      kind: EntityKind::Infrared,
+    }
+  }
+}
+
+#[napi]
+impl Infrared {
+  #[napi(getter)]
+  pub fn can_transmit(&self) -> bool {
+    self
+      .inner
+      .capabilities()
+      .contains(InfraredCapability::Transmitter)
+  }
+
+  #[napi(getter)]
+  pub fn can_receive(&self) -> bool {
+    self
+      .inner
+      .capabilities()
+      .contains(InfraredCapability::Receiver)
+  }
+
+  /// Transmit raw timings: positive marks and negative spaces in microseconds.
+  #[napi]
+  pub async fn transmit_raw(
+    &self,
+    carrier_frequency: u32,
+    repeat_count: u32,
+    timings: Vec<i32>,
+  ) -> Result<()> {
+    self
+      .inner
+      .transmit_raw(carrier_frequency, repeat_count, timings)
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  /// Transmit a learned Pronto hex code (`0000 006D ...`).
+  #[napi]
+  pub async fn transmit_pronto(&self, code: String, repeat_count: u32) -> Result<()> {
+    let signal = InfraredSignal::from_pronto(&code)
+      .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))?;
+    self
+      .inner
+      .transmit(&signal, repeat_count)
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+}
@@ -44,6 +44,7 @@ pub struct LightState {
 pub struct Light {
   inner: RustLight,
   pub key: u32,
+  pub device_id: u32,
   pub name: String,
   #[napi(ts_type = "EntityKind.Light")]
   pub kind: EntityKind,
@@ -54,6 +55,7 @@ impl Light {
     Light {
       inner: rust_light.clone(),
       key: rust_light.key(),
+      device_id: rust_light.device_id(),
       name: rust_light.name().to_string(),
       kind: EntityKind::Light,
     }
@@ -0,0 +1,113 @@
+use esphomeapi_manager::entity::{
+  BaseEntity as _, Lock as RustLock, LockEntityState as RustLockEntityState,
+};
+use esphomeapi_manager::EntityState;
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi_derive::napi;
+
+use crate::entity::EntityKind;
+use crate::model::LockState;
+
+#[napi(object)]
+pub struct LockEntityState {
+  pub state: LockState,
+}
+
+impl From<RustLockEntityState> for LockEntityState {
+  fn from(s: RustLockEntityState) -> Self {
+    LockEntityState {
+      state: s.state.into(),
+    }
+  }
+}
+
+#[napi]
+#[derive(Clone)]
+pub struct Lock {
+  inner: RustLock,
+  pub key: u32,
+  pub device_id: u32,
+  pub name: String,
+  #[napi(ts_type = "EntityKind.Lock")]
+  pub kind: EntityKind,
+}
+
+impl Lock {
+  pub fn new(rust_lock: &RustLock) -> Self {
+    Lock {
+      inner: rust_lock.clone(),
+      key: rust_lock.key(),
+      device_id: rust_lock.device_id(),
+      name: rust_lock.name().to_string(),
+      kind: EntityKind::Lock,
+    }
+  }
+}
+
+#[napi]
+impl Lock {
+  #[napi(getter)]
+  pub fn state(&self) -> Result<LockEntityState> {
+    self
+      .inner
+      .get_state()
+      .map(Into::into)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi(getter)]
+  pub fn lock_state(&self) -> Result<LockState> {
+    self
+      .inner
+      .lock_state()
+      .map(Into::into)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  /// Register a callback that is called whenever the lock state changes.
+  #[napi]
+  pub fn on_state_change(
+    &self,
+    callback: ThreadsafeFunction<LockEntityState, (), LockEntityState, Status, false, true>,
+  ) -> Result<()> {
+    let mut receiver = self.inner.state_receiver();
+
+    napi::bindgen_prelude::spawn(async move {
+      while receiver.changed().await.is_ok() {
+        if let Some(EntityState::Lock(s)) = receiver.borrow().clone() {
+          callback.call(s.into(), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+      }
+    });
+
+    Ok(())
+  }
+
+  #[napi]
+  pub async fn lock(&self, code: Option<String>) -> Result<()> {
+    self
+      .inner
+      .lock(code)
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi]
+  pub async fn unlock(&self, code: Option<String>) -> Result<()> {
+    self
+      .inner
+      .unlock(code)
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi]
+  pub async fn open(&self, code: Option<String>) -> Result<()> {
+    self
+      .inner
+      .open(code)
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+}
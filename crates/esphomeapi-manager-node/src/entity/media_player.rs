@@ -0,0 +1,179 @@
+use esphomeapi_manager::entity::{
+  BaseEntity as _, MediaPlayer as RustMediaPlayer,
+  MediaPlayerEntityState as RustMediaPlayerEntityState,
+};
+use esphomeapi_manager::EntityState;
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi_derive::napi;
+
+use crate::entity::EntityKind;
+use crate::model::MediaPlayerState;
+
+#[napi(object)]
+pub struct MediaPlayerEntityState {
+  pub state: MediaPlayerState,
+  pub volume: f64,
+  pub muted: bool,
+}
+
+impl From<RustMediaPlayerEntityState> for MediaPlayerEntityState {
+  fn from(s: RustMediaPlayerEntityState) -> Self {
+    MediaPlayerEntityState {
+      state: s.state.into(),
+      volume: s.volume as f64,
+      muted: s.muted,
+    }
+  }
+}
+
+#[napi]
+#[derive(Clone)]
+pub struct MediaPlayer {
+  inner: RustMediaPlayer,
+  pub key: u32,
+  pub device_id: u32,
+  pub name: String,
+  #[napi(ts_type = "EntityKind.MediaPlayer")]
+  pub kind: EntityKind,
+}
+
+impl MediaPlayer {
+  pub fn new(rust_media_player: &RustMediaPlayer) -> Self {
+    MediaPlayer {
+      inner: rust_media_player.clone(),
+      key: rust_media_player.key(),
+      device_id: rust_media_player.device_id(),
+      name: rust_media_player.name().to_string(),
+      kind: EntityKind::MediaPlayer,
+    }
+  }
+}
+
+#[napi]
+impl MediaPlayer {
+  #[napi(getter)]
+  pub fn state(&self) -> Result<MediaPlayerEntityState> {
+    self
+      .inner
+      .get_state()
+      .map(Into::into)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi(getter)]
+  pub fn player_state(&self) -> Result<MediaPlayerState> {
+    self
+      .inner
+      .player_state()
+      .map(Into::into)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi(getter)]
+  pub fn volume(&self) -> Result<f64> {
+    self
+      .inner
+      .volume()
+      .map(|v| v as f64)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi(getter)]
+  pub fn is_muted(&self) -> Result<bool> {
+    self
+      .inner
+      .is_muted()
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  /// Register a callback that is called whenever the media player state changes.
+  #[napi]
+  pub fn on_state_change(
+    &self,
+    callback: ThreadsafeFunction<
+      MediaPlayerEntityState,
+      (),
+      MediaPlayerEntityState,
+      Status,
+      false,
+      true,
+    >,
+  ) -> Result<()> {
+    let mut receiver = self.inner.state_receiver();
+
+    napi::bindgen_prelude::spawn(async move {
+      while receiver.changed().await.is_ok() {
+        if let Some(EntityState::MediaPlayer(s)) = receiver.borrow().clone() {
+          callback.call(s.into(), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+      }
+    });
+
+    Ok(())
+  }
+
+  #[napi]
+  pub async fn play(&self) -> Result<()> {
+    self
+      .inner
+      .play()
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi]
+  pub async fn pause(&self) -> Result<()> {
+    self
+      .inner
+      .pause()
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi]
+  pub async fn stop(&self) -> Result<()> {
+    self
+      .inner
+      .stop()
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi]
+  pub async fn mute(&self) -> Result<()> {
+    self
+      .inner
+      .mute()
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi]
+  pub async fn unmute(&self) -> Result<()> {
+    self
+      .inner
+      .unmute()
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  /// Volume from `0` to `1`.
+  #[napi]
+  pub async fn set_volume(&self, volume: f64) -> Result<()> {
+    self
+      .inner
+      .set_volume(volume as f32)
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi]
+  pub async fn play_media(&self, media_url: String, announcement: Option<bool>) -> Result<()> {
+    self
+      .inner
+      .play_media(media_url, announcement.unwrap_or(false))
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+}
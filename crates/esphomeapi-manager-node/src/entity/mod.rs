@@ -1,18 +1,113 @@
+mod alarm_control_panel;
+mod binary_sensor;
+mod button;
+mod camera;
+mod climate;
+mod cover;
+mod date;
+mod datetime;
+mod event;
+mod fan;
+mod infrared;
 mod light;
+mod lock;
+mod media_player;
+mod number;
+mod select;
+mod sensor;
+mod siren;
 mod switch;
+mod text;
+mod text_sensor;
+mod time;
+mod update;
+mod valve;
+mod water_heater;
 
-use napi::Either;
+use napi::bindgen_prelude::Either25;
 use napi_derive::napi;
 
+pub use alarm_control_panel::AlarmControlPanel;
+pub use binary_sensor::BinarySensor;
+pub use button::Button;
+pub use camera::Camera;
+pub use climate::Climate;
+pub use cover::Cover;
+pub use date::Date;
+pub use datetime::DateTime;
+pub use event::Event;
+pub use fan::Fan;
+pub use infrared::Infrared;
 pub use light::Light;
+pub use lock::Lock;
+pub use media_player::MediaPlayer;
+pub use number::Number;
+pub use select::Select;
+pub use sensor::Sensor;
+pub use siren::Siren;
 pub use switch::Switch;
+pub use text::Text;
+pub use text_sensor::TextSensor;
+pub use time::Time;
+pub use update::Update;
+pub use valve::Valve;
+pub use water_heater::WaterHeater;
 
 #[derive(Debug, Clone)]
 #[napi(string_enum)]
 pub enum EntityKind {
+  AlarmControlPanel,
+  BinarySensor,
+  Button,
+  Camera,
+  Climate,
+  Cover,
+  Date,
+  DateTime,
+  Event,
+  Fan,
+  Infrared,
   Light,
+  Lock,
+  MediaPlayer,
+  Number,
+  Select,
+  Sensor,
+  Siren,
   Switch,
+  Text,
+  TextSensor,
+  Time,
+  Update,
+  Valve,
+  WaterHeater,
 }
 
 #[napi]
-pub type Entity = Either<Light, Switch>;
+pub type Entity = Either25<
+  AlarmControlPanel,
+  BinarySensor,
+  Button,
+  Camera,
+  Climate,
+  Cover,
+  Date,
+  DateTime,
+  Event,
+  Fan,
+  Infrared,
+  Light,
+  Lock,
+  MediaPlayer,
+  Number,
+  Select,
+  Sensor,
+  Siren,
+  Switch,
+  Text,
+  TextSensor,
+  Time,
+  Update,
+  Valve,
+  WaterHeater,
+>;
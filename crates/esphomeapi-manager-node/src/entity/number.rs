@@ -0,0 +1,96 @@
+use esphomeapi_manager::entity::{
+  BaseEntity as _, Number as RustNumber, NumberState as RustNumberState,
+};
+use esphomeapi_manager::EntityState;
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi_derive::napi;
+
+use crate::entity::EntityKind;
+
+#[napi(object)]
+pub struct NumberState {
+  pub state: f64,
+  pub missing_state: bool,
+}
+
+impl From<RustNumberState> for NumberState {
+  fn from(s: RustNumberState) -> Self {
+    NumberState {
+      state: s.state as f64,
+      missing_state: s.missing_state,
+    }
+  }
+}
+
+#[napi]
+#[derive(Clone)]
+pub struct Number {
+  inner: RustNumber,
+  pub key: u32,
+  pub device_id: u32,
+  pub name: String,
+  #[napi(ts_type = "EntityKind.Number")]
+  pub kind: EntityKind,
+}
+
+impl Number {
+  pub fn new(rust_number: &RustNumber) -> Self {
+    Number {
+      inner: rust_number.clone(),
+      key: rust_number.key(),
+      device_id: rust_number.device_id(),
+      name: rust_number.name().to_string(),
+      kind: EntityKind::Number,
+    }
+  }
+}
+
+#[napi]
+impl Number {
+  #[napi(getter)]
+  pub fn state(&self) -> Result<NumberState> {
+    self
+      .inner
+      .get_state()
+      .map(Into::into)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi(getter)]
+  pub fn value(&self) -> Result<Option<f64>> {
+    self
+      .inner
+      .value()
+      .map(|v| v.map(|v| v as f64))
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  /// Register a callback that is called whenever the number state changes.
+  #[napi]
+  pub fn on_state_change(
+    &self,
+    callback: ThreadsafeFunction<NumberState, (), NumberState, Status, false, true>,
+  ) -> Result<()> {
+    let mut receiver = self.inner.state_receiver();
+
+    napi::bindgen_prelude::spawn(async move {
+      while receiver.changed().await.is_ok() {
+        if let Some(EntityState::Number(s)) = receiver.borrow().clone() {
+          callback.call(s.into(), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+      }
+    });
+
+    Ok(())
+  }
+
+  #[napi]
+  pub async fn set_value(&self, value: f64) -> Result<()> {
+    self
+      .inner
+      .set_value(value as f32)
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+}
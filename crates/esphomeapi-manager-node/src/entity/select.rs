@@ -0,0 +1,100 @@
+use esphomeapi_manager::entity::{
+  BaseEntity as _, Select as RustSelect, SelectState as RustSelectState,
+};
+use esphomeapi_manager::EntityState;
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi_derive::napi;
+
+use crate::entity::EntityKind;
+
+#[napi(object)]
+pub struct SelectState {
+  pub state: String,
+  pub missing_state: bool,
+}
+
+impl From<RustSelectState> for SelectState {
+  fn from(s: RustSelectState) -> Self {
+    SelectState {
+      state: s.state,
+      missing_state: s.missing_state,
+    }
+  }
+}
+
+#[napi]
+#[derive(Clone)]
+pub struct Select {
+  inner: RustSelect,
+  pub key: u32,
+  pub device_id: u32,
+  pub name: String,
+  #[napi(ts_type = "EntityKind.Select")]
+  pub kind: EntityKind,
+}
+
+impl Select {
+  pub fn new(rust_select: &RustSelect) -> Self {
+    Select {
+      inner: rust_select.clone(),
+      key: rust_select.key(),
+      device_id: rust_select.device_id(),
+      name: rust_select.name().to_string(),
+      kind: EntityKind::Select,
+    }
+  }
+}
+
+#[napi]
+impl Select {
+  #[napi(getter)]
+  pub fn state(&self) -> Result<SelectState> {
+    self
+      .inner
+      .get_state()
+      .map(Into::into)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi(getter)]
+  pub fn value(&self) -> Result<Option<String>> {
+    self
+      .inner
+      .value()
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi(getter)]
+  pub fn options(&self) -> Vec<String> {
+    self.inner.options().to_vec()
+  }
+
+  /// Register a callback that is called whenever the select state changes.
+  #[napi]
+  pub fn on_state_change(
+    &self,
+    callback: ThreadsafeFunction<SelectState, (), SelectState, Status, false, true>,
+  ) -> Result<()> {
+    let mut receiver = self.inner.state_receiver();
+
+    napi::bindgen_prelude::spawn(async move {
+      while receiver.changed().await.is_ok() {
+        if let Some(EntityState::Select(s)) = receiver.borrow().clone() {
+          callback.call(s.into(), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+      }
+    });
+
+    Ok(())
+  }
+
+  #[napi]
+  pub async fn select_option(&self, option: String) -> Result<()> {
+    self
+      .inner
+      .select_option(option)
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+}
@@ -0,0 +1,92 @@
+use esphomeapi_manager::entity::{
+  BaseEntity as _, Sensor as RustSensor, SensorState as RustSensorState,
+};
+use esphomeapi_manager::EntityState;
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi_derive::napi;
+
+use crate::entity::EntityKind;
+
+#[napi(object)]
+pub struct SensorState {
+  pub state: f64,
+  pub missing_state: bool,
+}
+
+impl From<RustSensorState> for SensorState {
+  fn from(s: RustSensorState) -> Self {
+    SensorState {
+      state: s.state as f64,
+      missing_state: s.missing_state,
+    }
+  }
+}
+
+#[napi]
+#[derive(Clone)]
+pub struct Sensor {
+  inner: RustSensor,
+  pub key: u32,
+  pub device_id: u32,
+  pub name: String,
+  #[napi(ts_type = "EntityKind.Sensor")]
+  pub kind: EntityKind,
+}
+
+impl Sensor {
+  pub fn new(rust_sensor: &RustSensor) -> Self {
+    Sensor {
+      inner: rust_sensor.clone(),
+      key: rust_sensor.key(),
+      device_id: rust_sensor.device_id(),
+      name: rust_sensor.name().to_string(),
+      kind: EntityKind::Sensor,
+    }
+  }
+}
+
+#[napi]
+impl Sensor {
+  #[napi(getter)]
+  pub fn state(&self) -> Result<SensorState> {
+    self
+      .inner
+      .get_state()
+      .map(Into::into)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi(getter)]
+  pub fn value(&self) -> Result<Option<f64>> {
+    self
+      .inner
+      .value()
+      .map(|v| v.map(|v| v as f64))
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi(getter)]
+  pub fn unit_of_measurement(&self) -> String {
+    self.inner.unit_of_measurement().to_string()
+  }
+
+  /// Register a callback that is called whenever the sensor state changes.
+  #[napi]
+  pub fn on_state_change(
+    &self,
+    callback: ThreadsafeFunction<SensorState, (), SensorState, Status, false, true>,
+  ) -> Result<()> {
+    let mut receiver = self.inner.state_receiver();
+
+    napi::bindgen_prelude::spawn(async move {
+      while receiver.changed().await.is_ok() {
+        if let Some(EntityState::Sensor(s)) = receiver.borrow().clone() {
+          callback.call(s.into(), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+      }
+    });
+
+    Ok(())
+  }
+}
@@ -0,0 +1,139 @@
+use esphomeapi_manager::entity::{
+  BaseEntity as _, Siren as RustSiren, SirenState as RustSirenState,
+};
+use esphomeapi_manager::EntityState;
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi_derive::napi;
+
+use crate::entity::EntityKind;
+
+#[napi(object)]
+pub struct SirenCommandOptions {
+  pub state: Option<bool>,
+  /// One of the tones the siren lists.
+  pub tone: Option<String>,
+  /// Volume from `0` to `1`.
+  pub volume: Option<f64>,
+  /// Seconds to sound for.
+  pub duration: Option<u32>,
+}
+
+#[napi(object)]
+pub struct SirenState {
+  pub state: bool,
+}
+
+impl From<RustSirenState> for SirenState {
+  fn from(s: RustSirenState) -> Self {
+    SirenState { state: s.state }
+  }
+}
+
+#[napi]
+#[derive(Clone)]
+pub struct Siren {
+  inner: RustSiren,
+  pub key: u32,
+  pub device_id: u32,
+  pub name: String,
+  #[napi(ts_type = "EntityKind.Siren")]
+  pub kind: EntityKind,
+}
+
+impl Siren {
+  pub fn new(rust_siren: &RustSiren) -> Self {
+    Siren {
+      inner: rust_siren.clone(),
+      key: rust_siren.key(),
+      device_id: rust_siren.device_id(),
+      name: rust_siren.name().to_string(),
+      kind: EntityKind::Siren,
+    }
+  }
+}
+
+#[napi]
+impl Siren {
+  #[napi(getter)]
+  pub fn state(&self) -> Result<SirenState> {
+    self
+      .inner
+      .get_state()
+      .map(Into::into)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi(getter)]
+  pub fn is_on(&self) -> Result<bool> {
+    self
+      .inner
+      .is_on()
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi(getter)]
+  pub fn tones(&self) -> Vec<String> {
+    self.inner.info().tones.clone()
+  }
+
+  /// Register a callback that is called whenever the siren state changes.
+  #[napi]
+  pub fn on_state_change(
+    &self,
+    callback: ThreadsafeFunction<SirenState, (), SirenState, Status, false, true>,
+  ) -> Result<()> {
+    let mut receiver = self.inner.state_receiver();
+
+    napi::bindgen_prelude::spawn(async move {
+      while receiver.changed().await.is_ok() {
+        if let Some(EntityState::Siren(s)) = receiver.borrow().clone() {
+          callback.call(s.into(), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+      }
+    });
+
+    Ok(())
+  }
+
+  #[napi]
+  pub async fn turn_on(&self) -> Result<()> {
+    self
+      .inner
+      .turn_on()
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi]
+  pub async fn turn_off(&self) -> Result<()> {
+    self
+      .inner
+      .turn_off()
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi]
+  pub async fn send_command(&self, options: SirenCommandOptions) -> Result<()> {
+    let mut builder = self.inner.command();
+
+    if let Some(state) = options.state {
+      builder = builder.state(state);
+    }
+    if let Some(tone) = options.tone {
+      builder = builder.tone(tone);
+    }
+    if let Some(volume) = options.volume {
+      builder = builder.volume(volume as f32);
+    }
+    if let Some(duration) = options.duration {
+      builder = builder.duration(duration);
+    }
+
+    builder
+      .send()
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+}
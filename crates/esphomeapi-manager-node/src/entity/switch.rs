@@ -11,6 +11,7 @@ use crate::entity::EntityKind;
 pub struct Switch {
   inner: RustSwitch,
   pub key: u32,
+  pub device_id: u32,
   pub name: String,
   #[napi(ts_type = "EntityKind.Switch")]
   pub kind: EntityKind,
@@ -21,6 +22,7 @@ impl Switch {
     Switch {
       inner: rust_switch.clone(),
       key: rust_switch.key(),
+      device_id: rust_switch.device_id(),
       name: rust_switch.name().to_string(),
       kind: EntityKind::Switch,
     }
@@ -0,0 +1,93 @@
+use esphomeapi_manager::entity::{BaseEntity as _, Text as RustText, TextState as RustTextState};
+use esphomeapi_manager::EntityState;
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi_derive::napi;
+
+use crate::entity::EntityKind;
+
+#[napi(object)]
+pub struct TextState {
+  pub state: String,
+  pub missing_state: bool,
+}
+
+impl From<RustTextState> for TextState {
+  fn from(s: RustTextState) -> Self {
+    TextState {
+      state: s.state,
+      missing_state: s.missing_state,
+    }
+  }
+}
+
+#[napi]
+#[derive(Clone)]
+pub struct Text {
+  inner: RustText,
+  pub key: u32,
+  pub device_id: u32,
+  pub name: String,
+  #[napi(ts_type = "EntityKind.Text")]
+  pub kind: EntityKind,
+}
+
+impl Text {
+  pub fn new(rust_text: &RustText) -> Self {
+    Text {
+      inner: rust_text.clone(),
+      key: rust_text.key(),
+      device_id: rust_text.device_id(),
+      name: rust_text.name().to_string(),
+      kind: EntityKind::Text,
+    }
+  }
+}
+
+#[napi]
+impl Text {
+  #[napi(getter)]
+  pub fn state(&self) -> Result<TextState> {
+    self
+      .inner
+      .get_state()
+      .map(Into::into)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi(getter)]
+  pub fn value(&self) -> Result<Option<String>> {
+    self
+      .inner
+      .value()
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  /// Register a callback that is called whenever the text state changes.
+  #[napi]
+  pub fn on_state_change(
+    &self,
+    callback: ThreadsafeFunction<TextState, (), TextState, Status, false, true>,
+  ) -> Result<()> {
+    let mut receiver = self.inner.state_receiver();
+
+    napi::bindgen_prelude::spawn(async move {
+      while receiver.changed().await.is_ok() {
+        if let Some(EntityState::Text(s)) = receiver.borrow().clone() {
+          callback.call(s.into(), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+      }
+    });
+
+    Ok(())
+  }
+
+  #[napi]
+  pub async fn set_value(&self, value: String) -> Result<()> {
+    self
+      .inner
+      .set_value(value)
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+}
@@ -0,0 +1,86 @@
+use esphomeapi_manager::entity::{
+  BaseEntity as _, TextSensor as RustTextSensor, TextSensorState as RustTextSensorState,
+};
+use esphomeapi_manager::EntityState;
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi_derive::napi;
+
+use crate::entity::EntityKind;
+
+#[napi(object)]
+pub struct TextSensorState {
+  pub state: String,
+  pub missing_state: bool,
+}
+
+impl From<RustTextSensorState> for TextSensorState {
+  fn from(s: RustTextSensorState) -> Self {
+    TextSensorState {
+      state: s.state,
+      missing_state: s.missing_state,
+    }
+  }
+}
+
+#[napi]
+#[derive(Clone)]
+pub struct TextSensor {
+  inner: RustTextSensor,
+  pub key: u32,
+  pub device_id: u32,
+  pub name: String,
+  #[napi(ts_type = "EntityKind.TextSensor")]
+  pub kind: EntityKind,
+}
+
+impl TextSensor {
+  pub fn new(rust_text_sensor: &RustTextSensor) -> Self {
+    TextSensor {
+      inner: rust_text_sensor.clone(),
+      key: rust_text_sensor.key(),
+      device_id: rust_text_sensor.device_id(),
+      name: rust_text_sensor.name().to_string(),
+      kind: EntityKind::TextSensor,
+    }
+  }
+}
+
+#[napi]
+impl TextSensor {
+  #[napi(getter)]
+  pub fn state(&self) -> Result<TextSensorState> {
+    self
+      .inner
+      .get_state()
+      .map(Into::into)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi(getter)]
+  pub fn value(&self) -> Result<Option<String>> {
+    self
+      .inner
+      .value()
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  /// Register a callback that is called whenever the text sensor state changes.
+  #[napi]
+  pub fn on_state_change(
+    &self,
+    callback: ThreadsafeFunction<TextSensorState, (), TextSensorState, Status, false, true>,
+  ) -> Result<()> {
+    let mut receiver = self.inner.state_receiver();
+
+    napi::bindgen_prelude::spawn(async move {
+      while receiver.changed().await.is_ok() {
+        if let Some(EntityState::TextSensor(s)) = receiver.borrow().clone() {
+          callback.call(s.into(), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+      }
+    });
+
+    Ok(())
+  }
+}
@@ -0,0 +1,89 @@
+use esphomeapi_manager::entity::{BaseEntity as _, Time as RustTime, TimeState as RustTimeState};
+use esphomeapi_manager::EntityState;
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi_derive::napi;
+
+use crate::entity::EntityKind;
+
+#[napi(object)]
+pub struct TimeState {
+  pub hour: u32,
+  pub minute: u32,
+  pub second: u32,
+  pub missing_state: bool,
+}
+
+impl From<RustTimeState> for TimeState {
+  fn from(s: RustTimeState) -> Self {
+    TimeState {
+      hour: s.hour,
+      minute: s.minute,
+      second: s.second,
+      missing_state: s.missing_state,
+    }
+  }
+}
+
+#[napi]
+#[derive(Clone)]
+pub struct Time {
+  inner: RustTime,
+  pub key: u32,
+  pub device_id: u32,
+  pub name: String,
+  #[napi(ts_type = "EntityKind.Time")]
+  pub kind: EntityKind,
+}
+
+impl Time {
+  pub fn new(rust_time: &RustTime) -> Self {
+    Time {
+      inner: rust_time.clone(),
+      key: rust_time.key(),
+      device_id: rust_time.device_id(),
+      name: rust_time.name().to_string(),
+      kind: EntityKind::Time,
+    }
+  }
+}
+
+#[napi]
+impl Time {
+  #[napi(getter)]
+  pub fn state(&self) -> Result<TimeState> {
+    self
+      .inner
+      .get_state()
+      .map(Into::into)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  /// Register a callback that is called whenever the time state changes.
+  #[napi]
+  pub fn on_state_change(
+    &self,
+    callback: ThreadsafeFunction<TimeState, (), TimeState, Status, false, true>,
+  ) -> Result<()> {
+    let mut receiver = self.inner.state_receiver();
+
+    napi::bindgen_prelude::spawn(async move {
+      while receiver.changed().await.is_ok() {
+        if let Some(EntityState::Time(s)) = receiver.borrow().clone() {
+          callback.call(s.into(), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+      }
+    });
+
+    Ok(())
+  }
+
+  #[napi]
+  pub async fn set_time(&self, hour: u32, minute: u32, second: u32) -> Result<()> {
+    self
+      .inner
+      .set_time(hour, minute, second)
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+}
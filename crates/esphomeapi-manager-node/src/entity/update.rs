@@ -0,0 +1,119 @@
+use esphomeapi_manager::entity::{
+  BaseEntity as _, Update as RustUpdate, UpdateState as RustUpdateState,
+};
+use esphomeapi_manager::EntityState;
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi_derive::napi;
+
+use crate::entity::EntityKind;
+
+#[napi(object)]
+pub struct UpdateState {
+  pub missing_state: bool,
+  pub in_progress: bool,
+  pub has_progress: bool,
+  pub progress: f64,
+  pub current_version: String,
+  pub latest_version: String,
+  pub title: String,
+  pub release_summary: String,
+  pub release_url: String,
+}
+
+impl From<RustUpdateState> for UpdateState {
+  fn from(s: RustUpdateState) -> Self {
+    UpdateState {
+      missing_state: s.missing_state,
+      in_progress: s.in_progress,
+      has_progress: s.has_progress,
+      progress: s.progress as f64,
+      current_version: s.current_version,
+      latest_version: s.latest_version,
+      title: s.title,
+      release_summary: s.release_summary,
+      release_url: s.release_url,
+    }
+  }
+}
+
+#[napi]
+#[derive(Clone)]
+pub struct Update {
+  inner: RustUpdate,
+  pub key: u32,
+  pub device_id: u32,
+  pub name: String,
+  #[napi(ts_type = "EntityKind.Update")]
+  pub kind: EntityKind,
+}
+
+impl Update {
+  pub fn new(rust_update: &RustUpdate) -> Self {
+    Update {
+      inner: rust_update.clone(),
+      key: rust_update.key(),
+      device_id: rust_update.device_id(),
+      name: rust_update.name().to_string(),
+      kind: EntityKind::Update,
+    }
+  }
+}
+
+#[napi]
+impl Update {
+  #[napi(getter)]
+  pub fn state(&self) -> Result<UpdateState> {
+    self
+      .inner
+      .get_state()
+      .map(Into::into)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi(getter)]
+  pub fn has_update(&self) -> Result<bool> {
+    self
+      .inner
+      .has_update()
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  /// Register a callback that is called whenever the update state changes.
+  #[napi]
+  pub fn on_state_change(
+    &self,
+    callback: ThreadsafeFunction<UpdateState, (), UpdateState, Status, false, true>,
+  ) -> Result<()> {
+    let mut receiver = self.inner.state_receiver();
+
+    napi::bindgen_prelude::spawn(async move {
+      while receiver.changed().await.is_ok() {
+        if let Some(EntityState::Update(s)) = receiver.borrow().clone() {
+          callback.call(s.into(), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+      }
+    });
+
+    Ok(())
+  }
+
+  #[napi]
+  pub async fn install(&self) -> Result<()> {
+    self
+      .inner
+      .install()
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  /// Ask the device to look for a newer version.
+  #[napi]
+  pub async fn check(&self) -> Result<()> {
+    self
+      .inner
+      .check()
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+}
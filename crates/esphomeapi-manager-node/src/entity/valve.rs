@@ -0,0 +1,134 @@
+use esphomeapi_manager::entity::{
+  BaseEntity as _, Valve as RustValve, ValveState as RustValveState,
+};
+use esphomeapi_manager::EntityState;
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi_derive::napi;
+
+use crate::entity::EntityKind;
+use crate::model::ValveOperation;
+
+#[napi(object)]
+pub struct ValveState {
+  pub position: f64,
+  pub current_operation: ValveOperation,
+}
+
+impl From<RustValveState> for ValveState {
+  fn from(s: RustValveState) -> Self {
+    ValveState {
+      position: s.position as f64,
+      current_operation: s.current_operation.into(),
+    }
+  }
+}
+
+#[napi]
+#[derive(Clone)]
+pub struct Valve {
+  inner: RustValve,
+  pub key: u32,
+  pub device_id: u32,
+  pub name: String,
+  #[napi(ts_type = "EntityKind.Valve")]
+  pub kind: EntityKind,
+}
+
+impl Valve {
+  pub fn new(rust_valve: &RustValve) -> Self {
+    Valve {
+      inner: rust_valve.clone(),
+      key: rust_valve.key(),
+      device_id: rust_valve.device_id(),
+      name: rust_valve.name().to_string(),
+      kind: EntityKind::Valve,
+    }
+  }
+}
+
+#[napi]
+impl Valve {
+  #[napi(getter)]
+  pub fn state(&self) -> Result<ValveState> {
+    self
+      .inner
+      .get_state()
+      .map(Into::into)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi(getter)]
+  pub fn position(&self) -> Result<f64> {
+    self
+      .inner
+      .position()
+      .map(|v| v as f64)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi(getter)]
+  pub fn current_operation(&self) -> Result<ValveOperation> {
+    self
+      .inner
+      .current_operation()
+      .map(Into::into)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  /// Register a callback that is called whenever the valve state changes.
+  #[napi]
+  pub fn on_state_change(
+    &self,
+    callback: ThreadsafeFunction<ValveState, (), ValveState, Status, false, true>,
+  ) -> Result<()> {
+    let mut receiver = self.inner.state_receiver();
+
+    napi::bindgen_prelude::spawn(async move {
+      while receiver.changed().await.is_ok() {
+        if let Some(EntityState::Valve(s)) = receiver.borrow().clone() {
+          callback.call(s.into(), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+      }
+    });
+
+    Ok(())
+  }
+
+  #[napi]
+  pub async fn open(&self) -> Result<()> {
+    self
+      .inner
+      .open()
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi]
+  pub async fn close(&self) -> Result<()> {
+    self
+      .inner
+      .close()
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi]
+  pub async fn stop(&self) -> Result<()> {
+    self
+      .inner
+      .stop()
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  /// Position from `0` (closed) to `1` (open).
+  #[napi]
+  pub async fn set_position(&self, position: f64) -> Result<()> {
+    self
+      .inner
+      .set_position(position as f32)
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+}
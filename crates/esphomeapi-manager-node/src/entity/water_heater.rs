@@ -0,0 +1,181 @@
+use esphomeapi_manager::entity::{
+  BaseEntity as _, WaterHeater as RustWaterHeater, WaterHeaterState as RustWaterHeaterState,
+  WaterHeaterStateFlag,
+};
+use esphomeapi_manager::EntityState;
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi_derive::napi;
+
+use crate::entity::EntityKind;
+use crate::model::WaterHeaterMode;
+
+#[napi(object)]
+pub struct WaterHeaterCommandOptions {
+  pub mode: Option<WaterHeaterMode>,
+  pub target_temperature: Option<f64>,
+  pub target_temperature_low: Option<f64>,
+  pub target_temperature_high: Option<f64>,
+  /// Left as last reported when unset.
+  pub away: Option<bool>,
+  /// Left as last reported when unset.
+  pub on: Option<bool>,
+}
+
+#[napi(object)]
+pub struct WaterHeaterState {
+  pub mode: WaterHeaterMode,
+  pub current_temperature: f64,
+  pub target_temperature: f64,
+  pub target_temperature_low: f64,
+  pub target_temperature_high: f64,
+  pub away: bool,
+  pub on: bool,
+}
+
+impl From<RustWaterHeaterState> for WaterHeaterState {
+  fn from(s: RustWaterHeaterState) -> Self {
+    WaterHeaterState {
+      mode: s.mode.into(),
+      current_temperature: s.current_temperature as f64,
+      target_temperature: s.target_temperature as f64,
+      target_temperature_low: s.target_temperature_low as f64,
+      target_temperature_high: s.target_temperature_high as f64,
+      away: s.state.contains(WaterHeaterStateFlag::Away),
+      on: s.state.contains(WaterHeaterStateFlag::On),
+    }
+  }
+}
+
+#[napi]
+#[derive(Clone)]
+pub struct WaterHeater {
+  inner: RustWaterHeater,
+  pub key: u32,
+  pub device_id: u32,
+  pub name: String,
+  #[napi(ts_type = "EntityKind.WaterHeater")]
+  pub kind: EntityKind,
+}
+
+impl WaterHeater {
+  pub fn new(rust_water_heater: &RustWaterHeater) -> Self {
+    WaterHeater {
+      inner: rust_water_heater.clone(),
+      key: rust_water_heater.key(),
+      device_id: rust_water_heater.device_id(),
+      name: rust_water_heater.name().to_string(),
+      kind: EntityKind::WaterHeater,
+    }
+  }
+}
+
+#[napi]
+impl WaterHeater {
+  #[napi(getter)]
+  pub fn state(&self) -> Result<WaterHeaterState> {
+    self
+      .inner
+      .get_state()
+      .map(Into::into)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi(getter)]
+  pub fn mode(&self) -> Result<WaterHeaterMode> {
+    self
+      .inner
+      .mode()
+      .map(Into::into)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi(getter)]
+  pub fn current_temperature(&self) -> Result<f64> {
+    self
+      .inner
+      .current_temperature()
+      .map(|v| v as f64)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi(getter)]
+  pub fn target_temperature(&self) -> Result<f64> {
+    self
+      .inner
+      .target_temperature()
+      .map(|v| v as f64)
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  /// Register a callback that is called whenever the water heater state changes.
+  #[napi]
+  pub fn on_state_change(
+    &self,
+    callback: ThreadsafeFunction<WaterHeaterState, (), WaterHeaterState, Status, false, true>,
+  ) -> Result<()> {
+    let mut receiver = self.inner.state_receiver();
+
+    napi::bindgen_prelude::spawn(async move {
+      while receiver.changed().await.is_ok() {
+        if let Some(EntityState::WaterHeater(s)) = receiver.borrow().clone() {
+          callback.call(s.into(), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+      }
+    });
+
+    Ok(())
+  }
+
+  #[napi]
+  pub async fn set_mode(&self, mode: WaterHeaterMode) -> Result<()> {
+    self
+      .inner
+      .set_mode(mode.into())
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi]
+  pub async fn set_target_temperature(&self, target_temperature: f64) -> Result<()> {
+    self
+      .inner
+      .set_target_temperature(target_temperature as f32)
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
+  #[napi]
+  pub async fn send_command(&self, options: WaterHeaterCommandOptions) -> Result<()> {
+    let mut builder = self.inner.command();
+
+    if let Some(mode) = options.mode {
+      builder = builder.mode(mode.into());
+    }
+    if let Some(target_temperature) = options.target_temperature {
+      builder = builder.target_temperature(target_temperature as f32);
+    }
+    if let Some(target_temperature_low) = options.target_temperature_low {
+      builder = builder.target_temperature_low(target_temperature_low as f32);
+    }
+    if let Some(target_temperature_high) = options.target_temperature_high {
+      builder = builder.target_temperature_high(target_temperature_high as f32);
+    }
+    if options.away.is_some() || options.on.is_some() {
+      // The device replaces both flags at once
+      let mut state = self.inner.get_state().map(|s| s.state).unwrap_or_default();
+      if let Some(away) = options.away {
+        state.set(WaterHeaterStateFlag::Away, away);
+      }
+      if let Some(on) = options.on {
+        state.set(WaterHeaterStateFlag::On, on);
+      }
+      builder = builder.state(state);
+    }
+
+    builder
+      .send()
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+}
@@ -4,6 +4,8 @@ pub mod logger;
 mod manager;
 mod model;
 
+pub use manager::generate_noise_psk;
+
 pub type Result<T> = std::result::Result<T, Error>;
 
 #[derive(Debug, thiserror::Error)]
@@ -12,8 +14,8 @@ pub enum Error {
   EsphomeapiError(#[from] esphomeapi_manager::Error),
 }
 
-impl Into<napi::Error> for Error {
-  fn into(self) -> napi::Error {
-    napi::Error::new(napi::Status::GenericFailure, self.to_string())
+impl From<Error> for napi::Error {
+  fn from(value: Error) -> Self {
+    napi::Error::new(napi::Status::GenericFailure, value.to_string())
   }
 }
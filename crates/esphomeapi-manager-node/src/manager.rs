@@ -1,4 +1,7 @@
-use esphomeapi_manager::Manager as RustManager;
+use std::time::Duration;
+
+use esphomeapi_manager::entity::Entity as RustEntity;
+use esphomeapi_manager::{ClientBuilder, Manager as RustManager, ReconnectPolicy, TimeSource};
 use napi::bindgen_prelude::*;
 use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
 
@@ -8,7 +11,8 @@ use tracing::warn;
 
 use crate::entity::{self, Entity};
 use crate::model::{
-  DeviceInfo, HomeAssistantEvent, HomeassistantActionRequest, LogEvent, LogLevel,
+  DeviceInfo, HomeAssistantEvent, HomeassistantActionRequest, HomeassistantActionResponse,
+  LogEvent, LogLevel,
 };
 
 #[napi(object)]
@@ -20,6 +24,87 @@ pub struct ConnectionOptions {
   pub psk: Option<String>,
   pub client_info: Option<String>,
   pub keep_alive_duration: Option<u32>,
+  /// Seconds allowed for dialing, the handshake and the hello exchange.
+  pub connect_timeout: Option<u32>,
+  /// Seconds to wait for a response to a single request.
+  pub request_timeout: Option<u32>,
+  pub reconnect: Option<ReconnectOptions>,
+  /// Timezone sent to devices asking for the time, either a POSIX TZ string
+  /// or an IANA zone like `Europe/Berlin`.
+  pub timezone: Option<String>,
+}
+
+/// Automatic reconnect behaviour after the device drops the connection.
+#[napi(object)]
+pub struct ReconnectOptions {
+  pub enabled: Option<bool>,
+  /// Seconds before the first attempt; doubles after each failure.
+  pub initial_delay: Option<u32>,
+  /// Upper bound in seconds for the delay between attempts.
+  pub max_delay: Option<u32>,
+  /// Give up after this many failed attempts. Retries forever when unset.
+  pub max_attempts: Option<u32>,
+  /// Random spread applied to each delay, as a fraction (e.g. `0.2` for ±20%).
+  pub jitter: Option<f64>,
+}
+
+impl TryFrom<ConnectionOptions> for ClientBuilder {
+  type Error = napi::Error;
+
+  fn try_from(options: ConnectionOptions) -> Result<Self> {
+    let mut builder = ClientBuilder::new(options.address).port(options.port);
+    if let Some(password) = options.password {
+      builder = builder.password(password);
+    }
+    if let Some(expected_name) = options.expected_name {
+      builder = builder.expected_name(expected_name);
+    }
+    if let Some(psk) = options.psk {
+      builder = builder.psk(psk);
+    }
+    if let Some(client_info) = options.client_info {
+      builder = builder.client_info(client_info);
+    }
+    if let Some(keep_alive) = options.keep_alive_duration {
+      builder = builder.keep_alive(Duration::from_secs(keep_alive as u64));
+    }
+    if let Some(connect_timeout) = options.connect_timeout {
+      builder = builder.connect_timeout(Duration::from_secs(connect_timeout as u64));
+    }
+    if let Some(request_timeout) = options.request_timeout {
+      builder = builder.request_timeout(Duration::from_secs(request_timeout as u64));
+    }
+    if let Some(reconnect) = options.reconnect {
+      builder = builder.reconnect_policy(reconnect.into());
+    }
+    if let Some(timezone) = options.timezone {
+      let time_source = if timezone.contains('/') {
+        TimeSource::iana(&timezone).map_err(|e| Error::new(Status::InvalidArg, e.to_string()))?
+      } else {
+        TimeSource::posix(timezone)
+      };
+      builder = builder.time_source(time_source);
+    }
+    Ok(builder)
+  }
+}
+
+impl From<ReconnectOptions> for ReconnectPolicy {
+  fn from(options: ReconnectOptions) -> Self {
+    let default = ReconnectPolicy::default();
+    ReconnectPolicy {
+      enabled: options.enabled.unwrap_or(default.enabled),
+      initial_delay: options.initial_delay.map_or(default.initial_delay, |secs| {
+        Duration::from_secs(secs as u64)
+      }),
+      max_delay: options
+        .max_delay
+        .map_or(default.max_delay, |secs| Duration::from_secs(secs as u64)),
+      max_attempts: options.max_attempts,
+      jitter: options.jitter.unwrap_or(default.jitter),
+      ..default
+    }
+  }
 }
 
 #[napi]
@@ -33,24 +118,39 @@ pub struct Manager {
 impl Manager {
   #[napi(factory)]
   pub async fn connect(options: ConnectionOptions) -> Result<Manager> {
-    let manager = RustManager::new(
-      options.address,
-      options.port,
-      options.password,
-      options.expected_name,
-      options.psk,
-      options.client_info,
-      options.keep_alive_duration,
-    )
-    .await;
+    let manager = RustManager::new(options.try_into()?)
+      .await
+      .map_err(crate::Error::from)?;
 
     let entities = manager
       .get_entities()
       .values()
-      .filter_map(|e| match e {
-        esphomeapi_manager::entity::Entity::Light(l) => Some(Either::A(entity::Light::new(l))),
-        esphomeapi_manager::entity::Entity::Switch(s) => Some(Either::B(entity::Switch::new(s))),
-        _ => None,
+      .map(|e| match e {
+        RustEntity::AlarmControlPanel(e) => Either25::A(entity::AlarmControlPanel::new(e)),
+        RustEntity::BinarySensor(e) => Either25::B(entity::BinarySensor::new(e)),
+        RustEntity::Button(e) => Either25::C(entity::Button::new(e)),
+        RustEntity::Camera(e) => Either25::D(entity::Camera::new(e)),
+        RustEntity::Climate(e) => Either25::E(entity::Climate::new(e)),
+        RustEntity::Cover(e) => Either25::F(entity::Cover::new(e)),
+        RustEntity::Date(e) => Either25::G(entity::Date::new(e)),
+        RustEntity::DateTime(e) => Either25::H(entity::DateTime::new(e)),
+        RustEntity::Event(e) => Either25::I(entity::Event::new(e)),
+        RustEntity::Fan(e) => Either25::J(entity::Fan::new(e)),
+        RustEntity::Infrared(e) => Either25::K(entity::Infrared::new(e)),
+        RustEntity::Light(e) => Either25::L(entity::Light::new(e)),
+        RustEntity::Lock(e) => Either25::M(entity::Lock::new(e)),
+        RustEntity::MediaPlayer(e) => Either25::N(entity::MediaPlayer::new(e)),
+        RustEntity::Number(e) => Either25::O(entity::Number::new(e)),
+        RustEntity::Select(e) => Either25::P(entity::Select::new(e)),
+        RustEntity::Sensor(e) => Either25::Q(entity::Sensor::new(e)),
+        RustEntity::Siren(e) => Either25::R(entity::Siren::new(e)),
+        RustEntity::Switch(e) => Either25::S(entity::Switch::new(e)),
+        RustEntity::Text(e) => Either25::T(entity::Text::new(e)),
+        RustEntity::TextSensor(e) => Either25::U(entity::TextSensor::new(e)),
+        RustEntity::Time(e) => Either25::V(entity::Time::new(e)),
+        RustEntity::Update(e) => Either25::W(entity::Update::new(e)),
+        RustEntity::Valve(e) => Either25::X(entity::Valve::new(e)),
+        RustEntity::WaterHeater(e) => Either25::Y(entity::WaterHeater::new(e)),
       })
       .collect();
 
@@ -58,8 +158,8 @@ impl Manager {
 
     Ok(Manager {
       inner: manager,
-      device_info: device_info,
-      entities: entities,
+      device_info,
+      entities,
     })
   }
 
@@ -223,6 +323,22 @@ impl Manager {
     Ok(())
   }
 
+  /// Answer an action request that set `wantsResponse`.
+  ///
+  /// Devices wait for this reply until they time out, so send it even when
+  /// the action failed.
+  #[napi]
+  pub async fn send_home_assistant_action_response(
+    &self,
+    response: HomeassistantActionResponse,
+  ) -> Result<()> {
+    self
+      .inner
+      .send_home_assistant_action_response(response.into())
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
   /// Subscribe to ESPHome logs.
   ///
   /// Sends the subscription request to the device, then calls `callback` for every
@@ -319,7 +435,8 @@ impl Manager {
   }
 
   /// Returns a Promise that resolves the next time the manager successfully
-  /// reconnects after a device-initiated disconnect.
+  /// reconnects after a device-initiated disconnect. Rejects once the manager
+  /// gives up reconnecting.
   #[napi]
   pub async fn on_reconnect(&self) -> Result<()> {
     let mut rx = self.inner.on_reconnect();
@@ -328,6 +445,19 @@ impl Manager {
       .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
   }
 
+  /// Move the device to a freshly generated Noise PSK and reconnect with it.
+  ///
+  /// Resolves to the new base64 key once connected with it. Store the key:
+  /// the device accepts no other connections from then on.
+  #[napi]
+  pub async fn rotate_noise_psk(&self) -> Result<String> {
+    self
+      .inner
+      .rotate_noise_psk()
+      .await
+      .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+  }
+
   #[napi]
   pub async fn disconnect(&self) -> Result<()> {
     self
@@ -337,3 +467,9 @@ impl Manager {
       .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
   }
 }
+
+/// Generate a random base64-encoded Noise PSK.
+#[napi]
+pub fn generate_noise_psk() -> String {
+  esphomeapi_manager::generate_noise_psk()
+}
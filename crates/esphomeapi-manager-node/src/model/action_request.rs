@@ -1,6 +1,10 @@
 use std::collections::HashMap;
 
-use esphomeapi_manager::HomeassistantActionRequest as RustActionRequest;
+use esphomeapi_manager::{
+  HomeassistantActionRequest as RustActionRequest,
+  HomeassistantActionResponse as RustActionResponse,
+};
+use napi::bindgen_prelude::Buffer;
 use napi_derive::napi;
 
 #[napi(object)]
@@ -29,3 +33,28 @@ impl From<RustActionRequest> for HomeassistantActionRequest {
     }
   }
 }
+
+/// Reply to an action request that set `wantsResponse`.
+#[napi(object)]
+pub struct HomeassistantActionResponse {
+  /// The `callId` of the request being answered.
+  pub call_id: u32,
+  pub success: bool,
+  pub error_message: Option<String>,
+  /// JSON result of the action.
+  pub response_data: Option<Buffer>,
+}
+
+impl From<HomeassistantActionResponse> for RustActionResponse {
+  fn from(r: HomeassistantActionResponse) -> Self {
+    Self {
+      call_id: r.call_id,
+      success: r.success,
+      error_message: r.error_message.unwrap_or_default(),
+      response_data: r
+        .response_data
+        .map(|data| data.to_vec())
+        .unwrap_or_default(),
+    }
+  }
+}
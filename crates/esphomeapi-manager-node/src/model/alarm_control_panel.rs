@@ -0,0 +1,50 @@
+use esphomeapi_manager::entity::AlarmControlPanelState as RustAlarmControlPanelState;
+use napi_derive::napi;
+
+#[napi]
+pub enum AlarmControlPanelState {
+  Disarmed = 0,
+  ArmedHome,
+  ArmedAway,
+  ArmedNight,
+  ArmedVacation,
+  ArmedCustomBypass,
+  Pending,
+  Arming,
+  Disarming,
+  Triggered,
+}
+
+impl From<RustAlarmControlPanelState> for AlarmControlPanelState {
+  fn from(value: RustAlarmControlPanelState) -> Self {
+    match value {
+      RustAlarmControlPanelState::Disarmed => AlarmControlPanelState::Disarmed,
+      RustAlarmControlPanelState::ArmedHome => AlarmControlPanelState::ArmedHome,
+      RustAlarmControlPanelState::ArmedAway => AlarmControlPanelState::ArmedAway,
+      RustAlarmControlPanelState::ArmedNight => AlarmControlPanelState::ArmedNight,
+      RustAlarmControlPanelState::ArmedVacation => AlarmControlPanelState::ArmedVacation,
+      RustAlarmControlPanelState::ArmedCustomBypass => AlarmControlPanelState::ArmedCustomBypass,
+      RustAlarmControlPanelState::Pending => AlarmControlPanelState::Pending,
+      RustAlarmControlPanelState::Arming => AlarmControlPanelState::Arming,
+      RustAlarmControlPanelState::Disarming => AlarmControlPanelState::Disarming,
+      RustAlarmControlPanelState::Triggered => AlarmControlPanelState::Triggered,
+    }
+  }
+}
+
+impl From<AlarmControlPanelState> for RustAlarmControlPanelState {
+  fn from(value: AlarmControlPanelState) -> Self {
+    match value {
+      AlarmControlPanelState::Disarmed => RustAlarmControlPanelState::Disarmed,
+      AlarmControlPanelState::ArmedHome => RustAlarmControlPanelState::ArmedHome,
+      AlarmControlPanelState::ArmedAway => RustAlarmControlPanelState::ArmedAway,
+      AlarmControlPanelState::ArmedNight => RustAlarmControlPanelState::ArmedNight,
+      AlarmControlPanelState::ArmedVacation => RustAlarmControlPanelState::ArmedVacation,
+      AlarmControlPanelState::ArmedCustomBypass => RustAlarmControlPanelState::ArmedCustomBypass,
+      AlarmControlPanelState::Pending => RustAlarmControlPanelState::Pending,
+      AlarmControlPanelState::Arming => RustAlarmControlPanelState::Arming,
+      AlarmControlPanelState::Disarming => RustAlarmControlPanelState::Disarming,
+      AlarmControlPanelState::Triggered => RustAlarmControlPanelState::Triggered,
+    }
+  }
+}
@@ -0,0 +1,201 @@
+use esphomeapi_manager::entity::{
+  ClimateAction as RustClimateAction, ClimateFanMode as RustClimateFanMode,
+  ClimateMode as RustClimateMode, ClimatePreset as RustClimatePreset,
+  ClimateSwingMode as RustClimateSwingMode,
+};
+use napi_derive::napi;
+
+#[napi]
+pub enum ClimateMode {
+  Off = 0,
+  HeatCool,
+  Cool,
+  Heat,
+  FanOnly,
+  Dry,
+  Auto,
+}
+
+impl From<RustClimateMode> for ClimateMode {
+  fn from(value: RustClimateMode) -> Self {
+    match value {
+      RustClimateMode::Off => ClimateMode::Off,
+      RustClimateMode::HeatCool => ClimateMode::HeatCool,
+      RustClimateMode::Cool => ClimateMode::Cool,
+      RustClimateMode::Heat => ClimateMode::Heat,
+      RustClimateMode::FanOnly => ClimateMode::FanOnly,
+      RustClimateMode::Dry => ClimateMode::Dry,
+      RustClimateMode::Auto => ClimateMode::Auto,
+    }
+  }
+}
+
+impl From<ClimateMode> for RustClimateMode {
+  fn from(value: ClimateMode) -> Self {
+    match value {
+      ClimateMode::Off => RustClimateMode::Off,
+      ClimateMode::HeatCool => RustClimateMode::HeatCool,
+      ClimateMode::Cool => RustClimateMode::Cool,
+      ClimateMode::Heat => RustClimateMode::Heat,
+      ClimateMode::FanOnly => RustClimateMode::FanOnly,
+      ClimateMode::Dry => RustClimateMode::Dry,
+      ClimateMode::Auto => RustClimateMode::Auto,
+    }
+  }
+}
+
+#[napi]
+pub enum ClimateFanMode {
+  On = 0,
+  Off,
+  Auto,
+  Low,
+  Medium,
+  High,
+  Middle,
+  Focus,
+  Diffuse,
+  Quiet,
+}
+
+impl From<RustClimateFanMode> for ClimateFanMode {
+  fn from(value: RustClimateFanMode) -> Self {
+    match value {
+      RustClimateFanMode::On => ClimateFanMode::On,
+      RustClimateFanMode::Off => ClimateFanMode::Off,
+      RustClimateFanMode::Auto => ClimateFanMode::Auto,
+      RustClimateFanMode::Low => ClimateFanMode::Low,
+      RustClimateFanMode::Medium => ClimateFanMode::Medium,
+      RustClimateFanMode::High => ClimateFanMode::High,
+      RustClimateFanMode::Middle => ClimateFanMode::Middle,
+      RustClimateFanMode::Focus => ClimateFanMode::Focus,
+      RustClimateFanMode::Diffuse => ClimateFanMode::Diffuse,
+      RustClimateFanMode::Quiet => ClimateFanMode::Quiet,
+    }
+  }
+}
+
+impl From<ClimateFanMode> for RustClimateFanMode {
+  fn from(value: ClimateFanMode) -> Self {
+    match value {
+      ClimateFanMode::On => RustClimateFanMode::On,
+      ClimateFanMode::Off => RustClimateFanMode::Off,
+      ClimateFanMode::Auto => RustClimateFanMode::Auto,
+      ClimateFanMode::Low => RustClimateFanMode::Low,
+      ClimateFanMode::Medium => RustClimateFanMode::Medium,
+      ClimateFanMode::High => RustClimateFanMode::High,
+      ClimateFanMode::Middle => RustClimateFanMode::Middle,
+      ClimateFanMode::Focus => RustClimateFanMode::Focus,
+      ClimateFanMode::Diffuse => RustClimateFanMode::Diffuse,
+      ClimateFanMode::Quiet => RustClimateFanMode::Quiet,
+    }
+  }
+}
+
+#[napi]
+pub enum ClimateSwingMode {
+  Off = 0,
+  Both,
+  Vertical,
+  Horizontal,
+}
+
+impl From<RustClimateSwingMode> for ClimateSwingMode {
+  fn from(value: RustClimateSwingMode) -> Self {
+    match value {
+      RustClimateSwingMode::Off => ClimateSwingMode::Off,
+      RustClimateSwingMode::Both => ClimateSwingMode::Both,
+      RustClimateSwingMode::Vertical => ClimateSwingMode::Vertical,
+      RustClimateSwingMode::Horizontal => ClimateSwingMode::Horizontal,
+    }
+  }
+}
+
+impl From<ClimateSwingMode> for RustClimateSwingMode {
+  fn from(value: ClimateSwingMode) -> Self {
+    match value {
+      ClimateSwingMode::Off => RustClimateSwingMode::Off,
+      ClimateSwingMode::Both => RustClimateSwingMode::Both,
+      ClimateSwingMode::Vertical => RustClimateSwingMode::Vertical,
+      ClimateSwingMode::Horizontal => RustClimateSwingMode::Horizontal,
+    }
+  }
+}
+
+#[napi]
+pub enum ClimateAction {
+  Off = 0,
+  Cooling,
+  Heating,
+  Idle,
+  Drying,
+  Fan,
+}
+
+impl From<RustClimateAction> for ClimateAction {
+  fn from(value: RustClimateAction) -> Self {
+    match value {
+      RustClimateAction::Off => ClimateAction::Off,
+      RustClimateAction::Cooling => ClimateAction::Cooling,
+      RustClimateAction::Heating => ClimateAction::Heating,
+      RustClimateAction::Idle => ClimateAction::Idle,
+      RustClimateAction::Drying => ClimateAction::Drying,
+      RustClimateAction::Fan => ClimateAction::Fan,
+    }
+  }
+}
+
+impl From<ClimateAction> for RustClimateAction {
+  fn from(value: ClimateAction) -> Self {
+    match value {
+      ClimateAction::Off => RustClimateAction::Off,
+      ClimateAction::Cooling => RustClimateAction::Cooling,
+      ClimateAction::Heating => RustClimateAction::Heating,
+      ClimateAction::Idle => RustClimateAction::Idle,
+      ClimateAction::Drying => RustClimateAction::Drying,
+      ClimateAction::Fan => RustClimateAction::Fan,
+    }
+  }
+}
+
+#[napi]
+pub enum ClimatePreset {
+  None = 0,
+  Home,
+  Away,
+  Boost,
+  Comfort,
+  Eco,
+  Sleep,
+  Activity,
+}
+
+impl From<RustClimatePreset> for ClimatePreset {
+  fn from(value: RustClimatePreset) -> Self {
+    match value {
+      RustClimatePreset::None => ClimatePreset::None,
+      RustClimatePreset::Home => ClimatePreset::Home,
+      RustClimatePreset::Away => ClimatePreset::Away,
+      RustClimatePreset::Boost => ClimatePreset::Boost,
+      RustClimatePreset::Comfort => ClimatePreset::Comfort,
+      RustClimatePreset::Eco => ClimatePreset::Eco,
+      RustClimatePreset::Sleep => ClimatePreset::Sleep,
+      RustClimatePreset::Activity => ClimatePreset::Activity,
+    }
+  }
+}
+
+impl From<ClimatePreset> for RustClimatePreset {
+  fn from(value: ClimatePreset) -> Self {
+    match value {
+      ClimatePreset::None => RustClimatePreset::None,
+      ClimatePreset::Home => RustClimatePreset::Home,
+      ClimatePreset::Away => RustClimatePreset::Away,
+      ClimatePreset::Boost => RustClimatePreset::Boost,
+      ClimatePreset::Comfort => RustClimatePreset::Comfort,
+      ClimatePreset::Eco => RustClimatePreset::Eco,
+      ClimatePreset::Sleep => RustClimatePreset::Sleep,
+      ClimatePreset::Activity => RustClimatePreset::Activity,
+    }
+  }
+}
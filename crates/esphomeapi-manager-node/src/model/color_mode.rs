@@ -2,6 +2,7 @@ use esphomeapi_manager::entity::ColorMode as RustColorMode;
 use napi_derive::napi;
 
 #[napi]
+#[allow(clippy::upper_case_acronyms)]
 pub enum ColorMode {
   Unknown = 0,
   OnOff = 1,
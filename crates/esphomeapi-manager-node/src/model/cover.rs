@@ -0,0 +1,29 @@
+use esphomeapi_manager::entity::CoverOperation as RustCoverOperation;
+use napi_derive::napi;
+
+#[napi]
+pub enum CoverOperation {
+  Idle = 0,
+  Opening,
+  Closing,
+}
+
+impl From<RustCoverOperation> for CoverOperation {
+  fn from(value: RustCoverOperation) -> Self {
+    match value {
+      RustCoverOperation::Idle => CoverOperation::Idle,
+      RustCoverOperation::Opening => CoverOperation::Opening,
+      RustCoverOperation::Closing => CoverOperation::Closing,
+    }
+  }
+}
+
+impl From<CoverOperation> for RustCoverOperation {
+  fn from(value: CoverOperation) -> Self {
+    match value {
+      CoverOperation::Idle => RustCoverOperation::Idle,
+      CoverOperation::Opening => RustCoverOperation::Opening,
+      CoverOperation::Closing => RustCoverOperation::Closing,
+    }
+  }
+}
@@ -1,4 +1,6 @@
-use esphomeapi_manager::DeviceInfo as RustDeviceInfo;
+use esphomeapi_manager::{
+  AreaInfo as RustAreaInfo, DeviceInfo as RustDeviceInfo, SubDeviceInfo as RustSubDeviceInfo,
+};
 use napi_derive::napi;
 
 #[derive(Clone)]
@@ -21,6 +23,15 @@ pub struct DeviceInfo {
   pub legacy_bluetooth_proxy_version: u32,
   pub bluetooth_proxy_feature_flags: u32,
   pub suggested_area: String,
+  pub bluetooth_mac_address: String,
+  pub api_encryption_supported: bool,
+  /// Sub-devices whose entities are exposed through this node.
+  pub devices: Vec<SubDeviceInfo>,
+  pub areas: Vec<AreaInfo>,
+  /// Area of the node itself.
+  pub area: Option<AreaInfo>,
+  pub zwave_proxy_feature_flags: u32,
+  pub zwave_home_id: u32,
 }
 
 impl From<RustDeviceInfo> for DeviceInfo {
@@ -43,6 +54,48 @@ impl From<RustDeviceInfo> for DeviceInfo {
       legacy_bluetooth_proxy_version: info.legacy_bluetooth_proxy_version,
       bluetooth_proxy_feature_flags: info.bluetooth_proxy_feature_flags,
       suggested_area: info.suggested_area,
+      bluetooth_mac_address: info.bluetooth_mac_address,
+      api_encryption_supported: info.api_encryption_supported,
+      devices: info.devices.into_iter().map(Into::into).collect(),
+      areas: info.areas.into_iter().map(Into::into).collect(),
+      area: info.area.map(Into::into),
+      zwave_proxy_feature_flags: info.zwave_proxy_feature_flags,
+      zwave_home_id: info.zwave_home_id,
+    }
+  }
+}
+
+#[derive(Clone)]
+#[napi(object)]
+pub struct AreaInfo {
+  pub area_id: u32,
+  pub name: String,
+}
+
+impl From<RustAreaInfo> for AreaInfo {
+  fn from(area: RustAreaInfo) -> Self {
+    AreaInfo {
+      area_id: area.area_id,
+      name: area.name,
+    }
+  }
+}
+
+#[derive(Clone)]
+#[napi(object)]
+pub struct SubDeviceInfo {
+  pub device_id: u32,
+  pub name: String,
+  /// 0 when the sub-device has no area.
+  pub area_id: u32,
+}
+
+impl From<RustSubDeviceInfo> for SubDeviceInfo {
+  fn from(device: RustSubDeviceInfo) -> Self {
+    SubDeviceInfo {
+      device_id: device.device_id,
+      name: device.name,
+      area_id: device.area_id,
     }
   }
 }
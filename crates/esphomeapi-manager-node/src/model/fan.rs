@@ -0,0 +1,26 @@
+use esphomeapi_manager::entity::FanDirection as RustFanDirection;
+use napi_derive::napi;
+
+#[napi]
+pub enum FanDirection {
+  Forward = 0,
+  Reverse,
+}
+
+impl From<RustFanDirection> for FanDirection {
+  fn from(value: RustFanDirection) -> Self {
+    match value {
+      RustFanDirection::Forward => FanDirection::Forward,
+      RustFanDirection::Reverse => FanDirection::Reverse,
+    }
+  }
+}
+
+impl From<FanDirection> for RustFanDirection {
+  fn from(value: FanDirection) -> Self {
+    match value {
+      FanDirection::Forward => RustFanDirection::Forward,
+      FanDirection::Reverse => RustFanDirection::Reverse,
+    }
+  }
+}
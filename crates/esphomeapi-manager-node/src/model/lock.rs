@@ -0,0 +1,38 @@
+use esphomeapi_manager::entity::LockState as RustLockState;
+use napi_derive::napi;
+
+#[napi]
+pub enum LockState {
+  None = 0,
+  Locked,
+  Unlocked,
+  Jammed,
+  Locking,
+  Unlocking,
+}
+
+impl From<RustLockState> for LockState {
+  fn from(value: RustLockState) -> Self {
+    match value {
+      RustLockState::None => LockState::None,
+      RustLockState::Locked => LockState::Locked,
+      RustLockState::Unlocked => LockState::Unlocked,
+      RustLockState::Jammed => LockState::Jammed,
+      RustLockState::Locking => LockState::Locking,
+      RustLockState::Unlocking => LockState::Unlocking,
+    }
+  }
+}
+
+impl From<LockState> for RustLockState {
+  fn from(value: LockState) -> Self {
+    match value {
+      LockState::None => RustLockState::None,
+      LockState::Locked => RustLockState::Locked,
+      LockState::Unlocked => RustLockState::Unlocked,
+      LockState::Jammed => RustLockState::Jammed,
+      LockState::Locking => RustLockState::Locking,
+      LockState::Unlocking => RustLockState::Unlocking,
+    }
+  }
+}
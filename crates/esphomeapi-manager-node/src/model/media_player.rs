@@ -0,0 +1,41 @@
+use esphomeapi_manager::entity::MediaPlayerState as RustMediaPlayerState;
+use napi_derive::napi;
+
+#[napi]
+pub enum MediaPlayerState {
+  None = 0,
+  Idle,
+  Playing,
+  Paused,
+  Announcing,
+  Off,
+  On,
+}
+
+impl From<RustMediaPlayerState> for MediaPlayerState {
+  fn from(value: RustMediaPlayerState) -> Self {
+    match value {
+      RustMediaPlayerState::None => MediaPlayerState::None,
+      RustMediaPlayerState::Idle => MediaPlayerState::Idle,
+      RustMediaPlayerState::Playing => MediaPlayerState::Playing,
+      RustMediaPlayerState::Paused => MediaPlayerState::Paused,
+      RustMediaPlayerState::Announcing => MediaPlayerState::Announcing,
+      RustMediaPlayerState::Off => MediaPlayerState::Off,
+      RustMediaPlayerState::On => MediaPlayerState::On,
+    }
+  }
+}
+
+impl From<MediaPlayerState> for RustMediaPlayerState {
+  fn from(value: MediaPlayerState) -> Self {
+    match value {
+      MediaPlayerState::None => RustMediaPlayerState::None,
+      MediaPlayerState::Idle => RustMediaPlayerState::Idle,
+      MediaPlayerState::Playing => RustMediaPlayerState::Playing,
+      MediaPlayerState::Paused => RustMediaPlayerState::Paused,
+      MediaPlayerState::Announcing => RustMediaPlayerState::Announcing,
+      MediaPlayerState::Off => RustMediaPlayerState::Off,
+      MediaPlayerState::On => RustMediaPlayerState::On,
+    }
+  }
+}
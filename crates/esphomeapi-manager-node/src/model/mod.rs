@@ -1,11 +1,27 @@
 mod action_request;
+mod alarm_control_panel;
+mod climate;
 mod color_mode;
+mod cover;
 mod device_info;
+mod fan;
 mod ha_event;
+mod lock;
 mod logs;
+mod media_player;
+mod valve;
+mod water_heater;
 
-pub use action_request::HomeassistantActionRequest;
+pub use action_request::{HomeassistantActionRequest, HomeassistantActionResponse};
+pub use alarm_control_panel::AlarmControlPanelState;
+pub use climate::{ClimateAction, ClimateFanMode, ClimateMode, ClimatePreset, ClimateSwingMode};
 pub use color_mode::ColorMode;
+pub use cover::CoverOperation;
 pub use device_info::DeviceInfo;
+pub use fan::FanDirection;
 pub use ha_event::HomeAssistantEvent;
+pub use lock::LockState;
 pub use logs::{LogEvent, LogLevel};
+pub use media_player::MediaPlayerState;
+pub use valve::ValveOperation;
+pub use water_heater::WaterHeaterMode;
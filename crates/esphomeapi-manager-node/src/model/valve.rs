@@ -0,0 +1,29 @@
+use esphomeapi_manager::entity::ValveOperation as RustValveOperation;
+use napi_derive::napi;
+
+#[napi]
+pub enum ValveOperation {
+  Idle = 0,
+  Opening,
+  Closing,
+}
+
+impl From<RustValveOperation> for ValveOperation {
+  fn from(value: RustValveOperation) -> Self {
+    match value {
+      RustValveOperation::Idle => ValveOperation::Idle,
+      RustValveOperation::Opening => ValveOperation::Opening,
+      RustValveOperation::Closing => ValveOperation::Closing,
+    }
+  }
+}
+
+impl From<ValveOperation> for RustValveOperation {
+  fn from(value: ValveOperation) -> Self {
+    match value {
+      ValveOperation::Idle => RustValveOperation::Idle,
+      ValveOperation::Opening => RustValveOperation::Opening,
+      ValveOperation::Closing => RustValveOperation::Closing,
+    }
+  }
+}
@@ -0,0 +1,41 @@
+use esphomeapi_manager::entity::WaterHeaterMode as RustWaterHeaterMode;
+use napi_derive::napi;
+
+#[napi]
+pub enum WaterHeaterMode {
+  Off = 0,
+  Eco,
+  Electric,
+  Performance,
+  HighDemand,
+  HeatPump,
+  Gas,
+}
+
+impl From<RustWaterHeaterMode> for WaterHeaterMode {
+  fn from(value: RustWaterHeaterMode) -> Self {
+    match value {
+      RustWaterHeaterMode::Off => WaterHeaterMode::Off,
+      RustWaterHeaterMode::Eco => WaterHeaterMode::Eco,
+      RustWaterHeaterMode::Electric => WaterHeaterMode::Electric,
+      RustWaterHeaterMode::Performance => WaterHeaterMode::Performance,
+      RustWaterHeaterMode::HighDemand => WaterHeaterMode::HighDemand,
+      RustWaterHeaterMode::HeatPump => WaterHeaterMode::HeatPump,
+      RustWaterHeaterMode::Gas => WaterHeaterMode::Gas,
+    }
+  }
+}
+
+impl From<WaterHeaterMode> for RustWaterHeaterMode {
+  fn from(value: WaterHeaterMode) -> Self {
+    match value {
+      WaterHeaterMode::Off => RustWaterHeaterMode::Off,
+      WaterHeaterMode::Eco => RustWaterHeaterMode::Eco,
+      WaterHeaterMode::Electric => RustWaterHeaterMode::Electric,
+      WaterHeaterMode::Performance => RustWaterHeaterMode::Performance,
+      WaterHeaterMode::HighDemand => RustWaterHeaterMode::HighDemand,
+      WaterHeaterMode::HeatPump => RustWaterHeaterMode::HeatPump,
+      WaterHeaterMode::Gas => RustWaterHeaterMode::Gas,
+    }
+  }
+}
@@ -0,0 +1,133 @@
+use std::sync::Arc;
+
+use esphomeapi::{
+  CommandHandle,
+  model::{AlarmControlPanelEntityState, AlarmControlPanelInfo, EntityState},
+};
+use tokio::sync::watch;
+
+pub use esphomeapi::model::{AlarmControlPanelCommand, AlarmControlPanelState};
+
+use super::{BaseEntity, StateError, StateResult};
+
+#[derive(Clone)]
+pub struct AlarmControlPanel {
+  client: Arc<CommandHandle>,
+  info: AlarmControlPanelInfo,
+  state: watch::Receiver<Option<EntityState>>,
+}
+
+impl AlarmControlPanel {
+  pub fn new(
+    client: Arc<CommandHandle>,
+    info: AlarmControlPanelInfo,
+    state: watch::Receiver<Option<EntityState>>,
+  ) -> Self {
+    AlarmControlPanel {
+      client,
+      info,
+      state,
+    }
+  }
+
+  pub fn info(&self) -> &AlarmControlPanelInfo {
+    &self.info
+  }
+
+  pub fn get_state(&self) -> StateResult<AlarmControlPanelEntityState> {
+    match self.state.borrow().as_ref() {
+      Some(EntityState::AlarmControlPanel(state)) => Ok(state.clone()),
+      Some(_) => Err(StateError::NotValidState),
+      None => Err(StateError::EntityKeyNotFound(self.info.entity_info.key)),
+    }
+  }
+
+  /// Returns a cloned receiver for watching state changes from an external context.
+  pub fn state_receiver(&self) -> watch::Receiver<Option<EntityState>> {
+    self.state.clone()
+  }
+
+  /// Wait for the next state change and return the updated state.
+  pub async fn state_changed(&mut self) -> StateResult<AlarmControlPanelEntityState> {
+    self
+      .state
+      .changed()
+      .await
+      .map_err(|_| StateError::EntityKeyNotFound(self.info.entity_info.key))?;
+    self.get_state()
+  }
+
+  pub fn alarm_state(&self) -> StateResult<AlarmControlPanelState> {
+    Ok(self.get_state()?.state)
+  }
+
+  /// Send a panel command; `code` is only needed when the panel requires one.
+  pub async fn send_command(
+    &self,
+    command: AlarmControlPanelCommand,
+    code: Option<String>,
+  ) -> crate::Result<()> {
+    Ok(
+      self
+        .client
+        .alarm_control_panel_command(self.info.entity_info.key, command, code)
+        .await?,
+    )
+  }
+
+  pub async fn disarm(&self, code: Option<String>) -> crate::Result<()> {
+    self
+      .send_command(AlarmControlPanelCommand::Disarm, code)
+      .await
+  }
+
+  pub async fn arm_home(&self, code: Option<String>) -> crate::Result<()> {
+    self
+      .send_command(AlarmControlPanelCommand::ArmHome, code)
+      .await
+  }
+
+  pub async fn arm_away(&self, code: Option<String>) -> crate::Result<()> {
+    self
+      .send_command(AlarmControlPanelCommand::ArmAway, code)
+      .await
+  }
+
+  pub async fn arm_night(&self, code: Option<String>) -> crate::Result<()> {
+    self
+      .send_command(AlarmControlPanelCommand::ArmNight, code)
+      .await
+  }
+
+  pub async fn arm_vacation(&self, code: Option<String>) -> crate::Result<()> {
+    self
+      .send_command(AlarmControlPanelCommand::ArmVacation, code)
+      .await
+  }
+
+  pub async fn arm_custom_bypass(&self, code: Option<String>) -> crate::Result<()> {
+    self
+      .send_command(AlarmControlPanelCommand::ArmCustomBypass, code)
+      .await
+  }
+
+  pub async fn trigger(&self, code: Option<String>) -> crate::Result<()> {
+    self
+      .send_command(AlarmControlPanelCommand::Trigger, code)
+      .await
+  }
+}
+
+impl BaseEntity for AlarmControlPanel {
+  fn key(&self) -> u32 {
+    self.info.entity_info.key
+  }
+
+  fn device_id(&self) -> u32 {
+    self.info.entity_info.device_id
+  }
+
+  fn name(&self) -> String {
+    self.info.entity_info.name.clone()
+  }
+}
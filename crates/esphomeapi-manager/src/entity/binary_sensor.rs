@@ -0,0 +1,61 @@
+use esphomeapi::model::{BinarySensorInfo, BinarySensorState, EntityState};
+use tokio::sync::watch;
+
+use super::{BaseEntity, StateError, StateResult};
+
+#[derive(Clone)]
+pub struct BinarySensor {
+  info: BinarySensorInfo,
+  state: watch::Receiver<Option<EntityState>>,
+}
+
+impl BinarySensor {
+  pub fn new(info: BinarySensorInfo, state: watch::Receiver<Option<EntityState>>) -> Self {
+    BinarySensor { info, state }
+  }
+
+  pub fn info(&self) -> &BinarySensorInfo {
+    &self.info
+  }
+
+  pub fn get_state(&self) -> StateResult<BinarySensorState> {
+    match self.state.borrow().as_ref() {
+      Some(EntityState::BinarySensor(state)) => Ok(state.clone()),
+      Some(_) => Err(StateError::NotValidState),
+      None => Err(StateError::EntityKeyNotFound(self.info.entity_info.key)),
+    }
+  }
+
+  /// Returns a cloned receiver for watching state changes from an external context.
+  pub fn state_receiver(&self) -> watch::Receiver<Option<EntityState>> {
+    self.state.clone()
+  }
+
+  /// Wait for the next state change and return the updated state.
+  pub async fn state_changed(&mut self) -> StateResult<BinarySensorState> {
+    self
+      .state
+      .changed()
+      .await
+      .map_err(|_| StateError::EntityKeyNotFound(self.info.entity_info.key))?;
+    self.get_state()
+  }
+
+  pub fn is_on(&self) -> StateResult<bool> {
+    Ok(self.get_state()?.state)
+  }
+}
+
+impl BaseEntity for BinarySensor {
+  fn key(&self) -> u32 {
+    self.info.entity_info.key
+  }
+
+  fn device_id(&self) -> u32 {
+    self.info.entity_info.device_id
+  }
+
+  fn name(&self) -> String {
+    self.info.entity_info.name.clone()
+  }
+}
@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use esphomeapi::{CommandHandle, model::ButtonInfo};
+
+use super::BaseEntity;
+
+/// A stateless button; the device never reports a state for it.
+#[derive(Clone)]
+pub struct Button {
+  client: Arc<CommandHandle>,
+  info: ButtonInfo,
+}
+
+impl Button {
+  pub fn new(client: Arc<CommandHandle>, info: ButtonInfo) -> Self {
+    Button { client, info }
+  }
+
+  pub fn info(&self) -> &ButtonInfo {
+    &self.info
+  }
+
+  pub async fn press(&self) -> crate::Result<()> {
+    Ok(
+      self
+        .client
+        .button_command(self.info.entity_info.key)
+        .await?,
+    )
+  }
+}
+
+impl BaseEntity for Button {
+  fn key(&self) -> u32 {
+    self.info.entity_info.key
+  }
+
+  fn device_id(&self) -> u32 {
+    self.info.entity_info.device_id
+  }
+
+  fn name(&self) -> String {
+    self.info.entity_info.name.clone()
+  }
+}
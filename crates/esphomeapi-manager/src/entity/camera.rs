@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use esphomeapi::{
+  CommandHandle,
+  model::{CameraImage, CameraInfo},
+};
+use tokio::sync::watch;
+
+use super::{BaseEntity, StateError, StateResult};
+
+/// A camera entity. Its state is the most recent complete image.
+#[derive(Clone)]
+pub struct Camera {
+  client: Arc<CommandHandle>,
+  info: CameraInfo,
+  image: watch::Receiver<Option<CameraImage>>,
+}
+
+impl Camera {
+  pub fn new(
+    client: Arc<CommandHandle>,
+    info: CameraInfo,
+    image: watch::Receiver<Option<CameraImage>>,
+  ) -> Self {
+    Camera {
+      client,
+      info,
+      image,
+    }
+  }
+
+  pub fn info(&self) -> &CameraInfo {
+    &self.info
+  }
+
+  pub fn get_state(&self) -> StateResult<CameraImage> {
+    match self.image.borrow().as_ref() {
+      Some(image) => Ok(image.clone()),
+      None => Err(StateError::EntityKeyNotFound(self.info.entity_info.key)),
+    }
+  }
+
+  /// Returns a cloned receiver for watching new images from an external context.
+  pub fn state_receiver(&self) -> watch::Receiver<Option<CameraImage>> {
+    self.image.clone()
+  }
+
+  /// Wait for the next complete image and return it.
+  pub async fn state_changed(&mut self) -> StateResult<CameraImage> {
+    self
+      .image
+      .changed()
+      .await
+      .map_err(|_| StateError::EntityKeyNotFound(self.info.entity_info.key))?;
+    self.get_state()
+  }
+
+  /// Ask the device for a single image.
+  ///
+  /// The request is not keyed: every camera on the device sends a frame.
+  pub async fn request_image(&self) -> crate::Result<()> {
+    Ok(self.client.camera_image(true, false).await?)
+  }
+
+  /// Ask the device to stream images for a while.
+  pub async fn request_stream(&self) -> crate::Result<()> {
+    Ok(self.client.camera_image(false, true).await?)
+  }
+}
+
+impl BaseEntity for Camera {
+  fn key(&self) -> u32 {
+    self.info.entity_info.key
+  }
+
+  fn device_id(&self) -> u32 {
+    self.info.entity_info.device_id
+  }
+
+  fn name(&self) -> String {
+    self.info.entity_info.name.clone()
+  }
+}
@@ -0,0 +1,221 @@
+use std::sync::Arc;
+
+use esphomeapi::{
+  CommandHandle,
+  model::{ClimateInfo, ClimateState, EntityState},
+};
+use tokio::sync::watch;
+
+pub use esphomeapi::model::{
+  ClimateAction, ClimateFanMode, ClimateMode, ClimatePreset, ClimateSwingMode,
+};
+
+use super::{BaseEntity, StateError, StateResult};
+
+/// Builder for constructing climate commands with a fluent API.
+///
+/// Created via [`Climate::command()`]. Only the fields that are set are sent.
+///
+/// # Example
+/// ```ignore
+/// climate.command()
+///     .mode(ClimateMode::Heat)
+///     .target_temperature(21.5)
+///     .send()
+///     .await?;
+/// ```
+pub struct ClimateCommandBuilder<'a> {
+  climate: &'a Climate,
+  mode: Option<ClimateMode>,
+  target_temperature: Option<f32>,
+  target_temperature_low: Option<f32>,
+  target_temperature_high: Option<f32>,
+  fan_mode: Option<ClimateFanMode>,
+  swing_mode: Option<ClimateSwingMode>,
+  custom_fan_mode: Option<String>,
+  preset: Option<ClimatePreset>,
+  custom_preset: Option<String>,
+  target_humidity: Option<f32>,
+}
+
+impl<'a> ClimateCommandBuilder<'a> {
+  fn new(climate: &'a Climate) -> Self {
+    Self {
+      climate,
+      mode: None,
+      target_temperature: None,
+      target_temperature_low: None,
+      target_temperature_high: None,
+      fan_mode: None,
+      swing_mode: None,
+      custom_fan_mode: None,
+      preset: None,
+      custom_preset: None,
+      target_humidity: None,
+    }
+  }
+
+  pub fn mode(mut self, mode: ClimateMode) -> Self {
+    self.mode = Some(mode);
+    self
+  }
+
+  pub fn target_temperature(mut self, target_temperature: f32) -> Self {
+    self.target_temperature = Some(target_temperature);
+    self
+  }
+
+  /// Lower bound for devices with a two-point target temperature.
+  pub fn target_temperature_low(mut self, target_temperature_low: f32) -> Self {
+    self.target_temperature_low = Some(target_temperature_low);
+    self
+  }
+
+  /// Upper bound for devices with a two-point target temperature.
+  pub fn target_temperature_high(mut self, target_temperature_high: f32) -> Self {
+    self.target_temperature_high = Some(target_temperature_high);
+    self
+  }
+
+  pub fn fan_mode(mut self, fan_mode: ClimateFanMode) -> Self {
+    self.fan_mode = Some(fan_mode);
+    self
+  }
+
+  pub fn swing_mode(mut self, swing_mode: ClimateSwingMode) -> Self {
+    self.swing_mode = Some(swing_mode);
+    self
+  }
+
+  pub fn custom_fan_mode(mut self, custom_fan_mode: impl Into<String>) -> Self {
+    self.custom_fan_mode = Some(custom_fan_mode.into());
+    self
+  }
+
+  pub fn preset(mut self, preset: ClimatePreset) -> Self {
+    self.preset = Some(preset);
+    self
+  }
+
+  pub fn custom_preset(mut self, custom_preset: impl Into<String>) -> Self {
+    self.custom_preset = Some(custom_preset.into());
+    self
+  }
+
+  pub fn target_humidity(mut self, target_humidity: f32) -> Self {
+    self.target_humidity = Some(target_humidity);
+    self
+  }
+
+  pub async fn send(self) -> crate::Result<()> {
+    Ok(
+      self
+        .climate
+        .client
+        .climate_command(
+          self.climate.info.entity_info.key,
+          self.mode,
+          self.target_temperature,
+          self.target_temperature_low,
+          self.target_temperature_high,
+          self.fan_mode,
+          self.swing_mode,
+          self.custom_fan_mode,
+          self.preset,
+          self.custom_preset,
+          self.target_humidity,
+        )
+        .await?,
+    )
+  }
+}
+
+#[derive(Clone)]
+pub struct Climate {
+  client: Arc<CommandHandle>,
+  info: ClimateInfo,
+  state: watch::Receiver<Option<EntityState>>,
+}
+
+impl Climate {
+  pub fn new(
+    client: Arc<CommandHandle>,
+    info: ClimateInfo,
+    state: watch::Receiver<Option<EntityState>>,
+  ) -> Self {
+    Climate {
+      client,
+      info,
+      state,
+    }
+  }
+
+  pub fn info(&self) -> &ClimateInfo {
+    &self.info
+  }
+
+  pub fn command(&self) -> ClimateCommandBuilder<'_> {
+    ClimateCommandBuilder::new(self)
+  }
+
+  pub fn get_state(&self) -> StateResult<ClimateState> {
+    match self.state.borrow().as_ref() {
+      Some(EntityState::Climate(state)) => Ok(state.clone()),
+      Some(_) => Err(StateError::NotValidState),
+      None => Err(StateError::EntityKeyNotFound(self.info.entity_info.key)),
+    }
+  }
+
+  /// Returns a cloned receiver for watching state changes from an external context.
+  pub fn state_receiver(&self) -> watch::Receiver<Option<EntityState>> {
+    self.state.clone()
+  }
+
+  /// Wait for the next state change and return the updated state.
+  pub async fn state_changed(&mut self) -> StateResult<ClimateState> {
+    self
+      .state
+      .changed()
+      .await
+      .map_err(|_| StateError::EntityKeyNotFound(self.info.entity_info.key))?;
+    self.get_state()
+  }
+
+  pub fn mode(&self) -> StateResult<ClimateMode> {
+    Ok(self.get_state()?.mode)
+  }
+
+  pub fn current_temperature(&self) -> StateResult<f32> {
+    Ok(self.get_state()?.current_temperature)
+  }
+
+  pub fn target_temperature(&self) -> StateResult<f32> {
+    Ok(self.get_state()?.target_temperature)
+  }
+
+  pub async fn set_mode(&self, mode: ClimateMode) -> crate::Result<()> {
+    self.command().mode(mode).send().await
+  }
+
+  pub async fn set_target_temperature(&self, target_temperature: f32) -> crate::Result<()> {
+    self
+      .command()
+      .target_temperature(target_temperature)
+      .send()
+      .await
+  }
+}
+
+impl BaseEntity for Climate {
+  fn key(&self) -> u32 {
+    self.info.entity_info.key
+  }
+
+  fn device_id(&self) -> u32 {
+    self.info.entity_info.device_id
+  }
+
+  fn name(&self) -> String {
+    self.info.entity_info.name.clone()
+  }
+}
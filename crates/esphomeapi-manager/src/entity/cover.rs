@@ -0,0 +1,121 @@
+use std::sync::Arc;
+
+use esphomeapi::{
+  CommandHandle,
+  model::{CoverInfo, CoverState, EntityState},
+};
+use tokio::sync::watch;
+
+pub use esphomeapi::model::CoverOperation;
+
+use super::{BaseEntity, StateError, StateResult};
+
+#[derive(Clone)]
+pub struct Cover {
+  client: Arc<CommandHandle>,
+  info: CoverInfo,
+  state: watch::Receiver<Option<EntityState>>,
+}
+
+impl Cover {
+  pub fn new(
+    client: Arc<CommandHandle>,
+    info: CoverInfo,
+    state: watch::Receiver<Option<EntityState>>,
+  ) -> Self {
+    Cover {
+      client,
+      info,
+      state,
+    }
+  }
+
+  pub fn info(&self) -> &CoverInfo {
+    &self.info
+  }
+
+  pub fn get_state(&self) -> StateResult<CoverState> {
+    match self.state.borrow().as_ref() {
+      Some(EntityState::Cover(state)) => Ok(state.clone()),
+      Some(_) => Err(StateError::NotValidState),
+      None => Err(StateError::EntityKeyNotFound(self.info.entity_info.key)),
+    }
+  }
+
+  /// Returns a cloned receiver for watching state changes from an external context.
+  pub fn state_receiver(&self) -> watch::Receiver<Option<EntityState>> {
+    self.state.clone()
+  }
+
+  /// Wait for the next state change and return the updated state.
+  pub async fn state_changed(&mut self) -> StateResult<CoverState> {
+    self
+      .state
+      .changed()
+      .await
+      .map_err(|_| StateError::EntityKeyNotFound(self.info.entity_info.key))?;
+    self.get_state()
+  }
+
+  /// Position from `0.0` (closed) to `1.0` (open).
+  pub fn position(&self) -> StateResult<f32> {
+    Ok(self.get_state()?.position)
+  }
+
+  pub fn tilt(&self) -> StateResult<f32> {
+    Ok(self.get_state()?.tilt)
+  }
+
+  pub fn current_operation(&self) -> StateResult<CoverOperation> {
+    Ok(self.get_state()?.current_operation)
+  }
+
+  pub async fn open(&self) -> crate::Result<()> {
+    self.set_position(1.0).await
+  }
+
+  pub async fn close(&self) -> crate::Result<()> {
+    self.set_position(0.0).await
+  }
+
+  pub async fn stop(&self) -> crate::Result<()> {
+    Ok(
+      self
+        .client
+        .cover_command(self.info.entity_info.key, None, None, true)
+        .await?,
+    )
+  }
+
+  pub async fn set_position(&self, position: f32) -> crate::Result<()> {
+    Ok(
+      self
+        .client
+        .cover_command(self.info.entity_info.key, Some(position), None, false)
+        .await?,
+    )
+  }
+
+  pub async fn set_tilt(&self, tilt: f32) -> crate::Result<()> {
+    Ok(
+      self
+        .client
+        .cover_command(self.info.entity_info.key, None, Some(tilt), false)
+        .await?,
+    )
+  }
+}
+
+impl BaseEntity for Cover {
+  fn key(&self) -> u32 {
+    self.info.entity_info.key
+  }
+
+  fn device_id(&self) -> u32 {
+    self.info.entity_info.device_id
+  }
+
+  fn name(&self) -> String {
+    self.info.entity_info.name.clone()
+  }
+}
@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use esphomeapi::{
+  CommandHandle,
+  model::{DateInfo, DateState, EntityState},
+};
+use tokio::sync::watch;
+
+use super::{BaseEntity, StateError, StateResult};
+
+#[derive(Clone)]
+pub struct Date {
+  client: Arc<CommandHandle>,
+  info: DateInfo,
+  state: watch::Receiver<Option<EntityState>>,
+}
+
+impl Date {
+  pub fn new(
+    client: Arc<CommandHandle>,
+    info: DateInfo,
+    state: watch::Receiver<Option<EntityState>>,
+  ) -> Self {
+    Date {
+      client,
+      info,
+      state,
+    }
+  }
+
+  pub fn info(&self) -> &DateInfo {
+    &self.info
+  }
+
+  pub fn get_state(&self) -> StateResult<DateState> {
+    match self.state.borrow().as_ref() {
+      Some(EntityState::Date(state)) => Ok(state.clone()),
+      Some(_) => Err(StateError::NotValidState),
+      None => Err(StateError::EntityKeyNotFound(self.info.entity_info.key)),
+    }
+  }
+
+  /// Returns a cloned receiver for watching state changes from an external context.
+  pub fn state_receiver(&self) -> watch::Receiver<Option<EntityState>> {
+    self.state.clone()
+  }
+
+  /// Wait for the next state change and return the updated state.
+  pub async fn state_changed(&mut self) -> StateResult<DateState> {
+    self
+      .state
+      .changed()
+      .await
+      .map_err(|_| StateError::EntityKeyNotFound(self.info.entity_info.key))?;
+    self.get_state()
+  }
+
+  /// The current `(year, month, day)`, or `None` while the device reports no value.
+  pub fn value(&self) -> StateResult<Option<(u32, u32, u32)>> {
+    let state = self.get_state()?;
+    Ok((!state.missing_state).then_some((state.year, state.month, state.day)))
+  }
+
+  pub async fn set_date(&self, year: u32, month: u32, day: u32) -> crate::Result<()> {
+    Ok(
+      self
+        .client
+        .date_command(self.info.entity_info.key, year, month, day)
+        .await?,
+    )
+  }
+}
+
+impl BaseEntity for Date {
+  fn key(&self) -> u32 {
+    self.info.entity_info.key
+  }
+
+  fn device_id(&self) -> u32 {
+    self.info.entity_info.device_id
+  }
+
+  fn name(&self) -> String {
+    self.info.entity_info.name.clone()
+  }
+}
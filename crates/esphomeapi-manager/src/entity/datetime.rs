@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use esphomeapi::{
+  CommandHandle,
+  model::{DateTimeInfo, DateTimeState, EntityState},
+};
+use tokio::sync::watch;
+
+use super::{BaseEntity, StateError, StateResult};
+
+#[derive(Clone)]
+pub struct DateTime {
+  client: Arc<CommandHandle>,
+  info: DateTimeInfo,
+  state: watch::Receiver<Option<EntityState>>,
+}
+
+impl DateTime {
+  pub fn new(
+    client: Arc<CommandHandle>,
+    info: DateTimeInfo,
+    state: watch::Receiver<Option<EntityState>>,
+  ) -> Self {
+    DateTime {
+      client,
+      info,
+      state,
+    }
+  }
+
+  pub fn info(&self) -> &DateTimeInfo {
+    &self.info
+  }
+
+  pub fn get_state(&self) -> StateResult<DateTimeState> {
+    match self.state.borrow().as_ref() {
+      Some(EntityState::DateTime(state)) => Ok(state.clone()),
+      Some(_) => Err(StateError::NotValidState),
+      None => Err(StateError::EntityKeyNotFound(self.info.entity_info.key)),
+    }
+  }
+
+  /// Returns a cloned receiver for watching state changes from an external context.
+  pub fn state_receiver(&self) -> watch::Receiver<Option<EntityState>> {
+    self.state.clone()
+  }
+
+  /// Wait for the next state change and return the updated state.
+  pub async fn state_changed(&mut self) -> StateResult<DateTimeState> {
+    self
+      .state
+      .changed()
+      .await
+      .map_err(|_| StateError::EntityKeyNotFound(self.info.entity_info.key))?;
+    self.get_state()
+  }
+
+  /// Seconds since the Unix epoch, or `None` while the device reports no value.
+  pub fn epoch_seconds(&self) -> StateResult<Option<u32>> {
+    let state = self.get_state()?;
+    Ok((!state.missing_state).then_some(state.epoch_seconds))
+  }
+
+  pub async fn set_epoch_seconds(&self, epoch_seconds: u32) -> crate::Result<()> {
+    Ok(
+      self
+        .client
+        .datetime_command(self.info.entity_info.key, epoch_seconds)
+        .await?,
+    )
+  }
+}
+
+impl BaseEntity for DateTime {
+  fn key(&self) -> u32 {
+    self.info.entity_info.key
+  }
+
+  fn device_id(&self) -> u32 {
+    self.info.entity_info.device_id
+  }
+
+  fn name(&self) -> String {
+    self.info.entity_info.name.clone()
+  }
+}
@@ -0,0 +1,62 @@
+use esphomeapi::model::{EntityState, Event as EventState, EventInfo};
+use tokio::sync::watch;
+
+use super::{BaseEntity, StateError, StateResult};
+
+/// An event entity. Its state is the most recently fired event.
+#[derive(Clone)]
+pub struct Event {
+  info: EventInfo,
+  state: watch::Receiver<Option<EntityState>>,
+}
+
+impl Event {
+  pub fn new(info: EventInfo, state: watch::Receiver<Option<EntityState>>) -> Self {
+    Event { info, state }
+  }
+
+  pub fn info(&self) -> &EventInfo {
+    &self.info
+  }
+
+  pub fn event_types(&self) -> &[String] {
+    &self.info.event_types
+  }
+
+  pub fn get_state(&self) -> StateResult<EventState> {
+    match self.state.borrow().as_ref() {
+      Some(EntityState::Event(state)) => Ok(state.clone()),
+      Some(_) => Err(StateError::NotValidState),
+      None => Err(StateError::EntityKeyNotFound(self.info.entity_info.key)),
+    }
+  }
+
+  /// Returns a cloned receiver for watching state changes from an external context.
+  pub fn state_receiver(&self) -> watch::Receiver<Option<EntityState>> {
+    self.state.clone()
+  }
+
+  /// Wait for the next event and return it.
+  pub async fn state_changed(&mut self) -> StateResult<EventState> {
+    self
+      .state
+      .changed()
+      .await
+      .map_err(|_| StateError::EntityKeyNotFound(self.info.entity_info.key))?;
+    self.get_state()
+  }
+}
+
+impl BaseEntity for Event {
+  fn key(&self) -> u32 {
+    self.info.entity_info.key
+  }
+
+  fn device_id(&self) -> u32 {
+    self.info.entity_info.device_id
+  }
+
+  fn name(&self) -> String {
+    self.info.entity_info.name.clone()
+  }
+}
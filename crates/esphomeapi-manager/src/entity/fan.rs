@@ -0,0 +1,198 @@
+use std::sync::Arc;
+
+use esphomeapi::{
+  CommandHandle,
+  model::{EntityState, FanInfo, FanState},
+};
+use tokio::sync::watch;
+
+pub use esphomeapi::model::{FanDirection, FanSpeed};
+
+use super::{BaseEntity, StateError, StateResult};
+
+/// Builder for constructing fan commands with a fluent API.
+///
+/// Created via [`Fan::command()`]. Only the fields that are set are sent.
+///
+/// # Example
+/// ```ignore
+/// fan.command()
+///     .state(true)
+///     .speed_level(3)
+///     .oscillating(true)
+///     .send()
+///     .await?;
+/// ```
+pub struct FanCommandBuilder<'a> {
+  fan: &'a Fan,
+  state: Option<bool>,
+  speed: Option<FanSpeed>,
+  speed_level: Option<i32>,
+  oscillating: Option<bool>,
+  direction: Option<FanDirection>,
+  preset_mode: Option<String>,
+}
+
+impl<'a> FanCommandBuilder<'a> {
+  fn new(fan: &'a Fan) -> Self {
+    Self {
+      fan,
+      state: None,
+      speed: None,
+      speed_level: None,
+      oscillating: None,
+      direction: None,
+      preset_mode: None,
+    }
+  }
+
+  pub fn state(mut self, state: bool) -> Self {
+    self.state = Some(state);
+    self
+  }
+
+  /// Legacy three-step speed, for devices older than API 1.4.
+  pub fn speed(mut self, speed: FanSpeed) -> Self {
+    self.speed = Some(speed);
+    self
+  }
+
+  pub fn speed_level(mut self, speed_level: i32) -> Self {
+    self.speed_level = Some(speed_level);
+    self
+  }
+
+  pub fn oscillating(mut self, oscillating: bool) -> Self {
+    self.oscillating = Some(oscillating);
+    self
+  }
+
+  pub fn direction(mut self, direction: FanDirection) -> Self {
+    self.direction = Some(direction);
+    self
+  }
+
+  pub fn preset_mode(mut self, preset_mode: impl Into<String>) -> Self {
+    self.preset_mode = Some(preset_mode.into());
+    self
+  }
+
+  pub async fn send(self) -> crate::Result<()> {
+    Ok(
+      self
+        .fan
+        .client
+        .fan_command(
+          self.fan.info.entity_info.key,
+          self.state,
+          self.speed,
+          self.speed_level,
+          self.oscillating,
+          self.direction,
+          self.preset_mode,
+        )
+        .await?,
+    )
+  }
+}
+
+#[derive(Clone)]
+pub struct Fan {
+  client: Arc<CommandHandle>,
+  info: FanInfo,
+  state: watch::Receiver<Option<EntityState>>,
+}
+
+impl Fan {
+  pub fn new(
+    client: Arc<CommandHandle>,
+    info: FanInfo,
+    state: watch::Receiver<Option<EntityState>>,
+  ) -> Self {
+    Fan {
+      client,
+      info,
+      state,
+    }
+  }
+
+  pub fn info(&self) -> &FanInfo {
+    &self.info
+  }
+
+  pub fn command(&self) -> FanCommandBuilder<'_> {
+    FanCommandBuilder::new(self)
+  }
+
+  pub fn get_state(&self) -> StateResult<FanState> {
+    match self.state.borrow().as_ref() {
+      Some(EntityState::Fan(state)) => Ok(state.clone()),
+      Some(_) => Err(StateError::NotValidState),
+      None => Err(StateError::EntityKeyNotFound(self.info.entity_info.key)),
+    }
+  }
+
+  /// Returns a cloned receiver for watching state changes from an external context.
+  pub fn state_receiver(&self) -> watch::Receiver<Option<EntityState>> {
+    self.state.clone()
+  }
+
+  /// Wait for the next state change and return the updated state.
+  pub async fn state_changed(&mut self) -> StateResult<FanState> {
+    self
+      .state
+      .changed()
+      .await
+      .map_err(|_| StateError::EntityKeyNotFound(self.info.entity_info.key))?;
+    self.get_state()
+  }
+
+  pub fn is_on(&self) -> StateResult<bool> {
+    Ok(self.get_state()?.state)
+  }
+
+  pub async fn turn_on(&self) -> crate::Result<()> {
+    self.command().state(true).send().await
+  }
+
+  pub async fn turn_off(&self) -> crate::Result<()> {
+    self.command().state(false).send().await
+  }
+
+  pub async fn toggle(&self) -> crate::Result<()> {
+    match self.is_on()? {
+      true => self.turn_off().await,
+      false => self.turn_on().await,
+    }
+  }
+
+  pub async fn set_speed_level(&self, speed_level: i32) -> crate::Result<()> {
+    self.command().speed_level(speed_level).send().await
+  }
+
+  pub async fn set_oscillating(&self, oscillating: bool) -> crate::Result<()> {
+    self.command().oscillating(oscillating).send().await
+  }
+
+  pub async fn set_direction(&self, direction: FanDirection) -> crate::Result<()> {
+    self.command().direction(direction).send().await
+  }
+
+  pub async fn set_preset_mode(&self, preset_mode: impl Into<String>) -> crate::Result<()> {
+    self.command().preset_mode(preset_mode).send().await
+  }
+}
+
+impl BaseEntity for Fan {
+  fn key(&self) -> u32 {
+    self.info.entity_info.key
+  }
+
+  fn device_id(&self) -> u32 {
+    self.info.entity_info.device_id
+  }
+
+  fn name(&self) -> String {
+    self.info.entity_info.name.clone()
+  }
+}
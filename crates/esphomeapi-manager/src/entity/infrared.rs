@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use enumflags2::BitFlags;
+use esphomeapi::{CommandHandle, model::InfraredInfo};
+
+pub use esphomeapi::infrared::{InfraredSignal, IrCode};
+pub use esphomeapi::model::InfraredCapability;
+
+use super::BaseEntity;
+
+/// An infrared/RF transceiver; the device never reports a state for it.
+///
+/// Captured signals arrive on
+/// [`Client::infrared_receive`](esphomeapi::Client::infrared_receive).
+#[derive(Clone)]
+pub struct Infrared {
+  client: Arc<CommandHandle>,
+  info: InfraredInfo,
+}
+
+impl Infrared {
+  pub fn new(client: Arc<CommandHandle>, info: InfraredInfo) -> Self {
+    Infrared { client, info }
+  }
+
+  pub fn info(&self) -> &InfraredInfo {
+    &self.info
+  }
+
+  pub fn capabilities(&self) -> BitFlags<InfraredCapability> {
+    self.info.capabilities
+  }
+
+  /// Transmit a signal `repeat_count` times.
+  pub async fn transmit(&self, signal: &InfraredSignal, repeat_count: u32) -> crate::Result<()> {
+    Ok(
+      self
+        .client
+        .infrared_transmit(self.info.entity_info.key, signal, repeat_count)
+        .await?,
+    )
+  }
+
+  /// Transmit a command of a supported protocol `repeat_count` times.
+  pub async fn transmit_code(&self, code: IrCode, repeat_count: u32) -> crate::Result<()> {
+    self.transmit(&code.encode(), repeat_count).await
+  }
+
+  /// Transmit raw timings: positive marks and negative spaces in microseconds.
+  pub async fn transmit_raw(
+    &self,
+    carrier_frequency: u32,
+    repeat_count: u32,
+    timings: Vec<i32>,
+  ) -> crate::Result<()> {
+    Ok(
+      self
+        .client
+        .infrared_transmit_raw(
+          self.info.entity_info.key,
+          carrier_frequency,
+          repeat_count,
+          timings,
+        )
+        .await?,
+    )
+  }
+}
+
+impl BaseEntity for Infrared {
+  fn key(&self) -> u32 {
+    self.info.entity_info.key
+  }
+
+  fn device_id(&self) -> u32 {
+    self.info.entity_info.device_id
+  }
+
+  fn name(&self) -> String {
+    self.info.entity_info.name.clone()
+  }
+}
@@ -119,26 +119,28 @@ impl<'a> LightCommandBuilder<'a> {
     self
   }
 
-  pub async fn send(self) -> esphomeapi::Result<()> {
-    self
-      .light
-      .client
-      .light_command(
-        self.light.info.entity_info.key,
-        self.state,
-        self.brightness,
-        self.color_mode,
-        self.color_brightness,
-        self.rgb,
-        self.white,
-        self.color_temperature,
-        self.cold_white,
-        self.warm_white,
-        self.transition_length,
-        self.flash_length,
-        self.effect,
-      )
-      .await
+  pub async fn send(self) -> crate::Result<()> {
+    Ok(
+      self
+        .light
+        .client
+        .light_command(
+          self.light.info.entity_info.key,
+          self.state,
+          self.brightness,
+          self.color_mode,
+          self.color_brightness,
+          self.rgb,
+          self.white,
+          self.color_temperature,
+          self.cold_white,
+          self.warm_white,
+          self.transition_length,
+          self.flash_length,
+          self.effect,
+        )
+        .await?,
+    )
   }
 }
 
@@ -189,76 +191,76 @@ impl Light {
     self.get_state()
   }
 
-  pub fn is_on(&self) -> esphomeapi::Result<bool> {
+  pub fn is_on(&self) -> crate::Result<bool> {
     let state = self.get_state()?;
 
     Ok(state.state)
   }
 
-  pub async fn turn_on(&self) -> esphomeapi::Result<()> {
+  pub async fn turn_on(&self) -> crate::Result<()> {
     self.command().state(true).send().await
   }
 
-  pub async fn turn_off(&self) -> esphomeapi::Result<()> {
+  pub async fn turn_off(&self) -> crate::Result<()> {
     self.command().state(false).send().await
   }
 
-  pub async fn toggle(&self) -> esphomeapi::Result<()> {
+  pub async fn toggle(&self) -> crate::Result<()> {
     match self.is_on()? {
       true => self.turn_off().await,
       false => self.turn_on().await,
     }
   }
 
-  pub fn brightness(&self) -> esphomeapi::Result<f32> {
+  pub fn brightness(&self) -> crate::Result<f32> {
     let state = self.get_state()?;
 
     Ok(state.brightness)
   }
 
-  pub fn color_mode(&self) -> esphomeapi::Result<ColorMode> {
+  pub fn color_mode(&self) -> crate::Result<ColorMode> {
     let state = self.get_state()?;
 
     Ok(state.color_mode)
   }
 
-  pub fn color_brightness(&self) -> esphomeapi::Result<f32> {
+  pub fn color_brightness(&self) -> crate::Result<f32> {
     let state = self.get_state()?;
 
     Ok(state.color_brightness)
   }
 
-  pub fn rgb(&self) -> esphomeapi::Result<(f32, f32, f32)> {
+  pub fn rgb(&self) -> crate::Result<(f32, f32, f32)> {
     let state = self.get_state()?;
 
     Ok((state.red, state.green, state.blue))
   }
 
-  pub fn white(&self) -> esphomeapi::Result<f32> {
+  pub fn white(&self) -> crate::Result<f32> {
     let state = self.get_state()?;
 
     Ok(state.white)
   }
 
-  pub fn color_temperature(&self) -> esphomeapi::Result<f32> {
+  pub fn color_temperature(&self) -> crate::Result<f32> {
     let state = self.get_state()?;
 
     Ok(state.color_temperature)
   }
 
-  pub fn cold_white(&self) -> esphomeapi::Result<f32> {
+  pub fn cold_white(&self) -> crate::Result<f32> {
     let state = self.get_state()?;
 
     Ok(state.cold_white)
   }
 
-  pub fn warm_white(&self) -> esphomeapi::Result<f32> {
+  pub fn warm_white(&self) -> crate::Result<f32> {
     let state = self.get_state()?;
 
     Ok(state.warm_white)
   }
 
-  pub fn effect(&self) -> esphomeapi::Result<String> {
+  pub fn effect(&self) -> crate::Result<String> {
     let state = self.get_state()?;
 
     Ok(state.effect)
@@ -270,6 +272,10 @@ impl BaseEntity for Light {
     self.info.entity_info.key
   }
 
+  fn device_id(&self) -> u32 {
+    self.info.entity_info.device_id
+  }
+
   fn name(&self) -> String {
     self.info.entity_info.name.clone()
   }
@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use esphomeapi::{
+  CommandHandle,
+  model::{EntityState, LockEntityState, LockInfo},
+};
+use tokio::sync::watch;
+
+pub use esphomeapi::model::{LockCommand, LockState};
+
+use super::{BaseEntity, StateError, StateResult};
+
+#[derive(Clone)]
+pub struct Lock {
+  client: Arc<CommandHandle>,
+  info: LockInfo,
+  state: watch::Receiver<Option<EntityState>>,
+}
+
+impl Lock {
+  pub fn new(
+    client: Arc<CommandHandle>,
+    info: LockInfo,
+    state: watch::Receiver<Option<EntityState>>,
+  ) -> Self {
+    Lock {
+      client,
+      info,
+      state,
+    }
+  }
+
+  pub fn info(&self) -> &LockInfo {
+    &self.info
+  }
+
+  pub fn get_state(&self) -> StateResult<LockEntityState> {
+    match self.state.borrow().as_ref() {
+      Some(EntityState::Lock(state)) => Ok(state.clone()),
+      Some(_) => Err(StateError::NotValidState),
+      None => Err(StateError::EntityKeyNotFound(self.info.entity_info.key)),
+    }
+  }
+
+  /// Returns a cloned receiver for watching state changes from an external context.
+  pub fn state_receiver(&self) -> watch::Receiver<Option<EntityState>> {
+    self.state.clone()
+  }
+
+  /// Wait for the next state change and return the updated state.
+  pub async fn state_changed(&mut self) -> StateResult<LockEntityState> {
+    self
+      .state
+      .changed()
+      .await
+      .map_err(|_| StateError::EntityKeyNotFound(self.info.entity_info.key))?;
+    self.get_state()
+  }
+
+  pub fn lock_state(&self) -> StateResult<LockState> {
+    Ok(self.get_state()?.state)
+  }
+
+  /// Send a lock command; `code` is only needed when the lock requires one.
+  pub async fn send_command(
+    &self,
+    command: LockCommand,
+    code: Option<String>,
+  ) -> crate::Result<()> {
+    Ok(
+      self
+        .client
+        .lock_command(self.info.entity_info.key, command, code)
+        .await?,
+    )
+  }
+
+  pub async fn lock(&self, code: Option<String>) -> crate::Result<()> {
+    self.send_command(LockCommand::Lock, code).await
+  }
+
+  pub async fn unlock(&self, code: Option<String>) -> crate::Result<()> {
+    self.send_command(LockCommand::Unlock, code).await
+  }
+
+  pub async fn open(&self, code: Option<String>) -> crate::Result<()> {
+    self.send_command(LockCommand::Open, code).await
+  }
+}
+
+impl BaseEntity for Lock {
+  fn key(&self) -> u32 {
+    self.info.entity_info.key
+  }
+
+  fn device_id(&self) -> u32 {
+    self.info.entity_info.device_id
+  }
+
+  fn name(&self) -> String {
+    self.info.entity_info.name.clone()
+  }
+}
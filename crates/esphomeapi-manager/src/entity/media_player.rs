@@ -0,0 +1,144 @@
+use std::sync::Arc;
+
+use esphomeapi::{
+  CommandHandle,
+  model::{EntityState, MediaPlayerEntityState, MediaPlayerInfo},
+};
+use tokio::sync::watch;
+
+pub use esphomeapi::model::{MediaPlayerCommand, MediaPlayerState};
+
+use super::{BaseEntity, StateError, StateResult};
+
+#[derive(Clone)]
+pub struct MediaPlayer {
+  client: Arc<CommandHandle>,
+  info: MediaPlayerInfo,
+  state: watch::Receiver<Option<EntityState>>,
+}
+
+impl MediaPlayer {
+  pub fn new(
+    client: Arc<CommandHandle>,
+    info: MediaPlayerInfo,
+    state: watch::Receiver<Option<EntityState>>,
+  ) -> Self {
+    MediaPlayer {
+      client,
+      info,
+      state,
+    }
+  }
+
+  pub fn info(&self) -> &MediaPlayerInfo {
+    &self.info
+  }
+
+  pub fn get_state(&self) -> StateResult<MediaPlayerEntityState> {
+    match self.state.borrow().as_ref() {
+      Some(EntityState::MediaPlayer(state)) => Ok(state.clone()),
+      Some(_) => Err(StateError::NotValidState),
+      None => Err(StateError::EntityKeyNotFound(self.info.entity_info.key)),
+    }
+  }
+
+  /// Returns a cloned receiver for watching state changes from an external context.
+  pub fn state_receiver(&self) -> watch::Receiver<Option<EntityState>> {
+    self.state.clone()
+  }
+
+  /// Wait for the next state change and return the updated state.
+  pub async fn state_changed(&mut self) -> StateResult<MediaPlayerEntityState> {
+    self
+      .state
+      .changed()
+      .await
+      .map_err(|_| StateError::EntityKeyNotFound(self.info.entity_info.key))?;
+    self.get_state()
+  }
+
+  pub fn player_state(&self) -> StateResult<MediaPlayerState> {
+    Ok(self.get_state()?.state)
+  }
+
+  pub fn volume(&self) -> StateResult<f32> {
+    Ok(self.get_state()?.volume)
+  }
+
+  pub fn is_muted(&self) -> StateResult<bool> {
+    Ok(self.get_state()?.muted)
+  }
+
+  pub async fn send_command(&self, command: MediaPlayerCommand) -> crate::Result<()> {
+    Ok(
+      self
+        .client
+        .media_player_command(self.info.entity_info.key, Some(command), None, None, None)
+        .await?,
+    )
+  }
+
+  pub async fn play(&self) -> crate::Result<()> {
+    self.send_command(MediaPlayerCommand::Play).await
+  }
+
+  pub async fn pause(&self) -> crate::Result<()> {
+    self.send_command(MediaPlayerCommand::Pause).await
+  }
+
+  pub async fn stop(&self) -> crate::Result<()> {
+    self.send_command(MediaPlayerCommand::Stop).await
+  }
+
+  pub async fn mute(&self) -> crate::Result<()> {
+    self.send_command(MediaPlayerCommand::Mute).await
+  }
+
+  pub async fn unmute(&self) -> crate::Result<()> {
+    self.send_command(MediaPlayerCommand::Unmute).await
+  }
+
+  /// Volume from `0.0` to `1.0`.
+  pub async fn set_volume(&self, volume: f32) -> crate::Result<()> {
+    Ok(
+      self
+        .client
+        .media_player_command(self.info.entity_info.key, None, Some(volume), None, None)
+        .await?,
+    )
+  }
+
+  /// Play the given URL; announcements duck and then resume the current media.
+  pub async fn play_media(
+    &self,
+    media_url: impl Into<String>,
+    announcement: bool,
+  ) -> crate::Result<()> {
+    Ok(
+      self
+        .client
+        .media_player_command(
+          self.info.entity_info.key,
+          None,
+          None,
+          Some(media_url.into()),
+          Some(announcement),
+        )
+        .await?,
+    )
+  }
+}
+
+impl BaseEntity for MediaPlayer {
+  fn key(&self) -> u32 {
+    self.info.entity_info.key
+  }
+
+  fn device_id(&self) -> u32 {
+    self.info.entity_info.device_id
+  }
+
+  fn name(&self) -> String {
+    self.info.entity_info.name.clone()
+  }
+}
@@ -1,10 +1,92 @@
+mod alarm_control_panel;
+mod binary_sensor;
+mod button;
+mod camera;
+mod climate;
+mod cover;
+mod date;
+mod datetime;
+mod event;
+mod fan;
+mod infrared;
 mod light;
+mod lock;
+mod media_player;
+mod number;
+mod select;
+mod sensor;
+mod siren;
 mod switch;
+mod text;
+mod text_sensor;
+mod time;
+mod update;
+mod valve;
+mod water_heater;
 
 use std::fmt;
 
-pub use light::{ColorMode, Light};
+pub use esphomeapi::model::{
+  AlarmControlPanelEntityState, BinarySensorState, CameraImage, ClimateState, CoverState,
+  DateState, DateTimeState, Event as EventState, FanState, LightState, LockEntityState,
+  MediaPlayerEntityState, NumberState, SelectState, SensorState, SirenState, SwitchState,
+  TextSensorState, TextState, TimeState, UpdateState, ValveState, WaterHeaterState,
+};
+
+pub use alarm_control_panel::{
+  AlarmControlPanel, AlarmControlPanelCommand, AlarmControlPanelState,
+};
+pub use binary_sensor::BinarySensor;
+pub use button::Button;
+pub use camera::Camera;
+pub use climate::{
+  Climate, ClimateAction, ClimateCommandBuilder, ClimateFanMode, ClimateMode, ClimatePreset,
+  ClimateSwingMode,
+};
+pub use cover::{Cover, CoverOperation};
+pub use date::Date;
+pub use datetime::DateTime;
+pub use event::Event;
+pub use fan::{Fan, FanCommandBuilder, FanDirection, FanSpeed};
+pub use infrared::{Infrared, InfraredCapability, InfraredSignal, IrCode};
+pub use light::{ColorMode, Light, LightCommandBuilder};
+pub use lock::{Lock, LockCommand, LockState};
+pub use media_player::{MediaPlayer, MediaPlayerCommand, MediaPlayerState};
+pub use number::{Number, NumberMode};
+pub use select::Select;
+pub use sensor::Sensor;
+pub use siren::{Siren, SirenCommandBuilder};
 pub use switch::Switch;
+pub use text::{Text, TextMode};
+pub use text_sensor::TextSensor;
+pub use time::Time;
+pub use update::Update;
+pub use valve::{Valve, ValveOperation};
+pub use water_heater::{
+  WaterHeater, WaterHeaterCommandBuilder, WaterHeaterMode, WaterHeaterStateFlag,
+};
+
+/// Identifies an entity on a node. Keys are only unique per sub-device, so
+/// entities of different sub-devices may share one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EntityId {
+  /// 0 for entities of the node itself
+  pub device_id: u32,
+  pub key: u32,
+}
+
+impl EntityId {
+  pub fn new(device_id: u32, key: u32) -> Self {
+    EntityId { device_id, key }
+  }
+}
+
+/// An entity of the node itself.
+impl From<u32> for EntityId {
+  fn from(key: u32) -> Self {
+    EntityId::new(0, key)
+  }
+}
 
 type StateResult<T> = std::result::Result<T, StateError>;
 
@@ -27,12 +109,84 @@ impl std::error::Error for StateError {}
 
 #[derive(Clone)]
 pub enum Entity {
-  Switch(Switch),
+  AlarmControlPanel(AlarmControlPanel),
+  BinarySensor(BinarySensor),
+  Button(Button),
+  Camera(Camera),
+  Climate(Climate),
+  Cover(Cover),
+  Date(Date),
+  DateTime(DateTime),
+  Event(Event),
+  Fan(Fan),
+  Infrared(Infrared),
   Light(Light),
-  Sensor(),
+  Lock(Lock),
+  MediaPlayer(MediaPlayer),
+  Number(Number),
+  Select(Select),
+  Sensor(Sensor),
+  Siren(Siren),
+  Switch(Switch),
+  Text(Text),
+  TextSensor(TextSensor),
+  Time(Time),
+  Update(Update),
+  Valve(Valve),
+  WaterHeater(WaterHeater),
+}
+
+impl Entity {
+  pub fn key(&self) -> u32 {
+    self.base().key()
+  }
+
+  pub fn device_id(&self) -> u32 {
+    self.base().device_id()
+  }
+
+  pub fn id(&self) -> EntityId {
+    EntityId::new(self.device_id(), self.key())
+  }
+
+  pub fn name(&self) -> String {
+    self.base().name()
+  }
+
+  fn base(&self) -> &dyn BaseEntity {
+    match self {
+      Entity::AlarmControlPanel(e) => e,
+      Entity::BinarySensor(e) => e,
+      Entity::Button(e) => e,
+      Entity::Camera(e) => e,
+      Entity::Climate(e) => e,
+      Entity::Cover(e) => e,
+      Entity::Date(e) => e,
+      Entity::DateTime(e) => e,
+      Entity::Event(e) => e,
+      Entity::Fan(e) => e,
+      Entity::Infrared(e) => e,
+      Entity::Light(e) => e,
+      Entity::Lock(e) => e,
+      Entity::MediaPlayer(e) => e,
+      Entity::Number(e) => e,
+      Entity::Select(e) => e,
+      Entity::Sensor(e) => e,
+      Entity::Siren(e) => e,
+      Entity::Switch(e) => e,
+      Entity::Text(e) => e,
+      Entity::TextSensor(e) => e,
+      Entity::Time(e) => e,
+      Entity::Update(e) => e,
+      Entity::Valve(e) => e,
+      Entity::WaterHeater(e) => e,
+    }
+  }
 }
 
 pub trait BaseEntity {
   fn key(&self) -> u32;
+  /// Sub-device of the entity, 0 for the node itself.
+  fn device_id(&self) -> u32;
   fn name(&self) -> String;
 }
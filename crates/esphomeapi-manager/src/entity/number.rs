@@ -0,0 +1,88 @@
+use std::sync::Arc;
+
+use esphomeapi::{
+  CommandHandle,
+  model::{EntityState, NumberInfo, NumberState},
+};
+use tokio::sync::watch;
+
+pub use esphomeapi::model::NumberMode;
+
+use super::{BaseEntity, StateError, StateResult};
+
+#[derive(Clone)]
+pub struct Number {
+  client: Arc<CommandHandle>,
+  info: NumberInfo,
+  state: watch::Receiver<Option<EntityState>>,
+}
+
+impl Number {
+  pub fn new(
+    client: Arc<CommandHandle>,
+    info: NumberInfo,
+    state: watch::Receiver<Option<EntityState>>,
+  ) -> Self {
+    Number {
+      client,
+      info,
+      state,
+    }
+  }
+
+  pub fn info(&self) -> &NumberInfo {
+    &self.info
+  }
+
+  pub fn get_state(&self) -> StateResult<NumberState> {
+    match self.state.borrow().as_ref() {
+      Some(EntityState::Number(state)) => Ok(state.clone()),
+      Some(_) => Err(StateError::NotValidState),
+      None => Err(StateError::EntityKeyNotFound(self.info.entity_info.key)),
+    }
+  }
+
+  /// Returns a cloned receiver for watching state changes from an external context.
+  pub fn state_receiver(&self) -> watch::Receiver<Option<EntityState>> {
+    self.state.clone()
+  }
+
+  /// Wait for the next state change and return the updated state.
+  pub async fn state_changed(&mut self) -> StateResult<NumberState> {
+    self
+      .state
+      .changed()
+      .await
+      .map_err(|_| StateError::EntityKeyNotFound(self.info.entity_info.key))?;
+    self.get_state()
+  }
+
+  /// The current value, or `None` while the device reports no value.
+  pub fn value(&self) -> StateResult<Option<f32>> {
+    let state = self.get_state()?;
+    Ok((!state.missing_state).then_some(state.state))
+  }
+
+  pub async fn set_value(&self, value: f32) -> crate::Result<()> {
+    Ok(
+      self
+        .client
+        .number_command(self.info.entity_info.key, value)
+        .await?,
+    )
+  }
+}
+
+impl BaseEntity for Number {
+  fn key(&self) -> u32 {
+    self.info.entity_info.key
+  }
+
+  fn device_id(&self) -> u32 {
+    self.info.entity_info.device_id
+  }
+
+  fn name(&self) -> String {
+    self.info.entity_info.name.clone()
+  }
+}
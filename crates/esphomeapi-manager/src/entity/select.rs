@@ -0,0 +1,90 @@
+use std::sync::Arc;
+
+use esphomeapi::{
+  CommandHandle,
+  model::{EntityState, SelectInfo, SelectState},
+};
+use tokio::sync::watch;
+
+use super::{BaseEntity, StateError, StateResult};
+
+#[derive(Clone)]
+pub struct Select {
+  client: Arc<CommandHandle>,
+  info: SelectInfo,
+  state: watch::Receiver<Option<EntityState>>,
+}
+
+impl Select {
+  pub fn new(
+    client: Arc<CommandHandle>,
+    info: SelectInfo,
+    state: watch::Receiver<Option<EntityState>>,
+  ) -> Self {
+    Select {
+      client,
+      info,
+      state,
+    }
+  }
+
+  pub fn info(&self) -> &SelectInfo {
+    &self.info
+  }
+
+  pub fn get_state(&self) -> StateResult<SelectState> {
+    match self.state.borrow().as_ref() {
+      Some(EntityState::Select(state)) => Ok(state.clone()),
+      Some(_) => Err(StateError::NotValidState),
+      None => Err(StateError::EntityKeyNotFound(self.info.entity_info.key)),
+    }
+  }
+
+  /// Returns a cloned receiver for watching state changes from an external context.
+  pub fn state_receiver(&self) -> watch::Receiver<Option<EntityState>> {
+    self.state.clone()
+  }
+
+  /// Wait for the next state change and return the updated state.
+  pub async fn state_changed(&mut self) -> StateResult<SelectState> {
+    self
+      .state
+      .changed()
+      .await
+      .map_err(|_| StateError::EntityKeyNotFound(self.info.entity_info.key))?;
+    self.get_state()
+  }
+
+  pub fn options(&self) -> &[String] {
+    &self.info.options
+  }
+
+  /// The selected option, or `None` while the device reports no value.
+  pub fn value(&self) -> StateResult<Option<String>> {
+    let state = self.get_state()?;
+    Ok((!state.missing_state).then_some(state.state))
+  }
+
+  pub async fn select_option(&self, option: impl Into<String>) -> crate::Result<()> {
+    Ok(
+      self
+        .client
+        .select_command(self.info.entity_info.key, option.into())
+        .await?,
+    )
+  }
+}
+
+impl BaseEntity for Select {
+  fn key(&self) -> u32 {
+    self.info.entity_info.key
+  }
+
+  fn device_id(&self) -> u32 {
+    self.info.entity_info.device_id
+  }
+
+  fn name(&self) -> String {
+    self.info.entity_info.name.clone()
+  }
+}
@@ -0,0 +1,67 @@
+use esphomeapi::model::{EntityState, SensorInfo, SensorState};
+use tokio::sync::watch;
+
+use super::{BaseEntity, StateError, StateResult};
+
+#[derive(Clone)]
+pub struct Sensor {
+  info: SensorInfo,
+  state: watch::Receiver<Option<EntityState>>,
+}
+
+impl Sensor {
+  pub fn new(info: SensorInfo, state: watch::Receiver<Option<EntityState>>) -> Self {
+    Sensor { info, state }
+  }
+
+  pub fn info(&self) -> &SensorInfo {
+    &self.info
+  }
+
+  pub fn get_state(&self) -> StateResult<SensorState> {
+    match self.state.borrow().as_ref() {
+      Some(EntityState::Sensor(state)) => Ok(state.clone()),
+      Some(_) => Err(StateError::NotValidState),
+      None => Err(StateError::EntityKeyNotFound(self.info.entity_info.key)),
+    }
+  }
+
+  /// Returns a cloned receiver for watching state changes from an external context.
+  pub fn state_receiver(&self) -> watch::Receiver<Option<EntityState>> {
+    self.state.clone()
+  }
+
+  /// Wait for the next state change and return the updated state.
+  pub async fn state_changed(&mut self) -> StateResult<SensorState> {
+    self
+      .state
+      .changed()
+      .await
+      .map_err(|_| StateError::EntityKeyNotFound(self.info.entity_info.key))?;
+    self.get_state()
+  }
+
+  /// The current reading, or `None` while the device reports no value.
+  pub fn value(&self) -> StateResult<Option<f32>> {
+    let state = self.get_state()?;
+    Ok((!state.missing_state).then_some(state.state))
+  }
+
+  pub fn unit_of_measurement(&self) -> &str {
+    &self.info.unit_of_measurement
+  }
+}
+
+impl BaseEntity for Sensor {
+  fn key(&self) -> u32 {
+    self.info.entity_info.key
+  }
+
+  fn device_id(&self) -> u32 {
+    self.info.entity_info.device_id
+  }
+
+  fn name(&self) -> String {
+    self.info.entity_info.name.clone()
+  }
+}
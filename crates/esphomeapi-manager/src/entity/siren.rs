@@ -0,0 +1,159 @@
+use std::sync::Arc;
+
+use esphomeapi::{
+  CommandHandle,
+  model::{EntityState, SirenInfo, SirenState},
+};
+use tokio::sync::watch;
+
+use super::{BaseEntity, StateError, StateResult};
+
+/// Builder for constructing siren commands with a fluent API.
+///
+/// Created via [`Siren::command()`]. Only the fields that are set are sent.
+///
+/// # Example
+/// ```ignore
+/// siren.command()
+///     .state(true)
+///     .tone("alarm")
+///     .duration(30)
+///     .send()
+///     .await?;
+/// ```
+pub struct SirenCommandBuilder<'a> {
+  siren: &'a Siren,
+  state: Option<bool>,
+  tone: Option<String>,
+  volume: Option<f32>,
+  duration: Option<u32>,
+}
+
+impl<'a> SirenCommandBuilder<'a> {
+  fn new(siren: &'a Siren) -> Self {
+    Self {
+      siren,
+      state: None,
+      tone: None,
+      volume: None,
+      duration: None,
+    }
+  }
+
+  pub fn state(mut self, state: bool) -> Self {
+    self.state = Some(state);
+    self
+  }
+
+  /// One of the tones listed in [`SirenInfo::tones`].
+  pub fn tone(mut self, tone: impl Into<String>) -> Self {
+    self.tone = Some(tone.into());
+    self
+  }
+
+  /// Volume from `0.0` to `1.0`, if the siren supports it.
+  pub fn volume(mut self, volume: f32) -> Self {
+    self.volume = Some(volume);
+    self
+  }
+
+  /// How long to sound, in seconds, if the siren supports it.
+  pub fn duration(mut self, duration: u32) -> Self {
+    self.duration = Some(duration);
+    self
+  }
+
+  pub async fn send(self) -> crate::Result<()> {
+    Ok(
+      self
+        .siren
+        .client
+        .siren_command(
+          self.siren.info.entity_info.key,
+          self.state,
+          self.tone,
+          self.volume,
+          self.duration,
+        )
+        .await?,
+    )
+  }
+}
+
+#[derive(Clone)]
+pub struct Siren {
+  client: Arc<CommandHandle>,
+  info: SirenInfo,
+  state: watch::Receiver<Option<EntityState>>,
+}
+
+impl Siren {
+  pub fn new(
+    client: Arc<CommandHandle>,
+    info: SirenInfo,
+    state: watch::Receiver<Option<EntityState>>,
+  ) -> Self {
+    Siren {
+      client,
+      info,
+      state,
+    }
+  }
+
+  pub fn info(&self) -> &SirenInfo {
+    &self.info
+  }
+
+  pub fn command(&self) -> SirenCommandBuilder<'_> {
+    SirenCommandBuilder::new(self)
+  }
+
+  pub fn get_state(&self) -> StateResult<SirenState> {
+    match self.state.borrow().as_ref() {
+      Some(EntityState::Siren(state)) => Ok(state.clone()),
+      Some(_) => Err(StateError::NotValidState),
+      None => Err(StateError::EntityKeyNotFound(self.info.entity_info.key)),
+    }
+  }
+
+  /// Returns a cloned receiver for watching state changes from an external context.
+  pub fn state_receiver(&self) -> watch::Receiver<Option<EntityState>> {
+    self.state.clone()
+  }
+
+  /// Wait for the next state change and return the updated state.
+  pub async fn state_changed(&mut self) -> StateResult<SirenState> {
+    self
+      .state
+      .changed()
+      .await
+      .map_err(|_| StateError::EntityKeyNotFound(self.info.entity_info.key))?;
+    self.get_state()
+  }
+
+  pub fn is_on(&self) -> StateResult<bool> {
+    Ok(self.get_state()?.state)
+  }
+
+  pub async fn turn_on(&self) -> crate::Result<()> {
+    self.command().state(true).send().await
+  }
+
+  pub async fn turn_off(&self) -> crate::Result<()> {
+    self.command().state(false).send().await
+  }
+}
+
+impl BaseEntity for Siren {
+  fn key(&self) -> u32 {
+    self.info.entity_info.key
+  }
+
+  fn device_id(&self) -> u32 {
+    self.info.entity_info.device_id
+  }
+
+  fn name(&self) -> String {
+    self.info.entity_info.name.clone()
+  }
+}
@@ -55,28 +55,32 @@ impl Switch {
     Ok(self.get_state()?.state)
   }
 
-  pub async fn turn_on(&self) -> esphomeapi::Result<()> {
-    self
-      .client
-      .switch_command(self.info.entity_info.key, true)
-      .await
+  pub async fn turn_on(&self) -> crate::Result<()> {
+    Ok(
+      self
+        .client
+        .switch_command(self.info.entity_info.key, true)
+        .await?,
+    )
   }
 
-  pub async fn turn_off(&self) -> esphomeapi::Result<()> {
-    self
-      .client
-      .switch_command(self.info.entity_info.key, false)
-      .await
+  pub async fn turn_off(&self) -> crate::Result<()> {
+    Ok(
+      self
+        .client
+        .switch_command(self.info.entity_info.key, false)
+        .await?,
+    )
   }
 
-  pub async fn toggle(&self) -> esphomeapi::Result<()> {
+  pub async fn toggle(&self) -> crate::Result<()> {
     match self.is_on()? {
       true => self.turn_off().await,
       false => self.turn_on().await,
     }
   }
 
-  pub async fn set_state(&self, state: bool) -> esphomeapi::Result<()> {
+  pub async fn set_state(&self, state: bool) -> crate::Result<()> {
     match state {
       true => self.turn_on().await,
       false => self.turn_off().await,
@@ -89,6 +93,10 @@ impl BaseEntity for Switch {
     self.info.entity_info.key
   }
 
+  fn device_id(&self) -> u32 {
+    self.info.entity_info.device_id
+  }
+
   fn name(&self) -> String {
     self.info.entity_info.name.clone()
   }
@@ -0,0 +1,88 @@
+use std::sync::Arc;
+
+use esphomeapi::{
+  CommandHandle,
+  model::{EntityState, TextInfo, TextState},
+};
+use tokio::sync::watch;
+
+pub use esphomeapi::model::TextMode;
+
+use super::{BaseEntity, StateError, StateResult};
+
+#[derive(Clone)]
+pub struct Text {
+  client: Arc<CommandHandle>,
+  info: TextInfo,
+  state: watch::Receiver<Option<EntityState>>,
+}
+
+impl Text {
+  pub fn new(
+    client: Arc<CommandHandle>,
+    info: TextInfo,
+    state: watch::Receiver<Option<EntityState>>,
+  ) -> Self {
+    Text {
+      client,
+      info,
+      state,
+    }
+  }
+
+  pub fn info(&self) -> &TextInfo {
+    &self.info
+  }
+
+  pub fn get_state(&self) -> StateResult<TextState> {
+    match self.state.borrow().as_ref() {
+      Some(EntityState::Text(state)) => Ok(state.clone()),
+      Some(_) => Err(StateError::NotValidState),
+      None => Err(StateError::EntityKeyNotFound(self.info.entity_info.key)),
+    }
+  }
+
+  /// Returns a cloned receiver for watching state changes from an external context.
+  pub fn state_receiver(&self) -> watch::Receiver<Option<EntityState>> {
+    self.state.clone()
+  }
+
+  /// Wait for the next state change and return the updated state.
+  pub async fn state_changed(&mut self) -> StateResult<TextState> {
+    self
+      .state
+      .changed()
+      .await
+      .map_err(|_| StateError::EntityKeyNotFound(self.info.entity_info.key))?;
+    self.get_state()
+  }
+
+  /// The current text, or `None` while the device reports no value.
+  pub fn value(&self) -> StateResult<Option<String>> {
+    let state = self.get_state()?;
+    Ok((!state.missing_state).then_some(state.state))
+  }
+
+  pub async fn set_value(&self, value: impl Into<String>) -> crate::Result<()> {
+    Ok(
+      self
+        .client
+        .text_command(self.info.entity_info.key, value.into())
+        .await?,
+    )
+  }
+}
+
+impl BaseEntity for Text {
+  fn key(&self) -> u32 {
+    self.info.entity_info.key
+  }
+
+  fn device_id(&self) -> u32 {
+    self.info.entity_info.device_id
+  }
+
+  fn name(&self) -> String {
+    self.info.entity_info.name.clone()
+  }
+}
@@ -0,0 +1,63 @@
+use esphomeapi::model::{EntityState, TextSensorInfo, TextSensorState};
+use tokio::sync::watch;
+
+use super::{BaseEntity, StateError, StateResult};
+
+#[derive(Clone)]
+pub struct TextSensor {
+  info: TextSensorInfo,
+  state: watch::Receiver<Option<EntityState>>,
+}
+
+impl TextSensor {
+  pub fn new(info: TextSensorInfo, state: watch::Receiver<Option<EntityState>>) -> Self {
+    TextSensor { info, state }
+  }
+
+  pub fn info(&self) -> &TextSensorInfo {
+    &self.info
+  }
+
+  pub fn get_state(&self) -> StateResult<TextSensorState> {
+    match self.state.borrow().as_ref() {
+      Some(EntityState::TextSensor(state)) => Ok(state.clone()),
+      Some(_) => Err(StateError::NotValidState),
+      None => Err(StateError::EntityKeyNotFound(self.info.entity_info.key)),
+    }
+  }
+
+  /// Returns a cloned receiver for watching state changes from an external context.
+  pub fn state_receiver(&self) -> watch::Receiver<Option<EntityState>> {
+    self.state.clone()
+  }
+
+  /// Wait for the next state change and return the updated state.
+  pub async fn state_changed(&mut self) -> StateResult<TextSensorState> {
+    self
+      .state
+      .changed()
+      .await
+      .map_err(|_| StateError::EntityKeyNotFound(self.info.entity_info.key))?;
+    self.get_state()
+  }
+
+  /// The current text, or `None` while the device reports no value.
+  pub fn value(&self) -> StateResult<Option<String>> {
+    let state = self.get_state()?;
+    Ok((!state.missing_state).then_some(state.state))
+  }
+}
+
+impl BaseEntity for TextSensor {
+  fn key(&self) -> u32 {
+    self.info.entity_info.key
+  }
+
+  fn device_id(&self) -> u32 {
+    self.info.entity_info.device_id
+  }
+
+  fn name(&self) -> String {
+    self.info.entity_info.name.clone()
+  }
+}
@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use esphomeapi::{
+  CommandHandle,
+  model::{EntityState, TimeInfo, TimeState},
+};
+use tokio::sync::watch;
+
+use super::{BaseEntity, StateError, StateResult};
+
+#[derive(Clone)]
+pub struct Time {
+  client: Arc<CommandHandle>,
+  info: TimeInfo,
+  state: watch::Receiver<Option<EntityState>>,
+}
+
+impl Time {
+  pub fn new(
+    client: Arc<CommandHandle>,
+    info: TimeInfo,
+    state: watch::Receiver<Option<EntityState>>,
+  ) -> Self {
+    Time {
+      client,
+      info,
+      state,
+    }
+  }
+
+  pub fn info(&self) -> &TimeInfo {
+    &self.info
+  }
+
+  pub fn get_state(&self) -> StateResult<TimeState> {
+    match self.state.borrow().as_ref() {
+      Some(EntityState::Time(state)) => Ok(state.clone()),
+      Some(_) => Err(StateError::NotValidState),
+      None => Err(StateError::EntityKeyNotFound(self.info.entity_info.key)),
+    }
+  }
+
+  /// Returns a cloned receiver for watching state changes from an external context.
+  pub fn state_receiver(&self) -> watch::Receiver<Option<EntityState>> {
+    self.state.clone()
+  }
+
+  /// Wait for the next state change and return the updated state.
+  pub async fn state_changed(&mut self) -> StateResult<TimeState> {
+    self
+      .state
+      .changed()
+      .await
+      .map_err(|_| StateError::EntityKeyNotFound(self.info.entity_info.key))?;
+    self.get_state()
+  }
+
+  /// The current `(hour, minute, second)`, or `None` while the device reports no value.
+  pub fn value(&self) -> StateResult<Option<(u32, u32, u32)>> {
+    let state = self.get_state()?;
+    Ok((!state.missing_state).then_some((state.hour, state.minute, state.second)))
+  }
+
+  pub async fn set_time(&self, hour: u32, minute: u32, second: u32) -> crate::Result<()> {
+    Ok(
+      self
+        .client
+        .time_command(self.info.entity_info.key, hour, minute, second)
+        .await?,
+    )
+  }
+}
+
+impl BaseEntity for Time {
+  fn key(&self) -> u32 {
+    self.info.entity_info.key
+  }
+
+  fn device_id(&self) -> u32 {
+    self.info.entity_info.device_id
+  }
+
+  fn name(&self) -> String {
+    self.info.entity_info.name.clone()
+  }
+}
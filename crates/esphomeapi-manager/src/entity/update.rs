@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use esphomeapi::{
+  CommandHandle,
+  model::{EntityState, UpdateCommand, UpdateInfo, UpdateState},
+};
+use tokio::sync::watch;
+
+use super::{BaseEntity, StateError, StateResult};
+
+#[derive(Clone)]
+pub struct Update {
+  client: Arc<CommandHandle>,
+  info: UpdateInfo,
+  state: watch::Receiver<Option<EntityState>>,
+}
+
+impl Update {
+  pub fn new(
+    client: Arc<CommandHandle>,
+    info: UpdateInfo,
+    state: watch::Receiver<Option<EntityState>>,
+  ) -> Self {
+    Update {
+      client,
+      info,
+      state,
+    }
+  }
+
+  pub fn info(&self) -> &UpdateInfo {
+    &self.info
+  }
+
+  pub fn get_state(&self) -> StateResult<UpdateState> {
+    match self.state.borrow().as_ref() {
+      Some(EntityState::Update(state)) => Ok(state.clone()),
+      Some(_) => Err(StateError::NotValidState),
+      None => Err(StateError::EntityKeyNotFound(self.info.entity_info.key)),
+    }
+  }
+
+  /// Returns a cloned receiver for watching state changes from an external context.
+  pub fn state_receiver(&self) -> watch::Receiver<Option<EntityState>> {
+    self.state.clone()
+  }
+
+  /// Wait for the next state change and return the updated state.
+  pub async fn state_changed(&mut self) -> StateResult<UpdateState> {
+    self
+      .state
+      .changed()
+      .await
+      .map_err(|_| StateError::EntityKeyNotFound(self.info.entity_info.key))?;
+    self.get_state()
+  }
+
+  /// Whether `latest_version` differs from the installed version.
+  pub fn has_update(&self) -> StateResult<bool> {
+    let state = self.get_state()?;
+    Ok(!state.missing_state && state.current_version != state.latest_version)
+  }
+
+  pub async fn install(&self) -> crate::Result<()> {
+    Ok(
+      self
+        .client
+        .update_command(self.info.entity_info.key, UpdateCommand::Install)
+        .await?,
+    )
+  }
+
+  /// Ask the device to look for a newer version.
+  pub async fn check(&self) -> crate::Result<()> {
+    Ok(
+      self
+        .client
+        .update_command(self.info.entity_info.key, UpdateCommand::Check)
+        .await?,
+    )
+  }
+}
+
+impl BaseEntity for Update {
+  fn key(&self) -> u32 {
+    self.info.entity_info.key
+  }
+
+  fn device_id(&self) -> u32 {
+    self.info.entity_info.device_id
+  }
+
+  fn name(&self) -> String {
+    self.info.entity_info.name.clone()
+  }
+}
@@ -0,0 +1,179 @@
+use std::sync::Arc;
+
+use enumflags2::BitFlags;
+use esphomeapi::{
+  CommandHandle,
+  model::{EntityState, WaterHeaterInfo, WaterHeaterState},
+};
+use tokio::sync::watch;
+
+pub use esphomeapi::model::{WaterHeaterMode, WaterHeaterStateFlag};
+
+use super::{BaseEntity, StateError, StateResult};
+
+/// Builder for constructing water heater commands with a fluent API.
+///
+/// Created via [`WaterHeater::command()`]. Only the fields that are set are sent.
+///
+/// # Example
+/// ```ignore
+/// water_heater.command()
+///     .mode(WaterHeaterMode::Eco)
+///     .target_temperature(55.0)
+///     .send()
+///     .await?;
+/// ```
+pub struct WaterHeaterCommandBuilder<'a> {
+  water_heater: &'a WaterHeater,
+  mode: Option<WaterHeaterMode>,
+  target_temperature: Option<f32>,
+  target_temperature_low: Option<f32>,
+  target_temperature_high: Option<f32>,
+  state: Option<BitFlags<WaterHeaterStateFlag>>,
+}
+
+impl<'a> WaterHeaterCommandBuilder<'a> {
+  fn new(water_heater: &'a WaterHeater) -> Self {
+    Self {
+      water_heater,
+      mode: None,
+      target_temperature: None,
+      target_temperature_low: None,
+      target_temperature_high: None,
+      state: None,
+    }
+  }
+
+  pub fn mode(mut self, mode: WaterHeaterMode) -> Self {
+    self.mode = Some(mode);
+    self
+  }
+
+  pub fn target_temperature(mut self, target_temperature: f32) -> Self {
+    self.target_temperature = Some(target_temperature);
+    self
+  }
+
+  pub fn target_temperature_low(mut self, target_temperature_low: f32) -> Self {
+    self.target_temperature_low = Some(target_temperature_low);
+    self
+  }
+
+  pub fn target_temperature_high(mut self, target_temperature_high: f32) -> Self {
+    self.target_temperature_high = Some(target_temperature_high);
+    self
+  }
+
+  /// Replace the away/on flags.
+  pub fn state(mut self, state: BitFlags<WaterHeaterStateFlag>) -> Self {
+    self.state = Some(state);
+    self
+  }
+
+  pub async fn send(self) -> crate::Result<()> {
+    Ok(
+      self
+        .water_heater
+        .client
+        .water_heater_command(
+          self.water_heater.info.entity_info.key,
+          self.mode,
+          self.target_temperature,
+          self.target_temperature_low,
+          self.target_temperature_high,
+          self.state,
+        )
+        .await?,
+    )
+  }
+}
+
+#[derive(Clone)]
+pub struct WaterHeater {
+  client: Arc<CommandHandle>,
+  info: WaterHeaterInfo,
+  state: watch::Receiver<Option<EntityState>>,
+}
+
+impl WaterHeater {
+  pub fn new(
+    client: Arc<CommandHandle>,
+    info: WaterHeaterInfo,
+    state: watch::Receiver<Option<EntityState>>,
+  ) -> Self {
+    WaterHeater {
+      client,
+      info,
+      state,
+    }
+  }
+
+  pub fn info(&self) -> &WaterHeaterInfo {
+    &self.info
+  }
+
+  pub fn command(&self) -> WaterHeaterCommandBuilder<'_> {
+    WaterHeaterCommandBuilder::new(self)
+  }
+
+  pub fn get_state(&self) -> StateResult<WaterHeaterState> {
+    match self.state.borrow().as_ref() {
+      Some(EntityState::WaterHeater(state)) => Ok(state.clone()),
+      Some(_) => Err(StateError::NotValidState),
+      None => Err(StateError::EntityKeyNotFound(self.info.entity_info.key)),
+    }
+  }
+
+  /// Returns a cloned receiver for watching state changes from an external context.
+  pub fn state_receiver(&self) -> watch::Receiver<Option<EntityState>> {
+    self.state.clone()
+  }
+
+  /// Wait for the next state change and return the updated state.
+  pub async fn state_changed(&mut self) -> StateResult<WaterHeaterState> {
+    self
+      .state
+      .changed()
+      .await
+      .map_err(|_| StateError::EntityKeyNotFound(self.info.entity_info.key))?;
+    self.get_state()
+  }
+
+  pub fn mode(&self) -> StateResult<WaterHeaterMode> {
+    Ok(self.get_state()?.mode)
+  }
+
+  pub fn current_temperature(&self) -> StateResult<f32> {
+    Ok(self.get_state()?.current_temperature)
+  }
+
+  pub fn target_temperature(&self) -> StateResult<f32> {
+    Ok(self.get_state()?.target_temperature)
+  }
+
+  pub async fn set_mode(&self, mode: WaterHeaterMode) -> crate::Result<()> {
+    self.command().mode(mode).send().await
+  }
+
+  pub async fn set_target_temperature(&self, target_temperature: f32) -> crate::Result<()> {
+    self
+      .command()
+      .target_temperature(target_temperature)
+      .send()
+      .await
+  }
+}
+
+impl BaseEntity for WaterHeater {
+  fn key(&self) -> u32 {
+    self.info.entity_info.key
+  }
+
+  fn device_id(&self) -> u32 {
+    self.info.entity_info.device_id
+  }
+
+  fn name(&self) -> String {
+    self.info.entity_info.name.clone()
+  }
+}
@@ -0,0 +1,21 @@
+use thiserror::Error;
+
+use crate::entity::StateError;
+
+/// Errors returned by the manager and its entities
+#[derive(Debug, Error)]
+pub enum Error {
+  /// An error from the underlying ESPHome client
+  #[error(transparent)]
+  Client(#[from] esphomeapi::Error),
+
+  /// The device was built without API encryption and cannot take a PSK
+  #[error("The device does not support API encryption")]
+  EncryptionUnsupported,
+
+  /// The entity has no usable state yet
+  #[error(transparent)]
+  State(#[from] StateError),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
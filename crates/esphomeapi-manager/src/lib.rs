@@ -1,74 +1,154 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, future::Future, sync::Arc, time::Duration};
 
 pub mod entity;
+mod error;
 
-use entity::Entity;
-pub use esphomeapi::model::{DeviceInfo, EntityState};
+use entity::{Entity, EntityId};
+pub use esphomeapi::model::{
+  APIVersion, AreaInfo, DeviceInfo, EntityState, ServerInfo, SubDeviceInfo,
+};
+pub use esphomeapi::{BufferSizes, ClientBuilder, ReconnectPolicy};
 use esphomeapi::{
   Client,
-  model::{EntityInfo, UserService},
+  model::{CameraImage, EntityInfo, UserService},
 };
 use tokio::sync::{broadcast, watch};
-use tracing::info;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
 
+pub use error::{Error, Result};
 pub use esphomeapi::discovery::{ServiceInfo, discover};
-pub use esphomeapi::model::{HomeAssistantEvent, HomeassistantActionRequest, LogEvent, LogLevel};
-pub use esphomeapi::{Error, Result};
+pub use esphomeapi::generate_noise_psk;
+pub use esphomeapi::model::{
+  ExecuteServiceResponse, HomeAssistantEvent, HomeassistantActionRequest,
+  HomeassistantActionResponse, LogEvent, LogLevel, UserServiceArgValue,
+};
+pub use esphomeapi::time::{DeviceTime, TimeSource};
+pub use esphomeapi::transport;
+
+/// How long [`Manager::rotate_noise_psk`] waits for the device to come back
+/// with the new key.
+const PSK_ROTATION_TIMEOUT: Duration = Duration::from_secs(60);
 
 pub struct Manager {
   pub client: Client,
   pub device_info: DeviceInfo,
-  entities: HashMap<u32, Entity>,
+  entities: HashMap<EntityId, Entity>,
   services: HashMap<u32, UserService>,
 }
 
 impl Manager {
-  pub async fn new(
-    address: String,
-    port: u32,
-    password: Option<String>,
-    expected_name: Option<String>,
-    psk: Option<String>,
-    client_info: Option<String>,
-    keep_alive_duration: Option<u32>,
-  ) -> Manager {
-    let client = Client::connect(
-      address,
-      port,
-      password,
-      expected_name,
-      psk,
-      client_info,
-      keep_alive_duration,
-    )
-    .await
-    .unwrap();
+  /// Connect with the given client configuration and load the device's entities.
+  pub async fn new(builder: ClientBuilder) -> Result<Manager> {
+    let client = builder.connect().await?;
 
-    let device_info = client.device_info().await.unwrap();
-    let (entities_response, services_response) = client.list_entities_services().await.unwrap();
+    let device_info = client.device_info().await?;
+    let (entities_response, services_response) = client.list_entities_services().await?;
 
-    let command_handle = Arc::new(client.command_handle());
+    let command_handle = client.command_handle();
 
     // Per-entity watch channels.
-    let mut state_senders: HashMap<u32, watch::Sender<Option<EntityState>>> = HashMap::new();
+    let mut state_senders: HashMap<EntityId, watch::Sender<Option<EntityState>>> = HashMap::new();
+    let mut camera_senders: HashMap<EntityId, watch::Sender<Option<CameraImage>>> = HashMap::new();
     let mut entities = HashMap::new();
 
     for entity in entities_response {
-      match entity {
-        EntityInfo::Light(info) => {
-          let (tx, rx) = watch::channel(None);
-          state_senders.insert(info.entity_info.key, tx);
-          let entity = entity::Light::new(Arc::clone(&command_handle), info.clone(), rx);
-          entities.insert(info.entity_info.key, Entity::Light(entity));
+      let id = EntityId::new(entity.device_id(), entity.key());
+      // Commands of sub-device entities must name the sub-device
+      let handle = Arc::new(command_handle.for_device(id.device_id));
+      let senders = &mut state_senders;
+      let entity = match entity {
+        EntityInfo::AlarmControlPanel(info) => Entity::AlarmControlPanel(
+          entity::AlarmControlPanel::new(handle, info, state_channel(senders, id)),
+        ),
+        EntityInfo::BinarySensor(info) => {
+          Entity::BinarySensor(entity::BinarySensor::new(info, state_channel(senders, id)))
         }
-        EntityInfo::Switch(info) => {
+        EntityInfo::Button(info) => Entity::Button(entity::Button::new(handle, info)),
+        EntityInfo::Camera(info) => {
           let (tx, rx) = watch::channel(None);
-          state_senders.insert(info.entity_info.key, tx);
-          let entity = entity::Switch::new(Arc::clone(&command_handle), info.clone(), rx);
-          entities.insert(info.entity_info.key, Entity::Switch(entity));
+          camera_senders.insert(id, tx);
+          Entity::Camera(entity::Camera::new(handle, info, rx))
         }
-        _ => {}
-      }
+        EntityInfo::Climate(info) => Entity::Climate(entity::Climate::new(
+          handle,
+          info,
+          state_channel(senders, id),
+        )),
+        EntityInfo::Cover(info) => {
+          Entity::Cover(entity::Cover::new(handle, info, state_channel(senders, id)))
+        }
+        EntityInfo::Date(info) => {
+          Entity::Date(entity::Date::new(handle, info, state_channel(senders, id)))
+        }
+        EntityInfo::DateTime(info) => Entity::DateTime(entity::DateTime::new(
+          handle,
+          info,
+          state_channel(senders, id),
+        )),
+        EntityInfo::Event(info) => {
+          Entity::Event(entity::Event::new(info, state_channel(senders, id)))
+        }
+        EntityInfo::Fan(info) => {
+          Entity::Fan(entity::Fan::new(handle, info, state_channel(senders, id)))
+        }
+        EntityInfo::Infrared(info) => Entity::Infrared(entity::Infrared::new(handle, info)),
+        EntityInfo::Light(info) => {
+          Entity::Light(entity::Light::new(handle, info, state_channel(senders, id)))
+        }
+        EntityInfo::Lock(info) => {
+          Entity::Lock(entity::Lock::new(handle, info, state_channel(senders, id)))
+        }
+        EntityInfo::MediaPlayer(info) => Entity::MediaPlayer(entity::MediaPlayer::new(
+          handle,
+          info,
+          state_channel(senders, id),
+        )),
+        EntityInfo::Number(info) => Entity::Number(entity::Number::new(
+          handle,
+          info,
+          state_channel(senders, id),
+        )),
+        EntityInfo::Select(info) => Entity::Select(entity::Select::new(
+          handle,
+          info,
+          state_channel(senders, id),
+        )),
+        EntityInfo::Sensor(info) => {
+          Entity::Sensor(entity::Sensor::new(info, state_channel(senders, id)))
+        }
+        EntityInfo::Siren(info) => {
+          Entity::Siren(entity::Siren::new(handle, info, state_channel(senders, id)))
+        }
+        EntityInfo::Switch(info) => Entity::Switch(entity::Switch::new(
+          handle,
+          info,
+          state_channel(senders, id),
+        )),
+        EntityInfo::Text(info) => {
+          Entity::Text(entity::Text::new(handle, info, state_channel(senders, id)))
+        }
+        EntityInfo::TextSensor(info) => {
+          Entity::TextSensor(entity::TextSensor::new(info, state_channel(senders, id)))
+        }
+        EntityInfo::Time(info) => {
+          Entity::Time(entity::Time::new(handle, info, state_channel(senders, id)))
+        }
+        EntityInfo::Update(info) => Entity::Update(entity::Update::new(
+          handle,
+          info,
+          state_channel(senders, id),
+        )),
+        EntityInfo::Valve(info) => {
+          Entity::Valve(entity::Valve::new(handle, info, state_channel(senders, id)))
+        }
+        EntityInfo::WaterHeater(info) => Entity::WaterHeater(entity::WaterHeater::new(
+          handle,
+          info,
+          state_channel(senders, id),
+        )),
+      };
+      entities.insert(id, entity);
     }
 
     let mut services = HashMap::new();
@@ -76,13 +156,16 @@ impl Manager {
       services.insert(service.key, service);
     }
 
-    client.request_states().await.unwrap();
+    client.request_states().await?;
 
     // The state receiver is tied to the long-lived SharedChannels — it keeps
     // working across reconnects without needing to be replaced.
     let state_subscriber = client.states_receiver();
     let state_senders = Arc::new(state_senders);
     Self::spawn_state_update_task(Arc::clone(&state_senders), state_subscriber);
+    if !camera_senders.is_empty() {
+      Self::spawn_camera_task(camera_senders, client.camera_frames_receiver());
+    }
 
     // After each reconnect, re-request entity states on the new connection.
     let reconnect_rx = client.on_reconnect();
@@ -100,22 +183,63 @@ impl Manager {
       }
     });
 
-    Manager {
+    Ok(Manager {
       client,
       device_info,
       entities,
       services,
-    }
+    })
   }
 
-  pub fn get_entities(&self) -> &HashMap<u32, Entity> {
+  pub fn get_entities(&self) -> &HashMap<EntityId, Entity> {
     &self.entities
   }
 
+  /// Get an entity of the node itself or, with a non-zero `device_id`, of
+  /// one of its sub-devices.
+  pub fn get_entity(&self, device_id: u32, key: u32) -> Option<&Entity> {
+    self.entities.get(&EntityId::new(device_id, key))
+  }
+
+  /// Entities grouped by sub-device id; the node's own entities are under 0.
+  pub fn entities_by_device(&self) -> HashMap<u32, Vec<&Entity>> {
+    let mut groups: HashMap<u32, Vec<&Entity>> = HashMap::new();
+    for entity in self.entities.values() {
+      groups.entry(entity.device_id()).or_default().push(entity);
+    }
+    groups
+  }
+
+  /// Entities grouped by the area of their (sub-)device. Entities whose
+  /// device has no area are left out.
+  pub fn entities_by_area(&self) -> HashMap<u32, Vec<&Entity>> {
+    let mut groups: HashMap<u32, Vec<&Entity>> = HashMap::new();
+    for entity in self.entities.values() {
+      if let Some(area) = self.device_info.device_area(entity.device_id()) {
+        groups.entry(area.area_id).or_default().push(entity);
+      }
+    }
+    groups
+  }
+
   pub fn get_services(&self) -> &HashMap<u32, UserService> {
     &self.services
   }
 
+  /// Execute a user-defined service without waiting for a response.
+  pub async fn execute_service(&self, key: u32, args: Vec<UserServiceArgValue>) -> Result<()> {
+    Ok(self.client.execute_service(key, args).await?)
+  }
+
+  /// Execute a user-defined service and wait for the device's response.
+  pub async fn execute_service_with_response(
+    &self,
+    key: u32,
+    args: Vec<UserServiceArgValue>,
+  ) -> Result<ExecuteServiceResponse> {
+    Ok(self.client.execute_service_with_response(key, args).await?)
+  }
+
   /// Subscribe to device-initiated disconnect events.
   pub fn on_device_disconnect(&self) -> broadcast::Receiver<()> {
     self.client.on_device_disconnect()
@@ -159,6 +283,81 @@ impl Manager {
     self.client.home_assistant_action_requests_receiver()
   }
 
+  /// Answer a Home Assistant action request.
+  pub async fn send_home_assistant_action_response(
+    &self,
+    response: HomeassistantActionResponse,
+  ) -> Result<()> {
+    Ok(
+      self
+        .client
+        .send_home_assistant_action_response(response)
+        .await?,
+    )
+  }
+
+  /// Subscribe to Home Assistant action requests and run `handler` for each.
+  ///
+  /// The handler returns the JSON response data on success or an error
+  /// message. Requests with `wants_response` set are answered with that
+  /// result; for the rest it is dropped. Each request gets its own task, so a
+  /// slow action does not hold up the ones after it. The subscription is
+  /// renewed after every reconnect. Abort the returned task to stop handling;
+  /// handlers already running still finish.
+  pub async fn on_home_assistant_action<F, Fut>(&self, handler: F) -> Result<JoinHandle<()>>
+  where
+    F: Fn(HomeassistantActionRequest) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = std::result::Result<Vec<u8>, String>> + Send + 'static,
+  {
+    // Subscribe before asking so no request slips past the handler
+    let mut subscriber = self.client.home_assistant_action_requests_receiver();
+    let mut reconnected = self.client.on_reconnect();
+    self.client.request_home_assistant_action_requests().await?;
+    let client = self.client.clone();
+    let handler = Arc::new(handler);
+    Ok(tokio::spawn(async move {
+      loop {
+        let request = tokio::select! {
+          request = subscriber.recv() => match request {
+            Ok(request) => request,
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+              warn!(skipped, "dropped Home Assistant action requests");
+              continue;
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+          },
+          reconnect = reconnected.recv() => {
+            if let Err(broadcast::error::RecvError::Closed) = reconnect {
+              break;
+            }
+            // The new connection starts without the subscription
+            if let Err(e) = client.request_home_assistant_action_requests().await {
+              warn!(error = %e, "failed to renew Home Assistant action subscription");
+            }
+            continue;
+          }
+        };
+        let handler = Arc::clone(&handler);
+        let client = client.clone();
+        tokio::spawn(async move {
+          let call_id = request.call_id;
+          let wants_response = request.wants_response;
+          let result = handler(request).await;
+          if !wants_response {
+            return;
+          }
+          let response = match result {
+            Ok(data) => HomeassistantActionResponse::success(call_id, data),
+            Err(message) => HomeassistantActionResponse::error(call_id, message),
+          };
+          if let Err(e) = client.send_home_assistant_action_response(response).await {
+            warn!(call_id, error = %e, "failed to answer Home Assistant action");
+          }
+        });
+      }
+    }))
+  }
+
   /// Subscribe to ESPHome logs.
   pub async fn subscribe_logs(
     &self,
@@ -181,28 +380,78 @@ impl Manager {
     state: String,
     attribute: Option<String>,
   ) -> Result<()> {
-    self
-      .client
-      .send_home_assistant_state(entity_id, state, attribute)
+    Ok(
+      self
+        .client
+        .send_home_assistant_state(entity_id, state, attribute)
+        .await?,
+    )
+  }
+
+  /// Move the device to a freshly generated Noise PSK and reconnect with it.
+  ///
+  /// Works for plaintext devices as well as for rotating an existing key.
+  /// Returns the new key once the client is connected with it; store it, as
+  /// the device accepts no other connections from then on.
+  pub async fn rotate_noise_psk(&self) -> Result<String> {
+    if !self.device_info.api_encryption_supported {
+      return Err(Error::EncryptionUnsupported);
+    }
+    let psk = generate_noise_psk();
+    let mut reconnected = self.client.on_reconnect();
+    self.client.set_noise_psk(&psk).await?;
+    tokio::time::timeout(PSK_ROTATION_TIMEOUT, reconnected.recv())
       .await
+      .map_err(|_| esphomeapi::Error::Timeout(PSK_ROTATION_TIMEOUT))?
+      .map_err(|_| esphomeapi::Error::Disconnected)?;
+    info!("device switched to the new Noise PSK");
+    Ok(psk)
   }
 
   /// Initiate a client-side disconnect.
   pub async fn disconnect(&self) -> Result<()> {
-    self.client.disconnect().await
+    Ok(self.client.disconnect().await?)
+  }
+
+  /// Publish each complete image to the camera entity it belongs to.
+  fn spawn_camera_task(
+    camera_senders: HashMap<EntityId, watch::Sender<Option<CameraImage>>>,
+    mut subscriber: broadcast::Receiver<CameraImage>,
+  ) {
+    tokio::spawn(async move {
+      loop {
+        let image = match subscriber.recv().await {
+          Ok(image) => image,
+          Err(broadcast::error::RecvError::Lagged(_)) => continue,
+          Err(broadcast::error::RecvError::Closed) => break,
+        };
+        if let Some(tx) = camera_senders.get(&EntityId::new(image.device_id, image.key)) {
+          let _ = tx.send(Some(image));
+        }
+      }
+    });
   }
 
   fn spawn_state_update_task(
-    state_senders: Arc<HashMap<u32, watch::Sender<Option<EntityState>>>>,
+    state_senders: Arc<HashMap<EntityId, watch::Sender<Option<EntityState>>>>,
     mut subscriber: broadcast::Receiver<EntityState>,
   ) {
     tokio::spawn(async move {
       while let Ok(state) = subscriber.recv().await {
         info!(state = ?state, "got state");
-        if let Some(tx) = state_senders.get(&state.key()) {
+        if let Some(tx) = state_senders.get(&EntityId::new(state.device_id(), state.key())) {
           let _ = tx.send(Some(state));
         }
       }
     });
   }
 }
+
+fn state_channel(
+  senders: &mut HashMap<EntityId, watch::Sender<Option<EntityState>>>,
+  id: EntityId,
+) -> watch::Receiver<Option<EntityState>> {
+  let (tx, rx) = watch::channel(None);
+  senders.insert(id, tx);
+  rx
+}
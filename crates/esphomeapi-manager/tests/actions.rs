@@ -0,0 +1,122 @@
+use std::time::Duration;
+
+use esphomeapi::api;
+use esphomeapi::testing::{MockDevice, frame};
+use esphomeapi_manager::Manager;
+
+fn action(call_id: u32, service: &str, wants_response: bool) -> api::HomeassistantActionRequest {
+  api::HomeassistantActionRequest {
+    service: service.to_string(),
+    call_id,
+    wants_response,
+    ..Default::default()
+  }
+}
+
+#[tokio::test]
+async fn action_handler_answers_requests_that_want_a_response() {
+  let device = MockDevice::builder()
+    .on::<api::SubscribeHomeassistantServicesRequest, _>(|_| {
+      vec![
+        frame(&action(0, "light.turn_on", false)),
+        frame(&action(7, "weather.get_forecasts", true)),
+        frame(&action(8, "script.fail", true)),
+      ]
+    })
+    .start()
+    .await
+    .unwrap();
+  let manager = Manager::new(device.client_builder()).await.unwrap();
+
+  let _handler = manager
+    .on_home_assistant_action(|request| async move {
+      match request.service.as_str() {
+        "script.fail" => Err("script not found".to_string()),
+        _ => Ok(br#"{"ok":true}"#.to_vec()),
+      }
+    })
+    .await
+    .unwrap();
+
+  let mut responses = Vec::new();
+  for _ in 0..2 {
+    let response: api::HomeassistantActionResponse = device.next_received().await.unwrap();
+    responses.push(response);
+  }
+  responses.sort_by_key(|response| response.call_id);
+
+  assert_eq!(responses[0].call_id, 7);
+  assert!(responses[0].success);
+  assert_eq!(responses[0].response_data, br#"{"ok":true}"#);
+
+  assert_eq!(responses[1].call_id, 8);
+  assert!(!responses[1].success);
+  assert_eq!(responses[1].error_message, "script not found");
+
+  assert_eq!(
+    device
+      .received_of::<api::HomeassistantActionResponse>()
+      .len(),
+    2
+  );
+}
+
+#[tokio::test]
+async fn slow_action_does_not_hold_up_later_ones() {
+  let device = MockDevice::builder()
+    .on::<api::SubscribeHomeassistantServicesRequest, _>(|_| {
+      vec![
+        frame(&action(1, "script.slow", true)),
+        frame(&action(2, "light.turn_on", true)),
+      ]
+    })
+    .start()
+    .await
+    .unwrap();
+  let manager = Manager::new(device.client_builder()).await.unwrap();
+
+  let _handler = manager
+    .on_home_assistant_action(|request| async move {
+      if request.service == "script.slow" {
+        tokio::time::sleep(Duration::from_secs(60)).await;
+      }
+      Ok(Vec::new())
+    })
+    .await
+    .unwrap();
+
+  let response: api::HomeassistantActionResponse = device.next_received().await.unwrap();
+  assert_eq!(response.call_id, 2);
+}
+
+#[tokio::test]
+async fn action_handler_keeps_answering_after_a_reconnect() {
+  let device = MockDevice::builder()
+    .on::<api::SubscribeHomeassistantServicesRequest, _>(|_| {
+      vec![frame(&action(3, "weather.get_forecasts", true))]
+    })
+    .start()
+    .await
+    .unwrap();
+  let manager = Manager::new(device.client_builder()).await.unwrap();
+
+  let _handler = manager
+    .on_home_assistant_action(|_| async move { Ok(Vec::new()) })
+    .await
+    .unwrap();
+  let before: api::HomeassistantActionResponse = device.next_received().await.unwrap();
+  assert_eq!(before.call_id, 3);
+
+  device.drop_connection();
+  device.wait_for_connections(2).await.unwrap();
+
+  // The helper subscribes again on the new connection and answers the replay
+  let after: api::HomeassistantActionResponse = device.next_received().await.unwrap();
+  assert_eq!(after.call_id, 3);
+  assert_eq!(
+    device
+      .received_of::<api::SubscribeHomeassistantServicesRequest>()
+      .len(),
+    2
+  );
+}
@@ -0,0 +1,42 @@
+use esphomeapi::api;
+use esphomeapi::testing::MockDevice;
+use esphomeapi_manager::{ClientBuilder, Error, Manager};
+
+#[tokio::test]
+async fn plaintext_device_is_rotated_to_noise() {
+  let device = MockDevice::builder()
+    .device_info(api::DeviceInfoResponse {
+      api_encryption_supported: true,
+      ..Default::default()
+    })
+    .start()
+    .await
+    .unwrap();
+  let manager = Manager::new(device.client_builder()).await.unwrap();
+
+  let psk = manager.rotate_noise_psk().await.unwrap();
+
+  assert_eq!(device.connection_count(), 2);
+  manager.client.device_info().await.unwrap();
+  ClientBuilder::new(device.host())
+    .port(device.port())
+    .psk(psk)
+    .connect()
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn devices_without_encryption_are_left_alone() {
+  let device = MockDevice::builder().start().await.unwrap();
+  let manager = Manager::new(device.client_builder()).await.unwrap();
+
+  let result = manager.rotate_noise_psk().await;
+
+  assert!(matches!(result, Err(Error::EncryptionUnsupported)));
+  assert!(
+    device
+      .received_of::<api::NoiseEncryptionSetKeyRequest>()
+      .is_empty()
+  );
+}
@@ -0,0 +1,209 @@
+use esphomeapi::api;
+use esphomeapi::testing::MockDevice;
+use esphomeapi_manager::Manager;
+use esphomeapi_manager::entity::{BaseEntity as _, Entity, EntityId};
+
+#[tokio::test]
+async fn every_listed_entity_gets_a_wrapper() {
+  let device = MockDevice::builder()
+    .entity(&api::ListEntitiesSensorResponse {
+      key: 1,
+      name: "Temperature".to_string(),
+      ..Default::default()
+    })
+    .entity(&api::ListEntitiesCoverResponse {
+      key: 2,
+      name: "Blind".to_string(),
+      ..Default::default()
+    })
+    .entity(&api::ListEntitiesButtonResponse {
+      key: 3,
+      name: "Restart".to_string(),
+      ..Default::default()
+    })
+    .start()
+    .await
+    .unwrap();
+  let manager = Manager::new(device.client_builder()).await.unwrap();
+
+  let entities = manager.get_entities();
+  assert_eq!(entities.len(), 3);
+  assert!(matches!(entities[&EntityId::from(1)], Entity::Sensor(_)));
+  assert!(matches!(entities[&EntityId::from(2)], Entity::Cover(_)));
+  assert!(matches!(entities[&EntityId::from(3)], Entity::Button(_)));
+  assert_eq!(entities[&EntityId::from(2)].name(), "Blind");
+}
+
+#[tokio::test]
+async fn wrapper_tracks_state_and_sends_commands() {
+  let device = MockDevice::builder()
+    .entity(&api::ListEntitiesCoverResponse {
+      key: 2,
+      name: "Blind".to_string(),
+      supports_position: true,
+      ..Default::default()
+    })
+    .state(&api::CoverStateResponse {
+      key: 2,
+      position: 0.25,
+      ..Default::default()
+    })
+    .start()
+    .await
+    .unwrap();
+  let manager = Manager::new(device.client_builder()).await.unwrap();
+
+  let Entity::Cover(cover) = &manager.get_entities()[&EntityId::from(2)] else {
+    panic!("expected a cover");
+  };
+  let mut cover = cover.clone();
+  let position = match cover.position() {
+    Ok(position) => position,
+    Err(_) => cover.state_changed().await.unwrap().position,
+  };
+  assert_eq!(position, 0.25);
+
+  cover.set_position(0.75).await.unwrap();
+  let request: api::CoverCommandRequest = device.next_received().await.unwrap();
+  assert_eq!(request.key, cover.key());
+  assert!(request.has_position);
+  assert_eq!(request.position, 0.75);
+}
+
+#[tokio::test]
+async fn siren_and_water_heater_send_commands() {
+  let device = MockDevice::builder()
+    .entity(&api::ListEntitiesSirenResponse {
+      key: 4,
+      name: "Alarm".to_string(),
+      tones: vec!["beep".to_string()],
+      ..Default::default()
+    })
+    .entity(&api::ListEntitiesWaterHeaterResponse {
+      key: 5,
+      name: "Boiler".to_string(),
+      ..Default::default()
+    })
+    .start()
+    .await
+    .unwrap();
+  let manager = Manager::new(device.client_builder()).await.unwrap();
+  let entities = manager.get_entities();
+
+  let Entity::Siren(siren) = &entities[&EntityId::from(4)] else {
+    panic!("expected a siren");
+  };
+  siren
+    .command()
+    .state(true)
+    .tone("beep")
+    .send()
+    .await
+    .unwrap();
+  let request: api::SirenCommandRequest = device.next_received().await.unwrap();
+  assert_eq!(request.key, 4);
+  assert!(request.has_state && request.state);
+  assert!(request.has_tone);
+  assert_eq!(request.tone, "beep");
+  assert!(!request.has_duration);
+
+  let Entity::WaterHeater(water_heater) = &entities[&EntityId::from(5)] else {
+    panic!("expected a water heater");
+  };
+  water_heater.set_target_temperature(55.0).await.unwrap();
+  let request: api::WaterHeaterCommandRequest = device.next_received().await.unwrap();
+  assert_eq!(request.key, 5);
+  assert_eq!(request.has_fields, 2);
+  assert_eq!(request.target_temperature, 55.0);
+}
+
+#[tokio::test]
+async fn sub_device_entities_with_the_same_key_stay_apart() {
+  let device = MockDevice::builder()
+    .device_info(api::DeviceInfoResponse {
+      name: "multi".to_string(),
+      devices: vec![
+        api::DeviceInfo {
+          device_id: 11,
+          name: "Left".to_string(),
+          area_id: 1,
+          ..Default::default()
+        },
+        api::DeviceInfo {
+          device_id: 12,
+          name: "Right".to_string(),
+          area_id: 2,
+          ..Default::default()
+        },
+      ],
+      areas: vec![
+        api::AreaInfo {
+          area_id: 1,
+          name: "Kitchen".to_string(),
+          ..Default::default()
+        },
+        api::AreaInfo {
+          area_id: 2,
+          name: "Hall".to_string(),
+          ..Default::default()
+        },
+      ],
+      ..Default::default()
+    })
+    .entity(&api::ListEntitiesSwitchResponse {
+      key: 7,
+      name: "Relay".to_string(),
+      device_id: 11,
+      ..Default::default()
+    })
+    .entity(&api::ListEntitiesSwitchResponse {
+      key: 7,
+      name: "Relay".to_string(),
+      device_id: 12,
+      ..Default::default()
+    })
+    .state(&api::SwitchStateResponse {
+      key: 7,
+      state: true,
+      device_id: 12,
+      ..Default::default()
+    })
+    .start()
+    .await
+    .unwrap();
+  let manager = Manager::new(device.client_builder()).await.unwrap();
+
+  assert_eq!(manager.device_info.devices.len(), 2);
+  assert_eq!(
+    manager
+      .device_info
+      .device_area(12)
+      .map(|area| area.name.as_str()),
+    Some("Hall")
+  );
+  assert_eq!(manager.get_entities().len(), 2);
+  let by_device = manager.entities_by_device();
+  assert_eq!(by_device[&11].len(), 1);
+  assert_eq!(by_device[&12].len(), 1);
+  let by_area = manager.entities_by_area();
+  assert_eq!(by_area[&1][0].device_id(), 11);
+  assert_eq!(by_area[&2][0].device_id(), 12);
+
+  let Some(Entity::Switch(right)) = manager.get_entity(12, 7) else {
+    panic!("expected a switch");
+  };
+  let mut right = right.clone();
+  let on = match right.is_on() {
+    Ok(on) => on,
+    Err(_) => right.state_changed().await.unwrap().state,
+  };
+  assert!(on);
+  let Some(Entity::Switch(left)) = manager.get_entity(11, 7) else {
+    panic!("expected a switch");
+  };
+  assert!(left.is_on().is_err());
+
+  left.turn_on().await.unwrap();
+  let request: api::SwitchCommandRequest = device.next_received().await.unwrap();
+  assert_eq!((request.key, request.device_id), (7, 11));
+}
@@ -0,0 +1,398 @@
+//! Remote GATT client for devices reached through an ESPHome Bluetooth proxy.
+//!
+//! ```ignore
+//! let device = client.bluetooth_device(0xAABBCCDDEEFF, 0);
+//! device.connect().await?;
+//! for service in device.services().await? {
+//!   println!("{} ({} characteristics)", service.uuid, service.characteristics.len());
+//! }
+//! let mut notifications = device.start_notify(0x2A).await?;
+//! while let Ok(data) = notifications.recv().await {
+//!   println!("{data:02X?}");
+//! }
+//! ```
+
+use protobuf::Message as _;
+use tokio::sync::broadcast;
+use tokio::time::timeout;
+use tracing::warn;
+
+use crate::connection::{ProtobufMessage, ResponseFilter};
+use crate::model::{BluetoothDeviceRequestType, BluetoothGATTNotifyData, BluetoothGATTService};
+use crate::utils::Options as _;
+use crate::{proto, Client, Error, Result};
+
+/// A handle to one remote BLE device, identified by its address.
+///
+/// Every request is correlated with its answer by address (and handle for
+/// GATT operations), so several devices can be driven through the same proxy
+/// concurrently.
+#[derive(Clone)]
+pub struct RemoteBleDevice {
+  client: Client,
+  address: u64,
+  address_type: u32,
+}
+
+impl RemoteBleDevice {
+  pub(crate) fn new(client: Client, address: u64, address_type: u32) -> Self {
+    Self {
+      client,
+      address,
+      address_type,
+    }
+  }
+
+  pub fn address(&self) -> u64 {
+    self.address
+  }
+
+  /// Connect, letting the proxy reuse its cached GATT services. Returns the MTU.
+  pub async fn connect(&self) -> Result<u16> {
+    self
+      .connect_with(BluetoothDeviceRequestType::ConnectV3WithCache)
+      .await
+  }
+
+  /// Connect and force a fresh service discovery on the proxy. Returns the MTU.
+  pub async fn connect_without_cache(&self) -> Result<u16> {
+    self
+      .connect_with(BluetoothDeviceRequestType::ConnectV3WithoutCache)
+      .await
+  }
+
+  async fn connect_with(&self, request_type: BluetoothDeviceRequestType) -> Result<u16> {
+    let response = self
+      .device_request::<proto::api::BluetoothDeviceConnectionResponse>(request_type, |r| r.address)
+      .await?;
+    if !response.connected {
+      return Err(self.device_error("connect", response.error));
+    }
+    Ok(response.mtu as u16)
+  }
+
+  pub async fn disconnect(&self) -> Result<()> {
+    let response = self
+      .device_request::<proto::api::BluetoothDeviceConnectionResponse>(
+        BluetoothDeviceRequestType::Disconnect,
+        |r| r.address,
+      )
+      .await?;
+    if response.error != 0 {
+      return Err(self.device_error("disconnect", response.error));
+    }
+    Ok(())
+  }
+
+  /// Pair with the connected device.
+  pub async fn pair(&self) -> Result<()> {
+    let response = self
+      .device_request::<proto::api::BluetoothDevicePairingResponse>(
+        BluetoothDeviceRequestType::Pair,
+        |r| r.address,
+      )
+      .await?;
+    if !response.paired {
+      return Err(self.device_error("pair", response.error));
+    }
+    Ok(())
+  }
+
+  pub async fn unpair(&self) -> Result<()> {
+    let response = self
+      .device_request::<proto::api::BluetoothDeviceUnpairingResponse>(
+        BluetoothDeviceRequestType::Unpair,
+        |r| r.address,
+      )
+      .await?;
+    if !response.success {
+      return Err(self.device_error("unpair", response.error));
+    }
+    Ok(())
+  }
+
+  /// Drop the proxy's cached GATT services for this device.
+  pub async fn clear_cache(&self) -> Result<()> {
+    let response = self
+      .device_request::<proto::api::BluetoothDeviceClearCacheResponse>(
+        BluetoothDeviceRequestType::ClearCache,
+        |r| r.address,
+      )
+      .await?;
+    if !response.success {
+      return Err(self.device_error("clear cache", response.error));
+    }
+    Ok(())
+  }
+
+  /// Discover the GATT services of the connected device.
+  ///
+  /// Fails as soon as the proxy reports a GATT error, and with
+  /// [`Error::Timeout`] if the proxy never signals that discovery is done.
+  pub async fn services(&self) -> Result<Vec<BluetoothGATTService>> {
+    let address = self.address;
+    let filter = ResponseFilter::new(proto::api::BluetoothGATTGetServicesResponse::get_option_id())
+      .matching::<proto::api::BluetoothGATTGetServicesResponse, _>(move |response| {
+        response.address == address
+      })
+      .matching::<proto::api::BluetoothGATTGetServicesDoneResponse, _>(move |response| {
+        response.address == address
+      })
+      .or::<proto::api::BluetoothGATTErrorResponse>()
+      .matching::<proto::api::BluetoothGATTErrorResponse, _>(move |response| {
+        response.address == address
+      });
+
+    let done_type = proto::api::BluetoothGATTGetServicesDoneResponse::get_option_id();
+    let mut responses = self
+      .client
+      .send_await_stream_filtered(
+        proto::api::BluetoothGATTGetServicesRequest {
+          address,
+          ..Default::default()
+        },
+        filter,
+        done_type,
+      )
+      .await?;
+
+    let request_timeout = self.client.request_timeout();
+    let mut services = Vec::new();
+    loop {
+      let message = match timeout(request_timeout, responses.recv()).await {
+        Ok(Some(message)) => message,
+        Ok(None) => return Err(Error::Disconnected),
+        Err(_) => return Err(Error::Timeout(request_timeout)),
+      };
+      if message.protobuf_type == done_type {
+        return Ok(services);
+      }
+      self.check_gatt_error(&message)?;
+      let response =
+        proto::api::BluetoothGATTGetServicesResponse::parse_from_bytes(&message.protobuf_data)?;
+      services.extend(response.services.into_iter().map(Into::into));
+    }
+  }
+
+  /// Read a characteristic value.
+  pub async fn read(&self, handle: u16) -> Result<Vec<u8>> {
+    self
+      .read_handle(
+        proto::api::BluetoothGATTReadRequest {
+          address: self.address,
+          handle: handle.into(),
+          ..Default::default()
+        },
+        handle,
+      )
+      .await
+  }
+
+  /// Read a descriptor value.
+  pub async fn read_descriptor(&self, handle: u16) -> Result<Vec<u8>> {
+    self
+      .read_handle(
+        proto::api::BluetoothGATTReadDescriptorRequest {
+          address: self.address,
+          handle: handle.into(),
+          ..Default::default()
+        },
+        handle,
+      )
+      .await
+  }
+
+  async fn read_handle<M: protobuf::MessageFull>(
+    &self,
+    request: M,
+    handle: u16,
+  ) -> Result<Vec<u8>> {
+    let message = self
+      .gatt_request::<_, proto::api::BluetoothGATTReadResponse>(request, handle, |r| {
+        (r.address, r.handle)
+      })
+      .await?;
+    let response = proto::api::BluetoothGATTReadResponse::parse_from_bytes(&message.protobuf_data)?;
+    Ok(response.data)
+  }
+
+  /// Write a characteristic value.
+  ///
+  /// With `response` the device acknowledges the write and this waits for
+  /// it; otherwise the write is fire-and-forget.
+  pub async fn write(&self, handle: u16, data: Vec<u8>, response: bool) -> Result<()> {
+    let request = proto::api::BluetoothGATTWriteRequest {
+      address: self.address,
+      handle: handle.into(),
+      response,
+      data,
+      ..Default::default()
+    };
+    if !response {
+      return self.client.send(request).await;
+    }
+    self
+      .gatt_request::<_, proto::api::BluetoothGATTWriteResponse>(request, handle, |r| {
+        (r.address, r.handle)
+      })
+      .await?;
+    Ok(())
+  }
+
+  /// Write a descriptor value and wait for the acknowledgement.
+  pub async fn write_descriptor(&self, handle: u16, data: Vec<u8>) -> Result<()> {
+    self
+      .gatt_request::<_, proto::api::BluetoothGATTWriteResponse>(
+        proto::api::BluetoothGATTWriteDescriptorRequest {
+          address: self.address,
+          handle: handle.into(),
+          data,
+          ..Default::default()
+        },
+        handle,
+        |r| (r.address, r.handle),
+      )
+      .await?;
+    Ok(())
+  }
+
+  /// Enable notifications on a characteristic and stream the values.
+  pub async fn start_notify(&self, handle: u16) -> Result<BleNotifications> {
+    // Subscribe first so no notification sent right after the ack is lost
+    let receiver = self.client.ble_notifications_receiver();
+    self.notify_request(handle, true).await?;
+    Ok(BleNotifications {
+      receiver,
+      address: self.address,
+      handle,
+    })
+  }
+
+  /// Disable notifications on a characteristic.
+  pub async fn stop_notify(&self, handle: u16) -> Result<()> {
+    self.notify_request(handle, false).await
+  }
+
+  async fn notify_request(&self, handle: u16, enable: bool) -> Result<()> {
+    self
+      .gatt_request::<_, proto::api::BluetoothGATTNotifyResponse>(
+        proto::api::BluetoothGATTNotifyRequest {
+          address: self.address,
+          handle: handle.into(),
+          enable,
+          ..Default::default()
+        },
+        handle,
+        |r| (r.address, r.handle),
+      )
+      .await?;
+    Ok(())
+  }
+
+  /// Send a `BluetoothDeviceRequest` and wait for the answer of type `R`
+  /// whose `address_of` is this device.
+  async fn device_request<R: protobuf::MessageFull>(
+    &self,
+    request_type: BluetoothDeviceRequestType,
+    address_of: fn(&R) -> u64,
+  ) -> Result<R> {
+    let address = self.address;
+    let filter = ResponseFilter::new(R::get_option_id())
+      .matching::<R, _>(move |response| address_of(response) == address);
+    let message = self
+      .client
+      .send_await_filtered(
+        proto::api::BluetoothDeviceRequest {
+          address,
+          request_type: protobuf::EnumOrUnknown::new(request_type.into()),
+          has_address_type: true,
+          address_type: self.address_type,
+          ..Default::default()
+        },
+        filter,
+        self.client.request_timeout(),
+      )
+      .await?;
+    Ok(R::parse_from_bytes(&message.protobuf_data)?)
+  }
+
+  /// Send a GATT request and wait for the answer of type `R` or a
+  /// `BluetoothGATTErrorResponse` for the same address and handle.
+  ///
+  /// `target_of` extracts the address and handle of an `R`.
+  async fn gatt_request<M, R>(
+    &self,
+    request: M,
+    handle: u16,
+    target_of: fn(&R) -> (u64, u32),
+  ) -> Result<ProtobufMessage>
+  where
+    M: protobuf::MessageFull,
+    R: protobuf::MessageFull,
+  {
+    let address = self.address;
+    let handle = u32::from(handle);
+    let filter = ResponseFilter::new(R::get_option_id())
+      .matching::<R, _>(move |response| target_of(response) == (address, handle))
+      .or::<proto::api::BluetoothGATTErrorResponse>()
+      .matching::<proto::api::BluetoothGATTErrorResponse, _>(move |response| {
+        response.address == address && response.handle == handle
+      });
+    let message = self
+      .client
+      .send_await_filtered(request, filter, self.client.request_timeout())
+      .await?;
+    self.check_gatt_error(&message)?;
+    Ok(message)
+  }
+
+  fn check_gatt_error(&self, message: &ProtobufMessage) -> Result<()> {
+    if message.protobuf_type != proto::api::BluetoothGATTErrorResponse::get_option_id() {
+      return Ok(());
+    }
+    let error = proto::api::BluetoothGATTErrorResponse::parse_from_bytes(&message.protobuf_data)?;
+    Err(Error::BluetoothGatt {
+      address: error.address,
+      handle: error.handle as u16,
+      error: error.error,
+    })
+  }
+
+  fn device_error(&self, operation: &'static str, error: i32) -> Error {
+    Error::BluetoothDevice {
+      address: self.address,
+      operation,
+      error,
+    }
+  }
+}
+
+/// Values notified by one characteristic, returned by
+/// [`RemoteBleDevice::start_notify`].
+pub struct BleNotifications {
+  receiver: broadcast::Receiver<BluetoothGATTNotifyData>,
+  address: u64,
+  handle: u16,
+}
+
+impl BleNotifications {
+  /// Wait for the next notified value.
+  ///
+  /// Fails with [`Error::Disconnected`] once the client is gone.
+  pub async fn recv(&mut self) -> Result<Vec<u8>> {
+    loop {
+      match self.receiver.recv().await {
+        Ok(notification)
+          if notification.address == self.address && notification.handle == self.handle =>
+        {
+          return Ok(notification.data);
+        }
+        Ok(_) => {}
+        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+          warn!(skipped, handle = self.handle, "dropped BLE notifications");
+        }
+        Err(broadcast::error::RecvError::Closed) => return Err(Error::Disconnected),
+      }
+    }
+  }
+}
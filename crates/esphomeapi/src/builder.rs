@@ -0,0 +1,311 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::connection::codec::decode_psk;
+use crate::connection::ConnectionConfig;
+use crate::time::TimeSource;
+use crate::transport::{Tcp, Transport};
+use crate::{Client, Error, Result};
+
+/// How the client reconnects after the device drops the connection abruptly.
+///
+/// The delay before attempt `n` (starting at 0) is
+/// `initial_delay * multiplier^n`, capped at `max_delay` and spread by
+/// `jitter` (a fraction of the delay, e.g. `0.2` for ±20 %).
+#[derive(Clone, Debug)]
+pub struct ReconnectPolicy {
+  pub enabled: bool,
+  pub initial_delay: Duration,
+  pub max_delay: Duration,
+  pub multiplier: f64,
+  /// Give up after this many failed attempts; `None` retries forever.
+  pub max_attempts: Option<u32>,
+  pub jitter: f64,
+}
+
+impl ReconnectPolicy {
+  /// Never reconnect automatically.
+  pub fn disabled() -> Self {
+    Self {
+      enabled: false,
+      ..Default::default()
+    }
+  }
+
+  /// Reject settings that would make [`delay`](Self::delay) panic or shrink.
+  fn validate(&self) -> Result<()> {
+    if !self.multiplier.is_finite() || self.multiplier < 1.0 {
+      return Err(Error::InvalidConfig(format!(
+        "reconnect multiplier must be finite and at least 1.0, got {}",
+        self.multiplier
+      )));
+    }
+    if !self.jitter.is_finite() {
+      return Err(Error::InvalidConfig(format!(
+        "reconnect jitter must be finite, got {}",
+        self.jitter
+      )));
+    }
+    Ok(())
+  }
+
+  /// Delay before the given (zero-based) reconnect attempt.
+  pub(crate) fn delay(&self, attempt: u32) -> Duration {
+    let base = self.initial_delay.as_secs_f64() * self.multiplier.powi(attempt as i32);
+    let base = base.min(self.max_delay.as_secs_f64());
+    let jitter = self.jitter.clamp(0.0, 1.0);
+    let factor = 1.0 + jitter * (fastrand::f64() * 2.0 - 1.0);
+    Duration::from_secs_f64(base * factor)
+  }
+}
+
+impl Default for ReconnectPolicy {
+  fn default() -> Self {
+    Self {
+      enabled: true,
+      initial_delay: Duration::from_secs(5),
+      max_delay: Duration::from_secs(60),
+      multiplier: 2.0,
+      max_attempts: None,
+      jitter: 0.0,
+    }
+  }
+}
+
+/// Capacities of the broadcast channels behind the client's receivers.
+///
+/// Slow subscribers that fall further behind than this miss messages
+/// (`RecvError::Lagged`). Every capacity must be at least 1.
+#[derive(Clone, Debug)]
+pub struct BufferSizes {
+  pub states: usize,
+  pub home_assistant_events: usize,
+  pub logs: usize,
+  pub action_requests: usize,
+  /// Raw camera image chunks
+  pub camera: usize,
+  /// Reassembled camera images
+  pub camera_frames: usize,
+  /// Advertisements are unbatched, so a busy proxy fills this quickly
+  pub ble_advertisements: usize,
+  pub bluetooth_scanner_states: usize,
+  pub ble_notifications: usize,
+  /// Connection state changes and free-slot updates of a Bluetooth proxy
+  pub bluetooth_connections: usize,
+  /// Pipeline requests and microphone audio from a voice satellite
+  pub voice_assistant: usize,
+  /// Timings captured by infrared/RF receivers
+  pub infrared: usize,
+  /// Controller frames and home id changes from a Z-Wave proxy
+  pub zwave: usize,
+}
+
+impl BufferSizes {
+  fn validate(&self) -> Result<()> {
+    let sizes = [
+      ("states", self.states),
+      ("home_assistant_events", self.home_assistant_events),
+      ("logs", self.logs),
+      ("action_requests", self.action_requests),
+      ("camera", self.camera),
+      ("camera_frames", self.camera_frames),
+      ("ble_advertisements", self.ble_advertisements),
+      ("bluetooth_scanner_states", self.bluetooth_scanner_states),
+      ("ble_notifications", self.ble_notifications),
+      ("bluetooth_connections", self.bluetooth_connections),
+      ("voice_assistant", self.voice_assistant),
+      ("infrared", self.infrared),
+      ("zwave", self.zwave),
+    ];
+    match sizes.iter().find(|(_, size)| *size == 0) {
+      Some((name, _)) => Err(Error::InvalidConfig(format!(
+        "buffer size `{name}` must be at least 1"
+      ))),
+      None => Ok(()),
+    }
+  }
+}
+
+impl Default for BufferSizes {
+  fn default() -> Self {
+    Self {
+      states: 64,
+      home_assistant_events: 32,
+      logs: 128,
+      action_requests: 32,
+      camera: 8,
+      camera_frames: 4,
+      ble_advertisements: 256,
+      bluetooth_scanner_states: 8,
+      ble_notifications: 64,
+      bluetooth_connections: 16,
+      voice_assistant: 128,
+      infrared: 16,
+      zwave: 64,
+    }
+  }
+}
+
+/// Builder for a [`Client`] connection.
+///
+/// # Example
+/// ```ignore
+/// let client = ClientBuilder::new("living-room.local")
+///   .psk("px7tsbK3C7bpXHr2OevEV2ZMg/FrNBw2+O2pNPbedtA=")
+///   .expected_name("living-room")
+///   .reconnect_policy(ReconnectPolicy { max_attempts: Some(10), ..Default::default() })
+///   .connect()
+///   .await?;
+/// ```
+#[derive(Clone, Debug)]
+pub struct ClientBuilder {
+  pub(crate) host: String,
+  pub(crate) port: u32,
+  pub(crate) password: Option<String>,
+  pub(crate) expected_name: Option<String>,
+  pub(crate) psk: Option<String>,
+  pub(crate) client_info: String,
+  pub(crate) keep_alive: Duration,
+  pub(crate) connect_timeout: Duration,
+  pub(crate) request_timeout: Duration,
+  pub(crate) list_entities_timeout: Duration,
+  pub(crate) api_version: (u32, u32),
+  pub(crate) reconnect_policy: ReconnectPolicy,
+  pub(crate) buffer_sizes: BufferSizes,
+  pub(crate) time_source: TimeSource,
+  pub(crate) transport: Arc<dyn Transport>,
+}
+
+impl ClientBuilder {
+  pub fn new(host: impl Into<String>) -> Self {
+    Self {
+      host: host.into(),
+      port: 6053,
+      password: None,
+      expected_name: None,
+      psk: None,
+      client_info: "esphome-rs".to_string(),
+      keep_alive: Duration::from_secs(20),
+      connect_timeout: Duration::from_secs(10),
+      request_timeout: Duration::from_secs(10),
+      list_entities_timeout: Duration::from_secs(60),
+      api_version: (1, 10),
+      reconnect_policy: ReconnectPolicy::default(),
+      buffer_sizes: BufferSizes::default(),
+      time_source: TimeSource::default(),
+      transport: Arc::new(Tcp),
+    }
+  }
+
+  pub fn port(mut self, port: u32) -> Self {
+    self.port = port;
+    self
+  }
+
+  /// Legacy API password (removed from ESPHome in 2026.1).
+  pub fn password(mut self, password: impl Into<String>) -> Self {
+    self.password = Some(password.into());
+    self
+  }
+
+  /// Reject the connection unless the device reports this node name.
+  pub fn expected_name(mut self, expected_name: impl Into<String>) -> Self {
+    self.expected_name = Some(expected_name.into());
+    self
+  }
+
+  /// Base64-encoded Noise pre-shared key; enables encryption.
+  pub fn psk(mut self, psk: impl Into<String>) -> Self {
+    self.psk = Some(psk.into());
+    self
+  }
+
+  /// Client name sent in `HelloRequest` and shown in the device logs.
+  pub fn client_info(mut self, client_info: impl Into<String>) -> Self {
+    self.client_info = client_info.into();
+    self
+  }
+
+  /// Interval between keep-alive pings.
+  pub fn keep_alive(mut self, keep_alive: Duration) -> Self {
+    self.keep_alive = keep_alive;
+    self
+  }
+
+  /// Upper bound for dialing, the handshake and the hello exchange.
+  pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+    self.connect_timeout = connect_timeout;
+    self
+  }
+
+  /// Timeout for single request/response exchanges such as `device_info`.
+  pub fn request_timeout(mut self, request_timeout: Duration) -> Self {
+    self.request_timeout = request_timeout;
+    self
+  }
+
+  /// Idle timeout while collecting the `ListEntities` responses.
+  pub fn list_entities_timeout(mut self, list_entities_timeout: Duration) -> Self {
+    self.list_entities_timeout = list_entities_timeout;
+    self
+  }
+
+  /// API version announced in `HelloRequest`.
+  pub fn api_version(mut self, major: u32, minor: u32) -> Self {
+    self.api_version = (major, minor);
+    self
+  }
+
+  pub fn reconnect_policy(mut self, reconnect_policy: ReconnectPolicy) -> Self {
+    self.reconnect_policy = reconnect_policy;
+    self
+  }
+
+  /// Where the time sent to devices asking for it comes from. Defaults to the
+  /// system clock without a timezone.
+  pub fn time_source(mut self, time_source: TimeSource) -> Self {
+    self.time_source = time_source;
+    self
+  }
+
+  /// How to reach the device, for every connection attempt including
+  /// reconnects. Defaults to TCP to the host and port.
+  pub fn transport(mut self, transport: impl Transport + 'static) -> Self {
+    self.transport = Arc::new(transport);
+    self
+  }
+
+  pub fn buffer_sizes(mut self, buffer_sizes: BufferSizes) -> Self {
+    self.buffer_sizes = buffer_sizes;
+    self
+  }
+
+  /// Connect to the device and start the automatic reconnect loop.
+  pub async fn connect(self) -> Result<Client> {
+    // Catch a malformed key or settings that would panic later before
+    // opening a socket
+    if let Some(psk) = &self.psk {
+      decode_psk(psk)?;
+    }
+    self.reconnect_policy.validate()?;
+    self.buffer_sizes.validate()?;
+
+    Client::connect_with(self).await
+  }
+
+  pub(crate) fn connection_config(&self) -> ConnectionConfig {
+    ConnectionConfig {
+      host: self.host.clone(),
+      port: self.port,
+      password: self.password.clone(),
+      expected_name: self.expected_name.clone(),
+      psk: self.psk.clone(),
+      client_info: self.client_info.clone(),
+      keep_alive_duration: self.keep_alive,
+      connect_timeout: self.connect_timeout,
+      api_version: self.api_version,
+      time_source: self.time_source.clone(),
+      transport: Arc::clone(&self.transport),
+    }
+  }
+}
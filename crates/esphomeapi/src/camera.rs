@@ -0,0 +1,88 @@
+//! Whole images from ESPHome camera entities.
+//!
+//! Devices send images in chunks; the router reassembles them per camera key,
+//! so interleaved chunks of several cameras on one node stay apart.
+//!
+//! ```ignore
+//! let jpeg = client.camera_snapshot(key).await?;
+//! let mut stream = client.camera_stream(key).await?;
+//! while let Ok(frame) = stream.recv().await {
+//!   println!("{} bytes", frame.len());
+//! }
+//! ```
+
+use std::time::Duration;
+
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+use crate::model::CameraImage;
+use crate::{Client, Error, Result};
+
+/// Devices stop streaming 5 seconds after the last stream request, so it is
+/// renewed a little more often than that.
+const STREAM_RENEW_INTERVAL: Duration = Duration::from_secs(4);
+
+/// Complete images of one camera, returned by
+/// [`Client::camera_stream`](crate::Client::camera_stream).
+///
+/// The stream request is renewed in the background until this is dropped;
+/// the device then stops sending on its own.
+pub struct CameraStream {
+  receiver: broadcast::Receiver<CameraImage>,
+  key: u32,
+  renew_task: JoinHandle<()>,
+}
+
+impl CameraStream {
+  pub(crate) fn new(client: Client, receiver: broadcast::Receiver<CameraImage>, key: u32) -> Self {
+    let renew_task = tokio::spawn(async move {
+      let mut interval = tokio::time::interval(STREAM_RENEW_INTERVAL);
+      // The first tick completes immediately and the initial request was
+      // already sent
+      interval.tick().await;
+      loop {
+        interval.tick().await;
+        if let Err(e) = client.camera_image(false, true).await {
+          debug!(key, "failed to renew camera stream: {e}");
+        }
+      }
+    });
+    Self {
+      receiver,
+      key,
+      renew_task,
+    }
+  }
+
+  /// Wait for the next complete image.
+  ///
+  /// Fails with [`Error::Disconnected`] once the client is gone.
+  pub async fn recv(&mut self) -> Result<Vec<u8>> {
+    next_image(&mut self.receiver, self.key).await
+  }
+}
+
+impl Drop for CameraStream {
+  fn drop(&mut self) {
+    self.renew_task.abort();
+  }
+}
+
+/// Wait for the next complete image of camera `key`.
+pub(crate) async fn next_image(
+  receiver: &mut broadcast::Receiver<CameraImage>,
+  key: u32,
+) -> Result<Vec<u8>> {
+  loop {
+    match receiver.recv().await {
+      Ok(image) if image.key == key => return Ok(image.data),
+      Ok(_) => {}
+      Err(broadcast::error::RecvError::Lagged(skipped)) => {
+        warn!(skipped, key, "dropped camera images");
+      }
+      Err(broadcast::error::RecvError::Closed) => return Err(Error::Disconnected),
+    }
+  }
+}
@@ -1,22 +1,41 @@
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
+use enumflags2::BitFlags;
 use protobuf::{EnumOrUnknown, Message as _};
-use tokio::sync::{broadcast, oneshot};
+use tokio::sync::{broadcast, mpsc, oneshot};
 use tokio::time::timeout;
 use tracing::{info, warn};
 
+use crate::bluetooth::RemoteBleDevice;
+use crate::camera::{self, CameraStream};
+use crate::connection::codec::decode_psk;
 use crate::connection::{
-  Connected, Connection, ConnectionConfig, ProtobufMessage, RouterHandle, SharedChannels,
+  Connected, Connection, ConnectionConfig, ProtobufMessage, ResponseFilter, RouterHandle,
+  SharedChannels,
 };
+use crate::discovery::{self, MdnsTransport};
+use crate::infrared::{InfraredReceiver, InfraredSignal};
 use crate::model::{
-  parse_user_service, CameraImage, ColorMode, DeviceInfo, EntityInfo, EntityState,
-  HomeAssistantEvent, HomeassistantActionRequest, LogEvent, LogLevel, UserService,
+  parse_user_service, APIVersion, AlarmControlPanelCommand, BluetoothConnectionsFree,
+  BluetoothDeviceConnection, BluetoothGATTNotifyData, BluetoothLEAdvertisement,
+  BluetoothProxySubscriptionFlag, BluetoothScannerMode, BluetoothScannerStatus, CameraImage,
+  ClimateFanMode, ClimateMode, ClimatePreset, ClimateSwingMode, ColorMode, DeviceInfo, EntityInfo,
+  EntityState, ExecuteServiceResponse, FanDirection, FanSpeed, HomeAssistantEvent,
+  HomeassistantActionRequest, HomeassistantActionResponse, InfraredReceiveEvent, LockCommand,
+  LogEvent, LogLevel, MediaPlayerCommand, ServerInfo, SupportsResponseType, UpdateCommand,
+  UserService, UserServiceArgValue, VoiceAssistantAnnounce, VoiceAssistantAnnounceFinished,
+  VoiceAssistantAudioData, VoiceAssistantConfigurationRequest, VoiceAssistantConfigurationResponse,
+  VoiceAssistantEventType, VoiceAssistantExternalWakeWord, VoiceAssistantMessage,
+  VoiceAssistantResponse, VoiceAssistantSubscriptionFlag, VoiceAssistantTimerEvent,
+  WaterHeaterMode, WaterHeaterStateFlag, ZWaveProxyEvent, LIST_ENTITIES_RESPONSE_IDS,
   LIST_ENTITIES_SERVICES_RESPONSE_TYPES,
 };
 use crate::utils::Options as _;
-use crate::{proto, CommandHandle, Result};
+use crate::zwave::ZWaveProxy;
+use crate::{proto, ClientBuilder, CommandHandle, Error, ReconnectPolicy, Result};
 
 /// A self-reconnecting ESPHome client.
 ///
@@ -33,34 +52,48 @@ pub struct Client {
   router: Arc<RwLock<RouterHandle>>,
   /// Fires when the connection drops (both graceful and abrupt).
   disconnect_tx: broadcast::Sender<()>,
-  /// Fires after each successful automatic reconnect.
-  reconnect_tx: broadcast::Sender<()>,
+  /// Fires after each successful automatic reconnect. Only the reconnect task
+  /// holds the sender, so the channel closes once it stops reconnecting.
+  reconnect_tx: broadcast::WeakSender<()>,
+  /// Used by the reconnect task; [`Client::set_noise_psk`] swaps the PSK.
+  config: Arc<RwLock<ConnectionConfig>>,
   /// Set to `true` by `disconnect()` to prevent reconnect after a deliberate disconnect.
   cancelled: Arc<AtomicBool>,
+  /// User services from the last `list_entities_services()`, used to validate calls.
+  services: Arc<RwLock<HashMap<u32, UserService>>>,
+  /// Source of `call_id`s for service calls that expect a response.
+  next_call_id: Arc<AtomicU32>,
+  request_timeout: Duration,
+  list_entities_timeout: Duration,
 }
 
 impl Client {
+  /// Start configuring a connection to the device at `host`.
+  pub fn builder(host: impl Into<String>) -> ClientBuilder {
+    ClientBuilder::new(host)
+  }
+
+  /// Connect to the node `name` (e.g. `living-room`) without relying on the
+  /// system resolver. The name is looked up through mDNS on every connection
+  /// attempt and all advertised addresses are raced.
+  ///
+  /// For further options, use
+  /// `Client::builder(name).transport(MdnsTransport::default())`.
+  pub async fn connect_by_name(name: &str) -> Result<Self> {
+    // The device reports its bare node name, whichever form was given
+    let name = discovery::node_name(name);
+    Self::builder(name)
+      .expected_name(name)
+      .transport(MdnsTransport::default())
+      .connect()
+      .await
+  }
+
   /// Connect to an ESPHome device and start the automatic reconnect loop.
-  pub async fn connect(
-    host: String,
-    port: u32,
-    password: Option<String>,
-    expected_name: Option<String>,
-    psk: Option<String>,
-    client_info: Option<String>,
-    keep_alive_duration: Option<u32>,
-  ) -> Result<Self> {
-    let config = ConnectionConfig {
-      host,
-      port,
-      password,
-      expected_name,
-      psk,
-      client_info: client_info.unwrap_or_else(|| "esphome-rs".to_string()),
-      keep_alive_duration: Duration::from_secs(keep_alive_duration.unwrap_or(20) as u64),
-    };
+  pub(crate) async fn connect_with(builder: ClientBuilder) -> Result<Self> {
+    let config = builder.connection_config();
 
-    let channels = Arc::new(SharedChannels::new());
+    let channels = Arc::new(SharedChannels::new(&builder.buffer_sizes));
 
     let mut conn = Connection::new_from_config(config.clone())
       .connect_with_channels(true, Arc::clone(&channels))
@@ -73,9 +106,11 @@ impl Client {
     let (disconnect_tx, _) = broadcast::channel(1);
     let (reconnect_tx, _) = broadcast::channel(1);
     let cancelled = Arc::new(AtomicBool::new(false));
+    let config = Arc::new(RwLock::new(config));
 
     Self::spawn_reconnect_task(
-      config,
+      Arc::clone(&config),
+      builder.reconnect_policy,
       Arc::clone(&channels),
       Arc::clone(&router),
       conn,
@@ -84,13 +119,19 @@ impl Client {
       reconnect_tx.clone(),
       Arc::clone(&cancelled),
     );
+    let reconnect_tx = reconnect_tx.downgrade();
 
     Ok(Self {
       channels,
       router,
       disconnect_tx,
       reconnect_tx,
+      config,
       cancelled,
+      services: Arc::new(RwLock::new(HashMap::new())),
+      next_call_id: Arc::new(AtomicU32::new(1)),
+      request_timeout: builder.request_timeout,
+      list_entities_timeout: builder.list_entities_timeout,
     })
   }
 
@@ -102,8 +143,15 @@ impl Client {
   }
 
   /// Subscribe to successful automatic reconnect events.
+  ///
+  /// The receiver reports `Closed` once the client stops reconnecting, e.g.
+  /// after [`ReconnectPolicy::max_attempts`] failed attempts in a row.
   pub fn on_reconnect(&self) -> broadcast::Receiver<()> {
-    self.reconnect_tx.subscribe()
+    match self.reconnect_tx.upgrade() {
+      Some(reconnect_tx) => reconnect_tx.subscribe(),
+      // No reconnect will follow, so hand out an already closed receiver
+      None => broadcast::channel(1).1,
+    }
   }
 
   /// Get a receiver for entity state updates.
@@ -128,11 +176,76 @@ impl Client {
     self.channels.subscribe_action_requests()
   }
 
-  /// Get a receiver for camera image frames.
+  /// Get a receiver for raw camera image chunks.
+  ///
+  /// An image is complete at the chunk with `done` set. See
+  /// [`Client::camera_frames_receiver`] for reassembled images.
   pub fn camera_receiver(&self) -> broadcast::Receiver<CameraImage> {
     self.channels.subscribe_camera()
   }
 
+  /// Get a receiver for timings captured by every infrared/RF receiver.
+  pub fn infrared_receiver(&self) -> broadcast::Receiver<InfraredReceiveEvent> {
+    self.channels.subscribe_infrared()
+  }
+
+  /// Stream the timings captured by the receiver with this key.
+  pub fn infrared_receive(&self, key: u32) -> InfraredReceiver {
+    InfraredReceiver::new(self.infrared_receiver(), key)
+  }
+
+  /// Get a receiver for Z-Wave proxy frames and home id changes.
+  ///
+  /// Nothing is forwarded until the proxy is subscribed, see
+  /// [`zwave_proxy`](Self::zwave_proxy).
+  pub fn zwave_receiver(&self) -> broadcast::Receiver<ZWaveProxyEvent> {
+    self.channels.subscribe_zwave()
+  }
+
+  /// Get a receiver for whole camera images, of every camera on the device.
+  pub fn camera_frames_receiver(&self) -> broadcast::Receiver<CameraImage> {
+    self.channels.subscribe_camera_frames()
+  }
+
+  /// Get a receiver for BLE advertisements without re-sending the subscription.
+  ///
+  /// Raw batches are split up, so every item is a single advertisement.
+  pub fn ble_advertisements_receiver(&self) -> broadcast::Receiver<BluetoothLEAdvertisement> {
+    self.channels.subscribe_ble_advertisements()
+  }
+
+  /// Get a receiver for Bluetooth proxy scanner state changes.
+  pub fn bluetooth_scanner_state_receiver(&self) -> broadcast::Receiver<BluetoothScannerStatus> {
+    self.channels.subscribe_bluetooth_scanner_states()
+  }
+
+  /// Get a receiver for GATT notifications from every connected BLE device.
+  ///
+  /// [`RemoteBleDevice::start_notify`] gives a stream for a single characteristic.
+  pub fn ble_notifications_receiver(&self) -> broadcast::Receiver<BluetoothGATTNotifyData> {
+    self.channels.subscribe_ble_notifications()
+  }
+
+  /// Get a receiver for connection changes of BLE devices not caused by a
+  /// request, such as a device going out of range.
+  pub fn bluetooth_connections_receiver(&self) -> broadcast::Receiver<BluetoothDeviceConnection> {
+    self.channels.subscribe_bluetooth_connections()
+  }
+
+  /// Get a receiver for voice pipeline requests and microphone audio without
+  /// re-sending the subscription.
+  pub fn voice_assistant_receiver(&self) -> broadcast::Receiver<VoiceAssistantMessage> {
+    self.channels.subscribe_voice_assistant()
+  }
+
+  /// Get a receiver for Bluetooth proxy connection slot updates without
+  /// re-sending the subscription.
+  pub fn bluetooth_connections_free_receiver(
+    &self,
+  ) -> broadcast::Receiver<BluetoothConnectionsFree> {
+    self.channels.subscribe_bluetooth_connections_free()
+  }
+
   // ── Command handle ─────────────────────────────────────────────────────────
 
   /// Create a cloneable handle for sending device commands.
@@ -145,13 +258,24 @@ impl Client {
 
   // ── Device requests ────────────────────────────────────────────────────────
 
+  /// What the device reported when the current connection was established.
+  pub fn server_info(&self) -> ServerInfo {
+    self.get_router().server_info().cloned().unwrap_or_default()
+  }
+
+  /// The API version the device speaks on the current connection. Requests
+  /// that need a newer version fail with [`Error::Unsupported`].
+  pub fn api_version(&self) -> APIVersion {
+    self.server_info().api_version
+  }
+
   /// Fetch device info from the device.
   pub async fn device_info(&self) -> Result<DeviceInfo> {
     let response = self
       .send_await_response(
         proto::api::DeviceInfoRequest::default(),
         proto::api::DeviceInfoResponse::get_option_id(),
-        Duration::from_secs(10),
+        self.request_timeout,
       )
       .await?;
     Ok(proto::api::DeviceInfoResponse::parse_from_bytes(&response.protobuf_data)?.into())
@@ -159,16 +283,12 @@ impl Client {
 
   /// Fetch all entities and user services from the device.
   pub async fn list_entities_services(&self) -> Result<(Vec<EntityInfo>, Vec<UserService>)> {
-    let entity_service_map = LIST_ENTITIES_SERVICES_RESPONSE_TYPES.clone();
-    let mut response_types: Vec<u32> = entity_service_map.keys().cloned().collect();
-    response_types.push(proto::api::ListEntitiesServicesResponse::get_option_id());
-
     let responses = self
       .send_await_multiple(
         proto::api::ListEntitiesRequest::new(),
-        response_types,
+        LIST_ENTITIES_RESPONSE_IDS.clone(),
         proto::api::ListEntitiesDoneResponse::get_option_id(),
-        Duration::from_secs(60),
+        self.list_entities_timeout,
       )
       .await?;
 
@@ -178,15 +298,129 @@ impl Client {
       if message.protobuf_type == proto::api::ListEntitiesServicesResponse::get_option_id() {
         services.push(parse_user_service(&message.protobuf_data)?);
       } else {
-        let parser = entity_service_map
-          .get(&message.protobuf_type)
-          .ok_or_else(|| format!("Unknown message type: {}", message.protobuf_type))?;
+        let Some(parser) = LIST_ENTITIES_SERVICES_RESPONSE_TYPES.get(&message.protobuf_type) else {
+          warn!(
+            message_type = message.protobuf_type,
+            "skipping entity of unsupported type"
+          );
+          continue;
+        };
         entities.push(parser(&message.protobuf_data)?);
       }
     }
+    *self.services.write().unwrap() = services
+      .iter()
+      .map(|service| (service.key, service.clone()))
+      .collect();
     Ok((entities, services))
   }
 
+  /// Execute a user-defined service without waiting for a response.
+  ///
+  /// `args` must match the signature the device declared for the service, in
+  /// order. Call `list_entities_services()` first so the signature is known.
+  /// Services that only run with a response fail with
+  /// [`Error::InvalidServiceArguments`]; use
+  /// [`execute_service_with_response`](Self::execute_service_with_response).
+  pub async fn execute_service(&self, key: u32, args: Vec<UserServiceArgValue>) -> Result<()> {
+    let service = self.validate_service_args(key, &args)?;
+    if service.supports_response == SupportsResponseType::Only {
+      return Err(Error::InvalidServiceArguments {
+        service: service.name,
+        reason: "service only runs with a response, use execute_service_with_response".to_string(),
+      });
+    }
+    self
+      .send(proto::api::ExecuteServiceRequest {
+        key,
+        args: args.into_iter().map(Into::into).collect(),
+        ..Default::default()
+      })
+      .await
+  }
+
+  /// Execute a user-defined service and wait for the device's response.
+  ///
+  /// Fails with [`Error::InvalidServiceArguments`] if the service does not
+  /// support responses.
+  pub async fn execute_service_with_response(
+    &self,
+    key: u32,
+    args: Vec<UserServiceArgValue>,
+  ) -> Result<ExecuteServiceResponse> {
+    let service = self.validate_service_args(key, &args)?;
+    if service.supports_response == SupportsResponseType::None {
+      return Err(Error::InvalidServiceArguments {
+        service: service.name,
+        reason: "service does not support responses".to_string(),
+      });
+    }
+
+    let call_id = self.next_call_id();
+    let filter = ResponseFilter::new(proto::api::ExecuteServiceResponse::get_option_id())
+      .matching::<proto::api::ExecuteServiceResponse, _>(move |response| {
+      response.call_id == call_id
+    });
+    let response = self
+      .send_await_filtered(
+        proto::api::ExecuteServiceRequest {
+          key,
+          args: args.into_iter().map(Into::into).collect(),
+          call_id,
+          return_response: true,
+          ..Default::default()
+        },
+        filter,
+        self.request_timeout,
+      )
+      .await?;
+    Ok(proto::api::ExecuteServiceResponse::parse_from_bytes(&response.protobuf_data)?.into())
+  }
+
+  /// Check `args` against the declared signature of service `key`.
+  fn validate_service_args(&self, key: u32, args: &[UserServiceArgValue]) -> Result<UserService> {
+    let service = self
+      .services
+      .read()
+      .unwrap()
+      .get(&key)
+      .cloned()
+      .ok_or(Error::UnknownService(key))?;
+
+    let invalid = |reason: String| Error::InvalidServiceArguments {
+      service: service.name.clone(),
+      reason,
+    };
+    if args.len() != service.args.len() {
+      return Err(invalid(format!(
+        "expected {} arguments, got {}",
+        service.args.len(),
+        args.len()
+      )));
+    }
+    for (declared, value) in service.args.iter().zip(args) {
+      if declared.arg_type != value.arg_type() {
+        return Err(invalid(format!(
+          "argument '{}' expects {:?}, got {:?}",
+          declared.name,
+          declared.arg_type,
+          value.arg_type()
+        )));
+      }
+    }
+    Ok(service)
+  }
+
+  /// Next non-zero call id; zero means "no response requested" on the wire.
+  fn next_call_id(&self) -> u32 {
+    loop {
+      let id = self.next_call_id.fetch_add(1, Ordering::Relaxed);
+      if id != 0 {
+        return id;
+      }
+    }
+  }
+
   /// Ask the device to start sending entity state updates.
   pub async fn request_states(&self) -> Result<()> {
     self.send(proto::api::SubscribeStatesRequest::new()).await
@@ -217,6 +451,210 @@ impl Client {
       .await
   }
 
+  /// Ask a Bluetooth proxy to forward the BLE advertisements it sees.
+  ///
+  /// Pass [`BluetoothProxySubscriptionFlag::RawAdvertisements`] for batched raw
+  /// advertisements, which proxies since ESPHome 2025.8 require. Either way
+  /// the returned receiver yields parsed advertisements. The subscription
+  /// does not survive a reconnect.
+  pub async fn subscribe_bluetooth_le_advertisements(
+    &self,
+    flags: BitFlags<BluetoothProxySubscriptionFlag>,
+  ) -> Result<broadcast::Receiver<BluetoothLEAdvertisement>> {
+    let receiver = self.ble_advertisements_receiver();
+    self
+      .send(proto::api::SubscribeBluetoothLEAdvertisementsRequest {
+        flags: flags.bits(),
+        ..Default::default()
+      })
+      .await?;
+    Ok(receiver)
+  }
+
+  /// Stop the advertisement stream started by `subscribe_bluetooth_le_advertisements()`.
+  pub async fn unsubscribe_bluetooth_le_advertisements(&self) -> Result<()> {
+    self
+      .send(proto::api::UnsubscribeBluetoothLEAdvertisementsRequest::new())
+      .await
+  }
+
+  /// Ask a Bluetooth proxy to report its free connection slots, now and
+  /// whenever they change.
+  pub async fn subscribe_bluetooth_connections_free(
+    &self,
+  ) -> Result<broadcast::Receiver<BluetoothConnectionsFree>> {
+    let receiver = self.bluetooth_connections_free_receiver();
+    self
+      .send(proto::api::SubscribeBluetoothConnectionsFreeRequest::new())
+      .await?;
+    Ok(receiver)
+  }
+
+  /// A handle for driving the remote BLE device at `address` through this
+  /// Bluetooth proxy. `address_type` comes from its advertisements.
+  pub fn bluetooth_device(&self, address: u64, address_type: u32) -> RemoteBleDevice {
+    RemoteBleDevice::new(self.clone(), address, address_type)
+  }
+
+  /// Switch a Bluetooth proxy's scanner between passive and active scanning.
+  ///
+  /// The proxy confirms with a state change on `bluetooth_scanner_state_receiver()`.
+  pub async fn bluetooth_scanner_set_mode(&self, mode: BluetoothScannerMode) -> Result<()> {
+    self
+      .send(proto::api::BluetoothScannerSetModeRequest {
+        mode: EnumOrUnknown::new(mode.into()),
+        ..Default::default()
+      })
+      .await
+  }
+
+  /// Act as the voice pipeline host for the device.
+  ///
+  /// The receiver yields pipeline start/stop requests and, with the
+  /// [`VoiceAssistantSubscriptionFlag::APIAudio`] flag, the microphone audio
+  /// streamed over the API connection.
+  pub async fn subscribe_voice_assistant(
+    &self,
+    flags: BitFlags<VoiceAssistantSubscriptionFlag>,
+  ) -> Result<broadcast::Receiver<VoiceAssistantMessage>> {
+    let receiver = self.voice_assistant_receiver();
+    self
+      .send(proto::api::SubscribeVoiceAssistantRequest {
+        subscribe: true,
+        flags: flags.bits(),
+        ..Default::default()
+      })
+      .await?;
+    Ok(receiver)
+  }
+
+  /// Stop acting as the voice pipeline host.
+  pub async fn unsubscribe_voice_assistant(&self) -> Result<()> {
+    self
+      .send(proto::api::SubscribeVoiceAssistantRequest {
+        subscribe: false,
+        ..Default::default()
+      })
+      .await
+  }
+
+  /// Answer a pipeline start request, telling the device where to stream audio.
+  pub async fn send_voice_assistant_response(
+    &self,
+    response: VoiceAssistantResponse,
+  ) -> Result<()> {
+    self
+      .send(proto::api::VoiceAssistantResponse::from(response))
+      .await
+  }
+
+  /// Report pipeline progress, e.g. `STTEnd` with the recognised `text`.
+  pub async fn send_voice_assistant_event(
+    &self,
+    event_type: VoiceAssistantEventType,
+    data: HashMap<String, String>,
+  ) -> Result<()> {
+    self
+      .send(proto::api::VoiceAssistantEventResponse {
+        event_type: EnumOrUnknown::new(event_type.into()),
+        data: data
+          .into_iter()
+          .map(|(name, value)| proto::api::VoiceAssistantEventData {
+            name,
+            value,
+            ..Default::default()
+          })
+          .collect(),
+        ..Default::default()
+      })
+      .await
+  }
+
+  /// Stream a chunk of TTS audio to the device's speaker.
+  pub async fn send_voice_assistant_audio(&self, audio: VoiceAssistantAudioData) -> Result<()> {
+    self
+      .send(proto::api::VoiceAssistantAudio::from(audio))
+      .await
+  }
+
+  /// Fetch the device's wake words and which of them are active.
+  ///
+  /// `external_wake_words` are offered for the device to download.
+  pub async fn voice_assistant_get_configuration(
+    &self,
+    external_wake_words: Vec<VoiceAssistantExternalWakeWord>,
+  ) -> Result<VoiceAssistantConfigurationResponse> {
+    let response = self
+      .send_await_response(
+        proto::api::VoiceAssistantConfigurationRequest::from(VoiceAssistantConfigurationRequest {
+          external_wake_words,
+        }),
+        proto::api::VoiceAssistantConfigurationResponse::get_option_id(),
+        self.request_timeout,
+      )
+      .await?;
+    Ok(
+      proto::api::VoiceAssistantConfigurationResponse::parse_from_bytes(&response.protobuf_data)?
+        .into(),
+    )
+  }
+
+  /// Replace the set of active wake words, by id.
+  pub async fn voice_assistant_set_configuration(
+    &self,
+    active_wake_words: Vec<String>,
+  ) -> Result<()> {
+    self
+      .send(proto::api::VoiceAssistantSetConfiguration {
+        active_wake_words,
+        ..Default::default()
+      })
+      .await
+  }
+
+  /// Play an announcement and wait until the device has finished it.
+  ///
+  /// Playback takes as long as the media, so the wait uses `timeout`
+  /// instead of the request timeout.
+  pub async fn voice_assistant_announce(
+    &self,
+    announcement: VoiceAssistantAnnounce,
+    timeout: Duration,
+  ) -> Result<VoiceAssistantAnnounceFinished> {
+    let response = self
+      .send_await_response(
+        proto::api::VoiceAssistantAnnounceRequest::from(announcement),
+        proto::api::VoiceAssistantAnnounceFinished::get_option_id(),
+        timeout,
+      )
+      .await?;
+    Ok(
+      proto::api::VoiceAssistantAnnounceFinished::parse_from_bytes(&response.protobuf_data)?.into(),
+    )
+  }
+
+  /// Tell the device about a timer started, updated, cancelled or finished by voice.
+  pub async fn send_voice_assistant_timer_event(
+    &self,
+    event: VoiceAssistantTimerEvent,
+  ) -> Result<()> {
+    self
+      .send(proto::api::VoiceAssistantTimerEventResponse::from(event))
+      .await
+  }
+
+  /// Answer a Home Assistant action request that set `wants_response`.
+  ///
+  /// `response.call_id` must be the `call_id` of the request being answered.
+  pub async fn send_home_assistant_action_response(
+    &self,
+    response: HomeassistantActionResponse,
+  ) -> Result<()> {
+    self
+      .send(proto::api::HomeassistantActionResponse::from(response))
+      .await
+  }
+
   /// Send the current state of a Home Assistant entity to the device.
   pub async fn send_home_assistant_state(
     &self,
@@ -234,6 +672,49 @@ impl Client {
       .await
   }
 
+  /// Store a new base64-encoded Noise PSK on the device, e.g. one from
+  /// [`generate_noise_psk`](crate::generate_noise_psk).
+  ///
+  /// Only devices reporting `api_encryption_supported` accept a key. On
+  /// success the device disconnects every client to switch to the key; this
+  /// client then reconnects with it even though the disconnect was graceful.
+  ///
+  /// The old key is only restored when the device refuses the new one. If
+  /// the answer is lost to the disconnect or never arrives, the device has
+  /// likely switched already, so the client keeps the new key for the next
+  /// reconnect.
+  pub async fn set_noise_psk(&self, psk: &str) -> Result<()> {
+    let key = decode_psk(psk)?;
+    // Switch before asking: the device disconnects right after its answer,
+    // possibly before this task sees the answer
+    let previous = self.config.write().unwrap().psk.replace(psk.to_string());
+    let result = self
+      .send_await_response(
+        proto::api::NoiseEncryptionSetKeyRequest {
+          key,
+          ..Default::default()
+        },
+        proto::api::NoiseEncryptionSetKeyResponse::get_option_id(),
+        self.request_timeout,
+      )
+      .await
+      .and_then(|response| {
+        Ok(proto::api::NoiseEncryptionSetKeyResponse::parse_from_bytes(
+          &response.protobuf_data,
+        )?)
+      });
+    match result {
+      Ok(response) if response.success => Ok(()),
+      Ok(_) => {
+        self.config.write().unwrap().psk = previous;
+        Err(Error::NoiseKeyRejected)
+      }
+      // The device drops its clients to apply the key, which can beat its answer
+      Err(Error::Disconnected) => Ok(()),
+      Err(e) => Err(e),
+    }
+  }
+
   /// Initiate a graceful client-side disconnect.
   ///
   /// Sets the cancelled flag (preventing automatic reconnect), sends
@@ -259,6 +740,7 @@ impl Client {
     self.command_handle().switch_command(key, state).await
   }
 
+  #[allow(clippy::too_many_arguments)]
   pub async fn light_command(
     &self,
     key: u32,
@@ -295,11 +777,266 @@ impl Client {
       .await
   }
 
+  pub async fn cover_command(
+    &self,
+    key: u32,
+    position: Option<f32>,
+    tilt: Option<f32>,
+    stop: bool,
+  ) -> Result<()> {
+    self
+      .command_handle()
+      .cover_command(key, position, tilt, stop)
+      .await
+  }
+
+  #[allow(clippy::too_many_arguments)]
+  pub async fn fan_command(
+    &self,
+    key: u32,
+    state: Option<bool>,
+    speed: Option<FanSpeed>,
+    speed_level: Option<i32>,
+    oscillating: Option<bool>,
+    direction: Option<FanDirection>,
+    preset_mode: Option<String>,
+  ) -> Result<()> {
+    self
+      .command_handle()
+      .fan_command(
+        key,
+        state,
+        speed,
+        speed_level,
+        oscillating,
+        direction,
+        preset_mode,
+      )
+      .await
+  }
+
+  #[allow(clippy::too_many_arguments)]
+  pub async fn climate_command(
+    &self,
+    key: u32,
+    mode: Option<ClimateMode>,
+    target_temperature: Option<f32>,
+    target_temperature_low: Option<f32>,
+    target_temperature_high: Option<f32>,
+    fan_mode: Option<ClimateFanMode>,
+    swing_mode: Option<ClimateSwingMode>,
+    custom_fan_mode: Option<String>,
+    preset: Option<ClimatePreset>,
+    custom_preset: Option<String>,
+    target_humidity: Option<f32>,
+  ) -> Result<()> {
+    self
+      .command_handle()
+      .climate_command(
+        key,
+        mode,
+        target_temperature,
+        target_temperature_low,
+        target_temperature_high,
+        fan_mode,
+        swing_mode,
+        custom_fan_mode,
+        preset,
+        custom_preset,
+        target_humidity,
+      )
+      .await
+  }
+
+  pub async fn number_command(&self, key: u32, state: f32) -> Result<()> {
+    self.command_handle().number_command(key, state).await
+  }
+
+  pub async fn select_command(&self, key: u32, state: String) -> Result<()> {
+    self.command_handle().select_command(key, state).await
+  }
+
+  pub async fn siren_command(
+    &self,
+    key: u32,
+    state: Option<bool>,
+    tone: Option<String>,
+    volume: Option<f32>,
+    duration: Option<u32>,
+  ) -> Result<()> {
+    self
+      .command_handle()
+      .siren_command(key, state, tone, volume, duration)
+      .await
+  }
+
+  pub async fn lock_command(
+    &self,
+    key: u32,
+    command: LockCommand,
+    code: Option<String>,
+  ) -> Result<()> {
+    self.command_handle().lock_command(key, command, code).await
+  }
+
+  pub async fn button_command(&self, key: u32) -> Result<()> {
+    self.command_handle().button_command(key).await
+  }
+
+  pub async fn media_player_command(
+    &self,
+    key: u32,
+    command: Option<MediaPlayerCommand>,
+    volume: Option<f32>,
+    media_url: Option<String>,
+    announcement: Option<bool>,
+  ) -> Result<()> {
+    self
+      .command_handle()
+      .media_player_command(key, command, volume, media_url, announcement)
+      .await
+  }
+
+  pub async fn alarm_control_panel_command(
+    &self,
+    key: u32,
+    command: AlarmControlPanelCommand,
+    code: Option<String>,
+  ) -> Result<()> {
+    self
+      .command_handle()
+      .alarm_control_panel_command(key, command, code)
+      .await
+  }
+
+  pub async fn text_command(&self, key: u32, state: String) -> Result<()> {
+    self.command_handle().text_command(key, state).await
+  }
+
+  pub async fn date_command(&self, key: u32, year: u32, month: u32, day: u32) -> Result<()> {
+    self
+      .command_handle()
+      .date_command(key, year, month, day)
+      .await
+  }
+
+  pub async fn time_command(&self, key: u32, hour: u32, minute: u32, second: u32) -> Result<()> {
+    self
+      .command_handle()
+      .time_command(key, hour, minute, second)
+      .await
+  }
+
+  pub async fn datetime_command(&self, key: u32, epoch_seconds: u32) -> Result<()> {
+    self
+      .command_handle()
+      .datetime_command(key, epoch_seconds)
+      .await
+  }
+
+  pub async fn valve_command(&self, key: u32, position: Option<f32>, stop: bool) -> Result<()> {
+    self
+      .command_handle()
+      .valve_command(key, position, stop)
+      .await
+  }
+
+  pub async fn update_command(&self, key: u32, command: UpdateCommand) -> Result<()> {
+    self.command_handle().update_command(key, command).await
+  }
+
+  pub async fn camera_image(&self, single: bool, stream: bool) -> Result<()> {
+    self.command_handle().camera_image(single, stream).await
+  }
+
+  /// Take a single image with camera `key` and return the whole JPEG.
+  pub async fn camera_snapshot(&self, key: u32) -> Result<Vec<u8>> {
+    // Subscribe first so an image arriving right after the request is not lost
+    let mut receiver = self.camera_frames_receiver();
+    self.camera_image(true, false).await?;
+    timeout(self.request_timeout, camera::next_image(&mut receiver, key))
+      .await
+      .map_err(|_| Error::Timeout(self.request_timeout))?
+  }
+
+  /// Stream whole images of camera `key` until the returned stream is dropped.
+  pub async fn camera_stream(&self, key: u32) -> Result<CameraStream> {
+    let receiver = self.camera_frames_receiver();
+    self.camera_image(false, true).await?;
+    Ok(CameraStream::new(self.clone(), receiver, key))
+  }
+
+  pub async fn infrared_transmit_raw(
+    &self,
+    key: u32,
+    carrier_frequency: u32,
+    repeat_count: u32,
+    timings: Vec<i32>,
+  ) -> Result<()> {
+    self
+      .command_handle()
+      .infrared_transmit_raw(key, carrier_frequency, repeat_count, timings)
+      .await
+  }
+
+  pub async fn infrared_transmit(
+    &self,
+    key: u32,
+    signal: &InfraredSignal,
+    repeat_count: u32,
+  ) -> Result<()> {
+    self
+      .command_handle()
+      .infrared_transmit(key, signal, repeat_count)
+      .await
+  }
+
+  /// Subscribe to the Z-Wave proxy and open its controller as a byte stream.
+  ///
+  /// The proxy is unsubscribed again when the stream is dropped.
+  pub async fn zwave_proxy(&self) -> Result<ZWaveProxy> {
+    let home_id = self.device_info().await?.zwave_home_id;
+    // Subscribe first so no frame sent right after the request is lost
+    let receiver = self.zwave_receiver();
+    self.command_handle().zwave_proxy_subscribe(true).await?;
+    Ok(ZWaveProxy::new(
+      self.command_handle(),
+      receiver,
+      (home_id != 0).then_some(home_id),
+    ))
+  }
+
+  pub async fn water_heater_command(
+    &self,
+    key: u32,
+    mode: Option<WaterHeaterMode>,
+    target_temperature: Option<f32>,
+    target_temperature_low: Option<f32>,
+    target_temperature_high: Option<f32>,
+    state: Option<BitFlags<WaterHeaterStateFlag>>,
+  ) -> Result<()> {
+    self
+      .command_handle()
+      .water_heater_command(
+        key,
+        mode,
+        target_temperature,
+        target_temperature_low,
+        target_temperature_high,
+        state,
+      )
+      .await
+  }
+
+  pub(crate) fn request_timeout(&self) -> Duration {
+    self.request_timeout
+  }
+
   fn get_router(&self) -> RouterHandle {
     self.router.read().unwrap().clone()
   }
 
-  async fn send<M: protobuf::MessageFull>(&self, message: M) -> Result<()> {
+  pub(crate) async fn send<M: protobuf::MessageFull>(&self, message: M) -> Result<()> {
     let router = self.get_router();
     router
       .send(ProtobufMessage {
@@ -314,20 +1051,31 @@ impl Client {
     message: M,
     response_type: u32,
     duration: Duration,
+  ) -> Result<ProtobufMessage> {
+    self
+      .send_await_filtered(message, ResponseFilter::new(response_type), duration)
+      .await
+  }
+
+  pub(crate) async fn send_await_filtered<M: protobuf::MessageFull>(
+    &self,
+    message: M,
+    filter: ResponseFilter,
+    duration: Duration,
   ) -> Result<ProtobufMessage> {
     let router = self.get_router();
     timeout(
       duration,
-      router.send_await_response(
+      router.send_await_filtered(
         ProtobufMessage {
           protobuf_type: M::get_option_id(),
           protobuf_data: message.write_to_bytes()?,
         },
-        response_type,
+        filter,
       ),
     )
     .await
-    .map_err(|_| "Timeout waiting for response")?
+    .map_err(|_| Error::Timeout(duration))?
   }
 
   async fn send_await_multiple<M: protobuf::MessageFull>(
@@ -337,29 +1085,66 @@ impl Client {
     until_type: u32,
     duration: Duration,
   ) -> Result<Vec<ProtobufMessage>> {
-    let router = self.get_router();
-    let mut rx = router
-      .send_await_multiple(
-        ProtobufMessage {
-          protobuf_type: M::get_option_id(),
-          protobuf_data: message.write_to_bytes()?,
-        },
-        response_types,
+    self
+      .send_await_multiple_filtered(
+        message,
+        ResponseFilter::any_of(response_types),
         until_type,
+        duration,
       )
+      .await
+  }
+
+  /// Collect matching responses until `until_type` arrives or `duration`
+  /// passes without a new message.
+  pub(crate) async fn send_await_multiple_filtered<M: protobuf::MessageFull>(
+    &self,
+    message: M,
+    filter: ResponseFilter,
+    until_type: u32,
+    duration: Duration,
+  ) -> Result<Vec<ProtobufMessage>> {
+    let mut rx = self
+      .send_await_stream_filtered(message, filter, until_type)
       .await?;
 
     let mut responses = Vec::new();
     while let Ok(Some(msg)) = timeout(duration, rx.recv()).await {
+      if msg.protobuf_type == until_type {
+        break;
+      }
       responses.push(msg);
     }
     Ok(responses)
   }
 
+  /// Hand out matching responses as they arrive, ending with the `until_type`
+  /// message itself. The channel closes early if the connection drops.
+  pub(crate) async fn send_await_stream_filtered<M: protobuf::MessageFull>(
+    &self,
+    message: M,
+    filter: ResponseFilter,
+    until_type: u32,
+  ) -> Result<mpsc::Receiver<ProtobufMessage>> {
+    self
+      .get_router()
+      .send_await_multiple_filtered(
+        ProtobufMessage {
+          protobuf_type: M::get_option_id(),
+          protobuf_data: message.write_to_bytes()?,
+        },
+        filter,
+        until_type,
+      )
+      .await
+  }
+
   // ── Reconnect task ─────────────────────────────────────────────────────────
 
+  #[allow(clippy::too_many_arguments)]
   fn spawn_reconnect_task(
-    config: ConnectionConfig,
+    config: Arc<RwLock<ConnectionConfig>>,
+    policy: ReconnectPolicy,
     channels: Arc<SharedChannels>,
     router: Arc<RwLock<RouterHandle>>,
     initial_conn: Connection<Connected>,
@@ -368,6 +1153,8 @@ impl Client {
     reconnect_tx: broadcast::Sender<()>,
     cancelled: Arc<AtomicBool>,
   ) {
+    // Read before spawning: the PSK may change before the task first runs
+    let mut connected_psk = config.read().unwrap().psk.clone();
     tokio::spawn(async move {
       // Keep the live connection alive here. Replacing it drops the old one,
       // aborting its reader / router / keep-alive tasks.
@@ -376,17 +1163,25 @@ impl Client {
 
       loop {
         // true = abrupt (reconnect), false = graceful DisconnectRequest (no reconnect)
-        let should_reconnect = disconnect_rx.await.unwrap_or(true);
+        let mut should_reconnect = disconnect_rx.await.unwrap_or(true);
         let _ = disconnect_tx.send(());
 
-        if !should_reconnect || cancelled.load(Ordering::Relaxed) {
+        // A device that accepted a new PSK disconnects gracefully to apply it
+        let config = config.read().unwrap().clone();
+        if config.psk != connected_psk {
+          info!("Noise PSK changed — reconnecting with the new key.");
+          should_reconnect = true;
+        }
+
+        if !should_reconnect || !policy.enabled || cancelled.load(Ordering::Relaxed) {
           info!("Connection closed — stopping reconnect loop.");
           break;
         }
 
         info!("Connection lost, attempting to reconnect…");
 
-        let mut delay = Duration::from_secs(5);
+        let mut attempt = 0;
+        let mut delay = policy.delay(attempt);
         let mut new_conn: Connection<Connected> = loop {
           tokio::time::sleep(delay).await;
 
@@ -396,12 +1191,20 @@ impl Client {
           {
             Ok(conn) => break conn,
             Err(e) => {
+              attempt += 1;
+              if policy.max_attempts.is_some_and(|max| attempt >= max) {
+                // Returning drops `reconnect_tx`, closing every `on_reconnect` receiver
+                warn!("Reconnect failed: {e}, giving up after {attempt} attempts");
+                return;
+              }
+              delay = policy.delay(attempt);
               warn!("Reconnect failed: {e}, retrying in {delay:?}");
-              delay = (delay * 2).min(Duration::from_secs(60));
             }
           }
         };
 
+        connected_psk = config.psk;
+
         // Swap the router handle — all CommandHandle clones see the new connection.
         *router.write().unwrap() = new_conn.router_handle().clone();
 
@@ -1,9 +1,16 @@
 use std::sync::{Arc, RwLock};
 
+use enumflags2::BitFlags;
+use protobuf::reflect::ReflectValueBox;
 use protobuf::EnumOrUnknown;
 
 use crate::connection::{ProtobufMessage, RouterHandle};
-use crate::model::ColorMode;
+use crate::infrared::InfraredSignal;
+use crate::model::{
+  AlarmControlPanelCommand, ClimateFanMode, ClimateMode, ClimatePreset, ClimateSwingMode,
+  ColorMode, FanDirection, FanSpeed, LockCommand, MediaPlayerCommand, UpdateCommand,
+  WaterHeaterCommandField, WaterHeaterMode, WaterHeaterStateFlag,
+};
 use crate::utils::Options as _;
 use crate::{proto, Result};
 
@@ -13,9 +20,13 @@ use crate::{proto, Result};
 /// All clones share the same underlying router reference, so calling
 /// [`update_from`](CommandHandle::update_from) on any clone (e.g. after a
 /// reconnect) immediately affects every entity that holds a clone.
+///
+/// Entity keys are only unique per sub-device; use
+/// [`for_device`](CommandHandle::for_device) to address entities of one.
 #[derive(Clone)]
 pub struct CommandHandle {
   router: Arc<RwLock<RouterHandle>>,
+  device_id: u32,
 }
 
 impl CommandHandle {
@@ -24,15 +35,38 @@ impl CommandHandle {
   /// All clones point to the same arc — swapping the router on reconnect is
   /// immediately visible to every entity that holds a clone.
   pub(crate) fn from_shared(router: Arc<RwLock<RouterHandle>>) -> Self {
-    Self { router }
+    Self {
+      router,
+      device_id: 0,
+    }
+  }
+
+  /// A handle whose commands target the entities of sub-device `device_id`.
+  ///
+  /// It shares the router of this handle; 0 addresses the node itself.
+  pub fn for_device(&self, device_id: u32) -> Self {
+    Self {
+      router: Arc::clone(&self.router),
+      device_id,
+    }
+  }
+
+  pub fn device_id(&self) -> u32 {
+    self.device_id
   }
 
   /// Acquire a read lock long enough to clone the `RouterHandle`, then drop
   /// the lock before awaiting — avoids holding a sync lock across `.await`.
-  async fn send_proto<M>(&self, message: M) -> Result<()>
+  async fn send_proto<M>(&self, mut message: M) -> Result<()>
   where
     M: protobuf::MessageFull,
   {
+    // Every command request carries the sub-device of its target
+    if self.device_id != 0 {
+      if let Some(field) = M::descriptor().field_by_name("device_id") {
+        field.set_singular_field(&mut message, ReflectValueBox::U32(self.device_id));
+      }
+    }
     let router = self.router.read().unwrap().clone();
     let protobuf_type = M::get_option_id();
     let protobuf_data = message.write_to_bytes()?;
@@ -53,6 +87,7 @@ impl CommandHandle {
     self.send_proto(message).await
   }
 
+  #[allow(clippy::too_many_arguments)]
   pub async fn light_command(
     &self,
     key: u32,
@@ -101,4 +136,369 @@ impl CommandHandle {
     };
     self.send_proto(message).await
   }
+
+  pub async fn cover_command(
+    &self,
+    key: u32,
+    position: Option<f32>,
+    tilt: Option<f32>,
+    stop: bool,
+  ) -> Result<()> {
+    let message = proto::api::CoverCommandRequest {
+      key,
+      has_position: position.is_some(),
+      position: position.unwrap_or_default(),
+      has_tilt: tilt.is_some(),
+      tilt: tilt.unwrap_or_default(),
+      stop,
+      ..Default::default()
+    };
+    self.send_proto(message).await
+  }
+
+  #[allow(clippy::too_many_arguments)]
+  pub async fn fan_command(
+    &self,
+    key: u32,
+    state: Option<bool>,
+    speed: Option<FanSpeed>,
+    speed_level: Option<i32>,
+    oscillating: Option<bool>,
+    direction: Option<FanDirection>,
+    preset_mode: Option<String>,
+  ) -> Result<()> {
+    let message = proto::api::FanCommandRequest {
+      key,
+      has_state: state.is_some(),
+      state: state.unwrap_or_default(),
+      has_speed: speed.is_some(),
+      speed: EnumOrUnknown::new(speed.map(Into::into).unwrap_or_default()),
+      has_speed_level: speed_level.is_some(),
+      speed_level: speed_level.unwrap_or_default(),
+      has_oscillating: oscillating.is_some(),
+      oscillating: oscillating.unwrap_or_default(),
+      has_direction: direction.is_some(),
+      direction: EnumOrUnknown::new(direction.map(Into::into).unwrap_or_default()),
+      has_preset_mode: preset_mode.is_some(),
+      preset_mode: preset_mode.unwrap_or_default(),
+      ..Default::default()
+    };
+    self.send_proto(message).await
+  }
+
+  #[allow(clippy::too_many_arguments)]
+  pub async fn climate_command(
+    &self,
+    key: u32,
+    mode: Option<ClimateMode>,
+    target_temperature: Option<f32>,
+    target_temperature_low: Option<f32>,
+    target_temperature_high: Option<f32>,
+    fan_mode: Option<ClimateFanMode>,
+    swing_mode: Option<ClimateSwingMode>,
+    custom_fan_mode: Option<String>,
+    preset: Option<ClimatePreset>,
+    custom_preset: Option<String>,
+    target_humidity: Option<f32>,
+  ) -> Result<()> {
+    let message = proto::api::ClimateCommandRequest {
+      key,
+      has_mode: mode.is_some(),
+      mode: EnumOrUnknown::new(mode.map(Into::into).unwrap_or_default()),
+      has_target_temperature: target_temperature.is_some(),
+      target_temperature: target_temperature.unwrap_or_default(),
+      has_target_temperature_low: target_temperature_low.is_some(),
+      target_temperature_low: target_temperature_low.unwrap_or_default(),
+      has_target_temperature_high: target_temperature_high.is_some(),
+      target_temperature_high: target_temperature_high.unwrap_or_default(),
+      has_fan_mode: fan_mode.is_some(),
+      fan_mode: EnumOrUnknown::new(fan_mode.map(Into::into).unwrap_or_default()),
+      has_swing_mode: swing_mode.is_some(),
+      swing_mode: EnumOrUnknown::new(swing_mode.map(Into::into).unwrap_or_default()),
+      has_custom_fan_mode: custom_fan_mode.is_some(),
+      custom_fan_mode: custom_fan_mode.unwrap_or_default(),
+      has_preset: preset.is_some(),
+      preset: EnumOrUnknown::new(preset.map(Into::into).unwrap_or_default()),
+      has_custom_preset: custom_preset.is_some(),
+      custom_preset: custom_preset.unwrap_or_default(),
+      has_target_humidity: target_humidity.is_some(),
+      target_humidity: target_humidity.unwrap_or_default(),
+      ..Default::default()
+    };
+    self.send_proto(message).await
+  }
+
+  pub async fn number_command(&self, key: u32, state: f32) -> Result<()> {
+    let message = proto::api::NumberCommandRequest {
+      key,
+      state,
+      ..Default::default()
+    };
+    self.send_proto(message).await
+  }
+
+  pub async fn select_command(&self, key: u32, state: String) -> Result<()> {
+    let message = proto::api::SelectCommandRequest {
+      key,
+      state,
+      ..Default::default()
+    };
+    self.send_proto(message).await
+  }
+
+  pub async fn siren_command(
+    &self,
+    key: u32,
+    state: Option<bool>,
+    tone: Option<String>,
+    volume: Option<f32>,
+    duration: Option<u32>,
+  ) -> Result<()> {
+    let message = proto::api::SirenCommandRequest {
+      key,
+      has_state: state.is_some(),
+      state: state.unwrap_or_default(),
+      has_tone: tone.is_some(),
+      tone: tone.unwrap_or_default(),
+      has_volume: volume.is_some(),
+      volume: volume.unwrap_or_default(),
+      has_duration: duration.is_some(),
+      duration: duration.unwrap_or_default(),
+      ..Default::default()
+    };
+    self.send_proto(message).await
+  }
+
+  pub async fn lock_command(
+    &self,
+    key: u32,
+    command: LockCommand,
+    code: Option<String>,
+  ) -> Result<()> {
+    let message = proto::api::LockCommandRequest {
+      key,
+      command: EnumOrUnknown::new(command.into()),
+      has_code: code.is_some(),
+      code: code.unwrap_or_default(),
+      ..Default::default()
+    };
+    self.send_proto(message).await
+  }
+
+  pub async fn button_command(&self, key: u32) -> Result<()> {
+    let message = proto::api::ButtonCommandRequest {
+      key,
+      ..Default::default()
+    };
+    self.send_proto(message).await
+  }
+
+  pub async fn media_player_command(
+    &self,
+    key: u32,
+    command: Option<MediaPlayerCommand>,
+    volume: Option<f32>,
+    media_url: Option<String>,
+    announcement: Option<bool>,
+  ) -> Result<()> {
+    let message = proto::api::MediaPlayerCommandRequest {
+      key,
+      has_command: command.is_some(),
+      command: EnumOrUnknown::new(command.map(Into::into).unwrap_or_default()),
+      has_volume: volume.is_some(),
+      volume: volume.unwrap_or_default(),
+      has_media_url: media_url.is_some(),
+      media_url: media_url.unwrap_or_default(),
+      has_announcement: announcement.is_some(),
+      announcement: announcement.unwrap_or_default(),
+      ..Default::default()
+    };
+    self.send_proto(message).await
+  }
+
+  pub async fn alarm_control_panel_command(
+    &self,
+    key: u32,
+    command: AlarmControlPanelCommand,
+    code: Option<String>,
+  ) -> Result<()> {
+    let message = proto::api::AlarmControlPanelCommandRequest {
+      key,
+      command: EnumOrUnknown::new(command.into()),
+      code: code.unwrap_or_default(),
+      ..Default::default()
+    };
+    self.send_proto(message).await
+  }
+
+  pub async fn text_command(&self, key: u32, state: String) -> Result<()> {
+    let message = proto::api::TextCommandRequest {
+      key,
+      state,
+      ..Default::default()
+    };
+    self.send_proto(message).await
+  }
+
+  pub async fn date_command(&self, key: u32, year: u32, month: u32, day: u32) -> Result<()> {
+    let message = proto::api::DateCommandRequest {
+      key,
+      year,
+      month,
+      day,
+      ..Default::default()
+    };
+    self.send_proto(message).await
+  }
+
+  pub async fn time_command(&self, key: u32, hour: u32, minute: u32, second: u32) -> Result<()> {
+    let message = proto::api::TimeCommandRequest {
+      key,
+      hour,
+      minute,
+      second,
+      ..Default::default()
+    };
+    self.send_proto(message).await
+  }
+
+  pub async fn datetime_command(&self, key: u32, epoch_seconds: u32) -> Result<()> {
+    let message = proto::api::DateTimeCommandRequest {
+      key,
+      epoch_seconds,
+      ..Default::default()
+    };
+    self.send_proto(message).await
+  }
+
+  pub async fn valve_command(&self, key: u32, position: Option<f32>, stop: bool) -> Result<()> {
+    let message = proto::api::ValveCommandRequest {
+      key,
+      has_position: position.is_some(),
+      position: position.unwrap_or_default(),
+      stop,
+      ..Default::default()
+    };
+    self.send_proto(message).await
+  }
+
+  pub async fn update_command(&self, key: u32, command: UpdateCommand) -> Result<()> {
+    let message = proto::api::UpdateCommandRequest {
+      key,
+      command: EnumOrUnknown::new(command.into()),
+      ..Default::default()
+    };
+    self.send_proto(message).await
+  }
+
+  /// Ask every camera on the device for a single image, a stream of images,
+  /// or both. Frames arrive on [`Client::camera_receiver`](crate::Client::camera_receiver).
+  pub async fn camera_image(&self, single: bool, stream: bool) -> Result<()> {
+    let message = proto::api::CameraImageRequest {
+      single,
+      stream,
+      ..Default::default()
+    };
+    self.send_proto(message).await
+  }
+
+  /// Transmit raw timings: positive marks and negative spaces in microseconds.
+  ///
+  /// `carrier_frequency` is in Hz; `repeat_count` is the total number of
+  /// transmissions.
+  pub async fn infrared_transmit_raw(
+    &self,
+    key: u32,
+    carrier_frequency: u32,
+    repeat_count: u32,
+    timings: Vec<i32>,
+  ) -> Result<()> {
+    let message = proto::api::InfraredRFTransmitRawTimingsRequest {
+      key,
+      carrier_frequency,
+      repeat_count,
+      timings,
+      ..Default::default()
+    };
+    self.send_proto(message).await
+  }
+
+  /// Write bytes to the serial port of a Z-Wave proxy's controller.
+  pub async fn zwave_proxy_frame(&self, data: Vec<u8>) -> Result<()> {
+    let message = proto::api::ZWaveProxyFrame {
+      data,
+      ..Default::default()
+    };
+    self.send_proto(message).await
+  }
+
+  /// Start or stop forwarding controller frames and home id changes.
+  pub async fn zwave_proxy_subscribe(&self, subscribe: bool) -> Result<()> {
+    let request_type = if subscribe {
+      proto::api::ZWaveProxyRequestType::ZWAVE_PROXY_REQUEST_TYPE_SUBSCRIBE
+    } else {
+      proto::api::ZWaveProxyRequestType::ZWAVE_PROXY_REQUEST_TYPE_UNSUBSCRIBE
+    };
+    let message = proto::api::ZWaveProxyRequest {
+      type_: protobuf::EnumOrUnknown::new(request_type),
+      ..Default::default()
+    };
+    self.send_proto(message).await
+  }
+
+  /// Transmit a signal, e.g. one built with [`IrCode::encode`](crate::infrared::IrCode::encode).
+  pub async fn infrared_transmit(
+    &self,
+    key: u32,
+    signal: &InfraredSignal,
+    repeat_count: u32,
+  ) -> Result<()> {
+    self
+      .infrared_transmit_raw(
+        key,
+        signal.carrier_frequency,
+        repeat_count,
+        signal.timings.clone(),
+      )
+      .await
+  }
+
+  pub async fn water_heater_command(
+    &self,
+    key: u32,
+    mode: Option<WaterHeaterMode>,
+    target_temperature: Option<f32>,
+    target_temperature_low: Option<f32>,
+    target_temperature_high: Option<f32>,
+    state: Option<BitFlags<WaterHeaterStateFlag>>,
+  ) -> Result<()> {
+    // Unlike the other commands, presence is signalled by a single bitmask
+    let mut has_fields = BitFlags::<WaterHeaterCommandField>::empty();
+    has_fields.set(WaterHeaterCommandField::Mode, mode.is_some());
+    has_fields.set(
+      WaterHeaterCommandField::TargetTemperature,
+      target_temperature.is_some(),
+    );
+    has_fields.set(
+      WaterHeaterCommandField::TargetTemperatureLow,
+      target_temperature_low.is_some(),
+    );
+    has_fields.set(
+      WaterHeaterCommandField::TargetTemperatureHigh,
+      target_temperature_high.is_some(),
+    );
+    has_fields.set(WaterHeaterCommandField::State, state.is_some());
+
+    let message = proto::api::WaterHeaterCommandRequest {
+      key,
+      has_fields: has_fields.bits(),
+      mode: EnumOrUnknown::new(mode.map(Into::into).unwrap_or_default()),
+      target_temperature: target_temperature.unwrap_or_default(),
+      target_temperature_low: target_temperature_low.unwrap_or_default(),
+      target_temperature_high: target_temperature_high.unwrap_or_default(),
+      state: state.unwrap_or_default().bits(),
+      ..Default::default()
+    };
+    self.send_proto(message).await
+  }
 }
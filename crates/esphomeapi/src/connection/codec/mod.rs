@@ -2,7 +2,10 @@ mod noise;
 mod plain;
 
 use bytes::{Bytes, BytesMut};
-pub use noise::{NoiseDecoder, NoiseEncoder, NoiseHandshake};
+pub use noise::{
+  decode_psk, generate_noise_psk, NoiseDecoder, NoiseEncoder, NoiseHandshake,
+  NoiseResponderHandshake,
+};
 pub use plain::{PlainDecoder, PlainEncoder};
 use thiserror::Error;
 use tokio_util::codec::{Decoder, Encoder};
@@ -28,6 +31,9 @@ pub enum CodecError {
   #[error("Invalid base64 PSK: {0}")]
   InvalidPsk(#[from] base64::DecodeError),
 
+  #[error("Invalid PSK length: expected {expected} bytes, got {actual}")]
+  InvalidPskLength { expected: usize, actual: usize },
+
   #[error("Handshake failed: {0}")]
   HandshakeFailed(String),
 
@@ -62,9 +68,9 @@ pub struct ProtobufMessage {
 
 /// Result of processing a handshake step
 pub enum HandshakeResult {
-  /// Need more data from the server to continue
+  /// Need more data from the peer to continue
   NeedMoreData,
-  /// Send this frame to the server, then continue handshake
+  /// Send this frame to the peer, then continue handshake
   SendFrame(Bytes),
   /// Handshake completed successfully, ready to split into encoder/decoder
   Complete(EspHomeDecoder, EspHomeEncoder),
@@ -72,7 +78,8 @@ pub enum HandshakeResult {
 
 /// Codec for handshake phase - handles both reading and writing during setup
 pub enum EspHomeHandshake {
-  Noise(NoiseHandshake),
+  Noise(Box<NoiseHandshake>),
+  NoiseResponder(Box<NoiseResponderHandshake>),
   Plain(PlainDecoder), // Plain doesn't need handshake, but we keep consistent API
 }
 
@@ -84,7 +91,32 @@ impl EspHomeHandshake {
   /// * `expected_name` - Optional server name to verify during handshake
   pub fn new(psk: Option<String>, expected_name: Option<String>) -> Result<Self, CodecError> {
     match psk {
-      Some(psk) => Ok(Self::Noise(NoiseHandshake::new(&psk, expected_name)?)),
+      Some(psk) => Ok(Self::Noise(Box::new(NoiseHandshake::new(
+        &psk,
+        expected_name,
+      )?))),
+      None => Ok(Self::Plain(PlainDecoder::new())),
+    }
+  }
+
+  /// Create a new codec for the device (responder) side of a connection
+  ///
+  /// Plain framing is the same in both directions, so without a PSK this
+  /// is the same as the client side.
+  ///
+  /// # Arguments
+  /// * `psk` - Optional base64-encoded pre-shared key for Noise encryption
+  /// * `server_name` - Node name announced to the client during handshake
+  /// * `mac_address` - MAC address announced to the client during handshake
+  pub fn new_responder(
+    psk: Option<String>,
+    server_name: String,
+    mac_address: String,
+  ) -> Result<Self, CodecError> {
+    match psk {
+      Some(psk) => Ok(Self::NoiseResponder(Box::new(
+        NoiseResponderHandshake::new(&psk, server_name, mac_address)?,
+      ))),
       None => Ok(Self::Plain(PlainDecoder::new())),
     }
   }
@@ -98,6 +130,7 @@ impl EspHomeHandshake {
   pub fn process(&mut self, src: &mut BytesMut) -> Result<HandshakeResult, CodecError> {
     match self {
       Self::Noise(handshake) => handshake.process(src),
+      Self::NoiseResponder(handshake) => handshake.process(src),
       Self::Plain(_) => {
         // Plain doesn't need handshake, immediately ready
         Ok(HandshakeResult::Complete(
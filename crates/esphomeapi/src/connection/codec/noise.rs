@@ -27,6 +27,29 @@ const ENCRYPTION_OVERHEAD: usize = 16;
 /// Size of the message header inside encrypted payload (type + length, both u16)
 const MESSAGE_HEADER_SIZE: usize = 4;
 
+/// Size of the decoded pre-shared key
+const PSK_SIZE: usize = 32;
+
+/// Decode a base64-encoded pre-shared key and check its length
+pub fn decode_psk(psk: &str) -> Result<Vec<u8>, CodecError> {
+  let psk_bytes = BASE64_STANDARD.decode(psk.as_bytes())?;
+  if psk_bytes.len() != PSK_SIZE {
+    return Err(CodecError::InvalidPskLength {
+      expected: PSK_SIZE,
+      actual: psk_bytes.len(),
+    });
+  }
+  Ok(psk_bytes)
+}
+
+/// Generate a random base64-encoded pre-shared key, e.g. for
+/// [`Client::set_noise_psk`](crate::Client::set_noise_psk).
+pub fn generate_noise_psk() -> String {
+  let mut psk = [0u8; PSK_SIZE];
+  getrandom::getrandom(&mut psk).expect("the OS random number generator is unavailable");
+  BASE64_STANDARD.encode(psk)
+}
+
 /// Parse a frame from the buffer, returning the frame data if complete
 fn parse_frame(src: &mut BytesMut) -> Result<Option<BytesMut>, CodecError> {
   if src.len() < HEADER_SIZE {
@@ -89,7 +112,7 @@ impl NoiseHandshake {
   /// * `psk` - Base64-encoded pre-shared key
   /// * `expected_server_name` - Optional server name to verify
   pub fn new(psk: &str, expected_server_name: Option<String>) -> Result<Self, CodecError> {
-    let psk_bytes = BASE64_STANDARD.decode(psk.as_bytes())?;
+    let psk_bytes = decode_psk(psk)?;
 
     let mut initiator = HandshakeState::new(
       noise_nn_psk0(),
@@ -234,6 +257,159 @@ impl NoiseHandshake {
   }
 }
 
+/// Internal state during the responder side of the handshake
+enum ResponderPhase {
+  /// Waiting for the client's hello frame
+  AwaitingClientHello,
+  /// Waiting for the client's Noise handshake frame
+  AwaitingHandshake(Box<HandshakeState<X25519, ChaCha20Poly1305, Sha256>>),
+  /// Handshake response has been sent, ready to split into encoder/decoder
+  Completed(Box<HandshakeState<X25519, ChaCha20Poly1305, Sha256>>),
+  /// Handshake failed, the error frame has been sent to the client
+  Failed(String),
+}
+
+/// Noise handshake handler for the device (responder) side of a connection
+///
+/// Mirrors [`NoiseHandshake`]: answers the client hello with a server hello
+/// carrying the node name and MAC address, then completes the
+/// `Noise_NNpsk0` exchange.
+pub struct NoiseResponderHandshake {
+  phase: ResponderPhase,
+  psk: Vec<u8>,
+  server_name: String,
+  mac_address: String,
+}
+
+impl NoiseResponderHandshake {
+  /// Create a new responder-side Noise handshake handler
+  ///
+  /// # Arguments
+  /// * `psk` - Base64-encoded pre-shared key
+  /// * `server_name` - Node name announced in the server hello
+  /// * `mac_address` - MAC address announced in the server hello, may be empty
+  pub fn new(psk: &str, server_name: String, mac_address: String) -> Result<Self, CodecError> {
+    Ok(Self {
+      phase: ResponderPhase::AwaitingClientHello,
+      psk: decode_psk(psk)?,
+      server_name,
+      mac_address,
+    })
+  }
+
+  /// Process handshake step
+  ///
+  /// Returns:
+  /// - `HandshakeResult::NeedMoreData` if more data is needed from the client
+  /// - `HandshakeResult::SendFrame` if a frame needs to be sent
+  /// - `HandshakeResult::Complete` when handshake is done
+  pub fn process(&mut self, src: &mut BytesMut) -> Result<HandshakeResult, CodecError> {
+    match std::mem::replace(&mut self.phase, ResponderPhase::AwaitingClientHello) {
+      ResponderPhase::AwaitingClientHello => match parse_frame(src) {
+        Ok(Some(data)) => Ok(HandshakeResult::SendFrame(self.handle_client_hello(&data))),
+        Ok(None) => Ok(HandshakeResult::NeedMoreData),
+        // A plaintext client; tell it that this device requires encryption
+        Err(CodecError::InvalidPreamble { actual, .. }) => Ok(HandshakeResult::SendFrame(
+          self.reject(format!("Bad indicator byte {actual:#04x}")),
+        )),
+        Err(error) => Err(error),
+      },
+      ResponderPhase::AwaitingHandshake(responder) => match parse_frame(src)? {
+        Some(data) => Ok(HandshakeResult::SendFrame(
+          self.handle_handshake(responder, data),
+        )),
+        None => {
+          self.phase = ResponderPhase::AwaitingHandshake(responder);
+          Ok(HandshakeResult::NeedMoreData)
+        }
+      },
+      ResponderPhase::Completed(responder) => {
+        let (decoder_cipher, encoder_cipher) = responder.get_ciphers();
+        Ok(HandshakeResult::Complete(
+          EspHomeDecoder::Noise(NoiseDecoder::new(decoder_cipher)),
+          EspHomeEncoder::Noise(NoiseEncoder::new(encoder_cipher)),
+        ))
+      }
+      ResponderPhase::Failed(error) => {
+        self.phase = ResponderPhase::Failed(error.clone());
+        Err(CodecError::HandshakeFailed(error))
+      }
+    }
+  }
+
+  /// Handle the client hello and build the server hello frame
+  ///
+  /// The client hello is reserved for future flags, but it is part of the
+  /// prologue, so both sides must agree on it.
+  fn handle_client_hello(&mut self, data: &[u8]) -> Bytes {
+    let mut prologue = NOISE_PROLOGUE[..NOISE_PROLOGUE.len() - 2].to_vec();
+    prologue.extend_from_slice(&(data.len() as u16).to_be_bytes());
+    prologue.extend_from_slice(data);
+
+    let mut responder =
+      HandshakeState::new(noise_nn_psk0(), false, &prologue, None, None, None, None);
+    responder.push_psk(&self.psk);
+    self.phase = ResponderPhase::AwaitingHandshake(Box::new(responder));
+
+    // Server hello: chosen protocol followed by the null-terminated node name
+    // and MAC address
+    let mut payload = BytesMut::with_capacity(self.server_name.len() + self.mac_address.len() + 3);
+    payload.put_u8(NOISE_PREAMBLE);
+    payload.extend_from_slice(self.server_name.as_bytes());
+    payload.put_u8(0x00);
+    payload.extend_from_slice(self.mac_address.as_bytes());
+    payload.put_u8(0x00);
+    build_frame(&payload)
+  }
+
+  /// Handle the client's handshake frame and build the response frame
+  ///
+  /// On failure the error frame is returned instead, and the next call to
+  /// [`process`](Self::process) reports the error.
+  fn handle_handshake(
+    &mut self,
+    mut responder: Box<HandshakeState<X25519, ChaCha20Poly1305, Sha256>>,
+    data: BytesMut,
+  ) -> Bytes {
+    let result = match data.first() {
+      Some(0x00) => responder
+        .read_message_vec(&data[1..])
+        .and_then(|_| responder.write_message_vec(&[]))
+        .map_err(|_| "Handshake MAC failure".to_string()),
+      _ => Err("Invalid handshake frame".to_string()),
+    };
+
+    match result {
+      Ok(message) => {
+        let mut payload = BytesMut::with_capacity(message.len() + 1);
+        payload.put_u8(0x00);
+        payload.extend_from_slice(&message);
+        self.phase = ResponderPhase::Completed(responder);
+        build_frame(&payload)
+      }
+      Err(error) => self.reject(error),
+    }
+  }
+
+  /// Build a handshake error frame and fail the handshake
+  fn reject(&mut self, error: String) -> Bytes {
+    let mut payload = BytesMut::with_capacity(error.len() + 1);
+    payload.put_u8(0x01);
+    payload.extend_from_slice(error.as_bytes());
+    self.phase = ResponderPhase::Failed(error);
+    build_frame(&payload)
+  }
+}
+
+/// Wrap a handshake payload in a noise frame header
+fn build_frame(payload: &[u8]) -> Bytes {
+  let mut frame = BytesMut::with_capacity(HEADER_SIZE + payload.len());
+  frame.put_u8(NOISE_PREAMBLE);
+  frame.put_u16(payload.len() as u16);
+  frame.extend_from_slice(payload);
+  frame.freeze()
+}
+
 /// Decoder for noise-encrypted messages
 ///
 /// Created after handshake completes. Decrypts incoming messages.
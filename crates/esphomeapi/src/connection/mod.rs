@@ -1,26 +1,32 @@
-mod codec;
+pub mod codec;
 mod router;
 
 use std::sync::Arc;
 use std::time::Duration;
 
 use bytes::BytesMut;
-use codec::{EspHomeDecoder, EspHomeEncoder, EspHomeHandshake, HandshakeResult};
+use codec::{CodecError, EspHomeDecoder, EspHomeEncoder, EspHomeHandshake, HandshakeResult};
 use protobuf::Message as _;
 use router::MessageRouter;
+pub(crate) use router::ResponseFilter;
 pub(crate) use router::RouterHandle;
 pub(crate) use router::SharedChannels;
-use tokio::io::{AsyncReadExt as _, AsyncWriteExt as _, BufReader, BufWriter};
-use tokio::net::TcpStream;
+use tokio::io::{AsyncReadExt as _, AsyncWriteExt as _, BufReader, BufWriter, ReadHalf, WriteHalf};
 use tokio::sync::oneshot;
 use tokio::task::JoinHandle;
 use tokio_stream::StreamExt;
 use tokio_util::codec::{FramedRead, FramedWrite};
 
+use crate::model::ServerInfo;
+use crate::time::TimeSource;
+use crate::transport::{BoxedStream, Transport};
 use crate::utils::Options as _;
-use crate::{proto, Result};
+use crate::{proto, Error, Result};
 pub use codec::ProtobufMessage;
 
+type Reader = BufReader<ReadHalf<BoxedStream>>;
+pub(crate) type Writer = BufWriter<WriteHalf<BoxedStream>>;
+
 pub(crate) struct Disconnected;
 
 pub(crate) struct Connected {
@@ -48,6 +54,10 @@ pub(crate) struct ConnectionConfig {
   pub psk: Option<String>,
   pub client_info: String,
   pub keep_alive_duration: Duration,
+  pub connect_timeout: Duration,
+  pub api_version: (u32, u32),
+  pub time_source: TimeSource,
+  pub transport: Arc<dyn Transport>,
 }
 
 pub(crate) struct Connection<S> {
@@ -69,29 +79,42 @@ impl Connection<Disconnected> {
     login: bool,
     channels: Arc<SharedChannels>,
   ) -> Result<Connection<Connected>> {
-    let stream = TcpStream::connect(format!("{}:{}", self.config.host, self.config.port)).await?;
-    let (reader, writer) = stream.into_split();
-
-    let codec = EspHomeHandshake::new(self.config.psk.clone(), self.config.expected_name.clone())?;
-
-    let mut reader = BufReader::new(reader);
-    let mut writer = BufWriter::new(writer);
+    let connect_timeout = self.config.connect_timeout;
+    let deadline = tokio::time::Instant::now() + connect_timeout;
 
-    let (decoder, encoder) = Self::perform_handshake(codec, &mut reader, &mut writer).await?;
+    let (reader, writer, decoder, encoder) =
+      tokio::time::timeout_at(deadline, Self::open(&self.config))
+        .await
+        .map_err(|_| Error::Timeout(connect_timeout))??;
 
     let (message_tx, message_rx) = tokio::sync::mpsc::channel(32);
     let framed_reader = FramedRead::new(reader, decoder);
     let reader_task = Self::spawn_reader_task(framed_reader, message_tx);
 
     let framed_writer = FramedWrite::new(writer, encoder);
-    let (router, router_handle, device_disconnect_rx) =
-      MessageRouter::new(message_rx, framed_writer, channels);
+    let (router, router_handle, device_disconnect_rx) = MessageRouter::new(
+      message_rx,
+      framed_writer,
+      channels,
+      self.config.time_source.clone(),
+    );
 
     let router_task = tokio::spawn(async move {
       router.run().await;
     });
 
-    Self::perform_hello(&router_handle, &self.config, login).await?;
+    let hello = tokio::time::timeout_at(
+      deadline,
+      Self::perform_hello(&router_handle, &self.config, login),
+    )
+    .await
+    .map_err(|_| Error::Timeout(connect_timeout))
+    .and_then(|result| result);
+    if let Err(e) = hello {
+      reader_task.abort();
+      router_task.abort();
+      return Err(e);
+    }
 
     let keep_alive_task =
       Self::spawn_keep_alive_task(router_handle.clone(), self.config.keep_alive_duration);
@@ -108,24 +131,51 @@ impl Connection<Disconnected> {
     })
   }
 
+  /// Dial the device and complete the framing handshake.
+  async fn open(
+    config: &ConnectionConfig,
+  ) -> Result<(Reader, Writer, EspHomeDecoder, EspHomeEncoder)> {
+    let stream = config
+      .transport
+      .dial(&config.host, config.port)
+      .await
+      .map_err(|source| Error::Connect {
+        address: config.transport.address(&config.host, config.port),
+        source,
+      })?;
+    let (reader, writer) = tokio::io::split(stream);
+
+    let codec = EspHomeHandshake::new(config.psk.clone(), config.expected_name.clone())?;
+
+    let mut reader = BufReader::new(reader);
+    let mut writer = BufWriter::new(writer);
+
+    let (decoder, encoder) = Self::perform_handshake(codec, &mut reader, &mut writer).await?;
+
+    Ok((reader, writer, decoder, encoder))
+  }
+
   async fn perform_handshake(
     mut codec: EspHomeHandshake,
-    reader: &mut BufReader<tokio::net::tcp::OwnedReadHalf>,
-    writer: &mut BufWriter<tokio::net::tcp::OwnedWriteHalf>,
+    reader: &mut Reader,
+    writer: &mut Writer,
   ) -> Result<(EspHomeDecoder, EspHomeEncoder)> {
     let mut buffer = BytesMut::with_capacity(1024);
 
     loop {
       match codec.process(&mut buffer)? {
         HandshakeResult::NeedMoreData => {
-          let n = reader.read_buf(&mut buffer).await?;
+          let n = reader
+            .read_buf(&mut buffer)
+            .await
+            .map_err(CodecError::from)?;
           if n == 0 {
-            return Err("Connection closed during handshake".into());
+            return Err(Error::Disconnected);
           }
         }
         HandshakeResult::SendFrame(frame) => {
-          writer.write_all(&frame).await?;
-          writer.flush().await?;
+          writer.write_all(&frame).await.map_err(CodecError::from)?;
+          writer.flush().await.map_err(CodecError::from)?;
         }
         HandshakeResult::Complete(decoder, encoder) => {
           return Ok((decoder, encoder));
@@ -135,18 +185,13 @@ impl Connection<Disconnected> {
   }
 
   fn spawn_reader_task(
-    mut reader: FramedRead<BufReader<tokio::net::tcp::OwnedReadHalf>, EspHomeDecoder>,
+    mut reader: FramedRead<Reader, EspHomeDecoder>,
     tx: tokio::sync::mpsc::Sender<ProtobufMessage>,
   ) -> JoinHandle<()> {
     tokio::spawn(async move {
-      loop {
-        match reader.next().await {
-          Some(Ok(message)) => {
-            if tx.send(message).await.is_err() {
-              break;
-            }
-          }
-          Some(Err(_)) | None => break,
+      while let Some(Ok(message)) = reader.next().await {
+        if tx.send(message).await.is_err() {
+          break;
         }
       }
     })
@@ -157,10 +202,12 @@ impl Connection<Disconnected> {
     config: &ConnectionConfig,
     login: bool,
   ) -> Result<()> {
-    let mut hello = proto::api::HelloRequest::default();
-    hello.client_info = config.client_info.clone();
-    hello.api_version_major = 1;
-    hello.api_version_minor = 10;
+    let hello = proto::api::HelloRequest {
+      client_info: config.client_info.clone(),
+      api_version_major: config.api_version.0,
+      api_version_minor: config.api_version.1,
+      ..Default::default()
+    };
 
     let response = router
       .send_await_response(
@@ -173,16 +220,14 @@ impl Connection<Disconnected> {
       .await?;
 
     let hello_response = proto::api::HelloResponse::parse_from_bytes(&response.protobuf_data)?;
+    let server_info = ServerInfo::from(hello_response);
 
     if let Some(expected_name) = &config.expected_name {
-      if &hello_response.name != expected_name {
-        return Err(
-          format!(
-            "Device name mismatch: expected '{}', got '{}'",
-            expected_name, hello_response.name
-          )
-          .into(),
-        );
+      if &server_info.name != expected_name {
+        return Err(Error::NameMismatch {
+          expected: expected_name.clone(),
+          actual: server_info.name,
+        });
       }
     }
 
@@ -191,14 +236,29 @@ impl Connection<Disconnected> {
       if let Some(password) = &config.password {
         auth.password = password.clone();
       }
-      router
-        .send(ProtobufMessage {
-          protobuf_type: proto::api::AuthenticationRequest::get_option_id(),
-          protobuf_data: auth.write_to_bytes()?,
-        })
-        .await?;
+      let message = ProtobufMessage {
+        protobuf_type: proto::api::AuthenticationRequest::get_option_id(),
+        protobuf_data: auth.write_to_bytes()?,
+      };
+
+      // Devices only answer when a password is configured, so only then is
+      // there a response to check.
+      if config.password.is_some() {
+        let response = router
+          .send_await_response(message, proto::api::AuthenticationResponse::get_option_id())
+          .await?;
+
+        let auth_response =
+          proto::api::AuthenticationResponse::parse_from_bytes(&response.protobuf_data)?;
+        if auth_response.invalid_password {
+          return Err(Error::Authentication);
+        }
+      } else {
+        router.send(message).await?;
+      }
     }
 
+    router.set_server_info(server_info);
     Ok(())
   }
 
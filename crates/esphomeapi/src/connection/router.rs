@@ -1,21 +1,24 @@
-use std::sync::Arc;
-use std::time::SystemTime;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
 
 use futures::SinkExt as _;
 use protobuf::{Message as _, MessageDyn};
-use tokio::io::BufWriter;
-use tokio::net::tcp::OwnedWriteHalf;
 use tokio::sync::{broadcast, mpsc, oneshot};
 use tokio_util::codec::FramedWrite;
 
+use crate::builder::BufferSizes;
 use crate::model::{
-  CameraImage, EntityState, HomeAssistantEvent, HomeassistantActionRequest, LogEvent,
-  SUBCRIBE_STATES_RESPONSE_TYPES,
+  BluetoothConnectionsFree, BluetoothDeviceConnection, BluetoothGATTNotifyData,
+  BluetoothLEAdvertisement, BluetoothScannerStatus, CameraImage, EntityState, HomeAssistantEvent,
+  HomeassistantActionRequest, InfraredReceiveEvent, LogEvent, ServerInfo, VoiceAssistantMessage,
+  ZWaveProxyEvent, MINIMUM_API_VERSIONS, SUBCRIBE_STATES_RESPONSE_TYPES,
 };
 use crate::proto;
+use crate::time::TimeSource;
 use crate::utils::Options as _;
 
 use super::codec::{EspHomeEncoder, ProtobufMessage};
+use super::Writer;
 
 /// Long-lived broadcast channels shared across reconnects.
 ///
@@ -28,16 +31,34 @@ pub(crate) struct SharedChannels {
   pub log_tx: broadcast::Sender<LogEvent>,
   pub action_request_tx: broadcast::Sender<HomeassistantActionRequest>,
   pub camera_tx: broadcast::Sender<CameraImage>,
+  pub camera_frame_tx: broadcast::Sender<CameraImage>,
+  pub ble_advertisement_tx: broadcast::Sender<BluetoothLEAdvertisement>,
+  pub bluetooth_scanner_tx: broadcast::Sender<BluetoothScannerStatus>,
+  pub ble_notify_tx: broadcast::Sender<BluetoothGATTNotifyData>,
+  pub bluetooth_connection_tx: broadcast::Sender<BluetoothDeviceConnection>,
+  pub bluetooth_connections_free_tx: broadcast::Sender<BluetoothConnectionsFree>,
+  pub voice_assistant_tx: broadcast::Sender<VoiceAssistantMessage>,
+  pub infrared_tx: broadcast::Sender<InfraredReceiveEvent>,
+  pub zwave_tx: broadcast::Sender<ZWaveProxyEvent>,
 }
 
 impl SharedChannels {
-  pub fn new() -> Self {
+  pub fn new(buffer_sizes: &BufferSizes) -> Self {
     Self {
-      state_tx: broadcast::channel(64).0,
-      ha_event_tx: broadcast::channel(32).0,
-      log_tx: broadcast::channel(128).0,
-      action_request_tx: broadcast::channel(32).0,
-      camera_tx: broadcast::channel(8).0,
+      state_tx: broadcast::channel(buffer_sizes.states).0,
+      ha_event_tx: broadcast::channel(buffer_sizes.home_assistant_events).0,
+      log_tx: broadcast::channel(buffer_sizes.logs).0,
+      action_request_tx: broadcast::channel(buffer_sizes.action_requests).0,
+      camera_tx: broadcast::channel(buffer_sizes.camera).0,
+      camera_frame_tx: broadcast::channel(buffer_sizes.camera_frames).0,
+      ble_advertisement_tx: broadcast::channel(buffer_sizes.ble_advertisements).0,
+      bluetooth_scanner_tx: broadcast::channel(buffer_sizes.bluetooth_scanner_states).0,
+      ble_notify_tx: broadcast::channel(buffer_sizes.ble_notifications).0,
+      bluetooth_connection_tx: broadcast::channel(buffer_sizes.bluetooth_connections).0,
+      bluetooth_connections_free_tx: broadcast::channel(buffer_sizes.bluetooth_connections).0,
+      voice_assistant_tx: broadcast::channel(buffer_sizes.voice_assistant).0,
+      infrared_tx: broadcast::channel(buffer_sizes.infrared).0,
+      zwave_tx: broadcast::channel(buffer_sizes.zwave).0,
     }
   }
 
@@ -60,17 +81,113 @@ impl SharedChannels {
   pub fn subscribe_camera(&self) -> broadcast::Receiver<CameraImage> {
     self.camera_tx.subscribe()
   }
+
+  pub fn subscribe_camera_frames(&self) -> broadcast::Receiver<CameraImage> {
+    self.camera_frame_tx.subscribe()
+  }
+
+  pub fn subscribe_ble_advertisements(&self) -> broadcast::Receiver<BluetoothLEAdvertisement> {
+    self.ble_advertisement_tx.subscribe()
+  }
+
+  pub fn subscribe_bluetooth_scanner_states(&self) -> broadcast::Receiver<BluetoothScannerStatus> {
+    self.bluetooth_scanner_tx.subscribe()
+  }
+
+  pub fn subscribe_ble_notifications(&self) -> broadcast::Receiver<BluetoothGATTNotifyData> {
+    self.ble_notify_tx.subscribe()
+  }
+
+  pub fn subscribe_bluetooth_connections(&self) -> broadcast::Receiver<BluetoothDeviceConnection> {
+    self.bluetooth_connection_tx.subscribe()
+  }
+
+  pub fn subscribe_bluetooth_connections_free(
+    &self,
+  ) -> broadcast::Receiver<BluetoothConnectionsFree> {
+    self.bluetooth_connections_free_tx.subscribe()
+  }
+
+  pub fn subscribe_voice_assistant(&self) -> broadcast::Receiver<VoiceAssistantMessage> {
+    self.voice_assistant_tx.subscribe()
+  }
+
+  pub fn subscribe_infrared(&self) -> broadcast::Receiver<InfraredReceiveEvent> {
+    self.infrared_tx.subscribe()
+  }
+
+  pub fn subscribe_zwave(&self) -> broadcast::Receiver<ZWaveProxyEvent> {
+    self.zwave_tx.subscribe()
+  }
+}
+
+type ResponsePredicate = Box<dyn Fn(&[u8]) -> bool + Send + Sync>;
+
+/// Describes which incoming messages answer a request.
+///
+/// A response must have one of the listed types. Per-type predicates narrow
+/// the match further using correlation fields (e.g. `call_id` or a BLE
+/// address), so overlapping requests of the same type get their own answers.
+pub struct ResponseFilter {
+  response_types: Vec<u32>,
+  predicates: Vec<(u32, ResponsePredicate)>,
+}
+
+impl ResponseFilter {
+  pub fn new(response_type: u32) -> Self {
+    Self::any_of(vec![response_type])
+  }
+
+  pub fn any_of(response_types: Vec<u32>) -> Self {
+    Self {
+      response_types,
+      predicates: Vec::new(),
+    }
+  }
+
+  /// Also accept messages of type `M` (e.g. an error response).
+  pub fn or<M: protobuf::MessageFull>(mut self) -> Self {
+    self.response_types.push(M::get_option_id());
+    self
+  }
+
+  /// Only accept messages of type `M` for which `predicate` returns `true`.
+  ///
+  /// Messages of other types are not affected.
+  pub fn matching<M, F>(mut self, predicate: F) -> Self
+  where
+    M: protobuf::MessageFull,
+    F: Fn(&M) -> bool + Send + Sync + 'static,
+  {
+    self.predicates.push((
+      M::get_option_id(),
+      Box::new(move |data| M::parse_from_bytes(data).is_ok_and(|message| predicate(&message))),
+    ));
+    self
+  }
+
+  fn accepts_type(&self, message: &ProtobufMessage) -> bool {
+    self.response_types.contains(&message.protobuf_type) && self.passes(message)
+  }
+
+  fn passes(&self, message: &ProtobufMessage) -> bool {
+    self
+      .predicates
+      .iter()
+      .filter(|(message_type, _)| *message_type == message.protobuf_type)
+      .all(|(_, predicate)| predicate(&message.protobuf_data))
+  }
 }
 
 /// Tracks a pending request awaiting a response
 struct PendingRequest {
-  response_type: u32,
+  filter: ResponseFilter,
   tx: oneshot::Sender<ProtobufMessage>,
 }
 
 /// Tracks a pending request awaiting multiple responses until a terminator
 struct PendingMultiRequest {
-  response_types: Vec<u32>,
+  filter: ResponseFilter,
   until_type: u32,
   tx: mpsc::Sender<ProtobufMessage>,
 }
@@ -79,16 +196,16 @@ struct PendingMultiRequest {
 pub enum RouterCommand {
   /// Send a message without waiting for a response
   Send { message: ProtobufMessage },
-  /// Send a message and wait for a specific response type
+  /// Send a message and wait for a matching response
   SendAwaitResponse {
     message: ProtobufMessage,
-    response_type: u32,
+    filter: ResponseFilter,
     tx: oneshot::Sender<ProtobufMessage>,
   },
-  /// Send a message and collect responses until a terminator type
+  /// Send a message and collect matching responses until a terminator type
   SendAwaitMultiple {
     message: ProtobufMessage,
-    response_types: Vec<u32>,
+    filter: ResponseFilter,
     until_type: u32,
     tx: mpsc::Sender<ProtobufMessage>,
   },
@@ -98,15 +215,57 @@ pub enum RouterCommand {
 #[derive(Clone)]
 pub struct RouterHandle {
   command_tx: mpsc::Sender<RouterCommand>,
+  // Set once the hello exchange completes
+  server_info: Arc<OnceLock<ServerInfo>>,
 }
 
 impl RouterHandle {
+  pub fn server_info(&self) -> Option<&ServerInfo> {
+    self.server_info.get()
+  }
+
+  pub(crate) fn set_server_info(&self, server_info: ServerInfo) {
+    let _ = self.server_info.set(server_info);
+  }
+
+  /// Refuses requests the device's API version does not know about.
+  fn check_supported(&self, message: &ProtobufMessage) -> crate::Result<()> {
+    let (Some(server_info), Some(&required)) = (
+      self.server_info.get(),
+      MINIMUM_API_VERSIONS.get(&message.protobuf_type),
+    ) else {
+      return Ok(());
+    };
+    if server_info.api_version >= required {
+      return Ok(());
+    }
+
+    let request = proto::api::file_descriptor()
+      .messages()
+      .find(|descriptor| {
+        descriptor
+          .proto()
+          .options
+          .as_ref()
+          .and_then(|options| proto::api_options::exts::id.get(options))
+          == Some(message.protobuf_type)
+      })
+      .map(|descriptor| descriptor.name().to_string())
+      .unwrap_or_else(|| format!("Message type {}", message.protobuf_type));
+    Err(crate::Error::Unsupported {
+      request,
+      required,
+      actual: server_info.api_version,
+    })
+  }
+
   pub async fn send(&self, message: ProtobufMessage) -> crate::Result<()> {
+    self.check_supported(&message)?;
     self
       .command_tx
       .send(RouterCommand::Send { message })
       .await
-      .map_err(|_| "Router channel closed")?;
+      .map_err(|_| crate::Error::RouterClosed)?;
     Ok(())
   }
 
@@ -115,37 +274,49 @@ impl RouterHandle {
     message: ProtobufMessage,
     response_type: u32,
   ) -> crate::Result<ProtobufMessage> {
+    self
+      .send_await_filtered(message, ResponseFilter::new(response_type))
+      .await
+  }
+
+  pub async fn send_await_filtered(
+    &self,
+    message: ProtobufMessage,
+    filter: ResponseFilter,
+  ) -> crate::Result<ProtobufMessage> {
+    self.check_supported(&message)?;
     let (tx, rx) = oneshot::channel();
     self
       .command_tx
       .send(RouterCommand::SendAwaitResponse {
         message,
-        response_type,
+        filter,
         tx,
       })
       .await
-      .map_err(|_| "Router channel closed")?;
+      .map_err(|_| crate::Error::RouterClosed)?;
 
-    rx.await.map_err(|_| "Response channel closed".into())
+    rx.await.map_err(|_| crate::Error::Disconnected)
   }
 
-  pub async fn send_await_multiple(
+  pub async fn send_await_multiple_filtered(
     &self,
     message: ProtobufMessage,
-    response_types: Vec<u32>,
+    filter: ResponseFilter,
     until_type: u32,
   ) -> crate::Result<mpsc::Receiver<ProtobufMessage>> {
+    self.check_supported(&message)?;
     let (tx, rx) = mpsc::channel(32);
     self
       .command_tx
       .send(RouterCommand::SendAwaitMultiple {
         message,
-        response_types,
+        filter,
         until_type,
         tx,
       })
       .await
-      .map_err(|_| "Router channel closed")?;
+      .map_err(|_| crate::Error::RouterClosed)?;
 
     Ok(rx)
   }
@@ -158,14 +329,22 @@ pub struct MessageRouter {
   /// Receives commands from the RouterHandle
   command_rx: mpsc::Receiver<RouterCommand>,
   /// Writer for sending messages to the device
-  writer: FramedWrite<BufWriter<OwnedWriteHalf>, EspHomeEncoder>,
+  writer: FramedWrite<Writer, EspHomeEncoder>,
 
   /// Shared broadcast channels (outlive this router instance)
   channels: Arc<SharedChannels>,
 
-  // Pending request tracking
-  pending_single: Option<PendingRequest>,
-  pending_multi: Option<PendingMultiRequest>,
+  // Pending requests, oldest first. The device answers requests in order, so
+  // the first waiter whose filter accepts a message is the one it belongs to.
+  pending_single: Vec<PendingRequest>,
+  pending_multi: Vec<PendingMultiRequest>,
+
+  // Partially received camera images, by device id and camera key. Chunks of
+  // different cameras may interleave.
+  camera_chunks: HashMap<(u32, u32), Vec<u8>>,
+
+  // Answers the device's GetTimeRequest
+  time_source: TimeSource,
 
   // Signals when the connection drops.
   // Sends `true` for abrupt disconnect (reconnect), `false` for graceful DisconnectRequest.
@@ -175,8 +354,9 @@ pub struct MessageRouter {
 impl MessageRouter {
   pub fn new(
     message_rx: mpsc::Receiver<ProtobufMessage>,
-    writer: FramedWrite<BufWriter<OwnedWriteHalf>, EspHomeEncoder>,
+    writer: FramedWrite<Writer, EspHomeEncoder>,
     channels: Arc<SharedChannels>,
+    time_source: TimeSource,
   ) -> (Self, RouterHandle, oneshot::Receiver<bool>) {
     let (command_tx, command_rx) = mpsc::channel(32);
     let (device_disconnect_tx, device_disconnect_rx) = oneshot::channel::<bool>();
@@ -186,12 +366,17 @@ impl MessageRouter {
       command_rx,
       writer,
       channels,
-      pending_single: None,
-      pending_multi: None,
+      pending_single: Vec::new(),
+      pending_multi: Vec::new(),
+      camera_chunks: HashMap::new(),
+      time_source,
       device_disconnect_tx: Some(device_disconnect_tx),
     };
 
-    let handle = RouterHandle { command_tx };
+    let handle = RouterHandle {
+      command_tx,
+      server_info: Arc::default(),
+    };
 
     (router, handle, device_disconnect_rx)
   }
@@ -251,36 +436,48 @@ impl MessageRouter {
   }
 
   async fn route_response(&mut self, message: ProtobufMessage) {
-    // First check if this matches a pending single request
-    if let Some(pending) = &self.pending_single {
-      if message.protobuf_type == pending.response_type {
-        if let Some(pending) = self.pending_single.take() {
-          let _ = pending.tx.send(message);
-          return;
-        }
-      }
+    self.prune_cancelled();
+
+    // First check if this answers a pending single request
+    if let Some(index) = self
+      .pending_single
+      .iter()
+      .position(|pending| pending.filter.accepts_type(&message))
+    {
+      let pending = self.pending_single.remove(index);
+      let _ = pending.tx.send(message);
+      return;
     }
 
-    // Check if this matches a pending multi request
-    if let Some(pending) = &self.pending_multi {
-      if message.protobuf_type == pending.until_type {
-        // Terminator received, complete the multi request
-        self.pending_multi = None;
-        return;
-      }
-      if pending.response_types.contains(&message.protobuf_type) {
-        if let Some(pending) = &self.pending_multi {
-          let _ = pending.tx.send(message).await;
-          return;
-        }
+    // Then check if it belongs to a pending multi request
+    if let Some(index) = self.pending_multi.iter().position(|pending| {
+      (message.protobuf_type == pending.until_type && pending.filter.passes(&message))
+        || pending.filter.accepts_type(&message)
+    }) {
+      if message.protobuf_type == self.pending_multi[index].until_type {
+        // Terminator received: hand it over so the caller can tell completion
+        // from a timeout, then dropping the sender completes the multi request
+        let pending = self.pending_multi.remove(index);
+        let _ = pending.tx.send(message).await;
+      } else if self.pending_multi[index].tx.send(message).await.is_err() {
+        self.pending_multi.remove(index);
       }
+      return;
     }
 
     // Route to appropriate broadcast channel based on message type
     self.broadcast_message(message).await;
   }
 
-  async fn broadcast_message(&self, message: ProtobufMessage) {
+  /// Forget waiters whose caller has given up (timed out or dropped the future).
+  fn prune_cancelled(&mut self) {
+    self
+      .pending_single
+      .retain(|pending| !pending.tx.is_closed());
+    self.pending_multi.retain(|pending| !pending.tx.is_closed());
+  }
+
+  async fn broadcast_message(&mut self, message: ProtobufMessage) {
     let msg_type = message.protobuf_type;
 
     // Entity state updates
@@ -330,10 +527,136 @@ impl MessageRouter {
         proto::api::CameraImageResponse::parse_from_bytes(&message.protobuf_data)
       {
         let image: CameraImage = proto_msg.into();
+        self.assemble_camera_image(&image);
         let _ = self.channels.camera_tx.send(image);
         return;
       }
     }
+
+    // BLE advertisements, legacy (one per message) and raw (batched)
+    if msg_type == proto::api::BluetoothLEAdvertisementResponse::get_option_id() {
+      if let Ok(proto_msg) =
+        proto::api::BluetoothLEAdvertisementResponse::parse_from_bytes(&message.protobuf_data)
+      {
+        let _ = self.channels.ble_advertisement_tx.send(proto_msg.into());
+        return;
+      }
+    }
+    if msg_type == proto::api::BluetoothLERawAdvertisementsResponse::get_option_id() {
+      if let Ok(proto_msg) =
+        proto::api::BluetoothLERawAdvertisementsResponse::parse_from_bytes(&message.protobuf_data)
+      {
+        for advertisement in proto_msg.advertisements {
+          let _ = self
+            .channels
+            .ble_advertisement_tx
+            .send(advertisement.into());
+        }
+        return;
+      }
+    }
+
+    // Bluetooth scanner state changes
+    if msg_type == proto::api::BluetoothScannerStateResponse::get_option_id() {
+      if let Ok(proto_msg) =
+        proto::api::BluetoothScannerStateResponse::parse_from_bytes(&message.protobuf_data)
+      {
+        let _ = self.channels.bluetooth_scanner_tx.send(proto_msg.into());
+        return;
+      }
+    }
+
+    // GATT notifications from connected devices
+    if msg_type == proto::api::BluetoothGATTNotifyDataResponse::get_option_id() {
+      if let Ok(proto_msg) =
+        proto::api::BluetoothGATTNotifyDataResponse::parse_from_bytes(&message.protobuf_data)
+      {
+        let _ = self.channels.ble_notify_tx.send(proto_msg.into());
+        return;
+      }
+    }
+
+    // Unsolicited connection changes, e.g. a device going out of range
+    if msg_type == proto::api::BluetoothDeviceConnectionResponse::get_option_id() {
+      if let Ok(proto_msg) =
+        proto::api::BluetoothDeviceConnectionResponse::parse_from_bytes(&message.protobuf_data)
+      {
+        let _ = self.channels.bluetooth_connection_tx.send(proto_msg.into());
+        return;
+      }
+    }
+
+    // Connection slot accounting
+    if msg_type == proto::api::BluetoothConnectionsFreeResponse::get_option_id() {
+      if let Ok(proto_msg) =
+        proto::api::BluetoothConnectionsFreeResponse::parse_from_bytes(&message.protobuf_data)
+      {
+        let _ = self
+          .channels
+          .bluetooth_connections_free_tx
+          .send(proto_msg.into());
+        return;
+      }
+    }
+
+    // Voice pipeline requests and microphone audio share one channel to keep their order
+    if msg_type == proto::api::VoiceAssistantRequest::get_option_id() {
+      if let Ok(proto_msg) =
+        proto::api::VoiceAssistantRequest::parse_from_bytes(&message.protobuf_data)
+      {
+        let _ = self
+          .channels
+          .voice_assistant_tx
+          .send(VoiceAssistantMessage::Command(proto_msg.into()));
+        return;
+      }
+    }
+    if msg_type == proto::api::VoiceAssistantAudio::get_option_id() {
+      if let Ok(proto_msg) =
+        proto::api::VoiceAssistantAudio::parse_from_bytes(&message.protobuf_data)
+      {
+        let _ = self
+          .channels
+          .voice_assistant_tx
+          .send(VoiceAssistantMessage::Audio(proto_msg.into()));
+        return;
+      }
+    }
+
+    // Infrared/RF captures
+    if msg_type == proto::api::InfraredRFReceiveEvent::get_option_id() {
+      if let Ok(proto_msg) =
+        proto::api::InfraredRFReceiveEvent::parse_from_bytes(&message.protobuf_data)
+      {
+        let _ = self.channels.infrared_tx.send(proto_msg.into());
+        return;
+      }
+    }
+
+    // Z-Wave controller frames and home id changes share one channel to keep their order
+    if msg_type == proto::api::ZWaveProxyFrame::get_option_id() {
+      if let Ok(proto_msg) = proto::api::ZWaveProxyFrame::parse_from_bytes(&message.protobuf_data) {
+        let _ = self
+          .channels
+          .zwave_tx
+          .send(ZWaveProxyEvent::Frame(proto_msg.data));
+        return;
+      }
+    }
+    if msg_type == proto::api::ZWaveProxyRequest::get_option_id() {
+      if let Ok(proto_msg) = proto::api::ZWaveProxyRequest::parse_from_bytes(&message.protobuf_data)
+      {
+        let home_id_change = proto_msg.type_.enum_value()
+          == Ok(proto::api::ZWaveProxyRequestType::ZWAVE_PROXY_REQUEST_TYPE_HOME_ID_CHANGE);
+        // The home id is sent as its four bytes, most significant first
+        if let (true, Ok(home_id)) = (home_id_change, <[u8; 4]>::try_from(proto_msg.data)) {
+          let _ = self
+            .channels
+            .zwave_tx
+            .send(ZWaveProxyEvent::HomeIdChanged(u32::from_be_bytes(home_id)));
+        }
+      }
+    }
   }
 
   /// Returns `false` when the router loop should exit (device-initiated disconnect or write error).
@@ -348,11 +671,12 @@ impl MessageRouter {
 
     // Handle GetTimeRequest
     if msg_type == proto::api::GetTimeRequest::get_option_id() {
-      let mut response = proto::api::GetTimeResponse::new();
-      response.epoch_seconds = SystemTime::now()
-        .duration_since(SystemTime::UNIX_EPOCH)
-        .unwrap()
-        .as_secs() as u32;
+      let time = self.time_source.now();
+      let response = proto::api::GetTimeResponse {
+        epoch_seconds: time.epoch_seconds,
+        timezone: time.timezone,
+        ..Default::default()
+      };
       return self.send_proto_message(&response).await;
     }
 
@@ -369,26 +693,44 @@ impl MessageRouter {
     true
   }
 
+  /// Collect a camera chunk and broadcast the whole image once it is done.
+  fn assemble_camera_image(&mut self, chunk: &CameraImage) {
+    let id = (chunk.device_id, chunk.key);
+    let buffer = self.camera_chunks.entry(id).or_default();
+    buffer.extend_from_slice(&chunk.data);
+    if chunk.done {
+      let data = self.camera_chunks.remove(&id).unwrap_or_default();
+      let _ = self.channels.camera_frame_tx.send(CameraImage {
+        key: chunk.key,
+        device_id: chunk.device_id,
+        data,
+        done: true,
+      });
+    }
+  }
+
   /// Returns `false` when the router loop should exit (write error = connection lost).
   async fn handle_command(&mut self, command: RouterCommand) -> bool {
     match command {
       RouterCommand::Send { message } => self.send_message(message).await,
       RouterCommand::SendAwaitResponse {
         message,
-        response_type,
+        filter,
         tx,
       } => {
-        self.pending_single = Some(PendingRequest { response_type, tx });
+        self.prune_cancelled();
+        self.pending_single.push(PendingRequest { filter, tx });
         self.send_message(message).await
       }
       RouterCommand::SendAwaitMultiple {
         message,
-        response_types,
+        filter,
         until_type,
         tx,
       } => {
-        self.pending_multi = Some(PendingMultiRequest {
-          response_types,
+        self.prune_cancelled();
+        self.pending_multi.push(PendingMultiRequest {
+          filter,
           until_type,
           tx,
         });
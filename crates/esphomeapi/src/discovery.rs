@@ -1,16 +1,25 @@
 use std::{
   collections::{HashMap, HashSet},
-  net::Ipv4Addr,
+  io,
+  net::{IpAddr, Ipv6Addr, SocketAddr, SocketAddrV6},
   time::Duration,
 };
 
-use mdns_sd::{ServiceDaemon, ServiceEvent};
-use tracing::{error, info};
+use futures::future::BoxFuture;
+use futures::stream::FuturesUnordered;
+use futures::{FutureExt as _, StreamExt as _};
+use mdns_sd::{ResolvedService, ScopedIp, ServiceDaemon, ServiceEvent};
+use tokio::net::TcpStream;
+use tracing::{debug, error, info};
 
-use crate::Result;
+use crate::transport::{BoxedStream, Transport};
+use crate::{Error, Result};
 
 const SERVICE_NAME: &str = "_esphomelib._tcp.local.";
 
+/// Head start of each connection attempt over the next one (RFC 8305).
+const CONNECTION_ATTEMPT_DELAY: Duration = Duration::from_millis(250);
+
 #[derive(Clone, Debug)]
 pub struct ServiceInfo {
   pub ty_domain: String, // <service>.<domain>
@@ -22,10 +31,58 @@ pub struct ServiceInfo {
   pub fullname: String,              // <instance>.<service>.<domain>
   pub server: String,                // fully qualified name for service host
   pub friendly_name: Option<String>, // friendly name set in the ESPHome yaml config file
-  pub addresses: HashSet<Ipv4Addr>,
+  pub addresses: HashSet<IpAddr>,
+  /// Interface index of each link-local IPv6 address, without which it
+  /// cannot be reached
+  pub scope_ids: HashMap<Ipv6Addr, u32>,
   pub port: u16,
 }
 
+impl ServiceInfo {
+  fn from_resolved(info: &ResolvedService) -> Self {
+    let mut scope_ids = HashMap::new();
+    for address in info.get_addresses() {
+      if let ScopedIp::V6(v6) = address {
+        if v6.addr().is_unicast_link_local() && v6.scope_id().index != 0 {
+          scope_ids.insert(*v6.addr(), v6.scope_id().index);
+        }
+      }
+    }
+
+    ServiceInfo {
+      ty_domain: info.ty_domain.clone(),
+      sub_domain: info.get_subtype().to_owned(),
+      fullname: info.get_fullname().to_owned(),
+      server: node_name(info.get_hostname()).to_owned(),
+      friendly_name: info
+        .get_property_val_str("friendly_name")
+        .map(str::to_string),
+      addresses: info
+        .get_addresses()
+        .iter()
+        .map(ScopedIp::to_ip_addr)
+        .collect(),
+      scope_ids,
+      port: info.get_port(),
+    }
+  }
+
+  /// Every address with the API port, scoped where needed.
+  pub fn socket_addrs(&self) -> Vec<SocketAddr> {
+    self
+      .addresses
+      .iter()
+      .map(|address| match address {
+        IpAddr::V6(v6) => {
+          let scope_id = self.scope_ids.get(v6).copied().unwrap_or_default();
+          SocketAddr::V6(SocketAddrV6::new(*v6, self.port, 0, scope_id))
+        }
+        IpAddr::V4(_) => SocketAddr::new(*address, self.port),
+      })
+      .collect()
+  }
+}
+
 pub async fn discover(seconds: u32) -> Result<Vec<ServiceInfo>> {
   let mdns = ServiceDaemon::new()?;
   let receiver = mdns.browse(SERVICE_NAME)?;
@@ -43,21 +100,7 @@ pub async fn discover(seconds: u32) -> Result<Vec<ServiceInfo>> {
       result = receiver.recv_async() => {
         match result {
           Ok(ServiceEvent::ServiceResolved(info)) => {
-            found_services.insert(
-              info.get_fullname().to_owned(),
-              ServiceInfo {
-                ty_domain: info.ty_domain.clone(),
-                sub_domain: info.get_subtype().to_owned(),
-                fullname: info.get_fullname().to_owned(),
-                server: info.get_hostname()
-                      .trim_end_matches('.')
-                      .trim_end_matches(".local")
-                      .to_owned(),
-                friendly_name: info.get_property_val_str("friendly_name").map(str::to_string),
-                addresses: info.get_addresses_v4(),
-                port: info.get_port()
-              },
-            );
+            found_services.insert(info.get_fullname().to_owned(), ServiceInfo::from_resolved(&info));
           }
           Ok(_) => {}
           Err(err) => {
@@ -79,3 +122,132 @@ pub async fn discover(seconds: u32) -> Result<Vec<ServiceInfo>> {
   info!(services = ?services, "discovery finished");
   Ok(services)
 }
+
+/// Strip the `.local` domain and root dot from a host name, leaving the node
+/// name (`living-room.local.` becomes `living-room`).
+pub(crate) fn node_name(name: &str) -> &str {
+  name.trim_end_matches('.').trim_end_matches(".local")
+}
+
+/// Resolve the node `name` (e.g. `living-room`) through mDNS, returning every
+/// address it advertises with its API port.
+pub async fn resolve(name: &str, timeout: Duration) -> Result<Vec<SocketAddr>> {
+  let name = node_name(name);
+  let fullname = format!("{name}.{SERVICE_NAME}");
+  let hostname = format!("{name}.local.");
+
+  let mdns = ServiceDaemon::new()?;
+  let receiver = mdns.browse(SERVICE_NAME)?;
+
+  let resolved = tokio::time::timeout(timeout, async {
+    loop {
+      match receiver.recv_async().await {
+        Ok(ServiceEvent::ServiceResolved(info))
+          if info.get_fullname().eq_ignore_ascii_case(&fullname)
+            || info.get_hostname().eq_ignore_ascii_case(&hostname) =>
+        {
+          return ServiceInfo::from_resolved(&info).socket_addrs();
+        }
+        Ok(_) => {}
+        Err(err) => {
+          error!(error = ?err, "failed to receive service event");
+          return Vec::new();
+        }
+      }
+    }
+  })
+  .await
+  .unwrap_or_default();
+
+  drop(receiver);
+  if let Err(err) = mdns.shutdown() {
+    error!(error = ?err, "mdns shutdown failed");
+  }
+
+  if resolved.is_empty() {
+    return Err(Error::NotResolved(name.to_string()));
+  }
+  debug!(name, addresses = ?resolved, "resolved node");
+  Ok(resolved)
+}
+
+/// Connect to whichever address answers first, starting the attempts
+/// staggered and alternating between IPv6 and IPv4 ("happy eyeballs").
+pub async fn connect_any(addresses: Vec<SocketAddr>) -> io::Result<TcpStream> {
+  let (v6, v4): (Vec<_>, Vec<_>) = addresses.into_iter().partition(SocketAddr::is_ipv6);
+  let mut order = Vec::with_capacity(v6.len() + v4.len());
+  let (mut v6, mut v4) = (v6.into_iter(), v4.into_iter());
+  loop {
+    match (v6.next(), v4.next()) {
+      (None, None) => break,
+      (first, second) => order.extend(first.into_iter().chain(second)),
+    }
+  }
+  let mut pending = order.into_iter();
+
+  let mut attempts = FuturesUnordered::new();
+  let mut last_error = None;
+  loop {
+    if attempts.is_empty() {
+      match pending.next() {
+        Some(address) => attempts.push(TcpStream::connect(address)),
+        None => {
+          return Err(last_error.unwrap_or_else(|| {
+            io::Error::new(
+              io::ErrorKind::AddrNotAvailable,
+              "no addresses to connect to",
+            )
+          }))
+        }
+      }
+    }
+
+    tokio::select! {
+      Some(result) = attempts.next() => match result {
+        Ok(stream) => return Ok(stream),
+        Err(err) => last_error = Some(err),
+      },
+      _ = tokio::time::sleep(CONNECTION_ATTEMPT_DELAY), if pending.len() > 0 => {
+        attempts.extend(pending.next().map(TcpStream::connect));
+      }
+    }
+  }
+}
+
+/// A [`Transport`] treating the configured host as an ESPHome node name. The
+/// name is resolved through mDNS on every connection attempt, so reconnects
+/// follow the device to a new address. The advertised port takes precedence
+/// over the configured one.
+#[derive(Debug, Clone)]
+pub struct MdnsTransport {
+  /// How long to wait for the node to answer the mDNS query
+  pub resolve_timeout: Duration,
+}
+
+impl Default for MdnsTransport {
+  fn default() -> Self {
+    Self {
+      resolve_timeout: Duration::from_secs(5),
+    }
+  }
+}
+
+impl Transport for MdnsTransport {
+  fn dial<'a>(&'a self, host: &'a str, _port: u32) -> BoxFuture<'a, io::Result<BoxedStream>> {
+    async move {
+      let addresses = resolve(host, self.resolve_timeout)
+        .await
+        .map_err(|err| match err {
+          Error::NotResolved(_) => io::Error::new(io::ErrorKind::NotFound, err),
+          err => io::Error::other(err),
+        })?;
+      let stream = connect_any(addresses).await?;
+      Ok(Box::new(stream) as BoxedStream)
+    }
+    .boxed()
+  }
+
+  fn address(&self, host: &str, _port: u32) -> String {
+    format!("{host} (mDNS)")
+  }
+}
@@ -0,0 +1,109 @@
+use std::time::Duration;
+
+use thiserror::Error;
+
+use crate::connection::codec::CodecError;
+use crate::model::APIVersion;
+
+/// Errors returned by the ESPHome client
+#[derive(Debug, Error)]
+pub enum Error {
+  /// The TCP connection to the device could not be established
+  #[error("Failed to connect to {address}: {source}")]
+  Connect {
+    address: String,
+    #[source]
+    source: std::io::Error,
+  },
+
+  /// The plain or Noise handshake failed (bad PSK, wrong framing, ...)
+  #[error("Handshake failed: {0}")]
+  Handshake(#[from] CodecError),
+
+  /// The device rejected the password
+  #[error("Authentication failed: invalid password")]
+  Authentication,
+
+  /// The device reported a different name than the expected one
+  #[error("Device name mismatch: expected '{expected}', got '{actual}'")]
+  NameMismatch { expected: String, actual: String },
+
+  /// A `ClientBuilder` setting is out of range
+  #[error("Invalid client configuration: {0}")]
+  InvalidConfig(String),
+
+  /// The device did not answer in time
+  #[error("Timed out after {0:?} waiting for a response")]
+  Timeout(Duration),
+
+  /// A protobuf message could not be encoded or decoded
+  #[error("Failed to decode message: {0}")]
+  Decode(#[from] protobuf::Error),
+
+  /// The device sent a message type that was not expected in this context
+  #[error("Unexpected message type: {0}")]
+  UnexpectedMessage(u32),
+
+  /// The router task for the connection has stopped
+  #[error("Connection router closed")]
+  RouterClosed,
+
+  /// The device closed the connection before the request completed
+  #[error("Device disconnected")]
+  Disconnected,
+
+  /// No user service with this key was listed by the device
+  #[error("Unknown service key: {0}")]
+  UnknownService(u32),
+
+  /// The arguments do not match the service's declared signature
+  #[error("Invalid arguments for service '{service}': {reason}")]
+  InvalidServiceArguments { service: String, reason: String },
+
+  /// The device's API version predates the request
+  #[error("{request} is unsupported by device: needs API {required}, device speaks {actual}")]
+  Unsupported {
+    request: String,
+    required: APIVersion,
+    actual: APIVersion,
+  },
+
+  /// The device did not store the new Noise PSK
+  #[error("The device rejected the new Noise PSK")]
+  NoiseKeyRejected,
+
+  /// A Bluetooth proxy could not connect, pair, unpair or clear the cache of a device
+  #[error("Bluetooth {operation} failed for {address:012X}: error {error}")]
+  BluetoothDevice {
+    address: u64,
+    operation: &'static str,
+    error: i32,
+  },
+
+  /// A GATT operation on a remote Bluetooth device failed
+  #[error("GATT operation on handle {handle} of {address:012X} failed: error {error}")]
+  BluetoothGatt {
+    address: u64,
+    handle: u16,
+    error: i32,
+  },
+
+  /// An infrared signal could not be parsed or converted
+  #[error("Invalid infrared signal: {0}")]
+  InvalidInfraredSignal(String),
+
+  /// The IANA timezone is unknown or has no POSIX equivalent
+  #[error("Invalid timezone: {0}")]
+  InvalidTimezone(String),
+
+  /// No node with this name answered the mDNS query
+  #[error("No ESPHome node named '{0}' found via mDNS")]
+  NotResolved(String),
+
+  /// mDNS discovery failed
+  #[error("Discovery failed: {0}")]
+  Discovery(#[from] mdns_sd::Error),
+
+  #[error("IO error: {0}")]
+  Io(#[from] std::io::Error),
+}
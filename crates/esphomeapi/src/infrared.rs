@@ -0,0 +1,384 @@
+//! Infrared/RF signals as raw mark/space timings, and codecs for common
+//! remote-control protocols built on top of them.
+//!
+//! Timings are in microseconds: positive values are marks (LED/TX on),
+//! negative values are spaces (LED/TX off).
+//!
+//! ```ignore
+//! let power = IrCode::Nec { address: 0x04, command: 0x08 }.encode();
+//! client.infrared_transmit(key, &power, 1).await?;
+//!
+//! let mut captures = client.infrared_receive(key);
+//! while let Ok(timings) = captures.recv().await {
+//!   println!("{:?}", IrCode::decode(&timings));
+//! }
+//! ```
+
+use tokio::sync::broadcast;
+use tracing::warn;
+
+use crate::model::InfraredReceiveEvent;
+use crate::{Error, Result};
+
+/// Pronto time base: one carrier cycle lasts `frequency code * PRONTO_CLOCK` µs.
+const PRONTO_CLOCK: f64 = 0.241246;
+/// Gap appended when a signal ending in a mark is written as Pronto, which
+/// only holds mark/space pairs.
+const PRONTO_TRAILING_GAP: i32 = 40_000;
+
+const NEC: PulseDistance = PulseDistance {
+  carrier_frequency: 38_000,
+  header_mark: 9000,
+  header_space: 4500,
+  bit_mark: 560,
+  one_space: 1690,
+  zero_space: 560,
+};
+
+const SAMSUNG: PulseDistance = PulseDistance {
+  header_mark: 4500,
+  header_space: 4500,
+  ..NEC
+};
+
+const RC5_CARRIER_FREQUENCY: u32 = 36_000;
+const RC5_HALF_BIT: i32 = 889;
+const RC5_BITS: usize = 14;
+
+/// A signal ready to transmit: a carrier frequency in Hz (0 for unmodulated
+/// RF) and mark/space timings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InfraredSignal {
+  pub carrier_frequency: u32,
+  pub timings: Vec<i32>,
+}
+
+impl InfraredSignal {
+  /// Parse a learned Pronto hex code (`0000 006D ...`).
+  ///
+  /// The once sequence is used, or the repeat sequence when there is none.
+  pub fn from_pronto(code: &str) -> Result<Self> {
+    let words = code
+      .split_whitespace()
+      .map(|word| u16::from_str_radix(word, 16))
+      .collect::<std::result::Result<Vec<_>, _>>()
+      .map_err(|e| Error::InvalidInfraredSignal(format!("bad Pronto word: {e}")))?;
+
+    let [kind, frequency_code, once_pairs, repeat_pairs, bursts @ ..] = words.as_slice() else {
+      return Err(Error::InvalidInfraredSignal(
+        "Pronto code is shorter than its header".to_string(),
+      ));
+    };
+    if *kind != 0 {
+      return Err(Error::InvalidInfraredSignal(format!(
+        "unsupported Pronto format {kind:04X}, only learned (0000) codes are supported"
+      )));
+    }
+    if *frequency_code == 0 {
+      return Err(Error::InvalidInfraredSignal(
+        "Pronto frequency code is zero".to_string(),
+      ));
+    }
+    let once_len = usize::from(*once_pairs) * 2;
+    if bursts.len() != once_len + usize::from(*repeat_pairs) * 2 {
+      return Err(Error::InvalidInfraredSignal(format!(
+        "Pronto code declares {} burst pairs but has {} words",
+        once_pairs + repeat_pairs,
+        bursts.len()
+      )));
+    }
+
+    let cycle = f64::from(*frequency_code) * PRONTO_CLOCK;
+    let sequence = if once_len > 0 {
+      &bursts[..once_len]
+    } else {
+      &bursts[once_len..]
+    };
+    let timings = sequence
+      .chunks(2)
+      .flat_map(|pair| {
+        let mark = (f64::from(pair[0]) * cycle).round() as i32;
+        let space = (f64::from(pair[1]) * cycle).round() as i32;
+        [mark, -space]
+      })
+      .collect();
+
+    Ok(Self {
+      carrier_frequency: (1_000_000.0 / cycle).round() as u32,
+      timings,
+    })
+  }
+
+  /// Write the signal as a learned Pronto hex code with only a once sequence.
+  pub fn to_pronto(&self) -> Result<String> {
+    if self.carrier_frequency == 0 {
+      return Err(Error::InvalidInfraredSignal(
+        "Pronto codes need a carrier frequency".to_string(),
+      ));
+    }
+    let frequency_code = (1_000_000.0 / (f64::from(self.carrier_frequency) * PRONTO_CLOCK)).round();
+    let cycle = frequency_code * PRONTO_CLOCK;
+
+    let mut timings: Vec<i32> = self
+      .timings
+      .iter()
+      .copied()
+      .skip_while(|t| *t < 0)
+      .collect();
+    if timings.len() % 2 == 1 {
+      timings.push(-PRONTO_TRAILING_GAP);
+    }
+
+    let mut words = vec![0, frequency_code as u32, timings.len() as u32 / 2, 0];
+    words.extend(
+      timings
+        .iter()
+        .map(|t| ((f64::from(t.unsigned_abs()) / cycle).round() as u32).clamp(1, 0xFFFF)),
+    );
+    Ok(
+      words
+        .iter()
+        .map(|word| format!("{word:04X}"))
+        .collect::<Vec<_>>()
+        .join(" "),
+    )
+  }
+}
+
+/// A command in one of the supported remote-control protocols.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IrCode {
+  /// NEC. Addresses up to `0xFF` are sent followed by their inverse; wider
+  /// ones as 16-bit extended NEC.
+  Nec { address: u16, command: u8 },
+  /// Samsung32: the address twice, then the command and its inverse.
+  Samsung { address: u8, command: u8 },
+  /// Philips RC5 with a 5-bit address. Commands from 64 to 127 use the
+  /// RC5X field bit. `toggle` flips on every new key press.
+  Rc5 {
+    address: u8,
+    command: u8,
+    toggle: bool,
+  },
+}
+
+impl IrCode {
+  pub fn encode(&self) -> InfraredSignal {
+    match *self {
+      IrCode::Nec { address, command } => {
+        let address = match u8::try_from(address) {
+          Ok(address) => u32::from(address) | u32::from(!address) << 8,
+          Err(_) => u32::from(address),
+        };
+        NEC.encode(address | u32::from(command) << 16 | u32::from(!command) << 24)
+      }
+      IrCode::Samsung { address, command } => SAMSUNG.encode(
+        u32::from(address)
+          | u32::from(address) << 8
+          | u32::from(command) << 16
+          | u32::from(!command) << 24,
+      ),
+      IrCode::Rc5 {
+        address,
+        command,
+        toggle,
+      } => encode_rc5(address, command, toggle),
+    }
+  }
+
+  /// Recognise a captured signal. Leading spaces and anything after the
+  /// first frame are ignored.
+  pub fn decode(timings: &[i32]) -> Option<Self> {
+    let start = timings.iter().position(|t| *t > 0)?;
+    let timings = &timings[start..];
+
+    if let Some(data) = NEC.decode(timings) {
+      let [address_low, address_high, command, command_inverse] = data.to_le_bytes();
+      if command != !command_inverse {
+        return None;
+      }
+      let address = if address_high == !address_low {
+        u16::from(address_low)
+      } else {
+        u16::from_le_bytes([address_low, address_high])
+      };
+      return Some(IrCode::Nec { address, command });
+    }
+    if let Some(data) = SAMSUNG.decode(timings) {
+      let [address, address_repeat, command, command_inverse] = data.to_le_bytes();
+      if address != address_repeat || command != !command_inverse {
+        return None;
+      }
+      return Some(IrCode::Samsung { address, command });
+    }
+    decode_rc5(timings)
+  }
+}
+
+/// Receives captured timings of one receiver, returned by
+/// [`Client::infrared_receive`](crate::Client::infrared_receive).
+pub struct InfraredReceiver {
+  receiver: broadcast::Receiver<InfraredReceiveEvent>,
+  key: u32,
+}
+
+impl InfraredReceiver {
+  pub(crate) fn new(receiver: broadcast::Receiver<InfraredReceiveEvent>, key: u32) -> Self {
+    Self { receiver, key }
+  }
+
+  /// Wait for the next captured signal.
+  ///
+  /// Fails with [`Error::Disconnected`] once the client is gone.
+  pub async fn recv(&mut self) -> Result<Vec<i32>> {
+    loop {
+      match self.receiver.recv().await {
+        Ok(event) if event.key == self.key => return Ok(event.timings),
+        Ok(_) => {}
+        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+          warn!(skipped, key = self.key, "dropped infrared captures");
+        }
+        Err(broadcast::error::RecvError::Closed) => return Err(Error::Disconnected),
+      }
+    }
+  }
+}
+
+/// Protocols that send a header, then 32 bits LSB first as a fixed mark
+/// followed by a short or long space, then a closing mark.
+struct PulseDistance {
+  carrier_frequency: u32,
+  header_mark: i32,
+  header_space: i32,
+  bit_mark: i32,
+  one_space: i32,
+  zero_space: i32,
+}
+
+impl PulseDistance {
+  fn encode(&self, data: u32) -> InfraredSignal {
+    let mut timings = vec![self.header_mark, -self.header_space];
+    for bit in 0..32 {
+      let space = if data >> bit & 1 == 1 {
+        self.one_space
+      } else {
+        self.zero_space
+      };
+      timings.extend([self.bit_mark, -space]);
+    }
+    timings.push(self.bit_mark);
+    InfraredSignal {
+      carrier_frequency: self.carrier_frequency,
+      timings,
+    }
+  }
+
+  fn decode(&self, timings: &[i32]) -> Option<u32> {
+    if timings.len() < 2 + 32 * 2 + 1
+      || !matches(timings[0], self.header_mark)
+      || !matches(timings[1], -self.header_space)
+    {
+      return None;
+    }
+    let mut data = 0;
+    for (bit, pair) in timings[2..2 + 32 * 2].chunks(2).enumerate() {
+      if !matches(pair[0], self.bit_mark) {
+        return None;
+      }
+      if matches(pair[1], -self.one_space) {
+        data |= 1 << bit;
+      } else if !matches(pair[1], -self.zero_space) {
+        return None;
+      }
+    }
+    matches(timings[2 + 32 * 2], self.bit_mark).then_some(data)
+  }
+}
+
+/// Whether a measured timing is close enough to the expected one. Receivers
+/// stretch marks and shrink spaces, so the tolerance is generous.
+fn matches(actual: i32, expected: i32) -> bool {
+  actual.signum() == expected.signum()
+    && actual.abs_diff(expected) <= expected.unsigned_abs() / 4 + 100
+}
+
+fn encode_rc5(address: u8, command: u8, toggle: bool) -> InfraredSignal {
+  // Start bit, field bit (inverted bit 6 of the command), toggle, 5 address
+  // bits and 6 command bits, MSB first
+  let frame = 1 << 13
+    | u16::from(command & 0x40 == 0) << 12
+    | u16::from(toggle) << 11
+    | u16::from(address & 0x1F) << 6
+    | u16::from(command & 0x3F);
+
+  // Manchester coding: a one is a space then a mark, a zero the reverse
+  let mut timings: Vec<i32> = Vec::new();
+  for bit in (0..RC5_BITS).rev() {
+    let halves = if frame >> bit & 1 == 1 {
+      [-RC5_HALF_BIT, RC5_HALF_BIT]
+    } else {
+      [RC5_HALF_BIT, -RC5_HALF_BIT]
+    };
+    for half in halves {
+      match timings.last_mut() {
+        Some(last) if last.signum() == half.signum() => *last += half,
+        _ => timings.push(half),
+      }
+    }
+  }
+  // The leading space of the start bit and a trailing space carry nothing
+  if timings.first().is_some_and(|t| *t < 0) {
+    timings.remove(0);
+  }
+  if timings.last().is_some_and(|t| *t < 0) {
+    timings.pop();
+  }
+  InfraredSignal {
+    carrier_frequency: RC5_CARRIER_FREQUENCY,
+    timings,
+  }
+}
+
+fn decode_rc5(timings: &[i32]) -> Option<IrCode> {
+  // Expand into half-bit levels; the start bit's leading space is implicit
+  let mut halves = vec![false];
+  for &timing in timings {
+    if halves.len() >= RC5_BITS * 2 {
+      break;
+    }
+    let count = if matches(timing.abs(), RC5_HALF_BIT) {
+      1
+    } else if matches(timing.abs(), 2 * RC5_HALF_BIT) {
+      2
+    } else {
+      break;
+    };
+    halves.extend(std::iter::repeat_n(timing > 0, count));
+  }
+  // A trailing zero ends in a space that merges with the gap after the frame
+  if halves.len() == RC5_BITS * 2 - 1 {
+    halves.push(false);
+  }
+  if halves.len() < RC5_BITS * 2 {
+    return None;
+  }
+
+  let mut frame: u16 = 0;
+  for pair in halves[..RC5_BITS * 2].chunks(2) {
+    let bit = match (pair[0], pair[1]) {
+      (false, true) => 1,
+      (true, false) => 0,
+      _ => return None,
+    };
+    frame = frame << 1 | bit;
+  }
+  if frame >> 13 != 1 {
+    return None;
+  }
+  let field = frame >> 12 & 1;
+  Some(IrCode::Rc5 {
+    address: (frame >> 6 & 0x1F) as u8,
+    command: (frame & 0x3F) as u8 | if field == 0 { 0x40 } else { 0 },
+    toggle: frame >> 11 & 1 == 1,
+  })
+}
@@ -4,15 +4,28 @@ mod proto {
 
 pub use proto::api;
 
+pub mod bluetooth;
+mod builder;
+pub mod camera;
 mod client;
 mod command_handle;
 mod connection;
 pub mod discovery;
+mod error;
+pub mod infrared;
 pub mod model;
+pub mod server;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod time;
+pub mod transport;
 mod utils;
+pub mod zwave;
 
+pub use builder::{BufferSizes, ClientBuilder, ReconnectPolicy};
 pub use client::Client;
 pub use command_handle::CommandHandle;
+pub use connection::codec::{self, generate_noise_psk, CodecError};
+pub use error::Error;
 
-pub type Error = Box<dyn std::error::Error + Send + Sync>;
 pub type Result<T> = std::result::Result<T, Error>;
@@ -1,12 +1,16 @@
 use std::collections::HashMap;
 
-use super::{entity_state::EntityState, EntityInfo};
+use super::{entity_state::EntityState, APIVersion, EntityInfo};
+use crate::proto::api_options::exts::id;
 use crate::{proto::api, utils::Options as _, Result};
 
+type EntityInfoParser = fn(&[u8]) -> Result<EntityInfo>;
+type EntityStateParser = fn(&[u8]) -> Result<EntityState>;
+
 lazy_static::lazy_static! {
-    pub static ref LIST_ENTITIES_SERVICES_RESPONSE_TYPES: HashMap<u32, fn(&[u8]) -> Result<EntityInfo>> = {
+    pub static ref LIST_ENTITIES_SERVICES_RESPONSE_TYPES: HashMap<u32, EntityInfoParser> = {
         let mut m = HashMap::new();
-        m.insert(api::ListEntitiesAlarmControlPanelResponse::get_option_id(), EntityInfo::parse_alarm_control_panel as fn(&[u8]) -> Result<EntityInfo>);
+        m.insert(api::ListEntitiesAlarmControlPanelResponse::get_option_id(), EntityInfo::parse_alarm_control_panel as EntityInfoParser);
         m.insert(api::ListEntitiesBinarySensorResponse::get_option_id(), EntityInfo::parse_binary_sensor);
         m.insert(api::ListEntitiesButtonResponse::get_option_id(), EntityInfo::parse_button);
         m.insert(api::ListEntitiesCameraResponse::get_option_id(), EntityInfo::parse_camera);
@@ -16,24 +20,56 @@ lazy_static::lazy_static! {
         m.insert(api::ListEntitiesDateTimeResponse::get_option_id(), EntityInfo::parse_date_time);
         m.insert(api::ListEntitiesEventResponse::get_option_id(), EntityInfo::parse_event);
         m.insert(api::ListEntitiesFanResponse::get_option_id(), EntityInfo::parse_fan);
+        m.insert(api::ListEntitiesInfraredResponse::get_option_id(), EntityInfo::parse_infrared);
         m.insert(api::ListEntitiesLightResponse::get_option_id(), EntityInfo::parse_light);
         m.insert(api::ListEntitiesLockResponse::get_option_id(), EntityInfo::parse_lock);
         m.insert(api::ListEntitiesMediaPlayerResponse::get_option_id(), EntityInfo::parse_media_player);
         m.insert(api::ListEntitiesNumberResponse::get_option_id(), EntityInfo::parse_number);
         m.insert(api::ListEntitiesSelectResponse::get_option_id(), EntityInfo::parse_select);
         m.insert(api::ListEntitiesSensorResponse::get_option_id(), EntityInfo::parse_sensor);
+        m.insert(api::ListEntitiesSirenResponse::get_option_id(), EntityInfo::parse_siren);
         m.insert(api::ListEntitiesSwitchResponse::get_option_id(), EntityInfo::parse_switch);
         m.insert(api::ListEntitiesTextResponse::get_option_id(), EntityInfo::parse_text);
         m.insert(api::ListEntitiesTextSensorResponse::get_option_id(), EntityInfo::parse_text_sensor);
         m.insert(api::ListEntitiesTimeResponse::get_option_id(), EntityInfo::parse_time);
         m.insert(api::ListEntitiesUpdateResponse::get_option_id(), EntityInfo::parse_update);
         m.insert(api::ListEntitiesValveResponse::get_option_id(), EntityInfo::parse_valve);
+        m.insert(api::ListEntitiesWaterHeaterResponse::get_option_id(), EntityInfo::parse_water_heater);
+        m
+    };
+
+    /// Ids of every `ListEntities*Response` in the protocol, including entity
+    /// types without a parser, so a listing can consume and skip them.
+    pub static ref LIST_ENTITIES_RESPONSE_IDS: Vec<u32> = api::file_descriptor()
+        .messages()
+        .filter(|message| {
+            let name = message.name();
+            name.starts_with("ListEntities")
+                && name.ends_with("Response")
+                && name != "ListEntitiesDoneResponse"
+        })
+        .filter_map(|message| id.get(message.proto().options.as_ref()?))
+        .collect();
+
+    /// Requests that older devices do not understand, with the first API
+    /// version that can support them. Devices silently drop unknown messages,
+    /// so these are refused before they are sent. Only requests with a
+    /// documented lower bound are listed.
+    pub static ref MINIMUM_API_VERSIONS: HashMap<u32, APIVersion> = {
+        let mut m = HashMap::new();
+        // Announcements and wake word configuration are advertised through
+        // `voice_assistant_feature_flags`, which replaced
+        // `legacy_voice_assistant_version` in API 1.10 (see `DeviceInfoResponse`
+        // in api.proto)
+        m.insert(api::VoiceAssistantAnnounceRequest::get_option_id(), APIVersion::new(1, 10));
+        m.insert(api::VoiceAssistantConfigurationRequest::get_option_id(), APIVersion::new(1, 10));
+        m.insert(api::VoiceAssistantSetConfiguration::get_option_id(), APIVersion::new(1, 10));
         m
     };
 
-    pub static ref SUBCRIBE_STATES_RESPONSE_TYPES: HashMap<u32, fn(&[u8]) -> Result<EntityState>> = {
+    pub static ref SUBCRIBE_STATES_RESPONSE_TYPES: HashMap<u32, EntityStateParser> = {
         let mut m = HashMap::new();
-        m.insert(api::AlarmControlPanelStateResponse::get_option_id(), EntityState::parse_alarm_control_panel as fn(&[u8]) -> Result<EntityState>);
+        m.insert(api::AlarmControlPanelStateResponse::get_option_id(), EntityState::parse_alarm_control_panel as EntityStateParser);
         m.insert(api::BinarySensorStateResponse::get_option_id(), EntityState::parse_binary_sensor);
         m.insert(api::ClimateStateResponse::get_option_id(), EntityState::parse_climate);
         m.insert(api::CoverStateResponse::get_option_id(), EntityState::parse_cover);
@@ -47,12 +83,14 @@ lazy_static::lazy_static! {
         m.insert(api::NumberStateResponse::get_option_id(), EntityState::parse_number);
         m.insert(api::SelectStateResponse::get_option_id(), EntityState::parse_select);
         m.insert(api::SensorStateResponse::get_option_id(), EntityState::parse_sensor);
+        m.insert(api::SirenStateResponse::get_option_id(), EntityState::parse_siren);
         m.insert(api::SwitchStateResponse::get_option_id(), EntityState::parse_switch);
         m.insert(api::TextStateResponse::get_option_id(), EntityState::parse_text);
         m.insert(api::TextSensorStateResponse::get_option_id(), EntityState::parse_text_sensor);
         m.insert(api::TimeStateResponse::get_option_id(), EntityState::parse_time);
         m.insert(api::UpdateStateResponse::get_option_id(), EntityState::parse_update);
         m.insert(api::ValveStateResponse::get_option_id(), EntityState::parse_valve);
+        m.insert(api::WaterHeaterStateResponse::get_option_id(), EntityState::parse_water_heater);
         m
     };
 }
@@ -1,9 +1,12 @@
+use enumflags2::BitFlags;
 use protobuf::Message as _;
 
 use super::services;
+use crate::connection::ProtobufMessage;
+use crate::utils::to_message;
 use crate::{api, Result};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum EntityInfo {
   AlarmControlPanel(services::AlarmControlPanelInfo),
   BinarySensor(services::BinarySensorInfo),
@@ -15,45 +18,497 @@ pub enum EntityInfo {
   DateTime(services::DateTimeInfo),
   Event(services::EventInfo),
   Fan(services::FanInfo),
+  Infrared(services::InfraredInfo),
   Light(services::LightInfo),
   Lock(services::LockInfo),
   MediaPlayer(services::MediaPlayerInfo),
   Number(services::NumberInfo),
   Select(services::SelectInfo),
   Sensor(services::SensorInfo),
+  Siren(services::SirenInfo),
   Switch(services::SwitchInfo),
   Text(services::TextInfo),
   TextSensor(services::TextSensorInfo),
   Time(services::TimeInfo),
   Update(services::UpdateInfo),
   Valve(services::ValveInfo),
+  WaterHeater(services::WaterHeaterInfo),
 }
 
 impl EntityInfo {
   pub fn key(&self) -> u32 {
+    self.entity_info().key
+  }
+
+  /// Sub-device of the entity, 0 for the node itself.
+  pub fn device_id(&self) -> u32 {
+    self.entity_info().device_id
+  }
+
+  pub fn entity_info(&self) -> &services::BaseEntityInfo {
+    match self {
+      EntityInfo::AlarmControlPanel(info) => &info.entity_info,
+      EntityInfo::BinarySensor(info) => &info.entity_info,
+      EntityInfo::Button(info) => &info.entity_info,
+      EntityInfo::Camera(info) => &info.entity_info,
+      EntityInfo::Climate(info) => &info.entity_info,
+      EntityInfo::Cover(info) => &info.entity_info,
+      EntityInfo::Date(info) => &info.entity_info,
+      EntityInfo::DateTime(info) => &info.entity_info,
+      EntityInfo::Event(info) => &info.entity_info,
+      EntityInfo::Fan(info) => &info.entity_info,
+      EntityInfo::Infrared(info) => &info.entity_info,
+      EntityInfo::Light(info) => &info.entity_info,
+      EntityInfo::Lock(info) => &info.entity_info,
+      EntityInfo::MediaPlayer(info) => &info.entity_info,
+      EntityInfo::Number(info) => &info.entity_info,
+      EntityInfo::Select(info) => &info.entity_info,
+      EntityInfo::Sensor(info) => &info.entity_info,
+      EntityInfo::Siren(info) => &info.entity_info,
+      EntityInfo::Switch(info) => &info.entity_info,
+      EntityInfo::Text(info) => &info.entity_info,
+      EntityInfo::TextSensor(info) => &info.entity_info,
+      EntityInfo::Time(info) => &info.entity_info,
+      EntityInfo::Update(info) => &info.entity_info,
+      EntityInfo::Valve(info) => &info.entity_info,
+      EntityInfo::WaterHeater(info) => &info.entity_info,
+    }
+  }
+
+  /// Encode the entity as the `ListEntities*Response` a device announces it
+  /// with.
+  pub fn encode(self) -> Result<ProtobufMessage> {
     match self {
-      EntityInfo::AlarmControlPanel(info) => info.entity_info.key,
-      EntityInfo::BinarySensor(info) => info.entity_info.key,
-      EntityInfo::Button(info) => info.entity_info.key,
-      EntityInfo::Camera(info) => info.entity_info.key,
-      EntityInfo::Climate(info) => info.entity_info.key,
-      EntityInfo::Cover(info) => info.entity_info.key,
-      EntityInfo::Date(info) => info.entity_info.key,
-      EntityInfo::DateTime(info) => info.entity_info.key,
-      EntityInfo::Event(info) => info.entity_info.key,
-      EntityInfo::Fan(info) => info.entity_info.key,
-      EntityInfo::Light(info) => info.entity_info.key,
-      EntityInfo::Lock(info) => info.entity_info.key,
-      EntityInfo::MediaPlayer(info) => info.entity_info.key,
-      EntityInfo::Number(info) => info.entity_info.key,
-      EntityInfo::Select(info) => info.entity_info.key,
-      EntityInfo::Sensor(info) => info.entity_info.key,
-      EntityInfo::Switch(info) => info.entity_info.key,
-      EntityInfo::Text(info) => info.entity_info.key,
-      EntityInfo::TextSensor(info) => info.entity_info.key,
-      EntityInfo::Time(info) => info.entity_info.key,
-      EntityInfo::Update(info) => info.entity_info.key,
-      EntityInfo::Valve(info) => info.entity_info.key,
+      EntityInfo::AlarmControlPanel(info) => {
+        let entity_info = info.entity_info;
+        to_message(&api::ListEntitiesAlarmControlPanelResponse {
+          object_id: entity_info.object_id,
+          key: entity_info.key,
+          device_id: entity_info.device_id,
+          name: entity_info.name,
+          disabled_by_default: entity_info.disabled_by_default,
+          icon: entity_info.icon,
+          entity_category: api::EntityCategory::from(entity_info.entity_category).into(),
+          supported_features: info.supported_features,
+          requires_code: info.requires_code,
+          requires_code_to_arm: info.requires_code_to_arm,
+          ..Default::default()
+        })
+      }
+      EntityInfo::BinarySensor(info) => {
+        let entity_info = info.entity_info;
+        to_message(&api::ListEntitiesBinarySensorResponse {
+          object_id: entity_info.object_id,
+          key: entity_info.key,
+          device_id: entity_info.device_id,
+          name: entity_info.name,
+          disabled_by_default: entity_info.disabled_by_default,
+          icon: entity_info.icon,
+          entity_category: api::EntityCategory::from(entity_info.entity_category).into(),
+          device_class: info.device_class,
+          is_status_binary_sensor: info.is_status_binary_sensor,
+          ..Default::default()
+        })
+      }
+      EntityInfo::Button(info) => {
+        let entity_info = info.entity_info;
+        to_message(&api::ListEntitiesButtonResponse {
+          object_id: entity_info.object_id,
+          key: entity_info.key,
+          device_id: entity_info.device_id,
+          name: entity_info.name,
+          disabled_by_default: entity_info.disabled_by_default,
+          icon: entity_info.icon,
+          entity_category: api::EntityCategory::from(entity_info.entity_category).into(),
+          device_class: info.device_class,
+          ..Default::default()
+        })
+      }
+      EntityInfo::Camera(services::CameraInfo { entity_info }) => {
+        to_message(&api::ListEntitiesCameraResponse {
+          object_id: entity_info.object_id,
+          key: entity_info.key,
+          device_id: entity_info.device_id,
+          name: entity_info.name,
+          disabled_by_default: entity_info.disabled_by_default,
+          icon: entity_info.icon,
+          entity_category: api::EntityCategory::from(entity_info.entity_category).into(),
+          ..Default::default()
+        })
+      }
+      EntityInfo::Climate(info) => {
+        let entity_info = info.entity_info;
+        to_message(&api::ListEntitiesClimateResponse {
+          object_id: entity_info.object_id,
+          key: entity_info.key,
+          device_id: entity_info.device_id,
+          name: entity_info.name,
+          disabled_by_default: entity_info.disabled_by_default,
+          icon: entity_info.icon,
+          entity_category: api::EntityCategory::from(entity_info.entity_category).into(),
+          supports_current_temperature: info.supports_current_temperature,
+          supports_two_point_target_temperature: info.supports_two_point_target_temperature,
+          supported_modes: info
+            .supported_modes
+            .into_iter()
+            .map(|v| api::ClimateMode::from(v).into())
+            .collect(),
+          visual_min_temperature: info.visual_min_temperature,
+          visual_max_temperature: info.visual_max_temperature,
+          visual_target_temperature_step: info.visual_target_temperature_step,
+          visual_current_temperature_step: info.visual_current_temperature_step,
+          legacy_supports_away: info.legacy_supports_away,
+          supports_action: info.supports_action,
+          supported_fan_modes: info
+            .supported_fan_modes
+            .into_iter()
+            .map(|v| api::ClimateFanMode::from(v).into())
+            .collect(),
+          supported_swing_modes: info
+            .supported_swing_modes
+            .into_iter()
+            .map(|v| api::ClimateSwingMode::from(v).into())
+            .collect(),
+          supported_custom_fan_modes: info.supported_custom_fan_modes,
+          supported_presets: info
+            .supported_presets
+            .into_iter()
+            .map(|v| api::ClimatePreset::from(v).into())
+            .collect(),
+          supported_custom_presets: info.supported_custom_presets,
+          supports_current_humidity: info.supports_current_humidity,
+          supports_target_humidity: info.supports_target_humidity,
+          visual_min_humidity: info.visual_min_humidity,
+          visual_max_humidity: info.visual_max_humidity,
+          ..Default::default()
+        })
+      }
+      EntityInfo::Cover(info) => {
+        let entity_info = info.entity_info;
+        to_message(&api::ListEntitiesCoverResponse {
+          object_id: entity_info.object_id,
+          key: entity_info.key,
+          device_id: entity_info.device_id,
+          name: entity_info.name,
+          disabled_by_default: entity_info.disabled_by_default,
+          icon: entity_info.icon,
+          entity_category: api::EntityCategory::from(entity_info.entity_category).into(),
+          assumed_state: info.assumed_state,
+          supports_stop: info.supports_stop,
+          supports_position: info.supports_position,
+          supports_tilt: info.supports_tilt,
+          device_class: info.device_class,
+          ..Default::default()
+        })
+      }
+      EntityInfo::Date(services::DateInfo { entity_info }) => {
+        to_message(&api::ListEntitiesDateResponse {
+          object_id: entity_info.object_id,
+          key: entity_info.key,
+          device_id: entity_info.device_id,
+          name: entity_info.name,
+          disabled_by_default: entity_info.disabled_by_default,
+          icon: entity_info.icon,
+          entity_category: api::EntityCategory::from(entity_info.entity_category).into(),
+          ..Default::default()
+        })
+      }
+      EntityInfo::DateTime(services::DateTimeInfo { entity_info }) => {
+        to_message(&api::ListEntitiesDateTimeResponse {
+          object_id: entity_info.object_id,
+          key: entity_info.key,
+          device_id: entity_info.device_id,
+          name: entity_info.name,
+          disabled_by_default: entity_info.disabled_by_default,
+          icon: entity_info.icon,
+          entity_category: api::EntityCategory::from(entity_info.entity_category).into(),
+          ..Default::default()
+        })
+      }
+      EntityInfo::Event(info) => {
+        let entity_info = info.entity_info;
+        to_message(&api::ListEntitiesEventResponse {
+          object_id: entity_info.object_id,
+          key: entity_info.key,
+          device_id: entity_info.device_id,
+          name: entity_info.name,
+          disabled_by_default: entity_info.disabled_by_default,
+          icon: entity_info.icon,
+          entity_category: api::EntityCategory::from(entity_info.entity_category).into(),
+          device_class: info.device_class,
+          event_types: info.event_types,
+          ..Default::default()
+        })
+      }
+      EntityInfo::Fan(info) => {
+        let entity_info = info.entity_info;
+        to_message(&api::ListEntitiesFanResponse {
+          object_id: entity_info.object_id,
+          key: entity_info.key,
+          device_id: entity_info.device_id,
+          name: entity_info.name,
+          disabled_by_default: entity_info.disabled_by_default,
+          icon: entity_info.icon,
+          entity_category: api::EntityCategory::from(entity_info.entity_category).into(),
+          supports_oscillation: info.supports_oscillation,
+          supports_speed: info.supports_speed,
+          supports_direction: info.supports_direction,
+          supported_speed_count: info.supported_speed_count,
+          supported_preset_modes: info.supported_preset_modes,
+          ..Default::default()
+        })
+      }
+      EntityInfo::Infrared(info) => {
+        let entity_info = info.entity_info;
+        to_message(&api::ListEntitiesInfraredResponse {
+          object_id: entity_info.object_id,
+          key: entity_info.key,
+          device_id: entity_info.device_id,
+          name: entity_info.name,
+          disabled_by_default: entity_info.disabled_by_default,
+          icon: entity_info.icon,
+          entity_category: api::EntityCategory::from(entity_info.entity_category).into(),
+          capabilities: info.capabilities.bits(),
+          ..Default::default()
+        })
+      }
+      EntityInfo::Light(info) => {
+        let entity_info = info.entity_info;
+        to_message(&api::ListEntitiesLightResponse {
+          object_id: entity_info.object_id,
+          key: entity_info.key,
+          device_id: entity_info.device_id,
+          name: entity_info.name,
+          disabled_by_default: entity_info.disabled_by_default,
+          icon: entity_info.icon,
+          entity_category: api::EntityCategory::from(entity_info.entity_category).into(),
+          supported_color_modes: info
+            .supported_color_modes
+            .into_iter()
+            .map(|v| api::ColorMode::from(v).into())
+            .collect(),
+          min_mireds: info.min_mireds,
+          max_mireds: info.max_mireds,
+          effects: info.effects,
+          legacy_supports_brightness: info.legacy_supports_brightness,
+          legacy_supports_rgb: info.legacy_supports_rgb,
+          legacy_supports_white_value: info.legacy_supports_white_value,
+          legacy_supports_color_temperature: info.legacy_supports_color_temperature,
+          ..Default::default()
+        })
+      }
+      EntityInfo::Lock(info) => {
+        let entity_info = info.entity_info;
+        to_message(&api::ListEntitiesLockResponse {
+          object_id: entity_info.object_id,
+          key: entity_info.key,
+          device_id: entity_info.device_id,
+          name: entity_info.name,
+          disabled_by_default: entity_info.disabled_by_default,
+          icon: entity_info.icon,
+          entity_category: api::EntityCategory::from(entity_info.entity_category).into(),
+          supports_open: info.supports_open,
+          assumed_state: info.assumed_state,
+          requires_code: info.requires_code,
+          code_format: info.code_format,
+          ..Default::default()
+        })
+      }
+      EntityInfo::MediaPlayer(info) => {
+        let entity_info = info.entity_info;
+        to_message(&api::ListEntitiesMediaPlayerResponse {
+          object_id: entity_info.object_id,
+          key: entity_info.key,
+          device_id: entity_info.device_id,
+          name: entity_info.name,
+          disabled_by_default: entity_info.disabled_by_default,
+          icon: entity_info.icon,
+          entity_category: api::EntityCategory::from(entity_info.entity_category).into(),
+          supports_pause: info.supports_pause,
+          supported_formats: info.supported_formats.into_iter().map(Into::into).collect(),
+          ..Default::default()
+        })
+      }
+      EntityInfo::Number(info) => {
+        let entity_info = info.entity_info;
+        to_message(&api::ListEntitiesNumberResponse {
+          object_id: entity_info.object_id,
+          key: entity_info.key,
+          device_id: entity_info.device_id,
+          name: entity_info.name,
+          disabled_by_default: entity_info.disabled_by_default,
+          icon: entity_info.icon,
+          entity_category: api::EntityCategory::from(entity_info.entity_category).into(),
+          min_value: info.min_value,
+          max_value: info.max_value,
+          step: info.step,
+          unit_of_measurement: info.unit_of_measurement,
+          mode: api::NumberMode::from(info.mode).into(),
+          device_class: info.device_class,
+          ..Default::default()
+        })
+      }
+      EntityInfo::Select(info) => {
+        let entity_info = info.entity_info;
+        to_message(&api::ListEntitiesSelectResponse {
+          object_id: entity_info.object_id,
+          key: entity_info.key,
+          device_id: entity_info.device_id,
+          name: entity_info.name,
+          disabled_by_default: entity_info.disabled_by_default,
+          icon: entity_info.icon,
+          entity_category: api::EntityCategory::from(entity_info.entity_category).into(),
+          options: info.options,
+          ..Default::default()
+        })
+      }
+      EntityInfo::Sensor(info) => {
+        let entity_info = info.entity_info;
+        to_message(&api::ListEntitiesSensorResponse {
+          object_id: entity_info.object_id,
+          key: entity_info.key,
+          device_id: entity_info.device_id,
+          name: entity_info.name,
+          disabled_by_default: entity_info.disabled_by_default,
+          icon: entity_info.icon,
+          entity_category: api::EntityCategory::from(entity_info.entity_category).into(),
+          device_class: info.device_class,
+          unit_of_measurement: info.unit_of_measurement,
+          accuracy_decimals: info.accuracy_decimals,
+          force_update: info.force_update,
+          state_class: api::SensorStateClass::from(info.state_class).into(),
+          legacy_last_reset_type: api::SensorLastResetType::from(info.legacy_last_reset_type)
+            .into(),
+          ..Default::default()
+        })
+      }
+      EntityInfo::Siren(info) => {
+        let entity_info = info.entity_info;
+        to_message(&api::ListEntitiesSirenResponse {
+          object_id: entity_info.object_id,
+          key: entity_info.key,
+          device_id: entity_info.device_id,
+          name: entity_info.name,
+          disabled_by_default: entity_info.disabled_by_default,
+          icon: entity_info.icon,
+          entity_category: api::EntityCategory::from(entity_info.entity_category).into(),
+          tones: info.tones,
+          supports_volume: info.supports_volume,
+          supports_duration: info.supports_duration,
+          ..Default::default()
+        })
+      }
+      EntityInfo::Switch(info) => {
+        let entity_info = info.entity_info;
+        to_message(&api::ListEntitiesSwitchResponse {
+          object_id: entity_info.object_id,
+          key: entity_info.key,
+          device_id: entity_info.device_id,
+          name: entity_info.name,
+          disabled_by_default: entity_info.disabled_by_default,
+          icon: entity_info.icon,
+          entity_category: api::EntityCategory::from(entity_info.entity_category).into(),
+          assumed_state: info.assumed_state,
+          device_class: info.device_class,
+          ..Default::default()
+        })
+      }
+      EntityInfo::Text(info) => {
+        let entity_info = info.entity_info;
+        to_message(&api::ListEntitiesTextResponse {
+          object_id: entity_info.object_id,
+          key: entity_info.key,
+          device_id: entity_info.device_id,
+          name: entity_info.name,
+          disabled_by_default: entity_info.disabled_by_default,
+          icon: entity_info.icon,
+          entity_category: api::EntityCategory::from(entity_info.entity_category).into(),
+          min_length: info.min_length,
+          max_length: info.max_length,
+          pattern: info.pattern,
+          mode: api::TextMode::from(info.mode).into(),
+          ..Default::default()
+        })
+      }
+      EntityInfo::TextSensor(info) => {
+        let entity_info = info.entity_info;
+        to_message(&api::ListEntitiesTextSensorResponse {
+          object_id: entity_info.object_id,
+          key: entity_info.key,
+          device_id: entity_info.device_id,
+          name: entity_info.name,
+          disabled_by_default: entity_info.disabled_by_default,
+          icon: entity_info.icon,
+          entity_category: api::EntityCategory::from(entity_info.entity_category).into(),
+          device_class: info.device_class,
+          ..Default::default()
+        })
+      }
+      EntityInfo::Time(services::TimeInfo { entity_info }) => {
+        to_message(&api::ListEntitiesTimeResponse {
+          object_id: entity_info.object_id,
+          key: entity_info.key,
+          device_id: entity_info.device_id,
+          name: entity_info.name,
+          disabled_by_default: entity_info.disabled_by_default,
+          icon: entity_info.icon,
+          entity_category: api::EntityCategory::from(entity_info.entity_category).into(),
+          ..Default::default()
+        })
+      }
+      EntityInfo::Update(info) => {
+        let entity_info = info.entity_info;
+        to_message(&api::ListEntitiesUpdateResponse {
+          object_id: entity_info.object_id,
+          key: entity_info.key,
+          device_id: entity_info.device_id,
+          name: entity_info.name,
+          disabled_by_default: entity_info.disabled_by_default,
+          icon: entity_info.icon,
+          entity_category: api::EntityCategory::from(entity_info.entity_category).into(),
+          device_class: info.device_class,
+          ..Default::default()
+        })
+      }
+      EntityInfo::Valve(info) => {
+        let entity_info = info.entity_info;
+        to_message(&api::ListEntitiesValveResponse {
+          object_id: entity_info.object_id,
+          key: entity_info.key,
+          device_id: entity_info.device_id,
+          name: entity_info.name,
+          disabled_by_default: entity_info.disabled_by_default,
+          icon: entity_info.icon,
+          entity_category: api::EntityCategory::from(entity_info.entity_category).into(),
+          device_class: info.device_class,
+          assumed_state: info.assumed_state,
+          supports_stop: info.supports_stop,
+          supports_position: info.supports_position,
+          ..Default::default()
+        })
+      }
+      EntityInfo::WaterHeater(info) => {
+        let entity_info = info.entity_info;
+        to_message(&api::ListEntitiesWaterHeaterResponse {
+          object_id: entity_info.object_id,
+          key: entity_info.key,
+          device_id: entity_info.device_id,
+          name: entity_info.name,
+          disabled_by_default: entity_info.disabled_by_default,
+          icon: entity_info.icon,
+          entity_category: api::EntityCategory::from(entity_info.entity_category).into(),
+          min_temperature: info.min_temperature,
+          max_temperature: info.max_temperature,
+          target_temperature_step: info.target_temperature_step,
+          supported_modes: info
+            .supported_modes
+            .into_iter()
+            .map(|v| api::WaterHeaterMode::from(v).into())
+            .collect(),
+          supported_features: info.supported_features,
+          ..Default::default()
+        })
+      }
     }
   }
 
@@ -65,6 +520,7 @@ impl EntityInfo {
       entity_category: data.entity_category.enum_value_or_default().into(),
       object_id: data.object_id,
       key: data.key,
+      device_id: data.device_id,
       name: data.name,
       icon: data.icon,
     };
@@ -87,6 +543,7 @@ impl EntityInfo {
       entity_category: data.entity_category.enum_value_or_default().into(),
       object_id: data.object_id,
       key: data.key,
+      device_id: data.device_id,
       name: data.name,
       icon: data.icon,
     };
@@ -106,6 +563,7 @@ impl EntityInfo {
       entity_category: data.entity_category.enum_value_or_default().into(),
       object_id: data.object_id,
       key: data.key,
+      device_id: data.device_id,
       name: data.name,
       icon: data.icon,
     };
@@ -124,6 +582,7 @@ impl EntityInfo {
       entity_category: data.entity_category.enum_value_or_default().into(),
       object_id: data.object_id,
       key: data.key,
+      device_id: data.device_id,
       name: data.name,
       icon: data.icon,
     };
@@ -139,6 +598,7 @@ impl EntityInfo {
       entity_category: data.entity_category.enum_value_or_default().into(),
       object_id: data.object_id,
       key: data.key,
+      device_id: data.device_id,
       name: data.name,
       icon: data.icon,
     };
@@ -190,6 +650,7 @@ impl EntityInfo {
       entity_category: data.entity_category.enum_value_or_default().into(),
       object_id: data.object_id,
       key: data.key,
+      device_id: data.device_id,
       name: data.name,
       icon: data.icon,
     };
@@ -212,6 +673,7 @@ impl EntityInfo {
       entity_category: data.entity_category.enum_value_or_default().into(),
       object_id: data.object_id,
       key: data.key,
+      device_id: data.device_id,
       name: data.name,
       icon: data.icon,
     };
@@ -227,6 +689,7 @@ impl EntityInfo {
       entity_category: data.entity_category.enum_value_or_default().into(),
       object_id: data.object_id,
       key: data.key,
+      device_id: data.device_id,
       name: data.name,
       icon: data.icon,
     };
@@ -242,6 +705,7 @@ impl EntityInfo {
       entity_category: data.entity_category.enum_value_or_default().into(),
       object_id: data.object_id,
       key: data.key,
+      device_id: data.device_id,
       name: data.name,
       icon: data.icon,
     };
@@ -261,6 +725,7 @@ impl EntityInfo {
       entity_category: data.entity_category.enum_value_or_default().into(),
       object_id: data.object_id,
       key: data.key,
+      device_id: data.device_id,
       name: data.name,
       icon: data.icon,
     };
@@ -275,6 +740,25 @@ impl EntityInfo {
     }))
   }
 
+  pub fn parse_infrared(data: &[u8]) -> Result<Self> {
+    let data = api::ListEntitiesInfraredResponse::parse_from_bytes(data)?;
+
+    let entity_info = services::BaseEntityInfo {
+      disabled_by_default: data.disabled_by_default,
+      entity_category: data.entity_category.enum_value_or_default().into(),
+      object_id: data.object_id,
+      key: data.key,
+      device_id: data.device_id,
+      name: data.name,
+      icon: data.icon,
+    };
+
+    Ok(EntityInfo::Infrared(services::InfraredInfo {
+      entity_info,
+      capabilities: BitFlags::from_bits_truncate(data.capabilities),
+    }))
+  }
+
   pub fn parse_light(data: &[u8]) -> Result<Self> {
     let data = api::ListEntitiesLightResponse::parse_from_bytes(data)?;
 
@@ -283,6 +767,7 @@ impl EntityInfo {
       entity_category: data.entity_category.enum_value_or_default().into(),
       object_id: data.object_id,
       key: data.key,
+      device_id: data.device_id,
       name: data.name,
       icon: data.icon,
     };
@@ -312,6 +797,7 @@ impl EntityInfo {
       entity_category: data.entity_category.enum_value_or_default().into(),
       object_id: data.object_id,
       key: data.key,
+      device_id: data.device_id,
       name: data.name,
       icon: data.icon,
     };
@@ -333,6 +819,7 @@ impl EntityInfo {
       entity_category: data.entity_category.enum_value_or_default().into(),
       object_id: data.object_id,
       key: data.key,
+      device_id: data.device_id,
       name: data.name,
       icon: data.icon,
     };
@@ -356,6 +843,7 @@ impl EntityInfo {
       entity_category: data.entity_category.enum_value_or_default().into(),
       object_id: data.object_id,
       key: data.key,
+      device_id: data.device_id,
       name: data.name,
       icon: data.icon,
     };
@@ -379,6 +867,7 @@ impl EntityInfo {
       entity_category: data.entity_category.enum_value_or_default().into(),
       object_id: data.object_id,
       key: data.key,
+      device_id: data.device_id,
       name: data.name,
       icon: data.icon,
     };
@@ -397,6 +886,7 @@ impl EntityInfo {
       entity_category: data.entity_category.enum_value_or_default().into(),
       object_id: data.object_id,
       key: data.key,
+      device_id: data.device_id,
       name: data.name,
       icon: data.icon,
     };
@@ -412,6 +902,27 @@ impl EntityInfo {
     }))
   }
 
+  pub fn parse_siren(data: &[u8]) -> Result<Self> {
+    let data = api::ListEntitiesSirenResponse::parse_from_bytes(data)?;
+
+    let entity_info = services::BaseEntityInfo {
+      disabled_by_default: data.disabled_by_default,
+      entity_category: data.entity_category.enum_value_or_default().into(),
+      object_id: data.object_id,
+      key: data.key,
+      device_id: data.device_id,
+      name: data.name,
+      icon: data.icon,
+    };
+
+    Ok(EntityInfo::Siren(services::SirenInfo {
+      entity_info,
+      tones: data.tones,
+      supports_volume: data.supports_volume,
+      supports_duration: data.supports_duration,
+    }))
+  }
+
   pub fn parse_switch(data: &[u8]) -> Result<Self> {
     let data = api::ListEntitiesSwitchResponse::parse_from_bytes(data)?;
 
@@ -420,6 +931,7 @@ impl EntityInfo {
       entity_category: data.entity_category.enum_value_or_default().into(),
       object_id: data.object_id,
       key: data.key,
+      device_id: data.device_id,
       name: data.name,
       icon: data.icon,
     };
@@ -439,6 +951,7 @@ impl EntityInfo {
       entity_category: data.entity_category.enum_value_or_default().into(),
       object_id: data.object_id,
       key: data.key,
+      device_id: data.device_id,
       name: data.name,
       icon: data.icon,
     };
@@ -460,6 +973,7 @@ impl EntityInfo {
       entity_category: data.entity_category.enum_value_or_default().into(),
       object_id: data.object_id,
       key: data.key,
+      device_id: data.device_id,
       name: data.name,
       icon: data.icon,
     };
@@ -478,6 +992,7 @@ impl EntityInfo {
       entity_category: data.entity_category.enum_value_or_default().into(),
       object_id: data.object_id,
       key: data.key,
+      device_id: data.device_id,
       name: data.name,
       icon: data.icon,
     };
@@ -493,6 +1008,7 @@ impl EntityInfo {
       entity_category: data.entity_category.enum_value_or_default().into(),
       object_id: data.object_id,
       key: data.key,
+      device_id: data.device_id,
       name: data.name,
       icon: data.icon,
     };
@@ -511,6 +1027,7 @@ impl EntityInfo {
       entity_category: data.entity_category.enum_value_or_default().into(),
       object_id: data.object_id,
       key: data.key,
+      device_id: data.device_id,
       name: data.name,
       icon: data.icon,
     };
@@ -523,6 +1040,32 @@ impl EntityInfo {
       supports_stop: data.supports_stop,
     }))
   }
+  pub fn parse_water_heater(data: &[u8]) -> Result<Self> {
+    let data = api::ListEntitiesWaterHeaterResponse::parse_from_bytes(data)?;
+
+    let entity_info = services::BaseEntityInfo {
+      disabled_by_default: data.disabled_by_default,
+      entity_category: data.entity_category.enum_value_or_default().into(),
+      object_id: data.object_id,
+      key: data.key,
+      device_id: data.device_id,
+      name: data.name,
+      icon: data.icon,
+    };
+
+    Ok(EntityInfo::WaterHeater(services::WaterHeaterInfo {
+      entity_info,
+      min_temperature: data.min_temperature,
+      max_temperature: data.max_temperature,
+      target_temperature_step: data.target_temperature_step,
+      supported_modes: data
+        .supported_modes
+        .iter()
+        .map(|v| v.enum_value_or_default().into())
+        .collect(),
+      supported_features: data.supported_features,
+    }))
+  }
 }
 
 pub fn parse_user_service(data: &[u8]) -> Result<services::UserService> {
@@ -541,5 +1084,6 @@ pub fn parse_user_service(data: &[u8]) -> Result<services::UserService> {
     key: data.key,
     name: data.name,
     args,
+    supports_response: data.supports_response.enum_value_or_default().into(),
   })
 }
@@ -1,9 +1,12 @@
+use enumflags2::BitFlags;
 use protobuf::Message as _;
 
 use super::services;
+use crate::connection::ProtobufMessage;
+use crate::utils::to_message;
 use crate::{api, Result};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum EntityState {
   AlarmControlPanel(services::AlarmControlPanelEntityState),
   BinarySensor(services::BinarySensorState),
@@ -19,37 +22,249 @@ pub enum EntityState {
   Number(services::NumberState),
   Select(services::SelectState),
   Sensor(services::SensorState),
+  Siren(services::SirenState),
   Switch(services::SwitchState),
   Text(services::TextState),
   TextSensor(services::TextSensorState),
   Time(services::TimeState),
   Update(services::UpdateState),
   Valve(services::ValveState),
+  WaterHeater(services::WaterHeaterState),
 }
 
 impl EntityState {
   pub fn key(&self) -> u32 {
+    self.entity_state().key
+  }
+
+  /// Sub-device of the entity, 0 for the node itself.
+  pub fn device_id(&self) -> u32 {
+    self.entity_state().device_id
+  }
+
+  pub fn entity_state(&self) -> &services::BaseEntityState {
+    match self {
+      EntityState::AlarmControlPanel(state) => &state.entity_state,
+      EntityState::BinarySensor(state) => &state.entity_state,
+      EntityState::Climate(state) => &state.entity_state,
+      EntityState::Cover(state) => &state.entity_state,
+      EntityState::Date(state) => &state.entity_state,
+      EntityState::DateTime(state) => &state.entity_state,
+      EntityState::Event(state) => &state.entity_state,
+      EntityState::Fan(state) => &state.entity_state,
+      EntityState::Light(state) => &state.entity_state,
+      EntityState::Lock(state) => &state.entity_state,
+      EntityState::MediaPlayer(state) => &state.entity_state,
+      EntityState::Number(state) => &state.entity_state,
+      EntityState::Select(state) => &state.entity_state,
+      EntityState::Sensor(state) => &state.entity_state,
+      EntityState::Siren(state) => &state.entity_state,
+      EntityState::Switch(state) => &state.entity_state,
+      EntityState::Text(state) => &state.entity_state,
+      EntityState::TextSensor(state) => &state.entity_state,
+      EntityState::Time(state) => &state.entity_state,
+      EntityState::Update(state) => &state.entity_state,
+      EntityState::Valve(state) => &state.entity_state,
+      EntityState::WaterHeater(state) => &state.entity_state,
+    }
+  }
+
+  /// Encode the state as the `*StateResponse` a device reports it with.
+  pub fn encode(self) -> Result<ProtobufMessage> {
     match self {
-      EntityState::AlarmControlPanel(state) => state.entity_state.key,
-      EntityState::BinarySensor(state) => state.entity_state.key,
-      EntityState::Climate(state) => state.entity_state.key,
-      EntityState::Cover(state) => state.entity_state.key,
-      EntityState::Date(state) => state.entity_state.key,
-      EntityState::DateTime(state) => state.entity_state.key,
-      EntityState::Event(state) => state.entity_state.key,
-      EntityState::Fan(state) => state.entity_state.key,
-      EntityState::Light(state) => state.entity_state.key,
-      EntityState::Lock(state) => state.entity_state.key,
-      EntityState::MediaPlayer(state) => state.entity_state.key,
-      EntityState::Number(state) => state.entity_state.key,
-      EntityState::Select(state) => state.entity_state.key,
-      EntityState::Sensor(state) => state.entity_state.key,
-      EntityState::Switch(state) => state.entity_state.key,
-      EntityState::Text(state) => state.entity_state.key,
-      EntityState::TextSensor(state) => state.entity_state.key,
-      EntityState::Time(state) => state.entity_state.key,
-      EntityState::Update(state) => state.entity_state.key,
-      EntityState::Valve(state) => state.entity_state.key,
+      EntityState::AlarmControlPanel(state) => to_message(&api::AlarmControlPanelStateResponse {
+        key: state.entity_state.key,
+        device_id: state.entity_state.device_id,
+        state: api::AlarmControlPanelState::from(state.state).into(),
+        ..Default::default()
+      }),
+      EntityState::BinarySensor(state) => to_message(&api::BinarySensorStateResponse {
+        key: state.entity_state.key,
+        device_id: state.entity_state.device_id,
+        state: state.state,
+        missing_state: state.missing_state,
+        ..Default::default()
+      }),
+      EntityState::Climate(state) => to_message(&api::ClimateStateResponse {
+        key: state.entity_state.key,
+        device_id: state.entity_state.device_id,
+        mode: api::ClimateMode::from(state.mode).into(),
+        action: api::ClimateAction::from(state.action).into(),
+        current_temperature: state.current_temperature,
+        target_temperature: state.target_temperature,
+        target_temperature_low: state.target_temperature_low,
+        target_temperature_high: state.target_temperature_high,
+        unused_legacy_away: state.legacy_away,
+        fan_mode: api::ClimateFanMode::from(state.fan_mode).into(),
+        swing_mode: api::ClimateSwingMode::from(state.swing_mode).into(),
+        custom_fan_mode: state.custom_fan_mode,
+        preset: api::ClimatePreset::from(state.preset).into(),
+        custom_preset: state.custom_preset,
+        current_humidity: state.current_humidity,
+        target_humidity: state.target_humidity,
+        ..Default::default()
+      }),
+      EntityState::Cover(state) => to_message(&api::CoverStateResponse {
+        key: state.entity_state.key,
+        device_id: state.entity_state.device_id,
+        legacy_state: api::LegacyCoverState::from(state.legacy_state).into(),
+        position: state.position,
+        tilt: state.tilt,
+        current_operation: api::CoverOperation::from(state.current_operation).into(),
+        ..Default::default()
+      }),
+      EntityState::Date(state) => to_message(&api::DateStateResponse {
+        key: state.entity_state.key,
+        device_id: state.entity_state.device_id,
+        missing_state: state.missing_state,
+        year: state.year,
+        month: state.month,
+        day: state.day,
+        ..Default::default()
+      }),
+      EntityState::DateTime(state) => to_message(&api::DateTimeStateResponse {
+        key: state.entity_state.key,
+        device_id: state.entity_state.device_id,
+        missing_state: state.missing_state,
+        epoch_seconds: state.epoch_seconds,
+        ..Default::default()
+      }),
+      EntityState::Event(state) => to_message(&api::EventResponse {
+        key: state.entity_state.key,
+        device_id: state.entity_state.device_id,
+        event_type: state.event_type,
+        ..Default::default()
+      }),
+      EntityState::Fan(state) => to_message(&api::FanStateResponse {
+        key: state.entity_state.key,
+        device_id: state.entity_state.device_id,
+        state: state.state,
+        oscillating: state.oscillating,
+        speed: api::FanSpeed::from(state.speed).into(),
+        speed_level: state.speed_level,
+        direction: api::FanDirection::from(state.direction).into(),
+        preset_mode: state.preset_mode,
+        ..Default::default()
+      }),
+      EntityState::Light(state) => to_message(&api::LightStateResponse {
+        key: state.entity_state.key,
+        device_id: state.entity_state.device_id,
+        state: state.state,
+        brightness: state.brightness,
+        color_mode: api::ColorMode::from(state.color_mode).into(),
+        color_brightness: state.color_brightness,
+        red: state.red,
+        green: state.green,
+        blue: state.blue,
+        white: state.white,
+        color_temperature: state.color_temperature,
+        cold_white: state.cold_white,
+        warm_white: state.warm_white,
+        effect: state.effect,
+        ..Default::default()
+      }),
+      EntityState::Lock(state) => to_message(&api::LockStateResponse {
+        key: state.entity_state.key,
+        device_id: state.entity_state.device_id,
+        state: api::LockState::from(state.state).into(),
+        ..Default::default()
+      }),
+      EntityState::MediaPlayer(state) => to_message(&api::MediaPlayerStateResponse {
+        key: state.entity_state.key,
+        device_id: state.entity_state.device_id,
+        state: api::MediaPlayerState::from(state.state).into(),
+        volume: state.volume,
+        muted: state.muted,
+        ..Default::default()
+      }),
+      EntityState::Number(state) => to_message(&api::NumberStateResponse {
+        key: state.entity_state.key,
+        device_id: state.entity_state.device_id,
+        state: state.state,
+        missing_state: state.missing_state,
+        ..Default::default()
+      }),
+      EntityState::Select(state) => to_message(&api::SelectStateResponse {
+        key: state.entity_state.key,
+        device_id: state.entity_state.device_id,
+        state: state.state,
+        missing_state: state.missing_state,
+        ..Default::default()
+      }),
+      EntityState::Sensor(state) => to_message(&api::SensorStateResponse {
+        key: state.entity_state.key,
+        device_id: state.entity_state.device_id,
+        state: state.state,
+        missing_state: state.missing_state,
+        ..Default::default()
+      }),
+      EntityState::Siren(state) => to_message(&api::SirenStateResponse {
+        key: state.entity_state.key,
+        device_id: state.entity_state.device_id,
+        state: state.state,
+        ..Default::default()
+      }),
+      EntityState::Switch(state) => to_message(&api::SwitchStateResponse {
+        key: state.entity_state.key,
+        device_id: state.entity_state.device_id,
+        state: state.state,
+        ..Default::default()
+      }),
+      EntityState::Text(state) => to_message(&api::TextStateResponse {
+        key: state.entity_state.key,
+        device_id: state.entity_state.device_id,
+        state: state.state,
+        missing_state: state.missing_state,
+        ..Default::default()
+      }),
+      EntityState::TextSensor(state) => to_message(&api::TextSensorStateResponse {
+        key: state.entity_state.key,
+        device_id: state.entity_state.device_id,
+        state: state.state,
+        missing_state: state.missing_state,
+        ..Default::default()
+      }),
+      EntityState::Time(state) => to_message(&api::TimeStateResponse {
+        key: state.entity_state.key,
+        device_id: state.entity_state.device_id,
+        missing_state: state.missing_state,
+        hour: state.hour,
+        minute: state.minute,
+        second: state.second,
+        ..Default::default()
+      }),
+      EntityState::Update(state) => to_message(&api::UpdateStateResponse {
+        key: state.entity_state.key,
+        device_id: state.entity_state.device_id,
+        missing_state: state.missing_state,
+        in_progress: state.in_progress,
+        has_progress: state.has_progress,
+        progress: state.progress,
+        current_version: state.current_version,
+        latest_version: state.latest_version,
+        title: state.title,
+        release_summary: state.release_summary,
+        release_url: state.release_url,
+        ..Default::default()
+      }),
+      EntityState::Valve(state) => to_message(&api::ValveStateResponse {
+        key: state.entity_state.key,
+        device_id: state.entity_state.device_id,
+        position: state.position,
+        current_operation: api::ValveOperation::from(state.current_operation).into(),
+        ..Default::default()
+      }),
+      EntityState::WaterHeater(state) => to_message(&api::WaterHeaterStateResponse {
+        key: state.entity_state.key,
+        device_id: state.entity_state.device_id,
+        current_temperature: state.current_temperature,
+        target_temperature: state.target_temperature,
+        target_temperature_low: state.target_temperature_low,
+        target_temperature_high: state.target_temperature_high,
+        mode: api::WaterHeaterMode::from(state.mode).into(),
+        state: state.state.bits(),
+        ..Default::default()
+      }),
     }
   }
 
@@ -57,7 +272,8 @@ impl EntityState {
     let data = api::AlarmControlPanelStateResponse::parse_from_bytes(data)?;
 
     let entity_state = services::BaseEntityState {
-      key: data.key.clone(),
+      key: data.key,
+      device_id: data.device_id,
     };
 
     Ok(EntityState::AlarmControlPanel(
@@ -72,7 +288,8 @@ impl EntityState {
     let data = api::BinarySensorStateResponse::parse_from_bytes(data)?;
 
     let entity_state = services::BaseEntityState {
-      key: data.key.clone(),
+      key: data.key,
+      device_id: data.device_id,
     };
 
     Ok(EntityState::BinarySensor(services::BinarySensorState {
@@ -86,7 +303,8 @@ impl EntityState {
     let data = api::ClimateStateResponse::parse_from_bytes(data)?;
 
     let entity_state = services::BaseEntityState {
-      key: data.key.clone(),
+      key: data.key,
+      device_id: data.device_id,
     };
 
     Ok(EntityState::Climate(services::ClimateState {
@@ -112,7 +330,8 @@ impl EntityState {
     let data = api::CoverStateResponse::parse_from_bytes(data)?;
 
     let entity_state = services::BaseEntityState {
-      key: data.key.clone(),
+      key: data.key,
+      device_id: data.device_id,
     };
 
     Ok(EntityState::Cover(services::CoverState {
@@ -128,7 +347,8 @@ impl EntityState {
     let data = api::DateStateResponse::parse_from_bytes(data)?;
 
     let entity_state = services::BaseEntityState {
-      key: data.key.clone(),
+      key: data.key,
+      device_id: data.device_id,
     };
 
     Ok(EntityState::Date(services::DateState {
@@ -144,7 +364,8 @@ impl EntityState {
     let data = api::DateTimeStateResponse::parse_from_bytes(data)?;
 
     let entity_state = services::BaseEntityState {
-      key: data.key.clone(),
+      key: data.key,
+      device_id: data.device_id,
     };
 
     Ok(EntityState::DateTime(services::DateTimeState {
@@ -158,7 +379,8 @@ impl EntityState {
     let data = api::EventResponse::parse_from_bytes(data)?;
 
     let entity_state = services::BaseEntityState {
-      key: data.key.clone(),
+      key: data.key,
+      device_id: data.device_id,
     };
 
     Ok(EntityState::Event(services::Event {
@@ -171,11 +393,13 @@ impl EntityState {
     let data = api::FanStateResponse::parse_from_bytes(data)?;
 
     let entity_state = services::BaseEntityState {
-      key: data.key.clone(),
+      key: data.key,
+      device_id: data.device_id,
     };
 
     Ok(EntityState::Fan(services::FanState {
       entity_state,
+      state: data.state,
       direction: data.direction.enum_value_or_default().into(),
       oscillating: data.oscillating,
       preset_mode: data.preset_mode.clone(),
@@ -188,7 +412,8 @@ impl EntityState {
     let data = api::LightStateResponse::parse_from_bytes(data)?;
 
     let entity_state = services::BaseEntityState {
-      key: data.key.clone(),
+      key: data.key,
+      device_id: data.device_id,
     };
 
     Ok(EntityState::Light(services::LightState {
@@ -212,7 +437,8 @@ impl EntityState {
     let data = api::LockStateResponse::parse_from_bytes(data)?;
 
     let entity_state = services::BaseEntityState {
-      key: data.key.clone(),
+      key: data.key,
+      device_id: data.device_id,
     };
 
     Ok(EntityState::Lock(services::LockEntityState {
@@ -225,7 +451,8 @@ impl EntityState {
     let data = api::MediaPlayerStateResponse::parse_from_bytes(data)?;
 
     let entity_state = services::BaseEntityState {
-      key: data.key.clone(),
+      key: data.key,
+      device_id: data.device_id,
     };
 
     Ok(EntityState::MediaPlayer(services::MediaPlayerEntityState {
@@ -240,7 +467,8 @@ impl EntityState {
     let data = api::NumberStateResponse::parse_from_bytes(data)?;
 
     let entity_state = services::BaseEntityState {
-      key: data.key.clone(),
+      key: data.key,
+      device_id: data.device_id,
     };
 
     Ok(EntityState::Number(services::NumberState {
@@ -254,7 +482,8 @@ impl EntityState {
     let data = api::SelectStateResponse::parse_from_bytes(data)?;
 
     let entity_state = services::BaseEntityState {
-      key: data.key.clone(),
+      key: data.key,
+      device_id: data.device_id,
     };
 
     Ok(EntityState::Select(services::SelectState {
@@ -268,7 +497,8 @@ impl EntityState {
     let data = api::SensorStateResponse::parse_from_bytes(data)?;
 
     let entity_state = services::BaseEntityState {
-      key: data.key.clone(),
+      key: data.key,
+      device_id: data.device_id,
     };
 
     Ok(EntityState::Sensor(services::SensorState {
@@ -278,11 +508,26 @@ impl EntityState {
     }))
   }
 
+  pub fn parse_siren(data: &[u8]) -> Result<Self> {
+    let data = api::SirenStateResponse::parse_from_bytes(data)?;
+
+    let entity_state = services::BaseEntityState {
+      key: data.key,
+      device_id: data.device_id,
+    };
+
+    Ok(EntityState::Siren(services::SirenState {
+      entity_state,
+      state: data.state,
+    }))
+  }
+
   pub fn parse_switch(data: &[u8]) -> Result<Self> {
     let data = api::SwitchStateResponse::parse_from_bytes(data)?;
 
     let entity_state = services::BaseEntityState {
-      key: data.key.clone(),
+      key: data.key,
+      device_id: data.device_id,
     };
 
     Ok(EntityState::Switch(services::SwitchState {
@@ -295,7 +540,8 @@ impl EntityState {
     let data = api::TextStateResponse::parse_from_bytes(data)?;
 
     let entity_state = services::BaseEntityState {
-      key: data.key.clone(),
+      key: data.key,
+      device_id: data.device_id,
     };
 
     Ok(EntityState::Text(services::TextState {
@@ -309,7 +555,8 @@ impl EntityState {
     let data = api::TextSensorStateResponse::parse_from_bytes(data)?;
 
     let entity_state = services::BaseEntityState {
-      key: data.key.clone(),
+      key: data.key,
+      device_id: data.device_id,
     };
 
     Ok(EntityState::TextSensor(services::TextSensorState {
@@ -323,7 +570,8 @@ impl EntityState {
     let data = api::TimeStateResponse::parse_from_bytes(data)?;
 
     let entity_state = services::BaseEntityState {
-      key: data.key.clone(),
+      key: data.key,
+      device_id: data.device_id,
     };
 
     Ok(EntityState::Time(services::TimeState {
@@ -339,7 +587,8 @@ impl EntityState {
     let data = api::UpdateStateResponse::parse_from_bytes(data)?;
 
     let entity_state = services::BaseEntityState {
-      key: data.key.clone(),
+      key: data.key,
+      device_id: data.device_id,
     };
 
     Ok(EntityState::Update(services::UpdateState {
@@ -360,7 +609,8 @@ impl EntityState {
     let data = api::ValveStateResponse::parse_from_bytes(data)?;
 
     let entity_state = services::BaseEntityState {
-      key: data.key.clone(),
+      key: data.key,
+      device_id: data.device_id,
     };
 
     Ok(EntityState::Valve(services::ValveState {
@@ -369,4 +619,22 @@ impl EntityState {
       position: data.position,
     }))
   }
+  pub fn parse_water_heater(data: &[u8]) -> Result<Self> {
+    let data = api::WaterHeaterStateResponse::parse_from_bytes(data)?;
+
+    let entity_state = services::BaseEntityState {
+      key: data.key,
+      device_id: data.device_id,
+    };
+
+    Ok(EntityState::WaterHeater(services::WaterHeaterState {
+      entity_state,
+      current_temperature: data.current_temperature,
+      target_temperature: data.target_temperature,
+      target_temperature_low: data.target_temperature_low,
+      target_temperature_high: data.target_temperature_high,
+      mode: data.mode.enum_value_or_default().into(),
+      state: BitFlags::from_bits_truncate(data.state),
+    }))
+  }
 }
@@ -3,6 +3,7 @@ mod entity_info;
 mod entity_state;
 mod services;
 
+pub(crate) use conversions::{LIST_ENTITIES_RESPONSE_IDS, MINIMUM_API_VERSIONS};
 pub use conversions::{LIST_ENTITIES_SERVICES_RESPONSE_TYPES, SUBCRIBE_STATES_RESPONSE_TYPES};
 pub use entity_info::{parse_user_service, EntityInfo};
 pub use entity_state::EntityState;
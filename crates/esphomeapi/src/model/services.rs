@@ -7,7 +7,7 @@ use crate::{
   proto,
 };
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
 pub struct APIVersion {
   pub major: u8,
   pub minor: u8,
@@ -19,6 +19,35 @@ impl APIVersion {
   }
 }
 
+impl std::fmt::Display for APIVersion {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}.{}", self.major, self.minor)
+  }
+}
+
+/// What the device reported about itself in its `HelloResponse`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ServerInfo {
+  /// API version the device speaks
+  pub api_version: APIVersion,
+  /// Free-form server identification, e.g. `"kitchen (esphome v2025.10.0)"`
+  pub server_info: String,
+  pub name: String,
+}
+
+impl From<api::HelloResponse> for ServerInfo {
+  fn from(value: api::HelloResponse) -> Self {
+    ServerInfo {
+      api_version: APIVersion::new(
+        value.api_version_major.try_into().unwrap_or(u8::MAX),
+        value.api_version_minor.try_into().unwrap_or(u8::MAX),
+      ),
+      server_info: value.server_info,
+      name: value.name,
+    }
+  }
+}
+
 #[bitflags]
 #[repr(u32)]
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
@@ -31,7 +60,9 @@ pub enum BluetoothProxyFeature {
   RawAdvertisements = 1 << 5,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[bitflags]
+#[repr(u32)]
+#[derive(Debug, Clone, PartialEq, Eq, Copy)]
 pub enum BluetoothProxySubscriptionFlag {
   RawAdvertisements = 1 << 0,
 }
@@ -47,12 +78,14 @@ pub enum VoiceAssistantFeature {
   Announce = 1 << 4,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[bitflags]
+#[repr(u32)]
+#[derive(Debug, Clone, PartialEq, Eq, Copy)]
 pub enum VoiceAssistantSubscriptionFlag {
   APIAudio = 1 << 0,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct DeviceInfo {
   pub uses_password: bool,
   pub name: String,
@@ -71,6 +104,16 @@ pub struct DeviceInfo {
   pub legacy_bluetooth_proxy_version: u32,
   pub bluetooth_proxy_feature_flags: u32,
   pub suggested_area: String,
+  pub bluetooth_mac_address: String,
+  pub api_encryption_supported: bool,
+  /// Sub-devices whose entities are exposed through this node
+  pub devices: Vec<SubDeviceInfo>,
+  pub areas: Vec<AreaInfo>,
+  /// Area of the node itself
+  pub area: Option<AreaInfo>,
+  pub zwave_proxy_feature_flags: u32,
+  /// Home id of the proxied Z-Wave controller, 0 when unknown
+  pub zwave_home_id: u32,
 }
 
 impl From<DeviceInfoResponse> for DeviceInfo {
@@ -93,31 +136,75 @@ impl From<DeviceInfoResponse> for DeviceInfo {
       legacy_bluetooth_proxy_version: proto.legacy_bluetooth_proxy_version,
       bluetooth_proxy_feature_flags: proto.bluetooth_proxy_feature_flags,
       suggested_area: proto.suggested_area,
+      bluetooth_mac_address: proto.bluetooth_mac_address,
+      api_encryption_supported: proto.api_encryption_supported,
+      devices: proto.devices.into_iter().map(Into::into).collect(),
+      areas: proto.areas.into_iter().map(Into::into).collect(),
+      area: proto.area.into_option().map(Into::into),
+      zwave_proxy_feature_flags: proto.zwave_proxy_feature_flags,
+      zwave_home_id: proto.zwave_home_id,
+    }
+  }
+}
+
+impl From<DeviceInfo> for DeviceInfoResponse {
+  fn from(info: DeviceInfo) -> Self {
+    DeviceInfoResponse {
+      uses_password: info.uses_password,
+      name: info.name,
+      friendly_name: info.friendly_name,
+      mac_address: info.mac_address,
+      compilation_time: info.compilation_time,
+      model: info.model,
+      manufacturer: info.manufacturer,
+      has_deep_sleep: info.has_deep_sleep,
+      esphome_version: info.esphome_version,
+      project_name: info.project_name,
+      project_version: info.project_version,
+      webserver_port: info.webserver_port,
+      legacy_voice_assistant_version: info.legacy_voice_assistant_version,
+      voice_assistant_feature_flags: info.voice_assistant_feature_flags,
+      legacy_bluetooth_proxy_version: info.legacy_bluetooth_proxy_version,
+      bluetooth_proxy_feature_flags: info.bluetooth_proxy_feature_flags,
+      suggested_area: info.suggested_area,
+      bluetooth_mac_address: info.bluetooth_mac_address,
+      api_encryption_supported: info.api_encryption_supported,
+      devices: info.devices.into_iter().map(Into::into).collect(),
+      areas: info.areas.into_iter().map(Into::into).collect(),
+      area: info.area.map(Into::into).into(),
+      zwave_proxy_feature_flags: info.zwave_proxy_feature_flags,
+      zwave_home_id: info.zwave_home_id,
+      ..Default::default()
     }
   }
 }
 
 impl DeviceInfo {
-  pub fn default() -> DeviceInfo {
-    DeviceInfo {
-      uses_password: false,
-      name: String::new(),
-      friendly_name: String::new(),
-      mac_address: String::new(),
-      compilation_time: String::new(),
-      model: String::new(),
-      manufacturer: String::new(),
-      has_deep_sleep: false,
-      esphome_version: String::new(),
-      project_name: String::new(),
-      project_version: String::new(),
-      webserver_port: 0,
-      legacy_voice_assistant_version: 0,
-      voice_assistant_feature_flags: 0,
-      legacy_bluetooth_proxy_version: 0,
-      bluetooth_proxy_feature_flags: 0,
-      suggested_area: String::new(),
+  /// Look up a sub-device by id.
+  pub fn sub_device(&self, device_id: u32) -> Option<&SubDeviceInfo> {
+    self
+      .devices
+      .iter()
+      .find(|device| device.device_id == device_id)
+  }
+
+  /// Look up an area by id.
+  pub fn area_info(&self, area_id: u32) -> Option<&AreaInfo> {
+    self
+      .areas
+      .iter()
+      .chain(self.area.as_ref())
+      .find(|area| area.area_id == area_id)
+  }
+
+  /// The area a device is placed in; `device_id` 0 is the node itself.
+  pub fn device_area(&self, device_id: u32) -> Option<&AreaInfo> {
+    if device_id == 0 {
+      return self.area.as_ref();
     }
+    self
+      .sub_device(device_id)
+      .and_then(|device| self.area_info(device.area_id))
   }
 
   pub fn bluetooth_proxy_feature_flags_compat(&self, api_version: APIVersion) -> u32 {
@@ -140,7 +227,7 @@ impl DeviceInfo {
       }
       return flags.bits();
     }
-    return self.bluetooth_proxy_feature_flags;
+    self.bluetooth_proxy_feature_flags
   }
 
   pub fn voice_assistant_feature_flags_compat(&self, api_version: APIVersion) -> u32 {
@@ -154,7 +241,7 @@ impl DeviceInfo {
       }
       return flags.bits();
     }
-    return self.voice_assistant_feature_flags;
+    self.voice_assistant_feature_flags
   }
 }
 
@@ -175,10 +262,79 @@ impl From<proto::api::EntityCategory> for EntityCategory {
   }
 }
 
+impl From<EntityCategory> for proto::api::EntityCategory {
+  fn from(value: EntityCategory) -> Self {
+    match value {
+      EntityCategory::None => proto::api::EntityCategory::ENTITY_CATEGORY_NONE,
+      EntityCategory::Config => proto::api::EntityCategory::ENTITY_CATEGORY_CONFIG,
+      EntityCategory::Diagnostic => proto::api::EntityCategory::ENTITY_CATEGORY_DIAGNOSTIC,
+    }
+  }
+}
+
+/// An area (room) devices are assigned to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AreaInfo {
+  pub area_id: u32,
+  pub name: String,
+}
+
+impl From<proto::api::AreaInfo> for AreaInfo {
+  fn from(proto: proto::api::AreaInfo) -> Self {
+    AreaInfo {
+      area_id: proto.area_id,
+      name: proto.name,
+    }
+  }
+}
+
+impl From<AreaInfo> for proto::api::AreaInfo {
+  fn from(info: AreaInfo) -> Self {
+    proto::api::AreaInfo {
+      area_id: info.area_id,
+      name: info.name,
+      ..Default::default()
+    }
+  }
+}
+
+/// A logical device behind a node, e.g. one of several sensors on a board.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubDeviceInfo {
+  pub device_id: u32,
+  pub name: String,
+  /// 0 when the sub-device has no area
+  pub area_id: u32,
+}
+
+impl From<proto::api::DeviceInfo> for SubDeviceInfo {
+  fn from(proto: proto::api::DeviceInfo) -> Self {
+    SubDeviceInfo {
+      device_id: proto.device_id,
+      name: proto.name,
+      area_id: proto.area_id,
+    }
+  }
+}
+
+impl From<SubDeviceInfo> for proto::api::DeviceInfo {
+  fn from(info: SubDeviceInfo) -> Self {
+    proto::api::DeviceInfo {
+      device_id: info.device_id,
+      name: info.name,
+      area_id: info.area_id,
+      ..Default::default()
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BaseEntityInfo {
   pub object_id: String,
   pub key: u32,
+  /// Sub-device the entity belongs to, 0 for the node itself. Keys are only
+  /// unique per sub-device.
+  pub device_id: u32,
   pub name: String,
   pub disabled_by_default: bool,
   pub icon: String,
@@ -188,6 +344,7 @@ pub struct BaseEntityInfo {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BaseEntityState {
   pub key: u32,
+  pub device_id: u32,
 }
 
 // ==================== BINARY SENSOR ====================
@@ -232,6 +389,15 @@ impl From<proto::api::LegacyCoverState> for LegacyCoverState {
   }
 }
 
+impl From<LegacyCoverState> for proto::api::LegacyCoverState {
+  fn from(value: LegacyCoverState) -> Self {
+    match value {
+      LegacyCoverState::Open => proto::api::LegacyCoverState::LEGACY_COVER_STATE_OPEN,
+      LegacyCoverState::Closed => proto::api::LegacyCoverState::LEGACY_COVER_STATE_CLOSED,
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum LegacyCoverCommand {
   Open = 0,
@@ -266,6 +432,16 @@ impl From<proto::api::CoverOperation> for CoverOperation {
   }
 }
 
+impl From<CoverOperation> for proto::api::CoverOperation {
+  fn from(value: CoverOperation) -> Self {
+    match value {
+      CoverOperation::Idle => proto::api::CoverOperation::COVER_OPERATION_IDLE,
+      CoverOperation::Opening => proto::api::CoverOperation::COVER_OPERATION_IS_OPENING,
+      CoverOperation::Closing => proto::api::CoverOperation::COVER_OPERATION_IS_CLOSING,
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct CoverState {
   pub entity_state: BaseEntityState,
@@ -280,7 +456,7 @@ impl CoverState {
     if api_version < APIVersion::new(1, 1) {
       return self.legacy_state == LegacyCoverState::Closed;
     }
-    return self.position == 0.0;
+    self.position == 0.0
   }
 }
 
@@ -326,6 +502,16 @@ impl From<proto::api::FanSpeed> for FanSpeed {
   }
 }
 
+impl From<FanSpeed> for proto::api::FanSpeed {
+  fn from(value: FanSpeed) -> Self {
+    match value {
+      FanSpeed::Low => proto::api::FanSpeed::FAN_SPEED_LOW,
+      FanSpeed::Medium => proto::api::FanSpeed::FAN_SPEED_MEDIUM,
+      FanSpeed::High => proto::api::FanSpeed::FAN_SPEED_HIGH,
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FanDirection {
   Forward = 0,
@@ -341,9 +527,19 @@ impl From<proto::api::FanDirection> for FanDirection {
   }
 }
 
+impl From<FanDirection> for proto::api::FanDirection {
+  fn from(value: FanDirection) -> Self {
+    match value {
+      FanDirection::Forward => proto::api::FanDirection::FAN_DIRECTION_FORWARD,
+      FanDirection::Reverse => proto::api::FanDirection::FAN_DIRECTION_REVERSE,
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FanState {
   pub entity_state: BaseEntityState,
+  pub state: bool,
   pub oscillating: bool,
   pub speed: FanSpeed,
   pub speed_level: i32,
@@ -398,9 +594,9 @@ impl From<proto::api::ColorMode> for ColorMode {
   }
 }
 
-impl Into<proto::api::ColorMode> for ColorMode {
-  fn into(self) -> proto::api::ColorMode {
-    match self {
+impl From<ColorMode> for proto::api::ColorMode {
+  fn from(value: ColorMode) -> Self {
+    match value {
       ColorMode::Unknown => proto::api::ColorMode::COLOR_MODE_UNKNOWN,
       ColorMode::OnOff => proto::api::ColorMode::COLOR_MODE_ON_OFF,
       ColorMode::LegacyBrightness => proto::api::ColorMode::COLOR_MODE_LEGACY_BRIGHTNESS,
@@ -416,9 +612,9 @@ impl Into<proto::api::ColorMode> for ColorMode {
   }
 }
 
-impl Into<u8> for ColorMode {
-  fn into(self) -> u8 {
-    self as u8
+impl From<ColorMode> for u8 {
+  fn from(value: ColorMode) -> Self {
+    value as u8
   }
 }
 
@@ -481,12 +677,12 @@ impl LightInfo {
 
       return vec![legacy_mode];
     }
-    return self
+    self
       .supported_color_modes
       .clone()
       .iter()
       .map(|x| (*x).into())
-      .collect();
+      .collect()
   }
 }
 
@@ -534,6 +730,22 @@ impl From<proto::api::SensorStateClass> for SensorStateClass {
   }
 }
 
+impl From<SensorStateClass> for proto::api::SensorStateClass {
+  fn from(value: SensorStateClass) -> Self {
+    match value {
+      SensorStateClass::None => proto::api::SensorStateClass::STATE_CLASS_NONE,
+      SensorStateClass::Measurement => proto::api::SensorStateClass::STATE_CLASS_MEASUREMENT,
+      SensorStateClass::TotalIncreasing => {
+        proto::api::SensorStateClass::STATE_CLASS_TOTAL_INCREASING
+      }
+      SensorStateClass::Total => proto::api::SensorStateClass::STATE_CLASS_TOTAL,
+      SensorStateClass::MeasurementAngle => {
+        proto::api::SensorStateClass::STATE_CLASS_MEASUREMENT_ANGLE
+      }
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum LastResetType {
   None = 0,
@@ -551,6 +763,16 @@ impl From<proto::api::SensorLastResetType> for LastResetType {
   }
 }
 
+impl From<LastResetType> for proto::api::SensorLastResetType {
+  fn from(value: LastResetType) -> Self {
+    match value {
+      LastResetType::None => proto::api::SensorLastResetType::LAST_RESET_NONE,
+      LastResetType::Never => proto::api::SensorLastResetType::LAST_RESET_NEVER,
+      LastResetType::Auto => proto::api::SensorLastResetType::LAST_RESET_AUTO,
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SensorInfo {
   pub entity_info: BaseEntityInfo,
@@ -636,6 +858,20 @@ impl From<proto::api::ClimateMode> for ClimateMode {
   }
 }
 
+impl From<ClimateMode> for proto::api::ClimateMode {
+  fn from(value: ClimateMode) -> Self {
+    match value {
+      ClimateMode::Off => proto::api::ClimateMode::CLIMATE_MODE_OFF,
+      ClimateMode::HeatCool => proto::api::ClimateMode::CLIMATE_MODE_HEAT_COOL,
+      ClimateMode::Cool => proto::api::ClimateMode::CLIMATE_MODE_COOL,
+      ClimateMode::Heat => proto::api::ClimateMode::CLIMATE_MODE_HEAT,
+      ClimateMode::FanOnly => proto::api::ClimateMode::CLIMATE_MODE_FAN_ONLY,
+      ClimateMode::Dry => proto::api::ClimateMode::CLIMATE_MODE_DRY,
+      ClimateMode::Auto => proto::api::ClimateMode::CLIMATE_MODE_AUTO,
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ClimateFanMode {
   On = 0,
@@ -667,6 +903,23 @@ impl From<proto::api::ClimateFanMode> for ClimateFanMode {
   }
 }
 
+impl From<ClimateFanMode> for proto::api::ClimateFanMode {
+  fn from(value: ClimateFanMode) -> Self {
+    match value {
+      ClimateFanMode::On => proto::api::ClimateFanMode::CLIMATE_FAN_ON,
+      ClimateFanMode::Off => proto::api::ClimateFanMode::CLIMATE_FAN_OFF,
+      ClimateFanMode::Auto => proto::api::ClimateFanMode::CLIMATE_FAN_AUTO,
+      ClimateFanMode::Low => proto::api::ClimateFanMode::CLIMATE_FAN_LOW,
+      ClimateFanMode::Medium => proto::api::ClimateFanMode::CLIMATE_FAN_MEDIUM,
+      ClimateFanMode::High => proto::api::ClimateFanMode::CLIMATE_FAN_HIGH,
+      ClimateFanMode::Middle => proto::api::ClimateFanMode::CLIMATE_FAN_MIDDLE,
+      ClimateFanMode::Focus => proto::api::ClimateFanMode::CLIMATE_FAN_FOCUS,
+      ClimateFanMode::Diffuse => proto::api::ClimateFanMode::CLIMATE_FAN_DIFFUSE,
+      ClimateFanMode::Quiet => proto::api::ClimateFanMode::CLIMATE_FAN_QUIET,
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ClimateSwingMode {
   Off = 0,
@@ -686,6 +939,17 @@ impl From<proto::api::ClimateSwingMode> for ClimateSwingMode {
   }
 }
 
+impl From<ClimateSwingMode> for proto::api::ClimateSwingMode {
+  fn from(value: ClimateSwingMode) -> Self {
+    match value {
+      ClimateSwingMode::Off => proto::api::ClimateSwingMode::CLIMATE_SWING_OFF,
+      ClimateSwingMode::Both => proto::api::ClimateSwingMode::CLIMATE_SWING_BOTH,
+      ClimateSwingMode::Vertical => proto::api::ClimateSwingMode::CLIMATE_SWING_VERTICAL,
+      ClimateSwingMode::Horizontal => proto::api::ClimateSwingMode::CLIMATE_SWING_HORIZONTAL,
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ClimateAction {
   Off = 0,
@@ -709,6 +973,19 @@ impl From<proto::api::ClimateAction> for ClimateAction {
   }
 }
 
+impl From<ClimateAction> for proto::api::ClimateAction {
+  fn from(value: ClimateAction) -> Self {
+    match value {
+      ClimateAction::Off => proto::api::ClimateAction::CLIMATE_ACTION_OFF,
+      ClimateAction::Cooling => proto::api::ClimateAction::CLIMATE_ACTION_COOLING,
+      ClimateAction::Heating => proto::api::ClimateAction::CLIMATE_ACTION_HEATING,
+      ClimateAction::Idle => proto::api::ClimateAction::CLIMATE_ACTION_IDLE,
+      ClimateAction::Drying => proto::api::ClimateAction::CLIMATE_ACTION_DRYING,
+      ClimateAction::Fan => proto::api::ClimateAction::CLIMATE_ACTION_FAN,
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
 pub enum ClimatePreset {
   None = 0,
@@ -736,6 +1013,21 @@ impl From<proto::api::ClimatePreset> for ClimatePreset {
   }
 }
 
+impl From<ClimatePreset> for proto::api::ClimatePreset {
+  fn from(value: ClimatePreset) -> Self {
+    match value {
+      ClimatePreset::None => proto::api::ClimatePreset::CLIMATE_PRESET_NONE,
+      ClimatePreset::Home => proto::api::ClimatePreset::CLIMATE_PRESET_HOME,
+      ClimatePreset::Away => proto::api::ClimatePreset::CLIMATE_PRESET_AWAY,
+      ClimatePreset::Boost => proto::api::ClimatePreset::CLIMATE_PRESET_BOOST,
+      ClimatePreset::Comfort => proto::api::ClimatePreset::CLIMATE_PRESET_COMFORT,
+      ClimatePreset::Eco => proto::api::ClimatePreset::CLIMATE_PRESET_ECO,
+      ClimatePreset::Sleep => proto::api::ClimatePreset::CLIMATE_PRESET_SLEEP,
+      ClimatePreset::Activity => proto::api::ClimatePreset::CLIMATE_PRESET_ACTIVITY,
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ClimateInfo {
   pub entity_info: BaseEntityInfo,
@@ -767,7 +1059,7 @@ impl ClimateInfo {
       }
       return vec![];
     }
-    return self.supported_presets.clone();
+    self.supported_presets.clone()
   }
 }
 
@@ -798,7 +1090,7 @@ impl ClimateState {
       }
       return ClimatePreset::Home;
     }
-    return self.preset;
+    self.preset
   }
 }
 
@@ -821,6 +1113,16 @@ impl From<proto::api::NumberMode> for NumberMode {
   }
 }
 
+impl From<NumberMode> for proto::api::NumberMode {
+  fn from(value: NumberMode) -> Self {
+    match value {
+      NumberMode::Auto => proto::api::NumberMode::NUMBER_MODE_AUTO,
+      NumberMode::Box => proto::api::NumberMode::NUMBER_MODE_BOX,
+      NumberMode::Slider => proto::api::NumberMode::NUMBER_MODE_SLIDER,
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct NumberInfo {
   pub entity_info: BaseEntityInfo,
@@ -948,6 +1250,19 @@ impl From<proto::api::LockState> for LockState {
   }
 }
 
+impl From<LockState> for proto::api::LockState {
+  fn from(value: LockState) -> Self {
+    match value {
+      LockState::None => proto::api::LockState::LOCK_STATE_NONE,
+      LockState::Locked => proto::api::LockState::LOCK_STATE_LOCKED,
+      LockState::Unlocked => proto::api::LockState::LOCK_STATE_UNLOCKED,
+      LockState::Jammed => proto::api::LockState::LOCK_STATE_JAMMED,
+      LockState::Locking => proto::api::LockState::LOCK_STATE_LOCKING,
+      LockState::Unlocking => proto::api::LockState::LOCK_STATE_UNLOCKING,
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum LockCommand {
   Unlock = 0,
@@ -965,6 +1280,16 @@ impl From<proto::api::LockCommand> for LockCommand {
   }
 }
 
+impl From<LockCommand> for proto::api::LockCommand {
+  fn from(value: LockCommand) -> Self {
+    match value {
+      LockCommand::Unlock => proto::api::LockCommand::LOCK_UNLOCK,
+      LockCommand::Lock => proto::api::LockCommand::LOCK_LOCK,
+      LockCommand::Open => proto::api::LockCommand::LOCK_OPEN,
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct LockInfo {
   pub entity_info: BaseEntityInfo,
@@ -1009,6 +1334,16 @@ impl From<proto::api::ValveOperation> for ValveOperation {
   }
 }
 
+impl From<ValveOperation> for proto::api::ValveOperation {
+  fn from(value: ValveOperation) -> Self {
+    match value {
+      ValveOperation::Idle => proto::api::ValveOperation::VALVE_OPERATION_IDLE,
+      ValveOperation::Opening => proto::api::ValveOperation::VALVE_OPERATION_IS_OPENING,
+      ValveOperation::Closing => proto::api::ValveOperation::VALVE_OPERATION_IS_CLOSING,
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ValveState {
   pub entity_state: BaseEntityState,
@@ -1043,6 +1378,20 @@ impl From<proto::api::MediaPlayerState> for MediaPlayerState {
   }
 }
 
+impl From<MediaPlayerState> for proto::api::MediaPlayerState {
+  fn from(value: MediaPlayerState) -> Self {
+    match value {
+      MediaPlayerState::None => proto::api::MediaPlayerState::MEDIA_PLAYER_STATE_NONE,
+      MediaPlayerState::Idle => proto::api::MediaPlayerState::MEDIA_PLAYER_STATE_IDLE,
+      MediaPlayerState::Playing => proto::api::MediaPlayerState::MEDIA_PLAYER_STATE_PLAYING,
+      MediaPlayerState::Paused => proto::api::MediaPlayerState::MEDIA_PLAYER_STATE_PAUSED,
+      MediaPlayerState::Announcing => proto::api::MediaPlayerState::MEDIA_PLAYER_STATE_ANNOUNCING,
+      MediaPlayerState::Off => proto::api::MediaPlayerState::MEDIA_PLAYER_STATE_OFF,
+      MediaPlayerState::On => proto::api::MediaPlayerState::MEDIA_PLAYER_STATE_ON,
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum MediaPlayerCommand {
   Play = 0,
@@ -1092,6 +1441,37 @@ impl From<proto::api::MediaPlayerCommand> for MediaPlayerCommand {
   }
 }
 
+impl From<MediaPlayerCommand> for proto::api::MediaPlayerCommand {
+  fn from(value: MediaPlayerCommand) -> Self {
+    match value {
+      MediaPlayerCommand::Play => proto::api::MediaPlayerCommand::MEDIA_PLAYER_COMMAND_PLAY,
+      MediaPlayerCommand::Pause => proto::api::MediaPlayerCommand::MEDIA_PLAYER_COMMAND_PAUSE,
+      MediaPlayerCommand::Stop => proto::api::MediaPlayerCommand::MEDIA_PLAYER_COMMAND_STOP,
+      MediaPlayerCommand::Mute => proto::api::MediaPlayerCommand::MEDIA_PLAYER_COMMAND_MUTE,
+      MediaPlayerCommand::Unmute => proto::api::MediaPlayerCommand::MEDIA_PLAYER_COMMAND_UNMUTE,
+      MediaPlayerCommand::Toggle => proto::api::MediaPlayerCommand::MEDIA_PLAYER_COMMAND_TOGGLE,
+      MediaPlayerCommand::VolumeUp => {
+        proto::api::MediaPlayerCommand::MEDIA_PLAYER_COMMAND_VOLUME_UP
+      }
+      MediaPlayerCommand::VolumeDown => {
+        proto::api::MediaPlayerCommand::MEDIA_PLAYER_COMMAND_VOLUME_DOWN
+      }
+      MediaPlayerCommand::Enqueue => proto::api::MediaPlayerCommand::MEDIA_PLAYER_COMMAND_ENQUEUE,
+      MediaPlayerCommand::RepeatOne => {
+        proto::api::MediaPlayerCommand::MEDIA_PLAYER_COMMAND_REPEAT_ONE
+      }
+      MediaPlayerCommand::RepeatOff => {
+        proto::api::MediaPlayerCommand::MEDIA_PLAYER_COMMAND_REPEAT_OFF
+      }
+      MediaPlayerCommand::ClearPlaylist => {
+        proto::api::MediaPlayerCommand::MEDIA_PLAYER_COMMAND_CLEAR_PLAYLIST
+      }
+      MediaPlayerCommand::TurnOn => proto::api::MediaPlayerCommand::MEDIA_PLAYER_COMMAND_TURN_ON,
+      MediaPlayerCommand::TurnOff => proto::api::MediaPlayerCommand::MEDIA_PLAYER_COMMAND_TURN_OFF,
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum MediaPlayerFormatPurpose {
   Default = 0,
@@ -1111,6 +1491,19 @@ impl From<proto::api::MediaPlayerFormatPurpose> for MediaPlayerFormatPurpose {
   }
 }
 
+impl From<MediaPlayerFormatPurpose> for proto::api::MediaPlayerFormatPurpose {
+  fn from(value: MediaPlayerFormatPurpose) -> Self {
+    match value {
+      MediaPlayerFormatPurpose::Default => {
+        proto::api::MediaPlayerFormatPurpose::MEDIA_PLAYER_FORMAT_PURPOSE_DEFAULT
+      }
+      MediaPlayerFormatPurpose::Announcement => {
+        proto::api::MediaPlayerFormatPurpose::MEDIA_PLAYER_FORMAT_PURPOSE_ANNOUNCEMENT
+      }
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MediaPlayerSupportedFormat {
   format: String,
@@ -1132,6 +1525,19 @@ impl From<proto::api::MediaPlayerSupportedFormat> for MediaPlayerSupportedFormat
   }
 }
 
+impl From<MediaPlayerSupportedFormat> for proto::api::MediaPlayerSupportedFormat {
+  fn from(value: MediaPlayerSupportedFormat) -> Self {
+    proto::api::MediaPlayerSupportedFormat {
+      format: value.format,
+      sample_rate: value.sample_rate,
+      num_channels: value.num_channels,
+      purpose: proto::api::MediaPlayerFormatPurpose::from(value.purpose).into(),
+      sample_bytes: value.sample_bytes,
+      ..Default::default()
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MediaPlayerInfo {
   pub entity_info: BaseEntityInfo,
@@ -1194,6 +1600,37 @@ impl From<proto::api::AlarmControlPanelState> for AlarmControlPanelState {
   }
 }
 
+impl From<AlarmControlPanelState> for proto::api::AlarmControlPanelState {
+  fn from(value: AlarmControlPanelState) -> Self {
+    match value {
+      AlarmControlPanelState::Disarmed => proto::api::AlarmControlPanelState::ALARM_STATE_DISARMED,
+      AlarmControlPanelState::ArmedHome => {
+        proto::api::AlarmControlPanelState::ALARM_STATE_ARMED_HOME
+      }
+      AlarmControlPanelState::ArmedAway => {
+        proto::api::AlarmControlPanelState::ALARM_STATE_ARMED_AWAY
+      }
+      AlarmControlPanelState::ArmedNight => {
+        proto::api::AlarmControlPanelState::ALARM_STATE_ARMED_NIGHT
+      }
+      AlarmControlPanelState::ArmedVacation => {
+        proto::api::AlarmControlPanelState::ALARM_STATE_ARMED_VACATION
+      }
+      AlarmControlPanelState::ArmedCustomBypass => {
+        proto::api::AlarmControlPanelState::ALARM_STATE_ARMED_CUSTOM_BYPASS
+      }
+      AlarmControlPanelState::Pending => proto::api::AlarmControlPanelState::ALARM_STATE_PENDING,
+      AlarmControlPanelState::Arming => proto::api::AlarmControlPanelState::ALARM_STATE_ARMING,
+      AlarmControlPanelState::Disarming => {
+        proto::api::AlarmControlPanelState::ALARM_STATE_DISARMING
+      }
+      AlarmControlPanelState::Triggered => {
+        proto::api::AlarmControlPanelState::ALARM_STATE_TRIGGERED
+      }
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AlarmControlPanelCommand {
   Disarm = 0,
@@ -1233,6 +1670,34 @@ impl From<proto::api::AlarmControlPanelStateCommand> for AlarmControlPanelComman
   }
 }
 
+impl From<AlarmControlPanelCommand> for proto::api::AlarmControlPanelStateCommand {
+  fn from(value: AlarmControlPanelCommand) -> Self {
+    match value {
+      AlarmControlPanelCommand::Disarm => {
+        proto::api::AlarmControlPanelStateCommand::ALARM_CONTROL_PANEL_DISARM
+      }
+      AlarmControlPanelCommand::ArmHome => {
+        proto::api::AlarmControlPanelStateCommand::ALARM_CONTROL_PANEL_ARM_HOME
+      }
+      AlarmControlPanelCommand::ArmAway => {
+        proto::api::AlarmControlPanelStateCommand::ALARM_CONTROL_PANEL_ARM_AWAY
+      }
+      AlarmControlPanelCommand::ArmNight => {
+        proto::api::AlarmControlPanelStateCommand::ALARM_CONTROL_PANEL_ARM_NIGHT
+      }
+      AlarmControlPanelCommand::ArmVacation => {
+        proto::api::AlarmControlPanelStateCommand::ALARM_CONTROL_PANEL_ARM_VACATION
+      }
+      AlarmControlPanelCommand::ArmCustomBypass => {
+        proto::api::AlarmControlPanelStateCommand::ALARM_CONTROL_PANEL_ARM_CUSTOM_BYPASS
+      }
+      AlarmControlPanelCommand::Trigger => {
+        proto::api::AlarmControlPanelStateCommand::ALARM_CONTROL_PANEL_TRIGGER
+      }
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AlarmControlPanelInfo {
   pub entity_info: BaseEntityInfo,
@@ -1263,6 +1728,15 @@ impl From<proto::api::TextMode> for TextMode {
   }
 }
 
+impl From<TextMode> for proto::api::TextMode {
+  fn from(value: TextMode) -> Self {
+    match value {
+      TextMode::Text => proto::api::TextMode::TEXT_MODE_TEXT,
+      TextMode::Password => proto::api::TextMode::TEXT_MODE_PASSWORD,
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TextInfo {
   pub entity_info: BaseEntityInfo,
@@ -1287,6 +1761,26 @@ pub enum UpdateCommand {
   Check,
 }
 
+impl From<proto::api::UpdateCommand> for UpdateCommand {
+  fn from(value: proto::api::UpdateCommand) -> Self {
+    match value {
+      proto::api::UpdateCommand::UPDATE_COMMAND_NONE => UpdateCommand::None,
+      proto::api::UpdateCommand::UPDATE_COMMAND_UPDATE => UpdateCommand::Install,
+      proto::api::UpdateCommand::UPDATE_COMMAND_CHECK => UpdateCommand::Check,
+    }
+  }
+}
+
+impl From<UpdateCommand> for proto::api::UpdateCommand {
+  fn from(value: UpdateCommand) -> Self {
+    match value {
+      UpdateCommand::None => proto::api::UpdateCommand::UPDATE_COMMAND_NONE,
+      UpdateCommand::Install => proto::api::UpdateCommand::UPDATE_COMMAND_UPDATE,
+      UpdateCommand::Check => proto::api::UpdateCommand::UPDATE_COMMAND_CHECK,
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct UpdateInfo {
   pub entity_info: BaseEntityInfo,
@@ -1307,51 +1801,227 @@ pub struct UpdateState {
   pub release_url: String,
 }
 
-// ==================== USER-DEFINED SERVICES ====================
+// ==================== WATER HEATER ====================
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct HomeassistantActionRequest {
-  pub service: String,
-  pub is_event: bool,
-  pub data: HashMap<String, String>,
-  pub data_template: HashMap<String, String>,
-  pub variables: HashMap<String, String>,
-  pub call_id: u32,
-  pub wants_response: bool,
-  pub response_template: String,
+pub enum WaterHeaterMode {
+  Off = 0,
+  Eco,
+  Electric,
+  Performance,
+  HighDemand,
+  HeatPump,
+  Gas,
 }
 
-impl From<proto::api::HomeassistantActionRequest> for HomeassistantActionRequest {
-  fn from(value: proto::api::HomeassistantActionRequest) -> Self {
-    let data = value
-      .data
-      .into_iter()
-      .map(|item| (item.key, item.value))
-      .collect();
-    let data_template = value
-      .data_template
-      .into_iter()
-      .map(|item| (item.key, item.value))
-      .collect();
-    let variables = value
-      .variables
-      .into_iter()
-      .map(|item| (item.key, item.value))
-      .collect();
+impl From<proto::api::WaterHeaterMode> for WaterHeaterMode {
+  fn from(value: proto::api::WaterHeaterMode) -> Self {
+    match value {
+      proto::api::WaterHeaterMode::WATER_HEATER_MODE_OFF => WaterHeaterMode::Off,
+      proto::api::WaterHeaterMode::WATER_HEATER_MODE_ECO => WaterHeaterMode::Eco,
+      proto::api::WaterHeaterMode::WATER_HEATER_MODE_ELECTRIC => WaterHeaterMode::Electric,
+      proto::api::WaterHeaterMode::WATER_HEATER_MODE_PERFORMANCE => WaterHeaterMode::Performance,
+      proto::api::WaterHeaterMode::WATER_HEATER_MODE_HIGH_DEMAND => WaterHeaterMode::HighDemand,
+      proto::api::WaterHeaterMode::WATER_HEATER_MODE_HEAT_PUMP => WaterHeaterMode::HeatPump,
+      proto::api::WaterHeaterMode::WATER_HEATER_MODE_GAS => WaterHeaterMode::Gas,
+    }
+  }
+}
 
-    Self {
-      service: value.service,
-      is_event: value.is_event,
-      data,
-      data_template,
-      variables,
-      call_id: value.call_id,
-      wants_response: value.wants_response,
-      response_template: value.response_template,
+impl From<WaterHeaterMode> for proto::api::WaterHeaterMode {
+  fn from(value: WaterHeaterMode) -> Self {
+    match value {
+      WaterHeaterMode::Off => proto::api::WaterHeaterMode::WATER_HEATER_MODE_OFF,
+      WaterHeaterMode::Eco => proto::api::WaterHeaterMode::WATER_HEATER_MODE_ECO,
+      WaterHeaterMode::Electric => proto::api::WaterHeaterMode::WATER_HEATER_MODE_ELECTRIC,
+      WaterHeaterMode::Performance => proto::api::WaterHeaterMode::WATER_HEATER_MODE_PERFORMANCE,
+      WaterHeaterMode::HighDemand => proto::api::WaterHeaterMode::WATER_HEATER_MODE_HIGH_DEMAND,
+      WaterHeaterMode::HeatPump => proto::api::WaterHeaterMode::WATER_HEATER_MODE_HEAT_PUMP,
+      WaterHeaterMode::Gas => proto::api::WaterHeaterMode::WATER_HEATER_MODE_GAS,
     }
   }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[bitflags]
+#[repr(u32)]
+#[derive(Debug, Clone, PartialEq, Eq, Copy)]
+pub enum WaterHeaterStateFlag {
+  Away = 1 << 0,
+  On = 1 << 1,
+}
+
+#[bitflags]
+#[repr(u32)]
+#[derive(Debug, Clone, PartialEq, Eq, Copy)]
+pub(crate) enum WaterHeaterCommandField {
+  Mode = 1 << 0,
+  TargetTemperature = 1 << 1,
+  State = 1 << 2,
+  TargetTemperatureLow = 1 << 3,
+  TargetTemperatureHigh = 1 << 4,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WaterHeaterInfo {
+  pub entity_info: BaseEntityInfo,
+  pub min_temperature: f32,
+  pub max_temperature: f32,
+  pub target_temperature_step: f32,
+  pub supported_modes: Vec<WaterHeaterMode>,
+  /// Bitmask of ESPHome's `WaterHeaterFeature` flags
+  pub supported_features: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WaterHeaterState {
+  pub entity_state: BaseEntityState,
+  pub current_temperature: f32,
+  pub target_temperature: f32,
+  pub target_temperature_low: f32,
+  pub target_temperature_high: f32,
+  pub mode: WaterHeaterMode,
+  pub state: BitFlags<WaterHeaterStateFlag>,
+}
+
+// ==================== INFRARED ====================
+
+#[bitflags]
+#[repr(u32)]
+#[derive(Debug, Clone, PartialEq, Eq, Copy)]
+pub enum InfraredCapability {
+  Transmitter = 1 << 0,
+  Receiver = 1 << 1,
+}
+
+/// An infrared/RF transceiver. It has no state; it only sends and captures
+/// raw timings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InfraredInfo {
+  pub entity_info: BaseEntityInfo,
+  pub capabilities: BitFlags<InfraredCapability>,
+}
+
+/// Timings captured by an infrared/RF receiver.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InfraredReceiveEvent {
+  pub key: u32,
+  /// Microseconds; positive marks and negative spaces
+  pub timings: Vec<i32>,
+}
+
+impl InfraredReceiveEvent {
+  /// Recognise the capture as a command of a supported protocol.
+  pub fn decode(&self) -> Option<crate::infrared::IrCode> {
+    crate::infrared::IrCode::decode(&self.timings)
+  }
+}
+
+impl From<proto::api::InfraredRFReceiveEvent> for InfraredReceiveEvent {
+  fn from(value: proto::api::InfraredRFReceiveEvent) -> Self {
+    Self {
+      key: value.key,
+      timings: value.timings,
+    }
+  }
+}
+
+// ==================== Z-WAVE ====================
+
+/// Traffic from a Z-Wave proxy, in the order the device sent it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ZWaveProxyEvent {
+  /// Bytes from the Z-Wave controller's serial port
+  Frame(Vec<u8>),
+  /// The controller joined another network
+  HomeIdChanged(u32),
+}
+
+// ==================== USER-DEFINED SERVICES ====================
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HomeassistantActionRequest {
+  pub service: String,
+  pub is_event: bool,
+  pub data: HashMap<String, String>,
+  pub data_template: HashMap<String, String>,
+  pub variables: HashMap<String, String>,
+  pub call_id: u32,
+  pub wants_response: bool,
+  pub response_template: String,
+}
+
+impl From<proto::api::HomeassistantActionRequest> for HomeassistantActionRequest {
+  fn from(value: proto::api::HomeassistantActionRequest) -> Self {
+    let data = value
+      .data
+      .into_iter()
+      .map(|item| (item.key, item.value))
+      .collect();
+    let data_template = value
+      .data_template
+      .into_iter()
+      .map(|item| (item.key, item.value))
+      .collect();
+    let variables = value
+      .variables
+      .into_iter()
+      .map(|item| (item.key, item.value))
+      .collect();
+
+    Self {
+      service: value.service,
+      is_event: value.is_event,
+      data,
+      data_template,
+      variables,
+      call_id: value.call_id,
+      wants_response: value.wants_response,
+      response_template: value.response_template,
+    }
+  }
+}
+
+/// Reply to a [`HomeassistantActionRequest`] that set `wants_response`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HomeassistantActionResponse {
+  pub call_id: u32,
+  pub success: bool,
+  pub error_message: String,
+  /// JSON result of the action, if any
+  pub response_data: Vec<u8>,
+}
+
+impl HomeassistantActionResponse {
+  pub fn success(call_id: u32, response_data: Vec<u8>) -> Self {
+    Self {
+      call_id,
+      success: true,
+      error_message: String::new(),
+      response_data,
+    }
+  }
+
+  pub fn error(call_id: u32, error_message: impl Into<String>) -> Self {
+    Self {
+      call_id,
+      success: false,
+      error_message: error_message.into(),
+      response_data: Vec::new(),
+    }
+  }
+}
+
+impl From<HomeassistantActionResponse> for proto::api::HomeassistantActionResponse {
+  fn from(value: HomeassistantActionResponse) -> Self {
+    proto::api::HomeassistantActionResponse {
+      call_id: value.call_id,
+      success: value.success,
+      error_message: value.error_message,
+      response_data: value.response_data,
+      ..Default::default()
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum UserServiceArgType {
   Bool = 0,
   Int,
@@ -1384,11 +2054,113 @@ pub struct UserServiceArg {
   pub arg_type: UserServiceArgType,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SupportsResponseType {
+  None = 0,
+  Optional,
+  Only,
+  /// Reports success or failure without a data payload
+  Status,
+}
+
+impl From<proto::api::SupportsResponseType> for SupportsResponseType {
+  fn from(value: proto::api::SupportsResponseType) -> Self {
+    match value {
+      proto::api::SupportsResponseType::SUPPORTS_RESPONSE_NONE => SupportsResponseType::None,
+      proto::api::SupportsResponseType::SUPPORTS_RESPONSE_OPTIONAL => {
+        SupportsResponseType::Optional
+      }
+      proto::api::SupportsResponseType::SUPPORTS_RESPONSE_ONLY => SupportsResponseType::Only,
+      proto::api::SupportsResponseType::SUPPORTS_RESPONSE_STATUS => SupportsResponseType::Status,
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct UserService {
   pub name: String,
   pub key: u32,
   pub args: Vec<UserServiceArg>,
+  pub supports_response: SupportsResponseType,
+}
+
+/// A typed argument value for executing a user service.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UserServiceArgValue {
+  Bool(bool),
+  Int(i32),
+  Float(f32),
+  String(String),
+  BoolArray(Vec<bool>),
+  IntArray(Vec<i32>),
+  FloatArray(Vec<f32>),
+  StringArray(Vec<String>),
+}
+
+impl UserServiceArgValue {
+  pub fn arg_type(&self) -> UserServiceArgType {
+    match self {
+      UserServiceArgValue::Bool(_) => UserServiceArgType::Bool,
+      UserServiceArgValue::Int(_) => UserServiceArgType::Int,
+      UserServiceArgValue::Float(_) => UserServiceArgType::Float,
+      UserServiceArgValue::String(_) => UserServiceArgType::String,
+      UserServiceArgValue::BoolArray(_) => UserServiceArgType::BoolArray,
+      UserServiceArgValue::IntArray(_) => UserServiceArgType::IntArray,
+      UserServiceArgValue::FloatArray(_) => UserServiceArgType::FloatArray,
+      UserServiceArgValue::StringArray(_) => UserServiceArgType::StringArray,
+    }
+  }
+}
+
+impl From<UserServiceArgValue> for proto::api::ExecuteServiceArgument {
+  fn from(value: UserServiceArgValue) -> Self {
+    let mut arg = proto::api::ExecuteServiceArgument::default();
+    match value {
+      UserServiceArgValue::Bool(v) => arg.bool_ = v,
+      UserServiceArgValue::Int(v) => {
+        // Devices before API 1.3 only read the legacy field
+        arg.legacy_int = v;
+        arg.int_ = v;
+      }
+      UserServiceArgValue::Float(v) => arg.float_ = v,
+      UserServiceArgValue::String(v) => arg.string_ = v,
+      UserServiceArgValue::BoolArray(v) => arg.bool_array = v,
+      UserServiceArgValue::IntArray(v) => arg.int_array = v,
+      UserServiceArgValue::FloatArray(v) => arg.float_array = v,
+      UserServiceArgValue::StringArray(v) => arg.string_array = v,
+    }
+    arg
+  }
+}
+
+/// Result of a user service call made with a response requested.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecuteServiceResponse {
+  pub call_id: u32,
+  pub success: bool,
+  pub error_message: String,
+  /// JSON payload; empty for status-only services
+  pub response_data: Vec<u8>,
+}
+
+impl ExecuteServiceResponse {
+  /// The response payload as a JSON string, if there is one.
+  pub fn response_json(&self) -> Option<&str> {
+    std::str::from_utf8(&self.response_data)
+      .ok()
+      .filter(|json| !json.is_empty())
+  }
+}
+
+impl From<proto::api::ExecuteServiceResponse> for ExecuteServiceResponse {
+  fn from(value: proto::api::ExecuteServiceResponse) -> Self {
+    ExecuteServiceResponse {
+      call_id: value.call_id,
+      success: value.success,
+      error_message: value.error_message,
+      response_data: value.response_data,
+    }
+  }
 }
 
 // ==================== BLUETOOTH ====================
@@ -1396,9 +2168,9 @@ pub struct UserService {
 pub fn uuid_convert(uuid: String) -> String {
   let mut uuid = uuid.to_lowercase();
   if uuid.len() < 8 {
-    uuid = format!("0000{}-0000-1000-8000-00805f9b34fb", uuid[2..].to_string());
+    uuid = format!("0000{}-0000-1000-8000-00805f9b34fb", &uuid[2..]);
   }
-  return uuid;
+  uuid
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -1422,18 +2194,19 @@ impl BluetoothLEAdvertisement {
     if !raw_manufacturer_data.is_empty() {
       if !raw_manufacturer_data[0].data.is_empty() {
         raw_manufacturer_data.iter().for_each(|item| {
-          manufacturer_data.insert(item.uuid.parse().unwrap(), item.data.clone());
+          if let Some(id) = parse_company_id(&item.uuid) {
+            manufacturer_data.insert(id, item.data.clone());
+          }
         });
       } else {
-        // legacy data
+        // legacy data: one byte per uint32
         raw_manufacturer_data.iter().for_each(|item| {
+          let Some(id) = parse_company_id(&item.uuid) else {
+            return;
+          };
           manufacturer_data.insert(
-            item.uuid.parse().unwrap(),
-            item
-              .legacy_data
-              .iter()
-              .flat_map(|&num| num.to_le_bytes().to_vec())
-              .collect(),
+            id,
+            item.legacy_data.iter().map(|&byte| byte as u8).collect(),
           );
         });
       }
@@ -1446,15 +2219,11 @@ impl BluetoothLEAdvertisement {
           service_data.insert(uuid_convert(item.uuid.clone()), item.data.clone());
         });
       } else {
-        // legacy data
+        // legacy data: one byte per uint32
         raw_service_data.iter().for_each(|item| {
           service_data.insert(
             uuid_convert(item.uuid.clone()),
-            item
-              .legacy_data
-              .iter()
-              .flat_map(|&num| num.to_le_bytes().to_vec())
-              .collect(),
+            item.legacy_data.iter().map(|&byte| byte as u8).collect(),
           );
         });
       }
@@ -1481,33 +2250,242 @@ impl BluetoothLEAdvertisement {
   }
 }
 
+impl From<proto::api::BluetoothLEAdvertisementResponse> for BluetoothLEAdvertisement {
+  fn from(value: proto::api::BluetoothLEAdvertisementResponse) -> Self {
+    Self::from_pb(value)
+  }
+}
+
+impl From<proto::api::BluetoothLERawAdvertisement> for BluetoothLEAdvertisement {
+  /// Decode the AD structures of a raw advertisement (Core Spec Vol 3, Part C, 11).
+  fn from(value: proto::api::BluetoothLERawAdvertisement) -> Self {
+    let mut advertisement = Self {
+      address: value.address,
+      rssi: value.rssi,
+      address_type: value.address_type,
+      name: String::new(),
+      service_uuids: Vec::new(),
+      service_data: HashMap::new(),
+      manufacturer_data: HashMap::new(),
+    };
+
+    let data = value.data;
+    let mut offset = 0;
+    while offset < data.len() {
+      let length = data[offset] as usize;
+      // A zero length marks the start of the padding
+      if length == 0 || offset + 1 + length > data.len() {
+        break;
+      }
+      let ad_type = data[offset + 1];
+      let payload = &data[offset + 2..offset + 1 + length];
+      offset += 1 + length;
+
+      match ad_type {
+        // Shortened and complete local name; prefer the complete one
+        0x08 if advertisement.name.is_empty() => {
+          advertisement.name = String::from_utf8_lossy(payload).into();
+        }
+        0x09 => advertisement.name = String::from_utf8_lossy(payload).into(),
+        // Incomplete and complete lists of 16, 32 and 128-bit service UUIDs
+        0x02 | 0x03 => push_uuids(&mut advertisement.service_uuids, payload, 2),
+        0x04 | 0x05 => push_uuids(&mut advertisement.service_uuids, payload, 4),
+        0x06 | 0x07 => push_uuids(&mut advertisement.service_uuids, payload, 16),
+        // Service data keyed by a 16, 32 or 128-bit UUID
+        0x16 | 0x20 | 0x21 => {
+          let uuid_len = match ad_type {
+            0x16 => 2,
+            0x20 => 4,
+            _ => 16,
+          };
+          if payload.len() >= uuid_len {
+            advertisement.service_data.insert(
+              bluetooth_uuid_from_le_bytes(&payload[..uuid_len]),
+              payload[uuid_len..].to_vec(),
+            );
+          }
+        }
+        // Manufacturer specific data, keyed by company identifier
+        0xFF if payload.len() >= 2 => {
+          advertisement.manufacturer_data.insert(
+            u16::from_le_bytes([payload[0], payload[1]]),
+            payload[2..].to_vec(),
+          );
+        }
+        _ => {}
+      }
+    }
+
+    advertisement
+  }
+}
+
+/// Company identifiers arrive as hex strings such as `0x004C`.
+fn parse_company_id(uuid: &str) -> Option<u16> {
+  let hex = uuid
+    .strip_prefix("0x")
+    .or_else(|| uuid.strip_prefix("0X"))
+    .unwrap_or(uuid);
+  u16::from_str_radix(hex, 16).ok()
+}
+
+fn push_uuids(uuids: &mut Vec<String>, payload: &[u8], uuid_len: usize) {
+  uuids.extend(
+    payload
+      .chunks_exact(uuid_len)
+      .map(bluetooth_uuid_from_le_bytes),
+  );
+}
+
+/// Format a little-endian 16, 32 or 128-bit UUID as a full 128-bit UUID string.
+fn bluetooth_uuid_from_le_bytes(bytes: &[u8]) -> String {
+  match bytes.len() {
+    2 => format!(
+      "0000{:04x}-0000-1000-8000-00805f9b34fb",
+      u16::from_le_bytes([bytes[0], bytes[1]])
+    ),
+    4 => format!(
+      "{:08x}-0000-1000-8000-00805f9b34fb",
+      u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+    ),
+    _ => {
+      let hex: String = bytes.iter().rev().map(|b| format!("{b:02x}")).collect();
+      format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+      )
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BluetoothScannerState {
+  Idle = 0,
+  Starting,
+  Running,
+  Failed,
+  Stopping,
+  Stopped,
+}
+
+impl From<proto::api::BluetoothScannerState> for BluetoothScannerState {
+  fn from(value: proto::api::BluetoothScannerState) -> Self {
+    match value {
+      proto::api::BluetoothScannerState::BLUETOOTH_SCANNER_STATE_IDLE => {
+        BluetoothScannerState::Idle
+      }
+      proto::api::BluetoothScannerState::BLUETOOTH_SCANNER_STATE_STARTING => {
+        BluetoothScannerState::Starting
+      }
+      proto::api::BluetoothScannerState::BLUETOOTH_SCANNER_STATE_RUNNING => {
+        BluetoothScannerState::Running
+      }
+      proto::api::BluetoothScannerState::BLUETOOTH_SCANNER_STATE_FAILED => {
+        BluetoothScannerState::Failed
+      }
+      proto::api::BluetoothScannerState::BLUETOOTH_SCANNER_STATE_STOPPING => {
+        BluetoothScannerState::Stopping
+      }
+      proto::api::BluetoothScannerState::BLUETOOTH_SCANNER_STATE_STOPPED => {
+        BluetoothScannerState::Stopped
+      }
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BluetoothScannerMode {
+  Passive = 0,
+  Active,
+}
+
+impl From<proto::api::BluetoothScannerMode> for BluetoothScannerMode {
+  fn from(value: proto::api::BluetoothScannerMode) -> Self {
+    match value {
+      proto::api::BluetoothScannerMode::BLUETOOTH_SCANNER_MODE_PASSIVE => {
+        BluetoothScannerMode::Passive
+      }
+      proto::api::BluetoothScannerMode::BLUETOOTH_SCANNER_MODE_ACTIVE => {
+        BluetoothScannerMode::Active
+      }
+    }
+  }
+}
+
+impl From<BluetoothScannerMode> for proto::api::BluetoothScannerMode {
+  fn from(value: BluetoothScannerMode) -> Self {
+    match value {
+      BluetoothScannerMode::Passive => {
+        proto::api::BluetoothScannerMode::BLUETOOTH_SCANNER_MODE_PASSIVE
+      }
+      BluetoothScannerMode::Active => {
+        proto::api::BluetoothScannerMode::BLUETOOTH_SCANNER_MODE_ACTIVE
+      }
+    }
+  }
+}
+
+/// Scanner state reported by a Bluetooth proxy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BluetoothScannerStatus {
+  pub state: BluetoothScannerState,
+  pub mode: BluetoothScannerMode,
+  /// The mode set in the device's YAML, which `mode` returns to after a restart
+  pub configured_mode: BluetoothScannerMode,
+}
+
+impl From<proto::api::BluetoothScannerStateResponse> for BluetoothScannerStatus {
+  fn from(value: proto::api::BluetoothScannerStateResponse) -> Self {
+    Self {
+      state: value.state.enum_value_or_default().into(),
+      mode: value.mode.enum_value_or_default().into(),
+      configured_mode: value.configured_mode.enum_value_or_default().into(),
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BluetoothDeviceConnection {
   pub address: u64,
   pub connected: bool,
   pub mtu: u16,
-  pub error: u8,
+  pub error: i32,
+}
+
+impl From<proto::api::BluetoothDeviceConnectionResponse> for BluetoothDeviceConnection {
+  fn from(value: proto::api::BluetoothDeviceConnectionResponse) -> Self {
+    Self {
+      address: value.address,
+      connected: value.connected,
+      mtu: value.mtu as u16,
+      error: value.error,
+    }
+  }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BluetoothDevicePairing {
   pub address: u64,
   pub paired: bool,
-  pub error: u8,
+  pub error: i32,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BluetoothDeviceUnpairing {
   pub address: u64,
   pub success: bool,
-  pub error: u8,
+  pub error: i32,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BluetoothDeviceClearCache {
   pub address: u64,
   pub success: bool,
-  pub error: u8,
+  pub error: i32,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -1517,12 +2495,55 @@ pub struct BluetoothGATTRead {
   pub data: Vec<u8>,
 }
 
+/// A notification or indication from a characteristic of a connected device.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BluetoothGATTNotifyData {
+  pub address: u64,
+  pub handle: u16,
+  pub data: Vec<u8>,
+}
+
+impl From<proto::api::BluetoothGATTNotifyDataResponse> for BluetoothGATTNotifyData {
+  fn from(value: proto::api::BluetoothGATTNotifyDataResponse) -> Self {
+    Self {
+      address: value.address,
+      handle: value.handle as u16,
+      data: value.data,
+    }
+  }
+}
+
+/// UUIDs come either split into two u64 halves or, from API 1.12, as a
+/// 16/32-bit `short_uuid` on the Bluetooth base UUID.
+fn gatt_uuid(uuid: &[u64], short_uuid: u32) -> String {
+  match uuid {
+    [high, low] => format!(
+      "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+      high >> 32,
+      (high >> 16) & 0xFFFF,
+      high & 0xFFFF,
+      low >> 48,
+      low & 0xFFFF_FFFF_FFFF
+    ),
+    _ => format!("{short_uuid:08x}-0000-1000-8000-00805f9b34fb"),
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BluetoothGATTDescriptor {
   pub uuid: String,
   pub handle: u16,
 }
 
+impl From<proto::api::BluetoothGATTDescriptor> for BluetoothGATTDescriptor {
+  fn from(value: proto::api::BluetoothGATTDescriptor) -> Self {
+    Self {
+      uuid: gatt_uuid(&value.uuid, value.short_uuid),
+      handle: value.handle as u16,
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BluetoothGATTCharacteristic {
   pub uuid: String,
@@ -1531,6 +2552,17 @@ pub struct BluetoothGATTCharacteristic {
   pub descriptors: Vec<BluetoothGATTDescriptor>,
 }
 
+impl From<proto::api::BluetoothGATTCharacteristic> for BluetoothGATTCharacteristic {
+  fn from(value: proto::api::BluetoothGATTCharacteristic) -> Self {
+    Self {
+      uuid: gatt_uuid(&value.uuid, value.short_uuid),
+      handle: value.handle as u16,
+      properties: value.properties as u8,
+      descriptors: value.descriptors.into_iter().map(Into::into).collect(),
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BluetoothGATTService {
   pub uuid: String,
@@ -1538,6 +2570,16 @@ pub struct BluetoothGATTService {
   pub characteristics: Vec<BluetoothGATTCharacteristic>,
 }
 
+impl From<proto::api::BluetoothGATTService> for BluetoothGATTService {
+  fn from(value: proto::api::BluetoothGATTService) -> Self {
+    Self {
+      uuid: gatt_uuid(&value.uuid, value.short_uuid),
+      handle: value.handle as u16,
+      characteristics: value.characteristics.into_iter().map(Into::into).collect(),
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BluetoothGATTServices {
   pub address: u64,
@@ -1550,20 +2592,33 @@ pub struct ESPHomeBluetoothGATTServices {
   pub services: Vec<BluetoothGATTService>,
 }
 
+/// Connection slot accounting of a Bluetooth proxy.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BluetoothConnectionsFree {
   pub free: u8,
   pub limit: u8,
+  /// Addresses currently holding a slot
+  pub allocated: Vec<u64>,
+}
+
+impl From<proto::api::BluetoothConnectionsFreeResponse> for BluetoothConnectionsFree {
+  fn from(value: proto::api::BluetoothConnectionsFreeResponse) -> Self {
+    Self {
+      free: value.free as u8,
+      limit: value.limit as u8,
+      allocated: value.allocated,
+    }
+  }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BluetoothGATTError {
   pub address: u64,
   pub handle: u16,
-  pub error: u8,
+  pub error: i32,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BluetoothDeviceRequestType {
   Connect = 0,
   Disconnect,
@@ -1574,7 +2629,27 @@ pub enum BluetoothDeviceRequestType {
   ClearCache,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+impl From<BluetoothDeviceRequestType> for proto::api::BluetoothDeviceRequestType {
+  fn from(value: BluetoothDeviceRequestType) -> Self {
+    match value {
+      BluetoothDeviceRequestType::Connect => proto::api::BluetoothDeviceRequestType::BLUETOOTH_DEVICE_REQUEST_TYPE_CONNECT,
+      BluetoothDeviceRequestType::Disconnect => proto::api::BluetoothDeviceRequestType::BLUETOOTH_DEVICE_REQUEST_TYPE_DISCONNECT,
+      BluetoothDeviceRequestType::Pair => proto::api::BluetoothDeviceRequestType::BLUETOOTH_DEVICE_REQUEST_TYPE_PAIR,
+      BluetoothDeviceRequestType::Unpair => proto::api::BluetoothDeviceRequestType::BLUETOOTH_DEVICE_REQUEST_TYPE_UNPAIR,
+      BluetoothDeviceRequestType::ConnectV3WithCache => {
+        proto::api::BluetoothDeviceRequestType::BLUETOOTH_DEVICE_REQUEST_TYPE_CONNECT_V3_WITH_CACHE
+      }
+      BluetoothDeviceRequestType::ConnectV3WithoutCache => {
+        proto::api::BluetoothDeviceRequestType::BLUETOOTH_DEVICE_REQUEST_TYPE_CONNECT_V3_WITHOUT_CACHE
+      }
+      BluetoothDeviceRequestType::ClearCache => proto::api::BluetoothDeviceRequestType::BLUETOOTH_DEVICE_REQUEST_TYPE_CLEAR_CACHE,
+    }
+  }
+}
+
+#[bitflags]
+#[repr(u32)]
+#[derive(Debug, Clone, PartialEq, Eq, Copy)]
 pub enum VoiceAssistantCommandFlag {
   UseVAD = 1 << 0,
   UseWakeWord = 1 << 1,
@@ -1587,26 +2662,138 @@ pub struct VoiceAssistantAudioSettings {
   pub volume_multiplier: f32,
 }
 
+impl From<proto::api::VoiceAssistantAudioSettings> for VoiceAssistantAudioSettings {
+  fn from(value: proto::api::VoiceAssistantAudioSettings) -> Self {
+    Self {
+      noise_suppression_level: value.noise_suppression_level as u8,
+      auto_gain: value.auto_gain as u8,
+      volume_multiplier: value.volume_multiplier,
+    }
+  }
+}
+
+/// A device asking to start or stop a voice pipeline run.
 #[derive(Debug, Clone, PartialEq)]
 pub struct VoiceAssistantCommand {
   pub start: bool,
   pub conversation_id: String,
-  pub flags: u8,
-  pub audio_settings: Vec<VoiceAssistantAudioSettings>,
+  pub flags: BitFlags<VoiceAssistantCommandFlag>,
+  pub audio_settings: Option<VoiceAssistantAudioSettings>,
+  /// The phrase that woke the device, when `UseWakeWord` is not set
   pub wake_word_phrase: String,
 }
 
+impl From<proto::api::VoiceAssistantRequest> for VoiceAssistantCommand {
+  fn from(value: proto::api::VoiceAssistantRequest) -> Self {
+    Self {
+      start: value.start,
+      conversation_id: value.conversation_id,
+      flags: BitFlags::from_bits_truncate(value.flags),
+      audio_settings: value.audio_settings.into_option().map(Into::into),
+      wake_word_phrase: value.wake_word_phrase,
+    }
+  }
+}
+
+/// A chunk of audio: microphone input from the device or TTS output to it.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct VoiceAssistantAudioData {
   pub data: Vec<u8>,
+  /// Marks the last chunk of the stream
   pub end: bool,
 }
 
+impl From<proto::api::VoiceAssistantAudio> for VoiceAssistantAudioData {
+  fn from(value: proto::api::VoiceAssistantAudio) -> Self {
+    Self {
+      data: value.data,
+      end: value.end,
+    }
+  }
+}
+
+impl From<VoiceAssistantAudioData> for proto::api::VoiceAssistantAudio {
+  fn from(value: VoiceAssistantAudioData) -> Self {
+    proto::api::VoiceAssistantAudio {
+      data: value.data,
+      end: value.end,
+      ..Default::default()
+    }
+  }
+}
+
+/// Messages a voice satellite sends to the pipeline host, in arrival order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VoiceAssistantMessage {
+  Command(VoiceAssistantCommand),
+  Audio(VoiceAssistantAudioData),
+}
+
+/// How the host answers a pipeline start request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoiceAssistantResponse {
+  /// Stream microphone audio over UDP to this port
+  Udp { port: u16 },
+  /// Stream microphone audio as `VoiceAssistantAudio` messages over the API
+  /// connection; requires the `APIAudio` subscription flag
+  ApiAudio,
+  /// The pipeline could not be started
+  Error,
+}
+
+impl From<VoiceAssistantResponse> for proto::api::VoiceAssistantResponse {
+  fn from(value: VoiceAssistantResponse) -> Self {
+    let (port, error) = match value {
+      VoiceAssistantResponse::Udp { port } => (port.into(), false),
+      VoiceAssistantResponse::ApiAudio => (0, false),
+      VoiceAssistantResponse::Error => (0, true),
+    };
+    proto::api::VoiceAssistantResponse {
+      port,
+      error,
+      ..Default::default()
+    }
+  }
+}
+
+/// An announcement for the device to play, optionally followed by listening.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct VoiceAssistantAnnounce {
+  /// URL of the media to play
+  pub media_id: String,
+  /// The announced text, for devices that display it
+  pub text: String,
+  /// URL of a chime played before the announcement
+  pub preannounce_media_id: String,
+  /// Start a conversation once the announcement has played
+  pub start_conversation: bool,
+}
+
+impl From<VoiceAssistantAnnounce> for proto::api::VoiceAssistantAnnounceRequest {
+  fn from(value: VoiceAssistantAnnounce) -> Self {
+    proto::api::VoiceAssistantAnnounceRequest {
+      media_id: value.media_id,
+      text: value.text,
+      preannounce_media_id: value.preannounce_media_id,
+      start_conversation: value.start_conversation,
+      ..Default::default()
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct VoiceAssistantAnnounceFinished {
   pub success: bool,
 }
 
+impl From<proto::api::VoiceAssistantAnnounceFinished> for VoiceAssistantAnnounceFinished {
+  fn from(value: proto::api::VoiceAssistantAnnounceFinished) -> Self {
+    Self {
+      success: value.success,
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct VoiceAssistantWakeWord {
   pub id: String,
@@ -1614,19 +2801,112 @@ pub struct VoiceAssistantWakeWord {
   pub trained_languages: Vec<String>,
 }
 
+impl From<proto::api::VoiceAssistantWakeWord> for VoiceAssistantWakeWord {
+  fn from(value: proto::api::VoiceAssistantWakeWord) -> Self {
+    Self {
+      id: value.id,
+      wake_word: value.wake_word,
+      trained_languages: value.trained_languages,
+    }
+  }
+}
+
+/// A wake word model hosted outside the device, offered for it to download.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VoiceAssistantExternalWakeWord {
+  pub id: String,
+  pub wake_word: String,
+  pub trained_languages: Vec<String>,
+  pub model_type: String,
+  pub model_size: u32,
+  pub model_hash: String,
+  pub url: String,
+}
+
+impl From<VoiceAssistantExternalWakeWord> for proto::api::VoiceAssistantExternalWakeWord {
+  fn from(value: VoiceAssistantExternalWakeWord) -> Self {
+    proto::api::VoiceAssistantExternalWakeWord {
+      id: value.id,
+      wake_word: value.wake_word,
+      trained_languages: value.trained_languages,
+      model_type: value.model_type,
+      model_size: value.model_size,
+      model_hash: value.model_hash,
+      url: value.url,
+      ..Default::default()
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct VoiceAssistantConfigurationResponse {
   pub available_wake_words: Vec<VoiceAssistantWakeWord>,
+  /// Ids of the wake words currently listened for
   pub active_wake_words: Vec<String>,
   pub max_active_wake_words: u8,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct VoiceAssistantConfigurationRequest {}
+impl From<proto::api::VoiceAssistantConfigurationResponse> for VoiceAssistantConfigurationResponse {
+  fn from(value: proto::api::VoiceAssistantConfigurationResponse) -> Self {
+    Self {
+      available_wake_words: value
+        .available_wake_words
+        .into_iter()
+        .map(Into::into)
+        .collect(),
+      active_wake_words: value.active_wake_words,
+      max_active_wake_words: value.max_active_wake_words as u8,
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct VoiceAssistantConfigurationRequest {
+  pub external_wake_words: Vec<VoiceAssistantExternalWakeWord>,
+}
+
+impl From<VoiceAssistantConfigurationRequest> for proto::api::VoiceAssistantConfigurationRequest {
+  fn from(value: VoiceAssistantConfigurationRequest) -> Self {
+    proto::api::VoiceAssistantConfigurationRequest {
+      external_wake_words: value
+        .external_wake_words
+        .into_iter()
+        .map(Into::into)
+        .collect(),
+      ..Default::default()
+    }
+  }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct VoiceAssistantSetConfiguration {
-  pub active_wake_words: Vec<u8>,
+  pub active_wake_words: Vec<String>,
+}
+
+/// A timer started by voice, reported to the device so it can show or ring it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VoiceAssistantTimerEvent {
+  pub event_type: VoiceAssistantTimerEventType,
+  pub timer_id: String,
+  pub name: String,
+  pub total_seconds: u32,
+  pub seconds_left: u32,
+  /// `false` while the timer is paused
+  pub is_active: bool,
+}
+
+impl From<VoiceAssistantTimerEvent> for proto::api::VoiceAssistantTimerEventResponse {
+  fn from(value: VoiceAssistantTimerEvent) -> Self {
+    proto::api::VoiceAssistantTimerEventResponse {
+      event_type: protobuf::EnumOrUnknown::new(value.event_type.into()),
+      timer_id: value.timer_id,
+      name: value.name,
+      total_seconds: value.total_seconds,
+      seconds_left: value.seconds_left,
+      is_active: value.is_active,
+      ..Default::default()
+    }
+  }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -1695,6 +2975,7 @@ impl From<proto::api::SubscribeLogsResponse> for LogEvent {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CameraImage {
   pub key: u32,
+  pub device_id: u32,
   pub data: Vec<u8>,
   /// Whether this is the last frame in the current image
   pub done: bool,
@@ -1704,6 +2985,7 @@ impl From<proto::api::CameraImageResponse> for CameraImage {
   fn from(value: proto::api::CameraImageResponse) -> Self {
     CameraImage {
       key: value.key,
+      device_id: value.device_id,
       data: value.data,
       done: value.done,
     }
@@ -1750,7 +3032,7 @@ impl From<proto::api::SubscribeHomeAssistantStateResponse> for HomeAssistantEven
   }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum VoiceAssistantEventType {
   Error = 0,
   RunStart,
@@ -1763,16 +3045,83 @@ pub enum VoiceAssistantEventType {
   TTSEnd,
   WakeWordStart,
   WakeWordEnd,
-  SSTVADStart,
-  SSTVADEnd,
+  STTVADStart,
+  STTVADEnd,
   TTSStreamStart = 98,
   TTSStreamEnd = 99,
+  IntentProgress = 100,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+impl From<VoiceAssistantEventType> for proto::api::VoiceAssistantEvent {
+  fn from(value: VoiceAssistantEventType) -> Self {
+    match value {
+      VoiceAssistantEventType::Error => proto::api::VoiceAssistantEvent::VOICE_ASSISTANT_ERROR,
+      VoiceAssistantEventType::RunStart => {
+        proto::api::VoiceAssistantEvent::VOICE_ASSISTANT_RUN_START
+      }
+      VoiceAssistantEventType::RunEnd => proto::api::VoiceAssistantEvent::VOICE_ASSISTANT_RUN_END,
+      VoiceAssistantEventType::STTStart => {
+        proto::api::VoiceAssistantEvent::VOICE_ASSISTANT_STT_START
+      }
+      VoiceAssistantEventType::STTEnd => proto::api::VoiceAssistantEvent::VOICE_ASSISTANT_STT_END,
+      VoiceAssistantEventType::IntentStart => {
+        proto::api::VoiceAssistantEvent::VOICE_ASSISTANT_INTENT_START
+      }
+      VoiceAssistantEventType::IntentEnd => {
+        proto::api::VoiceAssistantEvent::VOICE_ASSISTANT_INTENT_END
+      }
+      VoiceAssistantEventType::TTSStart => {
+        proto::api::VoiceAssistantEvent::VOICE_ASSISTANT_TTS_START
+      }
+      VoiceAssistantEventType::TTSEnd => proto::api::VoiceAssistantEvent::VOICE_ASSISTANT_TTS_END,
+      VoiceAssistantEventType::WakeWordStart => {
+        proto::api::VoiceAssistantEvent::VOICE_ASSISTANT_WAKE_WORD_START
+      }
+      VoiceAssistantEventType::WakeWordEnd => {
+        proto::api::VoiceAssistantEvent::VOICE_ASSISTANT_WAKE_WORD_END
+      }
+      VoiceAssistantEventType::STTVADStart => {
+        proto::api::VoiceAssistantEvent::VOICE_ASSISTANT_STT_VAD_START
+      }
+      VoiceAssistantEventType::STTVADEnd => {
+        proto::api::VoiceAssistantEvent::VOICE_ASSISTANT_STT_VAD_END
+      }
+      VoiceAssistantEventType::TTSStreamStart => {
+        proto::api::VoiceAssistantEvent::VOICE_ASSISTANT_TTS_STREAM_START
+      }
+      VoiceAssistantEventType::TTSStreamEnd => {
+        proto::api::VoiceAssistantEvent::VOICE_ASSISTANT_TTS_STREAM_END
+      }
+      VoiceAssistantEventType::IntentProgress => {
+        proto::api::VoiceAssistantEvent::VOICE_ASSISTANT_INTENT_PROGRESS
+      }
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum VoiceAssistantTimerEventType {
   TimerStarted = 0,
   TimerUpdated,
   TimerCancelled,
   TimerFinished,
 }
+
+impl From<VoiceAssistantTimerEventType> for proto::api::VoiceAssistantTimerEvent {
+  fn from(value: VoiceAssistantTimerEventType) -> Self {
+    match value {
+      VoiceAssistantTimerEventType::TimerStarted => {
+        proto::api::VoiceAssistantTimerEvent::VOICE_ASSISTANT_TIMER_STARTED
+      }
+      VoiceAssistantTimerEventType::TimerUpdated => {
+        proto::api::VoiceAssistantTimerEvent::VOICE_ASSISTANT_TIMER_UPDATED
+      }
+      VoiceAssistantTimerEventType::TimerCancelled => {
+        proto::api::VoiceAssistantTimerEvent::VOICE_ASSISTANT_TIMER_CANCELLED
+      }
+      VoiceAssistantTimerEventType::TimerFinished => {
+        proto::api::VoiceAssistantTimerEvent::VOICE_ASSISTANT_TIMER_FINISHED
+      }
+    }
+  }
+}
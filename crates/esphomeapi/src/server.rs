@@ -0,0 +1,491 @@
+//! Virtual ESPHome devices.
+//!
+//! A [`VirtualDevice`] accepts ESPHome API connections, e.g. from Home
+//! Assistant, and behaves like an ESPHome node: it lists a fixed set of
+//! entities, pushes their states to subscribed clients and hands incoming
+//! command requests to callbacks. Ping, time and disconnect requests are
+//! handled by the device itself.
+//!
+//! ```ignore
+//! let relay = SwitchInfo {
+//!   entity_info: BaseEntityInfo { key: 1, object_id: "relay".into(), name: "Relay".into(), .. },
+//!   ..
+//! };
+//!
+//! let device = VirtualDevice::builder("gateway")
+//!   .entity(EntityInfo::Switch(relay))
+//!   .on_command(|command: api::SwitchCommandRequest, device: &DeviceHandle| {
+//!     // drive the hardware, then report the new state
+//!     device.set_state(EntityState::Switch(SwitchState { .. }))?;
+//!     Ok(())
+//!   })
+//!   .bind("0.0.0.0:6053")
+//!   .await?;
+//! ```
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use bytes::BytesMut;
+use futures::SinkExt as _;
+use protobuf::{Message as _, MessageFull};
+use tokio::io::{AsyncReadExt as _, AsyncWriteExt as _, BufReader, BufWriter};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{TcpListener, TcpStream, ToSocketAddrs};
+use tokio::sync::{broadcast, watch};
+use tokio::task::JoinHandle;
+use tokio_stream::StreamExt as _;
+use tokio_util::codec::{FramedRead, FramedWrite};
+use tracing::{debug, warn};
+
+use crate::connection::codec::{EspHomeDecoder, EspHomeEncoder, EspHomeHandshake, HandshakeResult};
+use crate::connection::ProtobufMessage;
+use crate::model::{DeviceInfo, EntityInfo, EntityState};
+use crate::time::DeviceTime;
+use crate::utils::{to_message, Options as _};
+use crate::{proto, Error, Result};
+
+/// API version announced in the `HelloResponse`.
+const API_VERSION: (u32, u32) = (1, 10);
+
+/// State updates buffered per connection before a slow client starts
+/// missing some.
+const STATE_BUFFER_SIZE: usize = 256;
+
+type CommandHandler = Box<dyn Fn(&ProtobufMessage, &DeviceHandle) -> Result<()> + Send + Sync>;
+
+pub(crate) type ServerReader = FramedRead<BufReader<OwnedReadHalf>, EspHomeDecoder>;
+pub(crate) type ServerWriter = FramedWrite<BufWriter<OwnedWriteHalf>, EspHomeEncoder>;
+
+/// Builder for a [`VirtualDevice`].
+///
+/// Obtained via [`VirtualDevice::builder()`].
+pub struct VirtualDeviceBuilder {
+  device_info: DeviceInfo,
+  psk: Option<String>,
+  entities: Vec<EntityInfo>,
+  states: Vec<EntityState>,
+  handlers: HashMap<u32, CommandHandler>,
+}
+
+impl VirtualDeviceBuilder {
+  fn new(name: String) -> Self {
+    Self {
+      device_info: DeviceInfo {
+        name,
+        ..Default::default()
+      },
+      psk: None,
+      entities: Vec::new(),
+      states: Vec::new(),
+      handlers: HashMap::new(),
+    }
+  }
+
+  /// What the device reports in its `DeviceInfoResponse`. An empty `name`
+  /// keeps the one given to [`VirtualDevice::builder`].
+  pub fn device_info(mut self, mut device_info: DeviceInfo) -> Self {
+    if device_info.name.is_empty() {
+      device_info.name = std::mem::take(&mut self.device_info.name);
+    }
+    self.device_info = device_info;
+    self
+  }
+
+  /// Require the Noise encryption with this base64 pre-shared key.
+  pub fn psk(mut self, psk: impl Into<String>) -> Self {
+    self.psk = Some(psk.into());
+    self
+  }
+
+  /// Add an entity to the `ListEntitiesRequest` answer.
+  pub fn entity(mut self, entity: EntityInfo) -> Self {
+    self.entities.push(entity);
+    self
+  }
+
+  /// Initial state of an entity, sent when a client subscribes to states.
+  pub fn state(mut self, state: EntityState) -> Self {
+    self.states.push(state);
+    self
+  }
+
+  /// Call `handler` for every request of type `M` a client sends, such as
+  /// `api::SwitchCommandRequest`. Errors are logged and do not close the
+  /// connection.
+  pub fn on_command<M, F>(mut self, handler: F) -> Self
+  where
+    M: MessageFull,
+    F: Fn(M, &DeviceHandle) -> Result<()> + Send + Sync + 'static,
+  {
+    self.handlers.insert(
+      M::get_option_id(),
+      Box::new(move |message, device| {
+        handler(M::parse_from_bytes(&message.protobuf_data)?, device)
+      }),
+    );
+    self
+  }
+
+  /// Listen on `addr`, usually `0.0.0.0:6053`, and start accepting clients.
+  pub async fn bind(self, addr: impl ToSocketAddrs) -> Result<VirtualDevice> {
+    self.serve(TcpListener::bind(addr).await?)
+  }
+
+  /// Start accepting clients on an already bound listener.
+  pub fn serve(mut self, listener: TcpListener) -> Result<VirtualDevice> {
+    // Fail early on a malformed key instead of on the first connection
+    EspHomeHandshake::new_responder(
+      self.psk.clone(),
+      self.device_info.name.clone(),
+      self.device_info.mac_address.clone(),
+    )?;
+    let addr = listener.local_addr()?;
+
+    self.device_info.api_encryption_supported = self.psk.is_some();
+    let entities = self
+      .entities
+      .into_iter()
+      .map(EntityInfo::encode)
+      .collect::<Result<_>>()?;
+
+    let handle = DeviceHandle {
+      shared: Arc::new(Shared {
+        device_info: self.device_info.into(),
+        psk: self.psk,
+        entities,
+        handlers: self.handlers,
+        states: Mutex::new(HashMap::new()),
+        state_tx: broadcast::channel(STATE_BUFFER_SIZE).0,
+        disconnect_tx: broadcast::channel(1).0,
+        time_tx: watch::channel(None).0,
+        connections: Mutex::new(Vec::new()),
+      }),
+    };
+    for state in self.states {
+      handle.set_state(state)?;
+    }
+
+    let accept_task = tokio::spawn(accept_loop(listener, handle.clone()));
+
+    Ok(VirtualDevice {
+      addr,
+      handle,
+      accept_task,
+    })
+  }
+}
+
+/// A virtual ESPHome node accepting API connections.
+///
+/// Any number of clients may be connected at once; all of them see the same
+/// entities and states. Dropping the `VirtualDevice` closes the listener and
+/// every open connection.
+pub struct VirtualDevice {
+  addr: SocketAddr,
+  handle: DeviceHandle,
+  accept_task: JoinHandle<()>,
+}
+
+impl VirtualDevice {
+  pub fn builder(name: impl Into<String>) -> VirtualDeviceBuilder {
+    VirtualDeviceBuilder::new(name.into())
+  }
+
+  /// Local address the device is listening on.
+  pub fn local_addr(&self) -> SocketAddr {
+    self.addr
+  }
+
+  /// A cloneable handle for updating states from other tasks.
+  pub fn handle(&self) -> DeviceHandle {
+    self.handle.clone()
+  }
+
+  /// See [`DeviceHandle::set_state`].
+  pub fn set_state(&self, state: EntityState) -> Result<()> {
+    self.handle.set_state(state)
+  }
+}
+
+impl Drop for VirtualDevice {
+  fn drop(&mut self) {
+    self.accept_task.abort();
+    for connection in self.handle.shared.connections.lock().unwrap().drain(..) {
+      connection.abort();
+    }
+  }
+}
+
+/// Handle to a running [`VirtualDevice`], also passed to command callbacks.
+#[derive(Clone)]
+pub struct DeviceHandle {
+  shared: Arc<Shared>,
+}
+
+impl DeviceHandle {
+  /// Update the state of an entity and push it to subscribed clients.
+  /// Setting the state an entity already has sends nothing.
+  pub fn set_state(&self, state: EntityState) -> Result<()> {
+    let id = (state.device_id(), state.key());
+    let message = state.clone().encode()?;
+
+    let mut states = self.shared.states.lock().unwrap();
+    if states
+      .get(&id)
+      .is_some_and(|(_, current)| current.protobuf_data == message.protobuf_data)
+    {
+      return Ok(());
+    }
+    states.insert(id, (state, message.clone()));
+    // Sent while holding the lock so new subscribers see every update
+    // either in their snapshot or on the channel
+    let _ = self.shared.state_tx.send(message);
+    Ok(())
+  }
+
+  /// Current state of the entity `key` on the sub-device `device_id`.
+  pub fn state(&self, device_id: u32, key: u32) -> Option<EntityState> {
+    let states = self.shared.states.lock().unwrap();
+    states
+      .get(&(device_id, key))
+      .map(|(state, _)| state.clone())
+  }
+
+  /// Time and timezone most recently reported by a client. Clients are asked
+  /// when they subscribe to states.
+  pub fn time(&self) -> Option<DeviceTime> {
+    self.shared.time_tx.borrow().clone()
+  }
+
+  /// Get a receiver for the time reported by clients.
+  pub fn time_receiver(&self) -> watch::Receiver<Option<DeviceTime>> {
+    self.shared.time_tx.subscribe()
+  }
+
+  /// Ask every connected client to disconnect gracefully.
+  pub fn disconnect_clients(&self) {
+    let _ = self.shared.disconnect_tx.send(());
+  }
+}
+
+struct Shared {
+  device_info: proto::api::DeviceInfoResponse,
+  psk: Option<String>,
+  /// Pre-encoded `ListEntities*Response`s
+  entities: Vec<ProtobufMessage>,
+  handlers: HashMap<u32, CommandHandler>,
+  /// Latest state per device id and key, with its encoding
+  states: Mutex<HashMap<(u32, u32), (EntityState, ProtobufMessage)>>,
+  state_tx: broadcast::Sender<ProtobufMessage>,
+  disconnect_tx: broadcast::Sender<()>,
+  time_tx: watch::Sender<Option<DeviceTime>>,
+  connections: Mutex<Vec<JoinHandle<()>>>,
+}
+
+/// What to do with the connection after handling a message.
+enum Next {
+  Continue,
+  SubscribeStates,
+  Close,
+}
+
+impl DeviceHandle {
+  /// Answers a message from a client, pushing the responses to `replies`.
+  fn handle_message(
+    &self,
+    message: &ProtobufMessage,
+    replies: &mut Vec<ProtobufMessage>,
+  ) -> Result<Next> {
+    let shared = &self.shared;
+    let msg_type = message.protobuf_type;
+
+    if msg_type == proto::api::HelloRequest::get_option_id() {
+      let name = &shared.device_info.name;
+      replies.push(to_message(&proto::api::HelloResponse {
+        api_version_major: API_VERSION.0,
+        api_version_minor: API_VERSION.1,
+        server_info: format!("{name} (esphomeapi {})", env!("CARGO_PKG_VERSION")),
+        name: name.clone(),
+        ..Default::default()
+      })?);
+    } else if msg_type == proto::api::AuthenticationRequest::get_option_id() {
+      replies.push(to_message(&proto::api::AuthenticationResponse::default())?);
+    } else if msg_type == proto::api::DeviceInfoRequest::get_option_id() {
+      replies.push(to_message(&shared.device_info)?);
+    } else if msg_type == proto::api::ListEntitiesRequest::get_option_id() {
+      replies.extend(shared.entities.iter().cloned());
+      replies.push(to_message(&proto::api::ListEntitiesDoneResponse::default())?);
+    } else if msg_type == proto::api::SubscribeStatesRequest::get_option_id() {
+      replies.push(to_message(&proto::api::GetTimeRequest::default())?);
+      return Ok(Next::SubscribeStates);
+    } else if msg_type == proto::api::PingRequest::get_option_id() {
+      replies.push(to_message(&proto::api::PingResponse::default())?);
+    } else if msg_type == proto::api::GetTimeResponse::get_option_id() {
+      let response = proto::api::GetTimeResponse::parse_from_bytes(&message.protobuf_data)?;
+      shared.time_tx.send_replace(Some(DeviceTime {
+        epoch_seconds: response.epoch_seconds,
+        timezone: response.timezone,
+      }));
+    } else if msg_type == proto::api::NoiseEncryptionSetKeyRequest::get_option_id() {
+      // The key is fixed by whoever runs the virtual device
+      replies.push(to_message(
+        &proto::api::NoiseEncryptionSetKeyResponse::default(),
+      )?);
+    } else if msg_type == proto::api::DisconnectRequest::get_option_id() {
+      replies.push(to_message(&proto::api::DisconnectResponse::default())?);
+      return Ok(Next::Close);
+    } else if msg_type == proto::api::DisconnectResponse::get_option_id() {
+      return Ok(Next::Close);
+    } else if let Some(handler) = shared.handlers.get(&msg_type) {
+      handler(message, self)?;
+    } else {
+      debug!("Ignoring message type {msg_type}");
+    }
+    Ok(Next::Continue)
+  }
+
+  /// Subscribe to state updates and return the current states to send first.
+  fn subscribe_states(&self) -> (broadcast::Receiver<ProtobufMessage>, Vec<ProtobufMessage>) {
+    let states = self.shared.states.lock().unwrap();
+    let rx = self.shared.state_tx.subscribe();
+    (
+      rx,
+      states
+        .values()
+        .map(|(_, message)| message.clone())
+        .collect(),
+    )
+  }
+}
+
+async fn accept_loop(listener: TcpListener, device: DeviceHandle) {
+  loop {
+    let (stream, peer) = match listener.accept().await {
+      Ok(accepted) => accepted,
+      Err(e) => {
+        warn!("Failed to accept a connection: {e}");
+        continue;
+      }
+    };
+    debug!("Client connected from {peer}");
+
+    let task = tokio::spawn(serve(stream, device.clone()));
+    let mut connections = device.shared.connections.lock().unwrap();
+    connections.retain(|connection| !connection.is_finished());
+    connections.push(task);
+  }
+}
+
+async fn serve(stream: TcpStream, device: DeviceHandle) {
+  if let Err(e) = serve_connection(stream, &device).await {
+    debug!("Client connection closed: {e}");
+  }
+}
+
+/// Complete the device side of the handshake on an accepted connection.
+pub(crate) async fn accept_handshake(
+  stream: TcpStream,
+  mut codec: EspHomeHandshake,
+) -> Result<(ServerReader, ServerWriter)> {
+  let (reader, writer) = stream.into_split();
+  let mut reader = BufReader::new(reader);
+  let mut writer = BufWriter::new(writer);
+
+  let mut buffer = BytesMut::with_capacity(1024);
+  let (decoder, encoder) = loop {
+    match codec.process(&mut buffer)? {
+      HandshakeResult::NeedMoreData => {
+        if reader.read_buf(&mut buffer).await? == 0 {
+          return Err(Error::Disconnected);
+        }
+      }
+      HandshakeResult::SendFrame(frame) => {
+        writer.write_all(&frame).await?;
+        writer.flush().await?;
+      }
+      HandshakeResult::Complete(decoder, encoder) => break (decoder, encoder),
+    }
+  };
+
+  let mut reader = FramedRead::new(reader, decoder);
+  // Keep anything the client sent right after the handshake
+  reader.read_buffer_mut().extend_from_slice(&buffer);
+  Ok((reader, FramedWrite::new(writer, encoder)))
+}
+
+async fn serve_connection(stream: TcpStream, device: &DeviceHandle) -> Result<()> {
+  let shared = &device.shared;
+  let codec = EspHomeHandshake::new_responder(
+    shared.psk.clone(),
+    shared.device_info.name.clone(),
+    shared.device_info.mac_address.clone(),
+  )?;
+  let (mut reader, mut writer) = accept_handshake(stream, codec).await?;
+
+  let mut states: Option<broadcast::Receiver<ProtobufMessage>> = None;
+  let mut disconnect = shared.disconnect_tx.subscribe();
+  let mut replies = Vec::new();
+
+  loop {
+    tokio::select! {
+      message = reader.next() => {
+        let Some(message) = message else {
+          return Ok(());
+        };
+        let message = message?;
+
+        let next = device.handle_message(&message, &mut replies).unwrap_or_else(|e| {
+          warn!("Failed to handle message type {}: {e}", message.protobuf_type);
+          Next::Continue
+        });
+        if let Next::SubscribeStates = next {
+          let (rx, current) = device.subscribe_states();
+          states = Some(rx);
+          replies.extend(current);
+        }
+        for reply in replies.drain(..) {
+          writer.feed(reply).await?;
+        }
+        writer.flush().await?;
+
+        if let Next::Close = next {
+          return Ok(());
+        }
+      }
+      Some(updates) = next_states(&mut states, device) => {
+        for state in updates {
+          writer.feed(state).await?;
+        }
+        writer.flush().await?;
+      }
+      Ok(()) = disconnect.recv() => {
+        writer.send(to_message(&proto::api::DisconnectRequest::default())?).await?;
+      }
+    }
+  }
+}
+
+/// The next state update once the client subscribed; pending until then.
+///
+/// A client that fell behind is sent every current state again, like on
+/// subscribe, so the updates it missed cannot leave it out of date.
+async fn next_states(
+  states: &mut Option<broadcast::Receiver<ProtobufMessage>>,
+  device: &DeviceHandle,
+) -> Option<Vec<ProtobufMessage>> {
+  let Some(rx) = states else {
+    return std::future::pending().await;
+  };
+  match rx.recv().await {
+    Ok(state) => Some(vec![state]),
+    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+      warn!("Client is too slow, skipped {skipped} state updates; resending all states");
+      let (fresh, current) = device.subscribe_states();
+      *rx = fresh;
+      Some(current)
+    }
+    Err(broadcast::error::RecvError::Closed) => None,
+  }
+}
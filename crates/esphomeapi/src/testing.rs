@@ -0,0 +1,510 @@
+//! In-process mock ESPHome device for integration tests.
+//!
+//! Enabled with the `testing` cargo feature. A [`MockDevice`] listens on a
+//! local TCP port, speaks either the plain or the Noise framing, answers the
+//! connection setup requests from a script and records every frame the client
+//! sends so tests can assert on them.
+//!
+//! ```ignore
+//! let device = MockDevice::builder()
+//!   .name("kitchen")
+//!   .entity(&api::ListEntitiesSwitchResponse { key: 1, ..Default::default() })
+//!   .start()
+//!   .await?;
+//!
+//! // connect a client to `device.host()` / `device.port()` ...
+//!
+//! let command: api::SwitchCommandRequest = device.next_received().await?;
+//! ```
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use base64::prelude::*;
+use futures::SinkExt as _;
+use protobuf::{Message as _, MessageFull};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{mpsc, Notify};
+use tokio::task::JoinHandle;
+use tokio_stream::StreamExt as _;
+
+use crate::connection::codec::EspHomeHandshake;
+pub use crate::connection::ProtobufMessage;
+use crate::server::accept_handshake;
+use crate::utils::Options as _;
+use crate::{proto, Client, ClientBuilder, Error, ReconnectPolicy, Result};
+
+/// How long [`MockDevice::next_received`] waits for a matching frame.
+const RECEIVE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// First reconnect delay of clients from [`MockDevice::client_builder`].
+const RECONNECT_DELAY: Duration = Duration::from_millis(20);
+
+type Handler = Box<dyn Fn(&ProtobufMessage) -> Vec<ProtobufMessage> + Send + Sync>;
+
+/// Encode a protobuf message into a frame the mock device can send.
+pub fn frame<M: MessageFull>(message: &M) -> ProtobufMessage {
+  ProtobufMessage {
+    protobuf_type: M::get_option_id(),
+    protobuf_data: message.write_to_bytes().unwrap(),
+  }
+}
+
+/// Builder for a [`MockDevice`].
+///
+/// Obtained via [`MockDevice::builder()`].
+pub struct MockDeviceBuilder {
+  name: String,
+  mac_address: String,
+  psk: Option<String>,
+  password: Option<String>,
+  api_version: (u32, u32),
+  device_info: proto::api::DeviceInfoResponse,
+  entities: Vec<ProtobufMessage>,
+  states: Vec<ProtobufMessage>,
+  handlers: HashMap<u32, Handler>,
+}
+
+impl MockDeviceBuilder {
+  fn new() -> Self {
+    Self {
+      name: "mock-device".to_string(),
+      mac_address: "00:11:22:33:44:55".to_string(),
+      psk: None,
+      password: None,
+      api_version: (1, 10),
+      device_info: proto::api::DeviceInfoResponse::default(),
+      entities: Vec::new(),
+      states: Vec::new(),
+      handlers: HashMap::new(),
+    }
+  }
+
+  /// Node name sent in the server hello, `HelloResponse` and `DeviceInfoResponse`.
+  pub fn name(mut self, name: impl Into<String>) -> Self {
+    self.name = name.into();
+    self
+  }
+
+  /// MAC address reported in `DeviceInfoResponse`.
+  pub fn mac_address(mut self, mac_address: impl Into<String>) -> Self {
+    self.mac_address = mac_address.into();
+    self
+  }
+
+  /// Require the Noise framing with this base64-encoded pre-shared key.
+  pub fn psk(mut self, psk: impl Into<String>) -> Self {
+    self.psk = Some(psk.into());
+    self
+  }
+
+  /// Answer `AuthenticationRequest`s, rejecting any other password.
+  pub fn password(mut self, password: impl Into<String>) -> Self {
+    self.password = Some(password.into());
+    self
+  }
+
+  /// API version reported in `HelloResponse`.
+  pub fn api_version(mut self, major: u32, minor: u32) -> Self {
+    self.api_version = (major, minor);
+    self
+  }
+
+  /// Base `DeviceInfoResponse`. Empty `name` and `mac_address` fields are
+  /// filled in from the builder.
+  pub fn device_info(mut self, device_info: proto::api::DeviceInfoResponse) -> Self {
+    self.device_info = device_info;
+    self
+  }
+
+  /// Add an entity (any `ListEntities*Response`) or user service
+  /// (`ListEntitiesServicesResponse`) to the `ListEntitiesRequest` answer.
+  pub fn entity<M: MessageFull>(mut self, entity: &M) -> Self {
+    self.entities.push(frame(entity));
+    self
+  }
+
+  /// Add a state message sent in answer to `SubscribeStatesRequest`.
+  pub fn state<M: MessageFull>(mut self, state: &M) -> Self {
+    self.states.push(frame(state));
+    self
+  }
+
+  /// Answer every received `M` with the frames returned by `handler`.
+  ///
+  /// Handlers take precedence over the built-in answers.
+  pub fn on<M, F>(mut self, handler: F) -> Self
+  where
+    M: MessageFull,
+    F: Fn(M) -> Vec<ProtobufMessage> + Send + Sync + 'static,
+  {
+    self.handlers.insert(
+      M::get_option_id(),
+      Box::new(
+        move |message| match M::parse_from_bytes(&message.protobuf_data) {
+          Ok(message) => handler(message),
+          Err(_) => Vec::new(),
+        },
+      ),
+    );
+    self
+  }
+
+  /// Bind to a random local port and start accepting connections.
+  pub async fn start(mut self) -> Result<MockDevice> {
+    // Fail early on a malformed key instead of on the first connection
+    EspHomeHandshake::new_responder(
+      self.psk.clone(),
+      self.name.clone(),
+      self.mac_address.clone(),
+    )?;
+
+    if self.device_info.name.is_empty() {
+      self.device_info.name = self.name.clone();
+    }
+    if self.device_info.mac_address.is_empty() {
+      self.device_info.mac_address = self.mac_address.clone();
+    }
+
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+
+    let shared = Arc::new(Shared {
+      psk: Mutex::new(self.psk.clone()),
+      script: self,
+      received: Mutex::new(Vec::new()),
+      consumed: Mutex::new(HashMap::new()),
+      received_notify: Notify::new(),
+      connection: Mutex::new(None),
+      connections: AtomicUsize::new(0),
+      connected_notify: Notify::new(),
+    });
+
+    let accept_task = tokio::spawn(accept_loop(listener, Arc::clone(&shared)));
+
+    Ok(MockDevice {
+      addr,
+      shared,
+      accept_task,
+    })
+  }
+}
+
+/// A scripted fake ESPHome device listening on a local TCP port.
+///
+/// Only one client connection is served at a time; a new connection replaces
+/// the previous one. Dropping the `MockDevice` closes the listener and any
+/// open connection.
+pub struct MockDevice {
+  addr: SocketAddr,
+  shared: Arc<Shared>,
+  accept_task: JoinHandle<()>,
+}
+
+impl MockDevice {
+  pub fn builder() -> MockDeviceBuilder {
+    MockDeviceBuilder::new()
+  }
+
+  /// Local address the device is listening on.
+  pub fn addr(&self) -> SocketAddr {
+    self.addr
+  }
+
+  /// Host to connect to.
+  pub fn host(&self) -> String {
+    self.addr.ip().to_string()
+  }
+
+  /// Port to connect to.
+  pub fn port(&self) -> u32 {
+    self.addr.port() as u32
+  }
+
+  /// A [`ClientBuilder`] for this device with its current PSK, if any, and a
+  /// reconnect policy quick enough for tests that drop the connection.
+  pub fn client_builder(&self) -> ClientBuilder {
+    let psk = self.shared.psk.lock().unwrap().clone();
+    let builder = Client::builder(self.host())
+      .port(self.port())
+      .reconnect_policy(ReconnectPolicy {
+        initial_delay: RECONNECT_DELAY,
+        max_delay: RECONNECT_DELAY * 5,
+        ..Default::default()
+      });
+    match psk {
+      Some(psk) => builder.psk(psk),
+      None => builder,
+    }
+  }
+
+  /// Connect a [`Client`] built by [`client_builder`](Self::client_builder).
+  pub async fn client(&self) -> Result<Client> {
+    self.client_builder().connect().await
+  }
+
+  /// Require `psk` from new connections, as if the device stored a new key.
+  pub fn set_psk(&self, psk: Option<String>) {
+    *self.shared.psk.lock().unwrap() = psk;
+  }
+
+  /// Number of client connections accepted so far.
+  pub fn connection_count(&self) -> usize {
+    self.shared.connections.load(Ordering::SeqCst)
+  }
+
+  /// Wait until at least `count` client connections have been accepted.
+  pub async fn wait_for_connections(&self, count: usize) -> Result<()> {
+    tokio::time::timeout(RECEIVE_TIMEOUT, async {
+      loop {
+        let notified = self.shared.connected_notify.notified();
+        if self.connection_count() >= count {
+          return;
+        }
+        notified.await;
+      }
+    })
+    .await
+    .map_err(|_| Error::Timeout(RECEIVE_TIMEOUT))
+  }
+
+  /// Push a message (e.g. a state update) to the connected client.
+  pub fn send<M: MessageFull>(&self, message: &M) -> Result<()> {
+    self.send_frame(frame(message))
+  }
+
+  /// Push a raw frame to the connected client.
+  pub fn send_frame(&self, message: ProtobufMessage) -> Result<()> {
+    self.shared.outgoing(message)
+  }
+
+  /// Ask the client to disconnect gracefully by sending a `DisconnectRequest`.
+  pub fn request_disconnect(&self) -> Result<()> {
+    self.send(&proto::api::DisconnectRequest::default())
+  }
+
+  /// Close the current connection abruptly, without a `DisconnectRequest`.
+  pub fn drop_connection(&self) {
+    if let Some(connection) = self.shared.connection.lock().unwrap().take() {
+      connection.task.abort();
+    }
+  }
+
+  /// All frames received from clients so far, in order.
+  pub fn received(&self) -> Vec<ProtobufMessage> {
+    self.shared.received.lock().unwrap().clone()
+  }
+
+  /// All received messages of type `M`, in order.
+  pub fn received_of<M: MessageFull>(&self) -> Vec<M> {
+    self
+      .received()
+      .iter()
+      .filter(|message| message.protobuf_type == M::get_option_id())
+      .filter_map(|message| M::parse_from_bytes(&message.protobuf_data).ok())
+      .collect()
+  }
+
+  /// Wait for the next received message of type `M`.
+  ///
+  /// Each call returns a later message than the previous call for the same
+  /// type, so a test can step through the client's requests one by one.
+  pub async fn next_received<M: MessageFull>(&self) -> Result<M> {
+    let message_type = M::get_option_id();
+    let message = tokio::time::timeout(RECEIVE_TIMEOUT, async {
+      loop {
+        let notified = self.shared.received_notify.notified();
+        if let Some(message) = self.shared.take_next(message_type) {
+          return message;
+        }
+        notified.await;
+      }
+    })
+    .await
+    .map_err(|_| Error::Timeout(RECEIVE_TIMEOUT))?;
+
+    Ok(M::parse_from_bytes(&message.protobuf_data)?)
+  }
+}
+
+impl Drop for MockDevice {
+  fn drop(&mut self) {
+    self.accept_task.abort();
+    self.drop_connection();
+  }
+}
+
+struct ConnectionHandle {
+  tx: mpsc::UnboundedSender<ProtobufMessage>,
+  task: JoinHandle<()>,
+}
+
+struct Shared {
+  script: MockDeviceBuilder,
+  /// Required by new connections; replaced by `NoiseEncryptionSetKeyRequest`
+  psk: Mutex<Option<String>>,
+  received: Mutex<Vec<ProtobufMessage>>,
+  /// Per message type, how many frames `next_received` has already returned
+  consumed: Mutex<HashMap<u32, usize>>,
+  received_notify: Notify,
+  connection: Mutex<Option<ConnectionHandle>>,
+  connections: AtomicUsize,
+  connected_notify: Notify,
+}
+
+impl Shared {
+  fn outgoing(&self, message: ProtobufMessage) -> Result<()> {
+    let connection = self.connection.lock().unwrap();
+    let connection = connection.as_ref().ok_or(Error::Disconnected)?;
+    connection.tx.send(message).map_err(|_| Error::Disconnected)
+  }
+
+  fn record(&self, message: ProtobufMessage) {
+    self.received.lock().unwrap().push(message);
+    self.received_notify.notify_waiters();
+  }
+
+  fn take_next(&self, message_type: u32) -> Option<ProtobufMessage> {
+    let received = self.received.lock().unwrap();
+    let mut consumed = self.consumed.lock().unwrap();
+    let seen = consumed.entry(message_type).or_default();
+    let message = received
+      .iter()
+      .filter(|message| message.protobuf_type == message_type)
+      .nth(*seen)?
+      .clone();
+    *seen += 1;
+    Some(message)
+  }
+
+  /// Built-in answers for the connection setup and keep-alive requests.
+  fn respond(&self, message: &ProtobufMessage) -> Vec<ProtobufMessage> {
+    if let Some(handler) = self.script.handlers.get(&message.protobuf_type) {
+      return handler(message);
+    }
+
+    let script = &self.script;
+    let msg_type = message.protobuf_type;
+
+    if msg_type == proto::api::HelloRequest::get_option_id() {
+      vec![frame(&proto::api::HelloResponse {
+        api_version_major: script.api_version.0,
+        api_version_minor: script.api_version.1,
+        server_info: "esphomeapi mock device".to_string(),
+        name: script.name.clone(),
+        ..Default::default()
+      })]
+    } else if msg_type == proto::api::AuthenticationRequest::get_option_id() {
+      let Some(password) = &script.password else {
+        return Vec::new();
+      };
+      let request = proto::api::AuthenticationRequest::parse_from_bytes(&message.protobuf_data)
+        .unwrap_or_default();
+      vec![frame(&proto::api::AuthenticationResponse {
+        invalid_password: &request.password != password,
+        ..Default::default()
+      })]
+    } else if msg_type == proto::api::DeviceInfoRequest::get_option_id() {
+      vec![frame(&script.device_info)]
+    } else if msg_type == proto::api::ListEntitiesRequest::get_option_id() {
+      let mut responses = script.entities.clone();
+      responses.push(frame(&proto::api::ListEntitiesDoneResponse::default()));
+      responses
+    } else if msg_type == proto::api::SubscribeStatesRequest::get_option_id() {
+      script.states.clone()
+    } else if msg_type == proto::api::NoiseEncryptionSetKeyRequest::get_option_id() {
+      let request =
+        proto::api::NoiseEncryptionSetKeyRequest::parse_from_bytes(&message.protobuf_data)
+          .unwrap_or_default();
+      let success = script.device_info.api_encryption_supported && request.key.len() == 32;
+      let response = frame(&proto::api::NoiseEncryptionSetKeyResponse {
+        success,
+        ..Default::default()
+      });
+      if !success {
+        return vec![response];
+      }
+      // Like the firmware, switch to the key and drop clients to apply it
+      *self.psk.lock().unwrap() = Some(BASE64_STANDARD.encode(&request.key));
+      vec![response, frame(&proto::api::DisconnectRequest::default())]
+    } else if msg_type == proto::api::PingRequest::get_option_id() {
+      vec![frame(&proto::api::PingResponse::default())]
+    } else if msg_type == proto::api::DisconnectRequest::get_option_id() {
+      vec![frame(&proto::api::DisconnectResponse::default())]
+    } else {
+      Vec::new()
+    }
+  }
+}
+
+async fn accept_loop(listener: TcpListener, shared: Arc<Shared>) {
+  while let Ok((stream, _)) = listener.accept().await {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let task = tokio::spawn(serve(stream, Arc::clone(&shared), rx));
+
+    let previous = shared
+      .connection
+      .lock()
+      .unwrap()
+      .replace(ConnectionHandle { tx, task });
+    if let Some(previous) = previous {
+      previous.task.abort();
+    }
+
+    shared.connections.fetch_add(1, Ordering::SeqCst);
+    shared.connected_notify.notify_waiters();
+  }
+}
+
+async fn serve(
+  stream: TcpStream,
+  shared: Arc<Shared>,
+  mut rx: mpsc::UnboundedReceiver<ProtobufMessage>,
+) {
+  let psk = shared.psk.lock().unwrap().clone();
+  let Ok(codec) = EspHomeHandshake::new_responder(
+    psk,
+    shared.script.name.clone(),
+    shared.script.mac_address.clone(),
+  ) else {
+    return;
+  };
+  let Ok((mut reader, mut writer)) = accept_handshake(stream, codec).await else {
+    return;
+  };
+
+  loop {
+    tokio::select! {
+      message = reader.next() => {
+        let Some(Ok(message)) = message else {
+          break;
+        };
+
+        let msg_type = message.protobuf_type;
+        let responses = shared.respond(&message);
+        shared.record(message);
+
+        for response in responses {
+          if writer.send(response).await.is_err() {
+            return;
+          }
+        }
+
+        // Both parties close the connection once the disconnect is acknowledged
+        if msg_type == proto::api::DisconnectRequest::get_option_id()
+          || msg_type == proto::api::DisconnectResponse::get_option_id()
+        {
+          break;
+        }
+      }
+      Some(message) = rx.recv() => {
+        if writer.send(message).await.is_err() {
+          break;
+        }
+      }
+    }
+  }
+}
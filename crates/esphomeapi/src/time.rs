@@ -0,0 +1,122 @@
+//! Answering the device's `GetTimeRequest`.
+//!
+//! Devices with a `homeassistant` time platform ask the client for the
+//! current time. Besides the epoch seconds the response may carry a POSIX TZ
+//! string (e.g. `CET-1CEST,M3.5.0,M10.5.0/3`) that the device adopts as its
+//! local timezone.
+
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use crate::{Error, Result};
+
+/// Where the zoneinfo database lives unless `TZDIR` points elsewhere.
+const ZONEINFO_DIR: &str = "/usr/share/zoneinfo";
+
+/// The time reported to a device.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceTime {
+  pub epoch_seconds: u32,
+  /// POSIX TZ string; empty leaves the device's timezone unchanged
+  pub timezone: String,
+}
+
+type TimeHook = Arc<dyn Fn() -> DeviceTime + Send + Sync>;
+
+/// Where the client takes the time it reports to devices from.
+#[derive(Clone)]
+pub enum TimeSource {
+  /// The system clock, with an optional POSIX TZ string
+  System { timezone: String },
+  /// A caller-supplied clock, e.g. to simulate DST transitions
+  Custom(TimeHook),
+}
+
+impl TimeSource {
+  /// The system clock without a timezone, so devices keep their own.
+  pub fn device_default() -> Self {
+    TimeSource::System {
+      timezone: String::new(),
+    }
+  }
+
+  /// The system clock with a POSIX TZ string such as `EST5EDT,M3.2.0,M11.1.0`.
+  pub fn posix(timezone: impl Into<String>) -> Self {
+    TimeSource::System {
+      timezone: timezone.into(),
+    }
+  }
+
+  /// The system clock with an IANA zone such as `Europe/Berlin`, converted to
+  /// POSIX from the zoneinfo database in `TZDIR` or the system's.
+  pub fn iana(zone: &str) -> Result<Self> {
+    let invalid = || Error::InvalidTimezone(zone.to_string());
+    if zone.is_empty() || zone.split('/').any(|part| part.is_empty() || part == "..") {
+      return Err(invalid());
+    }
+    let data = zoneinfo_dirs()
+      .find_map(|dir| std::fs::read(dir.join(zone)).ok())
+      .ok_or_else(invalid)?;
+    Ok(Self::posix(posix_from_tzif(&data).ok_or_else(invalid)?))
+  }
+
+  /// A caller-supplied clock, consulted on every `GetTimeRequest`.
+  pub fn custom<F>(hook: F) -> Self
+  where
+    F: Fn() -> DeviceTime + Send + Sync + 'static,
+  {
+    TimeSource::Custom(Arc::new(hook))
+  }
+
+  pub fn now(&self) -> DeviceTime {
+    match self {
+      TimeSource::System { timezone } => DeviceTime {
+        epoch_seconds: SystemTime::now()
+          .duration_since(SystemTime::UNIX_EPOCH)
+          .unwrap()
+          .as_secs() as u32,
+        timezone: timezone.clone(),
+      },
+      TimeSource::Custom(hook) => hook(),
+    }
+  }
+}
+
+impl Default for TimeSource {
+  fn default() -> Self {
+    Self::device_default()
+  }
+}
+
+impl fmt::Debug for TimeSource {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      TimeSource::System { timezone } => f
+        .debug_struct("System")
+        .field("timezone", timezone)
+        .finish(),
+      TimeSource::Custom(_) => f.write_str("Custom(..)"),
+    }
+  }
+}
+
+/// `TZDIR` if set, then the usual system location.
+fn zoneinfo_dirs() -> impl Iterator<Item = PathBuf> {
+  std::env::var_os("TZDIR")
+    .map(PathBuf::from)
+    .into_iter()
+    .chain([PathBuf::from(ZONEINFO_DIR)])
+}
+
+/// Extracts the POSIX TZ footer of a version 2+ TZif file.
+fn posix_from_tzif(data: &[u8]) -> Option<String> {
+  if !data.starts_with(b"TZif") || data.get(4).is_none_or(|version| *version < b'2') {
+    return None;
+  }
+  let footer = data.strip_suffix(b"\n")?;
+  let start = footer.iter().rposition(|byte| *byte == b'\n')? + 1;
+  let posix = std::str::from_utf8(&footer[start..]).ok()?;
+  (!posix.is_empty()).then(|| posix.to_string())
+}
@@ -0,0 +1,133 @@
+//! How the client reaches a device.
+//!
+//! By default the client dials `host:port` over TCP. A [`Transport`] replaces
+//! the dialing step, e.g. to go through an SSH tunnel or a SOCKS proxy, to use
+//! a Unix socket, or to hand over one end of a `tokio::io::duplex` pair in
+//! tests. The framing handshake and everything after it run on whatever
+//! stream the transport returns. Reconnects dial through the same transport.
+//!
+//! ```ignore
+//! let client = Client::builder("living-room")
+//!   .transport(transport::from_fn(|host, port| async move {
+//!     let stream = TcpStream::connect("127.0.0.1:1080").await?;
+//!     socks5_connect(stream, &host, port).await
+//!   }))
+//!   .connect()
+//!   .await?;
+//! ```
+
+use std::fmt;
+use std::future::Future;
+use std::io;
+#[cfg(unix)]
+use std::path::PathBuf;
+
+use futures::future::BoxFuture;
+use futures::FutureExt as _;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpStream;
+
+/// A bidirectional byte stream to a device.
+pub trait Stream: AsyncRead + AsyncWrite + Send + Unpin {}
+
+impl<T: AsyncRead + AsyncWrite + Send + Unpin> Stream for T {}
+
+pub type BoxedStream = Box<dyn Stream>;
+
+/// Opens streams to a device.
+pub trait Transport: Send + Sync {
+  /// Open a new stream for the device configured with `host` and `port`.
+  fn dial<'a>(&'a self, host: &'a str, port: u32) -> BoxFuture<'a, io::Result<BoxedStream>>;
+
+  /// Where the stream leads, for error messages.
+  fn address(&self, host: &str, port: u32) -> String {
+    if host.contains(':') && !host.starts_with('[') {
+      format!("[{host}]:{port}")
+    } else {
+      format!("{host}:{port}")
+    }
+  }
+}
+
+impl fmt::Debug for dyn Transport {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str("Transport(..)")
+  }
+}
+
+/// Plain TCP to `host:port`; the default. The host may be a name or an IPv4
+/// or IPv6 address.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Tcp;
+
+impl Transport for Tcp {
+  fn dial<'a>(&'a self, host: &'a str, port: u32) -> BoxFuture<'a, io::Result<BoxedStream>> {
+    async move {
+      let port = u16::try_from(port)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, format!("invalid port {port}")))?;
+      // Joining IPv6 literals with the port needs brackets; a tuple takes
+      // them as they are, including scoped ones like `fe80::1%eth0`
+      let host = host
+        .strip_prefix('[')
+        .and_then(|host| host.strip_suffix(']'))
+        .unwrap_or(host);
+      let stream = TcpStream::connect((host, port)).await?;
+      Ok(Box::new(stream) as BoxedStream)
+    }
+    .boxed()
+  }
+}
+
+/// A Unix domain socket, e.g. one forwarded with `ssh -L`. Host and port are
+/// ignored.
+#[cfg(unix)]
+#[derive(Debug, Clone)]
+pub struct UnixSocket(pub PathBuf);
+
+#[cfg(unix)]
+impl Transport for UnixSocket {
+  fn dial<'a>(&'a self, _host: &'a str, _port: u32) -> BoxFuture<'a, io::Result<BoxedStream>> {
+    async move {
+      let stream = tokio::net::UnixStream::connect(&self.0).await?;
+      Ok(Box::new(stream) as BoxedStream)
+    }
+    .boxed()
+  }
+
+  fn address(&self, _host: &str, _port: u32) -> String {
+    self.0.display().to_string()
+  }
+}
+
+/// A transport calling `dial` with the configured host and port for every
+/// connection attempt.
+pub fn from_fn<F, Fut, S>(dial: F) -> FnTransport<F>
+where
+  F: Fn(String, u32) -> Fut + Send + Sync,
+  Fut: Future<Output = io::Result<S>> + Send + 'static,
+  S: Stream + 'static,
+{
+  FnTransport(dial)
+}
+
+/// See [`from_fn`].
+#[derive(Clone)]
+pub struct FnTransport<F>(F);
+
+impl<F, Fut, S> Transport for FnTransport<F>
+where
+  F: Fn(String, u32) -> Fut + Send + Sync,
+  Fut: Future<Output = io::Result<S>> + Send + 'static,
+  S: Stream + 'static,
+{
+  fn dial<'a>(&'a self, host: &'a str, port: u32) -> BoxFuture<'a, io::Result<BoxedStream>> {
+    let stream = (self.0)(host.to_string(), port);
+    async move { Ok(Box::new(stream.await?) as BoxedStream) }.boxed()
+  }
+}
+
+impl<F> fmt::Debug for FnTransport<F> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str("FnTransport(..)")
+  }
+}
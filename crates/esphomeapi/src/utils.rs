@@ -1,6 +1,8 @@
 use protobuf::{Message, MessageDyn, MessageFull};
 
+use crate::connection::ProtobufMessage;
 use crate::proto::api_options::exts::id;
+use crate::Result;
 
 pub trait Options {
   fn get_option_id() -> u32;
@@ -16,3 +18,11 @@ where
     id.get(options).unwrap()
   }
 }
+
+/// Encode a protobuf message into a frame.
+pub(crate) fn to_message<M: MessageFull>(message: &M) -> Result<ProtobufMessage> {
+  Ok(ProtobufMessage {
+    protobuf_type: M::get_option_id(),
+    protobuf_data: message.write_to_bytes()?,
+  })
+}
@@ -0,0 +1,169 @@
+//! Serial-over-API bridge for devices acting as a Z-Wave proxy.
+//!
+//! The proxy forwards the raw serial traffic of its Z-Wave controller, so a
+//! Z-Wave stack can talk to it as if the controller were plugged in locally.
+//!
+//! ```ignore
+//! let mut proxy = client.zwave_proxy().await?;
+//! proxy.write_all(&frame).await?;
+//! let read = proxy.read(&mut buf).await?;
+//!
+//! // Or expose it to a stack that supports `tcp://` serial ports
+//! let listener = TcpListener::bind("127.0.0.1:4001").await?;
+//! zwave::serve(&client, listener).await?;
+//! ```
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::io::{
+  AsyncRead, AsyncReadExt as _, AsyncWrite, AsyncWriteExt as _, DuplexStream, ReadBuf,
+};
+use tokio::net::TcpListener;
+use tokio::sync::{broadcast, watch};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::model::ZWaveProxyEvent;
+use crate::{Client, CommandHandle, Result};
+
+/// Bytes buffered between the device and the reader of a [`ZWaveProxy`].
+const BUFFER_SIZE: usize = 4096;
+
+/// The serial port of a proxied Z-Wave controller, returned by
+/// [`Client::zwave_proxy`](crate::Client::zwave_proxy).
+///
+/// Reading yields the bytes the controller sent; writing sends bytes to it.
+/// Reads end once the client is gone. Dropping this unsubscribes the proxy.
+pub struct ZWaveProxy {
+  io: DuplexStream,
+  home_id: watch::Receiver<Option<u32>>,
+  client: CommandHandle,
+  forward_task: JoinHandle<()>,
+  send_task: JoinHandle<()>,
+}
+
+impl ZWaveProxy {
+  pub(crate) fn new(
+    client: CommandHandle,
+    mut receiver: broadcast::Receiver<ZWaveProxyEvent>,
+    home_id: Option<u32>,
+  ) -> Self {
+    let (io, device_io) = tokio::io::duplex(BUFFER_SIZE);
+    let (mut from_device, mut to_device) = tokio::io::split(device_io);
+    let (home_id_tx, home_id) = watch::channel(home_id);
+
+    let forward_task = tokio::spawn(async move {
+      loop {
+        match receiver.recv().await {
+          Ok(ZWaveProxyEvent::Frame(data)) => {
+            if to_device.write_all(&data).await.is_err() {
+              break;
+            }
+          }
+          Ok(ZWaveProxyEvent::HomeIdChanged(id)) => {
+            info!(home_id = format!("{id:08X}"), "Z-Wave home id changed");
+            home_id_tx.send_replace(Some(id));
+          }
+          Err(broadcast::error::RecvError::Lagged(skipped)) => {
+            warn!(skipped, "dropped Z-Wave proxy frames");
+          }
+          Err(broadcast::error::RecvError::Closed) => break,
+        }
+      }
+    });
+
+    let send_client = client.clone();
+    let send_task = tokio::spawn(async move {
+      let mut buf = vec![0; BUFFER_SIZE];
+      loop {
+        let read = match from_device.read(&mut buf).await {
+          Ok(0) | Err(_) => break,
+          Ok(read) => read,
+        };
+        if let Err(e) = send_client.zwave_proxy_frame(buf[..read].to_vec()).await {
+          warn!("failed to send Z-Wave proxy frame: {e}");
+        }
+      }
+    });
+
+    Self {
+      io,
+      home_id,
+      client,
+      forward_task,
+      send_task,
+    }
+  }
+
+  /// The home id of the controller's network, if it has joined one.
+  pub fn home_id(&self) -> Option<u32> {
+    *self.home_id.borrow()
+  }
+
+  /// Get a receiver that is notified whenever the home id changes.
+  pub fn home_id_receiver(&self) -> watch::Receiver<Option<u32>> {
+    self.home_id.clone()
+  }
+}
+
+impl AsyncRead for ZWaveProxy {
+  fn poll_read(
+    mut self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+    buf: &mut ReadBuf<'_>,
+  ) -> Poll<io::Result<()>> {
+    Pin::new(&mut self.io).poll_read(cx, buf)
+  }
+}
+
+impl AsyncWrite for ZWaveProxy {
+  fn poll_write(
+    mut self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+    buf: &[u8],
+  ) -> Poll<io::Result<usize>> {
+    Pin::new(&mut self.io).poll_write(cx, buf)
+  }
+
+  fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+    Pin::new(&mut self.io).poll_flush(cx)
+  }
+
+  fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+    Pin::new(&mut self.io).poll_shutdown(cx)
+  }
+}
+
+impl Drop for ZWaveProxy {
+  fn drop(&mut self) {
+    self.forward_task.abort();
+    self.send_task.abort();
+    let client = self.client.clone();
+    if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+      runtime.spawn(async move {
+        if let Err(e) = client.zwave_proxy_subscribe(false).await {
+          debug!("failed to unsubscribe from Z-Wave proxy: {e}");
+        }
+      });
+    }
+  }
+}
+
+/// Expose the Z-Wave proxy of `client` to local Z-Wave stacks over TCP.
+///
+/// Connections are served one at a time, each with its own subscription, as
+/// a serial port has a single owner. Only returns if accepting or
+/// subscribing fails.
+pub async fn serve(client: &Client, listener: TcpListener) -> Result<()> {
+  loop {
+    let (mut socket, peer) = listener.accept().await?;
+    info!(%peer, "Z-Wave serial client connected");
+    let mut proxy = client.zwave_proxy().await?;
+    if let Err(e) = tokio::io::copy_bidirectional(&mut socket, &mut proxy).await {
+      debug!(%peer, "Z-Wave serial connection ended: {e}");
+    }
+    info!(%peer, "Z-Wave serial client disconnected");
+  }
+}
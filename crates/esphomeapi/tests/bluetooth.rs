@@ -0,0 +1,381 @@
+use std::time::{Duration, Instant};
+
+use enumflags2::BitFlags;
+use esphomeapi::model::{
+  BluetoothProxySubscriptionFlag, BluetoothScannerMode, BluetoothScannerState,
+};
+use esphomeapi::testing::{frame, MockDevice};
+use esphomeapi::{api, Client, Error};
+
+fn raw_advertisement() -> api::BluetoothLERawAdvertisement {
+  let mut data = vec![
+    // Flags
+    0x02, 0x01, 0x06, //
+    // Complete list of 16-bit UUIDs: 0x180F
+    0x03, 0x03, 0x0F, 0x18, //
+    // Complete local name "Tag"
+    0x04, 0x09, b'T', b'a', b'g', //
+    // Service data for 0xFEAA
+    0x05, 0x16, 0xAA, 0xFE, 0x10, 0x20, //
+    // Manufacturer data for Apple (0x004C)
+    0x05, 0xFF, 0x4C, 0x00, 0x02, 0x15,
+  ];
+  // Devices pad the fixed-size buffer with zeros
+  data.resize(31, 0);
+  api::BluetoothLERawAdvertisement {
+    address: 0xAABBCCDDEEFF,
+    rssi: -60,
+    address_type: 1,
+    data,
+    ..Default::default()
+  }
+}
+
+#[tokio::test]
+async fn raw_advertisement_batches_are_split_and_parsed() {
+  let device = MockDevice::builder()
+    .on::<api::SubscribeBluetoothLEAdvertisementsRequest, _>(|_| {
+      let mut second = raw_advertisement();
+      second.address = 1;
+      vec![frame(&api::BluetoothLERawAdvertisementsResponse {
+        advertisements: vec![raw_advertisement(), second],
+        ..Default::default()
+      })]
+    })
+    .start()
+    .await
+    .unwrap();
+  let client = device.client().await.unwrap();
+
+  let mut advertisements = client
+    .subscribe_bluetooth_le_advertisements(BluetoothProxySubscriptionFlag::RawAdvertisements.into())
+    .await
+    .unwrap();
+
+  let request: api::SubscribeBluetoothLEAdvertisementsRequest =
+    device.next_received().await.unwrap();
+  assert_eq!(request.flags, 1);
+
+  let first = advertisements.recv().await.unwrap();
+  assert_eq!(first.address, 0xAABBCCDDEEFF);
+  assert_eq!(first.rssi, -60);
+  assert_eq!(first.name, "Tag");
+  assert_eq!(
+    first.service_uuids,
+    vec!["0000180f-0000-1000-8000-00805f9b34fb"]
+  );
+  assert_eq!(
+    first.service_data["0000feaa-0000-1000-8000-00805f9b34fb"],
+    vec![0x10, 0x20]
+  );
+  assert_eq!(first.manufacturer_data[&0x004C], vec![0x02, 0x15]);
+
+  let second = advertisements.recv().await.unwrap();
+  assert_eq!(second.address, 1);
+}
+
+#[tokio::test]
+async fn legacy_advertisements_are_parsed() {
+  let device = MockDevice::builder()
+    .on::<api::SubscribeBluetoothLEAdvertisementsRequest, _>(|_| {
+      vec![frame(&api::BluetoothLEAdvertisementResponse {
+        address: 42,
+        name: b"Sensor".to_vec(),
+        rssi: -70,
+        manufacturer_data: vec![api::BluetoothServiceData {
+          uuid: "0x004C".to_string(),
+          data: vec![1, 2],
+          ..Default::default()
+        }],
+        ..Default::default()
+      })]
+    })
+    .start()
+    .await
+    .unwrap();
+  let client = device.client().await.unwrap();
+
+  let mut advertisements = client
+    .subscribe_bluetooth_le_advertisements(BitFlags::empty())
+    .await
+    .unwrap();
+
+  let advertisement = advertisements.recv().await.unwrap();
+  assert_eq!(advertisement.address, 42);
+  assert_eq!(advertisement.name, "Sensor");
+  assert_eq!(advertisement.manufacturer_data[&0x004C], vec![1, 2]);
+
+  client
+    .unsubscribe_bluetooth_le_advertisements()
+    .await
+    .unwrap();
+  device
+    .next_received::<api::UnsubscribeBluetoothLEAdvertisementsRequest>()
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn scanner_mode_changes_are_reported() {
+  let device = MockDevice::builder()
+    .on::<api::BluetoothScannerSetModeRequest, _>(|request| {
+      vec![frame(&api::BluetoothScannerStateResponse {
+        state: api::BluetoothScannerState::BLUETOOTH_SCANNER_STATE_RUNNING.into(),
+        mode: request.mode,
+        configured_mode: api::BluetoothScannerMode::BLUETOOTH_SCANNER_MODE_PASSIVE.into(),
+        ..Default::default()
+      })]
+    })
+    .start()
+    .await
+    .unwrap();
+  let client = device.client().await.unwrap();
+  let mut states = client.bluetooth_scanner_state_receiver();
+
+  client
+    .bluetooth_scanner_set_mode(BluetoothScannerMode::Active)
+    .await
+    .unwrap();
+
+  let status = states.recv().await.unwrap();
+  assert_eq!(status.state, BluetoothScannerState::Running);
+  assert_eq!(status.mode, BluetoothScannerMode::Active);
+  assert_eq!(status.configured_mode, BluetoothScannerMode::Passive);
+}
+
+const ADDRESS: u64 = 0x112233445566;
+
+async fn gatt_device() -> MockDevice {
+  MockDevice::builder()
+    .on::<api::BluetoothDeviceRequest, _>(|request| {
+      let connected = request.request_type.enum_value()
+        == Ok(api::BluetoothDeviceRequestType::BLUETOOTH_DEVICE_REQUEST_TYPE_CONNECT_V3_WITH_CACHE);
+      vec![
+        // Another device dropping out must not answer this request
+        frame(&api::BluetoothDeviceConnectionResponse {
+          address: 1,
+          connected: false,
+          error: 8,
+          ..Default::default()
+        }),
+        frame(&api::BluetoothDeviceConnectionResponse {
+          address: request.address,
+          connected,
+          mtu: 185,
+          ..Default::default()
+        }),
+      ]
+    })
+    .on::<api::BluetoothGATTGetServicesRequest, _>(|request| {
+      let service = |short_uuid, handle| api::BluetoothGATTService {
+        short_uuid,
+        handle,
+        characteristics: vec![api::BluetoothGATTCharacteristic {
+          uuid: vec![0x0000_2A19_0000_1000, 0x8000_0080_5F9B_34FB],
+          handle: handle + 1,
+          properties: 0x12,
+          ..Default::default()
+        }],
+        ..Default::default()
+      };
+      vec![
+        frame(&api::BluetoothGATTGetServicesResponse {
+          address: request.address,
+          services: vec![service(0x180F, 1)],
+          ..Default::default()
+        }),
+        frame(&api::BluetoothGATTGetServicesResponse {
+          address: request.address,
+          services: vec![service(0x180A, 10)],
+          ..Default::default()
+        }),
+        frame(&api::BluetoothGATTGetServicesDoneResponse {
+          address: request.address,
+          ..Default::default()
+        }),
+      ]
+    })
+    .on::<api::BluetoothGATTReadRequest, _>(|request| {
+      if request.handle == 2 {
+        vec![frame(&api::BluetoothGATTReadResponse {
+          address: request.address,
+          handle: request.handle,
+          data: vec![87],
+          ..Default::default()
+        })]
+      } else {
+        vec![frame(&api::BluetoothGATTErrorResponse {
+          address: request.address,
+          handle: request.handle,
+          error: 2,
+          ..Default::default()
+        })]
+      }
+    })
+    .on::<api::BluetoothGATTWriteRequest, _>(|request| {
+      vec![frame(&api::BluetoothGATTWriteResponse {
+        address: request.address,
+        handle: request.handle,
+        ..Default::default()
+      })]
+    })
+    .on::<api::BluetoothGATTNotifyRequest, _>(|request| {
+      let notify = |handle, data| {
+        frame(&api::BluetoothGATTNotifyDataResponse {
+          address: request.address,
+          handle,
+          data,
+          ..Default::default()
+        })
+      };
+      vec![
+        frame(&api::BluetoothGATTNotifyResponse {
+          address: request.address,
+          handle: request.handle,
+          ..Default::default()
+        }),
+        notify(99, vec![0]),
+        notify(request.handle, vec![42]),
+      ]
+    })
+    .start()
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn remote_device_connects_and_discovers_services() {
+  let device = gatt_device().await;
+  let client = device.client().await.unwrap();
+  let mut connections = client.bluetooth_connections_receiver();
+  let remote = client.bluetooth_device(ADDRESS, 1);
+
+  assert_eq!(remote.connect().await.unwrap(), 185);
+  let request: api::BluetoothDeviceRequest = device.next_received().await.unwrap();
+  assert_eq!(request.address, ADDRESS);
+  assert_eq!(request.address_type, 1);
+
+  // The other device's drop was routed to the connection receiver instead
+  let other = connections.recv().await.unwrap();
+  assert_eq!(other.address, 1);
+  assert!(!other.connected);
+
+  let services = remote.services().await.unwrap();
+  assert_eq!(services.len(), 2);
+  assert_eq!(services[0].uuid, "0000180f-0000-1000-8000-00805f9b34fb");
+  assert_eq!(services[1].handle, 10);
+  let characteristic = &services[0].characteristics[0];
+  assert_eq!(characteristic.uuid, "00002a19-0000-1000-8000-00805f9b34fb");
+  assert_eq!(characteristic.properties, 0x12);
+
+  let failed = client
+    .bluetooth_device(ADDRESS, 1)
+    .connect_without_cache()
+    .await;
+  assert!(matches!(
+    failed,
+    Err(Error::BluetoothDevice {
+      address: ADDRESS,
+      operation: "connect",
+      ..
+    })
+  ));
+}
+
+#[tokio::test]
+async fn remote_device_reads_writes_and_reports_gatt_errors() {
+  let device = gatt_device().await;
+  let client = device.client().await.unwrap();
+  let remote = client.bluetooth_device(ADDRESS, 0);
+
+  assert_eq!(remote.read(2).await.unwrap(), vec![87]);
+
+  let error = remote.read(5).await;
+  assert!(matches!(
+    error,
+    Err(Error::BluetoothGatt {
+      address: ADDRESS,
+      handle: 5,
+      error: 2
+    })
+  ));
+
+  remote.write(2, vec![1, 2], true).await.unwrap();
+  remote.write(2, vec![3], false).await.unwrap();
+  let acknowledged: api::BluetoothGATTWriteRequest = device.next_received().await.unwrap();
+  assert!(acknowledged.response);
+  let unacknowledged: api::BluetoothGATTWriteRequest = device.next_received().await.unwrap();
+  assert!(!unacknowledged.response);
+  assert_eq!(unacknowledged.data, vec![3]);
+}
+
+#[tokio::test]
+async fn notifications_are_filtered_by_handle() {
+  let device = gatt_device().await;
+  let client = device.client().await.unwrap();
+  let remote = client.bluetooth_device(ADDRESS, 0);
+
+  let mut notifications = remote.start_notify(7).await.unwrap();
+  assert_eq!(notifications.recv().await.unwrap(), vec![42]);
+
+  let request: api::BluetoothGATTNotifyRequest = device.next_received().await.unwrap();
+  assert_eq!(request.handle, 7);
+  assert!(request.enable);
+}
+
+#[tokio::test]
+async fn service_discovery_stops_at_the_first_gatt_error() {
+  let device = MockDevice::builder()
+    .on::<api::BluetoothGATTGetServicesRequest, _>(|request| {
+      vec![frame(&api::BluetoothGATTErrorResponse {
+        address: request.address,
+        error: 133,
+        ..Default::default()
+      })]
+    })
+    .start()
+    .await
+    .unwrap();
+  let client = device.client().await.unwrap();
+
+  let started = Instant::now();
+  let result = client.bluetooth_device(ADDRESS, 0).services().await;
+
+  assert!(matches!(
+    result,
+    Err(Error::BluetoothGatt {
+      address: ADDRESS,
+      error: 133,
+      ..
+    })
+  ));
+  assert!(started.elapsed() < Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn service_discovery_without_done_times_out() {
+  let device = MockDevice::builder()
+    .on::<api::BluetoothGATTGetServicesRequest, _>(|request| {
+      vec![frame(&api::BluetoothGATTGetServicesResponse {
+        address: request.address,
+        services: vec![api::BluetoothGATTService {
+          short_uuid: 0x180F,
+          ..Default::default()
+        }],
+        ..Default::default()
+      })]
+    })
+    .start()
+    .await
+    .unwrap();
+  let client = Client::builder(device.host())
+    .port(device.port())
+    .request_timeout(Duration::from_millis(200))
+    .connect()
+    .await
+    .unwrap();
+
+  let result = client.bluetooth_device(ADDRESS, 0).services().await;
+
+  assert!(matches!(result, Err(Error::Timeout(_))));
+}
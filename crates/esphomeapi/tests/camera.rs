@@ -0,0 +1,60 @@
+use esphomeapi::api;
+use esphomeapi::testing::{frame, MockDevice, ProtobufMessage};
+
+fn chunk(key: u32, data: &[u8], done: bool) -> ProtobufMessage {
+  frame(&api::CameraImageResponse {
+    key,
+    data: data.to_vec(),
+    done,
+    ..Default::default()
+  })
+}
+
+#[tokio::test]
+async fn snapshot_reassembles_interleaved_chunks_per_camera() {
+  let device = MockDevice::builder()
+    .on::<api::CameraImageRequest, _>(|_| {
+      vec![
+        chunk(1, &[0xFF, 0xD8], false),
+        chunk(2, &[0xAA], false),
+        chunk(1, &[0x01], false),
+        chunk(2, &[0xBB], true),
+        chunk(1, &[0xFF, 0xD9], true),
+      ]
+    })
+    .start()
+    .await
+    .unwrap();
+  let client = device.client().await.unwrap();
+
+  let image = client.camera_snapshot(1).await.unwrap();
+  assert_eq!(image, vec![0xFF, 0xD8, 0x01, 0xFF, 0xD9]);
+
+  let request: api::CameraImageRequest = device.next_received().await.unwrap();
+  assert!(request.single);
+  assert!(!request.stream);
+}
+
+#[tokio::test]
+async fn stream_yields_whole_images_of_one_camera() {
+  let device = MockDevice::builder()
+    .on::<api::CameraImageRequest, _>(|_| {
+      vec![
+        chunk(7, &[1, 2], false),
+        chunk(7, &[3], true),
+        chunk(8, &[9], true),
+        chunk(7, &[4], true),
+      ]
+    })
+    .start()
+    .await
+    .unwrap();
+  let client = device.client().await.unwrap();
+
+  let mut stream = client.camera_stream(7).await.unwrap();
+  assert_eq!(stream.recv().await.unwrap(), vec![1, 2, 3]);
+  assert_eq!(stream.recv().await.unwrap(), vec![4]);
+
+  let request: api::CameraImageRequest = device.next_received().await.unwrap();
+  assert!(request.stream);
+}
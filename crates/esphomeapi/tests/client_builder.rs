@@ -0,0 +1,222 @@
+use std::time::Duration;
+
+use esphomeapi::model::APIVersion;
+use esphomeapi::testing::MockDevice;
+use esphomeapi::{api, BufferSizes, Client, CodecError, Error, ReconnectPolicy};
+use tokio::net::TcpListener;
+use tokio::sync::broadcast::error::RecvError;
+
+fn fast_reconnect() -> ReconnectPolicy {
+  ReconnectPolicy {
+    initial_delay: Duration::from_millis(20),
+    max_delay: Duration::from_millis(100),
+    ..Default::default()
+  }
+}
+
+#[tokio::test]
+async fn invalid_psk_is_rejected_before_dialing() {
+  let device = MockDevice::builder().start().await.unwrap();
+
+  let result = Client::builder(device.host())
+    .port(device.port())
+    .psk("AAAAAAAAAAAAAAAAAAAAAA==")
+    .connect()
+    .await;
+
+  assert!(matches!(
+    result,
+    Err(Error::Handshake(CodecError::InvalidPskLength {
+      expected: 32,
+      actual: 16
+    }))
+  ));
+  assert_eq!(device.connection_count(), 0);
+}
+
+#[tokio::test]
+async fn zero_buffer_size_is_rejected_before_dialing() {
+  let device = MockDevice::builder().start().await.unwrap();
+
+  let result = Client::builder(device.host())
+    .port(device.port())
+    .buffer_sizes(BufferSizes {
+      camera: 0,
+      ..Default::default()
+    })
+    .connect()
+    .await;
+
+  assert!(matches!(result, Err(Error::InvalidConfig(_))));
+  assert_eq!(device.connection_count(), 0);
+}
+
+#[tokio::test]
+async fn shrinking_reconnect_policy_is_rejected_before_dialing() {
+  let device = MockDevice::builder().start().await.unwrap();
+
+  let result = Client::builder(device.host())
+    .port(device.port())
+    .reconnect_policy(ReconnectPolicy {
+      multiplier: -2.0,
+      ..Default::default()
+    })
+    .connect()
+    .await;
+
+  assert!(matches!(result, Err(Error::InvalidConfig(_))));
+  assert_eq!(device.connection_count(), 0);
+}
+
+#[tokio::test]
+async fn connect_times_out_on_silent_device() {
+  let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let port = listener.local_addr().unwrap().port() as u32;
+  let _accept = tokio::spawn(async move {
+    let mut streams = Vec::new();
+    while let Ok((stream, _)) = listener.accept().await {
+      streams.push(stream);
+    }
+  });
+
+  let result = Client::builder("127.0.0.1")
+    .port(port)
+    .psk("px7tsbK3C7bpXHr2OevEV2ZMg/FrNBw2+O2pNPbedtA=")
+    .connect_timeout(Duration::from_millis(200))
+    .connect()
+    .await;
+
+  assert!(matches!(result, Err(Error::Timeout(_))));
+}
+
+#[tokio::test]
+async fn api_version_is_sent_in_hello() {
+  let device = MockDevice::builder().start().await.unwrap();
+
+  let _client = Client::builder(device.host())
+    .port(device.port())
+    .api_version(1, 12)
+    .client_info("builder-test")
+    .connect()
+    .await
+    .unwrap();
+
+  let hello: api::HelloRequest = device.next_received().await.unwrap();
+  assert_eq!(hello.api_version_minor, 12);
+  assert_eq!(hello.client_info, "builder-test");
+}
+
+#[tokio::test]
+async fn server_info_from_hello_is_exposed() {
+  let device = MockDevice::builder()
+    .name("kitchen")
+    .api_version(1, 9)
+    .start()
+    .await
+    .unwrap();
+
+  let client = device.client().await.unwrap();
+
+  let server_info = client.server_info();
+  assert_eq!(server_info.api_version, APIVersion::new(1, 9));
+  assert_eq!(server_info.name, "kitchen");
+  assert_eq!(server_info.server_info, "esphomeapi mock device");
+  assert_eq!(client.api_version().to_string(), "1.9");
+}
+
+#[tokio::test]
+async fn requests_newer_than_the_device_are_refused() {
+  let device = MockDevice::builder()
+    .api_version(1, 9)
+    .start()
+    .await
+    .unwrap();
+  let client = device.client().await.unwrap();
+
+  let result = client.voice_assistant_get_configuration(Vec::new()).await;
+
+  let Err(Error::Unsupported {
+    request,
+    required,
+    actual,
+  }) = result
+  else {
+    panic!("expected an unsupported error, got {result:?}");
+  };
+  assert_eq!(request, "VoiceAssistantConfigurationRequest");
+  assert_eq!(required, APIVersion::new(1, 10));
+  assert_eq!(actual, APIVersion::new(1, 9));
+  assert!(device
+    .received_of::<api::VoiceAssistantConfigurationRequest>()
+    .is_empty());
+  // Older requests still go through
+  client.device_info().await.unwrap();
+}
+
+#[tokio::test]
+async fn reconnects_after_connection_drop() {
+  let device = MockDevice::builder().start().await.unwrap();
+
+  let client = Client::builder(device.host())
+    .port(device.port())
+    .reconnect_policy(fast_reconnect())
+    .connect()
+    .await
+    .unwrap();
+  let mut reconnected = client.on_reconnect();
+
+  device.drop_connection();
+
+  tokio::time::timeout(Duration::from_secs(5), reconnected.recv())
+    .await
+    .unwrap()
+    .unwrap();
+  assert_eq!(device.connection_count(), 2);
+  assert_eq!(device.received_of::<api::HelloRequest>().len(), 2);
+}
+
+#[tokio::test]
+async fn exhausted_reconnect_attempts_close_the_reconnect_channel() {
+  let device = MockDevice::builder().start().await.unwrap();
+  let client = Client::builder(device.host())
+    .port(device.port())
+    .reconnect_policy(ReconnectPolicy {
+      max_attempts: Some(2),
+      ..fast_reconnect()
+    })
+    .connect()
+    .await
+    .unwrap();
+  let mut reconnected = client.on_reconnect();
+
+  // Nothing listens any more, so every attempt is refused
+  drop(device);
+
+  let result = tokio::time::timeout(Duration::from_secs(5), reconnected.recv())
+    .await
+    .unwrap();
+  assert_eq!(result, Err(RecvError::Closed));
+  assert_eq!(client.on_reconnect().recv().await, Err(RecvError::Closed));
+}
+
+#[tokio::test]
+async fn disabled_reconnect_policy_does_not_redial() {
+  let device = MockDevice::builder().start().await.unwrap();
+
+  let client = Client::builder(device.host())
+    .port(device.port())
+    .reconnect_policy(ReconnectPolicy::disabled())
+    .connect()
+    .await
+    .unwrap();
+  let mut disconnected = client.on_device_disconnect();
+
+  device.drop_connection();
+
+  tokio::time::timeout(Duration::from_secs(5), disconnected.recv())
+    .await
+    .unwrap()
+    .unwrap();
+  tokio::time::sleep(Duration::from_millis(200)).await;
+  assert_eq!(device.connection_count(), 1);
+}
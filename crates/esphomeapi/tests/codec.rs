@@ -0,0 +1,152 @@
+use bytes::{Bytes, BytesMut};
+use esphomeapi::codec::{
+  EspHomeDecoder, EspHomeEncoder, EspHomeHandshake, HandshakeResult, PlainEncoder, ProtobufMessage,
+};
+use esphomeapi::CodecError;
+use tokio_util::codec::{Decoder as _, Encoder as _};
+
+const PSK: &str = "px7tsbK3C7bpXHr2OevEV2ZMg/FrNBw2+O2pNPbedtA=";
+const OTHER_PSK: &str = "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8=";
+
+type Codecs = (EspHomeDecoder, EspHomeEncoder);
+
+/// Runs one side of the handshake on `inbox` until it needs the peer.
+fn step(
+  handshake: &mut EspHomeHandshake,
+  inbox: &mut BytesMut,
+  outbox: &mut Vec<Bytes>,
+) -> Result<Option<Codecs>, CodecError> {
+  loop {
+    match handshake.process(inbox)? {
+      HandshakeResult::NeedMoreData => return Ok(None),
+      HandshakeResult::SendFrame(frame) => outbox.push(frame),
+      HandshakeResult::Complete(decoder, encoder) => return Ok(Some((decoder, encoder))),
+    }
+  }
+}
+
+/// Exchanges frames until both sides have completed or failed.
+fn handshake(
+  mut client: EspHomeHandshake,
+  mut server: EspHomeHandshake,
+) -> (Result<Codecs, CodecError>, Result<Codecs, CodecError>) {
+  let (mut to_server, mut to_client) = (BytesMut::new(), BytesMut::new());
+  let (mut client_done, mut server_done) = (None, None);
+  for _ in 0..8 {
+    let mut outbox = Vec::new();
+    if client_done.is_none() {
+      client_done = step(&mut client, &mut to_client, &mut outbox).transpose();
+    }
+    outbox
+      .drain(..)
+      .for_each(|frame| to_server.extend_from_slice(&frame));
+    if server_done.is_none() {
+      server_done = step(&mut server, &mut to_server, &mut outbox).transpose();
+    }
+    outbox
+      .drain(..)
+      .for_each(|frame| to_client.extend_from_slice(&frame));
+    if client_done.is_some() && server_done.is_some() {
+      break;
+    }
+  }
+  (client_done.unwrap(), server_done.unwrap())
+}
+
+fn message(protobuf_type: u32, data: &[u8]) -> ProtobufMessage {
+  ProtobufMessage {
+    protobuf_type,
+    protobuf_data: data.to_vec(),
+  }
+}
+
+fn round_trip(
+  encoder: &mut EspHomeEncoder,
+  decoder: &mut EspHomeDecoder,
+  message: ProtobufMessage,
+) -> ProtobufMessage {
+  let mut wire = BytesMut::new();
+  encoder.encode(message, &mut wire).unwrap();
+  decoder.decode(&mut wire).unwrap().unwrap()
+}
+
+#[test]
+fn noise_client_and_responder_exchange_messages() {
+  let client = EspHomeHandshake::new(Some(PSK.to_string()), Some("node".to_string())).unwrap();
+  let server = EspHomeHandshake::new_responder(
+    Some(PSK.to_string()),
+    "node".to_string(),
+    "AA:BB:CC:DD:EE:FF".to_string(),
+  )
+  .unwrap();
+
+  let (client, server) = handshake(client, server);
+  let (mut client_decoder, mut client_encoder) = client.unwrap();
+  let (mut server_decoder, mut server_encoder) = server.unwrap();
+
+  let request = round_trip(
+    &mut client_encoder,
+    &mut server_decoder,
+    message(1, b"hello"),
+  );
+  assert_eq!(
+    (request.protobuf_type, &request.protobuf_data[..]),
+    (1, &b"hello"[..])
+  );
+  let response = round_trip(&mut server_encoder, &mut client_decoder, message(2, b""));
+  assert_eq!(response.protobuf_type, 2);
+}
+
+#[test]
+fn server_hello_announces_name_and_mac() {
+  let mut server = EspHomeHandshake::new_responder(
+    Some(PSK.to_string()),
+    "node".to_string(),
+    "AA:BB:CC:DD:EE:FF".to_string(),
+  )
+  .unwrap();
+
+  let mut outbox = Vec::new();
+  step(
+    &mut server,
+    &mut BytesMut::from(&[0x01, 0x00, 0x00][..]),
+    &mut outbox,
+  )
+  .unwrap();
+
+  assert_eq!(&outbox[0][3..], b"\x01node\x00AA:BB:CC:DD:EE:FF\x00");
+}
+
+#[test]
+fn client_with_wrong_psk_is_rejected() {
+  let client = EspHomeHandshake::new(Some(OTHER_PSK.to_string()), None).unwrap();
+  let server =
+    EspHomeHandshake::new_responder(Some(PSK.to_string()), "node".to_string(), String::new())
+      .unwrap();
+
+  let (client, server) = handshake(client, server);
+
+  assert!(
+    matches!(client, Err(CodecError::HandshakeFailed(error)) if error == "Handshake MAC failure")
+  );
+  assert!(matches!(server, Err(CodecError::HandshakeFailed(_))));
+}
+
+#[test]
+fn plaintext_client_is_told_to_use_encryption() {
+  let mut server =
+    EspHomeHandshake::new_responder(Some(PSK.to_string()), "node".to_string(), String::new())
+      .unwrap();
+  let mut hello = BytesMut::new();
+  PlainEncoder::new()
+    .encode(message(1, b"\x0a\x04test"), &mut hello)
+    .unwrap();
+
+  let mut outbox = Vec::new();
+  let result = step(&mut server, &mut hello, &mut outbox);
+
+  assert!(matches!(result, Err(CodecError::HandshakeFailed(_))));
+  // A noise frame, which plaintext clients take as "encryption required"
+  assert_eq!(outbox.len(), 1);
+  assert_eq!(outbox[0][0], 0x01);
+}
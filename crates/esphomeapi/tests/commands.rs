@@ -0,0 +1,154 @@
+use enumflags2::BitFlags;
+use esphomeapi::model::{
+  AlarmControlPanelCommand, ClimateMode, ClimatePreset, LockCommand, MediaPlayerCommand,
+  WaterHeaterMode, WaterHeaterStateFlag,
+};
+use esphomeapi::testing::MockDevice;
+use esphomeapi::{api, Client};
+
+async fn connect() -> (MockDevice, Client) {
+  let device = MockDevice::builder().start().await.unwrap();
+  let client = device.client().await.unwrap();
+  (device, client)
+}
+
+#[tokio::test]
+async fn climate_command_sets_only_given_fields() {
+  let (device, client) = connect().await;
+
+  client
+    .climate_command(
+      3,
+      Some(ClimateMode::Heat),
+      Some(21.5),
+      None,
+      None,
+      None,
+      None,
+      None,
+      Some(ClimatePreset::Eco),
+      None,
+      None,
+    )
+    .await
+    .unwrap();
+
+  let request: api::ClimateCommandRequest = device.next_received().await.unwrap();
+  assert_eq!(request.key, 3);
+  assert!(request.has_mode);
+  assert_eq!(
+    request.mode.enum_value(),
+    Ok(api::ClimateMode::CLIMATE_MODE_HEAT)
+  );
+  assert!(request.has_target_temperature);
+  assert_eq!(request.target_temperature, 21.5);
+  assert!(!request.has_fan_mode);
+  assert!(request.has_preset);
+  assert_eq!(
+    request.preset.enum_value(),
+    Ok(api::ClimatePreset::CLIMATE_PRESET_ECO)
+  );
+}
+
+#[tokio::test]
+async fn cover_and_valve_commands() {
+  let (device, client) = connect().await;
+
+  client
+    .cover_command(4, Some(0.5), None, false)
+    .await
+    .unwrap();
+  client.valve_command(5, None, true).await.unwrap();
+
+  let cover: api::CoverCommandRequest = device.next_received().await.unwrap();
+  assert!(cover.has_position);
+  assert_eq!(cover.position, 0.5);
+  assert!(!cover.has_tilt);
+
+  let valve: api::ValveCommandRequest = device.next_received().await.unwrap();
+  assert!(!valve.has_position);
+  assert!(valve.stop);
+}
+
+#[tokio::test]
+async fn enum_commands_map_to_protocol_values() {
+  let (device, client) = connect().await;
+
+  client
+    .lock_command(6, LockCommand::Open, Some("1234".to_string()))
+    .await
+    .unwrap();
+  client
+    .alarm_control_panel_command(7, AlarmControlPanelCommand::ArmAway, None)
+    .await
+    .unwrap();
+  client
+    .media_player_command(8, Some(MediaPlayerCommand::Pause), None, None, None)
+    .await
+    .unwrap();
+
+  let lock: api::LockCommandRequest = device.next_received().await.unwrap();
+  assert_eq!(lock.command.enum_value(), Ok(api::LockCommand::LOCK_OPEN));
+  assert!(lock.has_code);
+  assert_eq!(lock.code, "1234");
+
+  let alarm: api::AlarmControlPanelCommandRequest = device.next_received().await.unwrap();
+  assert_eq!(
+    alarm.command.enum_value(),
+    Ok(api::AlarmControlPanelStateCommand::ALARM_CONTROL_PANEL_ARM_AWAY)
+  );
+
+  let media: api::MediaPlayerCommandRequest = device.next_received().await.unwrap();
+  assert!(media.has_command);
+  assert_eq!(
+    media.command.enum_value(),
+    Ok(api::MediaPlayerCommand::MEDIA_PLAYER_COMMAND_PAUSE)
+  );
+  assert!(!media.has_volume);
+}
+
+#[tokio::test]
+async fn water_heater_command_builds_field_mask() {
+  let (device, client) = connect().await;
+
+  client
+    .water_heater_command(
+      9,
+      Some(WaterHeaterMode::HeatPump),
+      None,
+      None,
+      Some(60.0),
+      Some(BitFlags::from(WaterHeaterStateFlag::On)),
+    )
+    .await
+    .unwrap();
+
+  let request: api::WaterHeaterCommandRequest = device.next_received().await.unwrap();
+  // mode | state | target_temperature_high
+  assert_eq!(request.has_fields, 1 | 4 | 16);
+  assert_eq!(
+    request.mode.enum_value(),
+    Ok(api::WaterHeaterMode::WATER_HEATER_MODE_HEAT_PUMP)
+  );
+  assert_eq!(request.target_temperature_high, 60.0);
+  assert_eq!(request.state, 2);
+}
+
+#[tokio::test]
+async fn simple_value_commands() {
+  let (device, client) = connect().await;
+
+  client.button_command(10).await.unwrap();
+  client.number_command(11, 42.0).await.unwrap();
+  client.select_command(12, "b".to_string()).await.unwrap();
+  client.date_command(13, 2024, 2, 29).await.unwrap();
+
+  let button: api::ButtonCommandRequest = device.next_received().await.unwrap();
+  assert_eq!(button.key, 10);
+  let number: api::NumberCommandRequest = device.next_received().await.unwrap();
+  assert_eq!(number.state, 42.0);
+  let select: api::SelectCommandRequest = device.next_received().await.unwrap();
+  assert_eq!(select.state, "b");
+  let date: api::DateCommandRequest = device.next_received().await.unwrap();
+  assert_eq!((date.year, date.month, date.day), (2024, 2, 29));
+}
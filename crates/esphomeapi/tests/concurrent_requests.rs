@@ -0,0 +1,84 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use esphomeapi::testing::{frame, MockDevice};
+use esphomeapi::{api, Client, Error};
+
+fn switch_entity(key: u32) -> api::ListEntitiesSwitchResponse {
+  api::ListEntitiesSwitchResponse {
+    object_id: format!("switch_{key}"),
+    key,
+    name: format!("Switch {key}"),
+    ..Default::default()
+  }
+}
+
+#[tokio::test]
+async fn overlapping_device_info_requests_all_complete() {
+  let device = MockDevice::builder().name("busy").start().await.unwrap();
+  let client = device.client().await.unwrap();
+
+  let requests = (0..8).map(|_| {
+    let client = client.clone();
+    tokio::spawn(async move { client.device_info().await })
+  });
+
+  for request in requests {
+    assert_eq!(request.await.unwrap().unwrap().name, "busy");
+  }
+}
+
+#[tokio::test]
+async fn overlapping_list_entities_requests_get_full_results() {
+  let device = MockDevice::builder()
+    .entity(&switch_entity(1))
+    .entity(&switch_entity(2))
+    .entity(&switch_entity(3))
+    .start()
+    .await
+    .unwrap();
+  let client = device.client().await.unwrap();
+
+  let (first, second, info) = tokio::join!(
+    client.list_entities_services(),
+    client.list_entities_services(),
+    client.device_info(),
+  );
+
+  assert_eq!(first.unwrap().0.len(), 3);
+  assert_eq!(second.unwrap().0.len(), 3);
+  assert!(info.is_ok());
+}
+
+#[tokio::test]
+async fn timed_out_waiter_does_not_steal_later_response() {
+  let calls = Arc::new(AtomicUsize::new(0));
+  let device = MockDevice::builder()
+    .on::<api::DeviceInfoRequest, _>({
+      let calls = Arc::clone(&calls);
+      move |_| {
+        // Leave the first request unanswered
+        if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+          Vec::new()
+        } else {
+          vec![frame(&api::DeviceInfoResponse {
+            name: "late".to_string(),
+            ..Default::default()
+          })]
+        }
+      }
+    })
+    .start()
+    .await
+    .unwrap();
+  let client = Client::builder(device.host())
+    .port(device.port())
+    .request_timeout(Duration::from_millis(100))
+    .connect()
+    .await
+    .unwrap();
+
+  assert!(matches!(client.device_info().await, Err(Error::Timeout(_))));
+  assert_eq!(client.device_info().await.unwrap().name, "late");
+}
@@ -0,0 +1,128 @@
+use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+
+use esphomeapi::discovery::{self, connect_any, resolve};
+use esphomeapi::testing::MockDevice;
+use esphomeapi::{Client, Error};
+use mdns_sd::{ServiceDaemon, ServiceInfo};
+use tokio::net::TcpListener;
+use tokio::time::timeout;
+
+/// An address nothing listens on.
+async fn closed_addr() -> SocketAddr {
+  let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+  listener.local_addr().unwrap()
+}
+
+/// Advertises a node the way ESPHome does, until dropped.
+fn advertise(name: &str, device: &MockDevice) -> ServiceDaemon {
+  let mdns = ServiceDaemon::new().unwrap();
+  let service = ServiceInfo::new(
+    "_esphomelib._tcp.local.",
+    name,
+    &format!("{name}.local."),
+    "127.0.0.1",
+    device.addr().port(),
+    None,
+  )
+  .unwrap();
+  mdns.register(service).unwrap();
+  mdns
+}
+
+#[tokio::test]
+async fn next_address_is_tried_when_one_refuses() {
+  let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let live = listener.local_addr().unwrap();
+
+  let stream = connect_any(vec![closed_addr().await, live]).await.unwrap();
+
+  assert_eq!(stream.peer_addr().unwrap(), live);
+}
+
+#[tokio::test]
+async fn unanswered_address_does_not_hold_up_the_next() {
+  let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let live = listener.local_addr().unwrap();
+  // TEST-NET-1, never answers
+  let blackhole = "192.0.2.1:6053".parse().unwrap();
+
+  let stream = timeout(Duration::from_secs(2), connect_any(vec![blackhole, live]))
+    .await
+    .unwrap()
+    .unwrap();
+
+  assert_eq!(stream.peer_addr().unwrap(), live);
+}
+
+#[tokio::test]
+async fn last_error_is_returned_when_all_addresses_fail() {
+  let result = connect_any(vec![closed_addr().await, closed_addr().await]).await;
+
+  assert_eq!(
+    result.unwrap_err().kind(),
+    std::io::ErrorKind::ConnectionRefused
+  );
+}
+
+#[tokio::test]
+async fn node_is_resolved_and_connected_by_name() {
+  let device = MockDevice::builder()
+    .name("mdns-node")
+    .start()
+    .await
+    .unwrap();
+  let _mdns = advertise("mdns-node", &device);
+
+  let addresses = resolve("mdns-node.local", Duration::from_secs(5))
+    .await
+    .unwrap();
+  let client = Client::connect_by_name("mdns-node").await.unwrap();
+
+  assert!(addresses.contains(&device.addr()));
+  assert_eq!(client.server_info().name, "mdns-node");
+  client.disconnect().await.unwrap();
+
+  // The device reports its bare name, so a qualified one must match it too
+  let client = Client::connect_by_name("mdns-node.local.").await.unwrap();
+  assert_eq!(client.server_info().name, "mdns-node");
+}
+
+#[tokio::test]
+async fn unknown_name_is_not_resolved() {
+  let result = resolve("no-such-node", Duration::from_millis(500)).await;
+
+  assert!(matches!(result, Err(Error::NotResolved(name)) if name == "no-such-node"));
+}
+
+#[test]
+fn link_local_addresses_keep_their_scope() {
+  let link_local = "fe80::1".parse().unwrap();
+  let info = discovery::ServiceInfo {
+    ty_domain: "_esphomelib._tcp.local.".to_string(),
+    sub_domain: None,
+    fullname: "node._esphomelib._tcp.local.".to_string(),
+    server: "node".to_string(),
+    friendly_name: None,
+    addresses: HashSet::from([
+      IpAddr::V6(link_local),
+      "2001:db8::1".parse().unwrap(),
+      "192.168.1.2".parse().unwrap(),
+    ]),
+    scope_ids: HashMap::from([(link_local, 3)]),
+    port: 6053,
+  };
+
+  let mut addrs: Vec<String> = info
+    .socket_addrs()
+    .iter()
+    .map(SocketAddr::to_string)
+    .collect();
+  addrs.sort();
+
+  assert_eq!(
+    addrs,
+    ["192.168.1.2:6053", "[2001:db8::1]:6053", "[fe80::1%3]:6053"]
+  );
+}
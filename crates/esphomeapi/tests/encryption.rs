@@ -0,0 +1,110 @@
+use std::time::Duration;
+
+use base64::prelude::*;
+use esphomeapi::testing::MockDevice;
+use esphomeapi::{api, generate_noise_psk, Client, CodecError, Error};
+
+async fn device(api_encryption_supported: bool) -> MockDevice {
+  MockDevice::builder()
+    .device_info(api::DeviceInfoResponse {
+      api_encryption_supported,
+      ..Default::default()
+    })
+    .start()
+    .await
+    .unwrap()
+}
+
+#[test]
+fn generated_keys_are_random_32_byte_keys() {
+  let psk = generate_noise_psk();
+  assert_eq!(BASE64_STANDARD.decode(&psk).unwrap().len(), 32);
+  assert_ne!(psk, generate_noise_psk());
+}
+
+#[tokio::test]
+async fn plaintext_device_is_switched_to_the_new_key() {
+  let device = device(true).await;
+  let client = device.client().await.unwrap();
+  let mut reconnected = client.on_reconnect();
+
+  let psk = generate_noise_psk();
+  client.set_noise_psk(&psk).await.unwrap();
+
+  let request: api::NoiseEncryptionSetKeyRequest = device.next_received().await.unwrap();
+  assert_eq!(request.key, BASE64_STANDARD.decode(&psk).unwrap());
+
+  // The device disconnects gracefully; the client comes back over Noise
+  tokio::time::timeout(Duration::from_secs(5), reconnected.recv())
+    .await
+    .unwrap()
+    .unwrap();
+  assert_eq!(device.connection_count(), 2);
+  client.device_info().await.unwrap();
+
+  // The key also works for new clients
+  Client::builder(device.host())
+    .port(device.port())
+    .psk(psk)
+    .connect()
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn key_is_kept_when_the_device_drops_the_connection_before_answering() {
+  let device = MockDevice::builder()
+    .device_info(api::DeviceInfoResponse {
+      api_encryption_supported: true,
+      ..Default::default()
+    })
+    .on::<api::NoiseEncryptionSetKeyRequest, _>(|_| Vec::new())
+    .start()
+    .await
+    .unwrap();
+  let client = device.client().await.unwrap();
+  let mut reconnected = client.on_reconnect();
+  let psk = generate_noise_psk();
+
+  let (result, _) = tokio::join!(client.set_noise_psk(&psk), async {
+    let _: api::NoiseEncryptionSetKeyRequest = device.next_received().await.unwrap();
+    device.set_psk(Some(psk.clone()));
+    device.drop_connection();
+  });
+
+  result.unwrap();
+  tokio::time::timeout(Duration::from_secs(5), reconnected.recv())
+    .await
+    .unwrap()
+    .unwrap();
+  client.device_info().await.unwrap();
+}
+
+#[tokio::test]
+async fn key_is_rejected_by_devices_without_encryption() {
+  let device = device(false).await;
+  let client = device.client().await.unwrap();
+
+  let result = client.set_noise_psk(&generate_noise_psk()).await;
+
+  assert!(matches!(result, Err(Error::NoiseKeyRejected)));
+  // Still usable in plaintext
+  client.device_info().await.unwrap();
+  assert_eq!(device.connection_count(), 1);
+}
+
+#[tokio::test]
+async fn malformed_key_is_not_sent() {
+  let device = device(true).await;
+  let client = device.client().await.unwrap();
+
+  let result = client.set_noise_psk("AAAAAAAAAAAAAAAAAAAAAA==").await;
+
+  assert!(matches!(
+    result,
+    Err(Error::Handshake(CodecError::InvalidPskLength { .. }))
+  ));
+  assert!(device
+    .received_of::<api::NoiseEncryptionSetKeyRequest>()
+    .is_empty());
+}
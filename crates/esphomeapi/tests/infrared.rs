@@ -0,0 +1,156 @@
+use esphomeapi::infrared::{InfraredSignal, IrCode};
+use esphomeapi::testing::{frame, MockDevice};
+use esphomeapi::{api, Error};
+
+/// Simulate a receiver: stretch marks, shrink spaces and add a leading gap.
+fn captured(signal: &InfraredSignal) -> Vec<i32> {
+  std::iter::once(-20_000)
+    .chain(
+      signal
+        .timings
+        .iter()
+        .map(|t| if *t > 0 { t + 90 } else { t + 80 }),
+    )
+    .collect()
+}
+
+#[test]
+fn nec_frames_round_trip() {
+  let code = IrCode::Nec {
+    address: 0x04,
+    command: 0x08,
+  };
+  let signal = code.encode();
+  assert_eq!(signal.carrier_frequency, 38_000);
+  assert_eq!(signal.timings.len(), 2 + 64 + 1);
+  assert_eq!(&signal.timings[..4], &[9000, -4500, 560, -560]);
+  assert_eq!(IrCode::decode(&captured(&signal)), Some(code));
+
+  let extended = IrCode::Nec {
+    address: 0x1234,
+    command: 0xFF,
+  };
+  assert_eq!(IrCode::decode(&extended.encode().timings), Some(extended));
+}
+
+#[test]
+fn samsung_frames_round_trip() {
+  let code = IrCode::Samsung {
+    address: 0x07,
+    command: 0x02,
+  };
+  let signal = code.encode();
+  assert_eq!(&signal.timings[..2], &[4500, -4500]);
+  assert_eq!(IrCode::decode(&captured(&signal)), Some(code));
+}
+
+#[test]
+fn rc5_frames_round_trip() {
+  for (address, command, toggle) in [(0, 0, false), (5, 0x35, true), (0x1F, 0x7F, false)] {
+    let code = IrCode::Rc5 {
+      address,
+      command,
+      toggle,
+    };
+    let signal = code.encode();
+    assert_eq!(signal.carrier_frequency, 36_000);
+    assert!(signal.timings[0] > 0);
+    assert!(*signal.timings.last().unwrap() > 0);
+    assert_eq!(IrCode::decode(&captured(&signal)), Some(code), "{code:?}");
+  }
+}
+
+#[test]
+fn noise_is_not_decoded() {
+  assert_eq!(IrCode::decode(&[]), None);
+  assert_eq!(IrCode::decode(&[300, -300, 300, -5000, 1200]), None);
+}
+
+#[test]
+fn pronto_codes_convert_to_timings_and_back() {
+  let signal = InfraredSignal::from_pronto("0000 006D 0002 0000 0157 00AC 0015 0016").unwrap();
+  assert_eq!(signal.carrier_frequency, 38029);
+  assert_eq!(signal.timings, vec![9019, -4523, 552, -579]);
+
+  let nec = IrCode::Nec {
+    address: 0x10,
+    command: 0x20,
+  };
+  let pronto = nec.encode().to_pronto().unwrap();
+  assert!(pronto.starts_with("0000 006D 0022 0000 "));
+  let parsed = InfraredSignal::from_pronto(&pronto).unwrap();
+  assert_eq!(IrCode::decode(&parsed.timings), Some(nec));
+}
+
+#[test]
+fn malformed_pronto_codes_are_rejected() {
+  for code in [
+    "0000 006D",
+    "0100 006D 0001 0000 0010 0010",
+    "0000 006D 0002 0000 0010 0010",
+    "0000 006D 0001 0000 0010 XYZ0",
+  ] {
+    assert!(
+      matches!(
+        InfraredSignal::from_pronto(code),
+        Err(Error::InvalidInfraredSignal(_))
+      ),
+      "{code}"
+    );
+  }
+}
+
+#[tokio::test]
+async fn signals_are_transmitted_as_raw_timings() {
+  let device = MockDevice::builder().start().await.unwrap();
+  let client = device.client().await.unwrap();
+
+  let signal = IrCode::Nec {
+    address: 0x04,
+    command: 0x08,
+  }
+  .encode();
+  client.infrared_transmit(9, &signal, 2).await.unwrap();
+
+  let request: api::InfraredRFTransmitRawTimingsRequest = device.next_received().await.unwrap();
+  assert_eq!(request.key, 9);
+  assert_eq!(request.carrier_frequency, 38_000);
+  assert_eq!(request.repeat_count, 2);
+  assert_eq!(request.timings, signal.timings);
+}
+
+#[tokio::test]
+async fn captures_are_streamed_per_receiver() {
+  let code = IrCode::Samsung {
+    address: 0x07,
+    command: 0x02,
+  };
+  let timings = code.encode().timings;
+  let device = MockDevice::builder()
+    .on::<api::SubscribeStatesRequest, _>(move |_| {
+      vec![
+        frame(&api::InfraredRFReceiveEvent {
+          key: 1,
+          timings: vec![500, -500],
+          ..Default::default()
+        }),
+        frame(&api::InfraredRFReceiveEvent {
+          key: 2,
+          timings: timings.clone(),
+          ..Default::default()
+        }),
+      ]
+    })
+    .start()
+    .await
+    .unwrap();
+  let client = device.client().await.unwrap();
+
+  let mut captures = client.infrared_receive(2);
+  let mut events = client.infrared_receiver();
+  client.request_states().await.unwrap();
+
+  assert_eq!(IrCode::decode(&captures.recv().await.unwrap()), Some(code));
+  assert_eq!(events.recv().await.unwrap().key, 1);
+  assert_eq!(events.recv().await.unwrap().decode(), Some(code));
+}
@@ -0,0 +1,290 @@
+use std::time::Duration;
+
+use esphomeapi::api;
+use esphomeapi::model::{
+  EntityInfo, EntityState, InfraredCapability, WaterHeaterMode, WaterHeaterStateFlag,
+};
+use esphomeapi::testing::MockDevice;
+use esphomeapi::{Client, Error};
+
+const PSK: &str = "px7tsbK3C7bpXHr2OevEV2ZMg/FrNBw2+O2pNPbedtA=";
+
+async fn connect(
+  device: &MockDevice,
+  psk: Option<&str>,
+  name: Option<&str>,
+) -> esphomeapi::Result<Client> {
+  connect_with_password(device, psk, name, None).await
+}
+
+async fn connect_with_password(
+  device: &MockDevice,
+  psk: Option<&str>,
+  name: Option<&str>,
+  password: Option<&str>,
+) -> esphomeapi::Result<Client> {
+  let mut builder = Client::builder(device.host()).port(device.port());
+  if let Some(psk) = psk {
+    builder = builder.psk(psk);
+  }
+  if let Some(name) = name {
+    builder = builder.expected_name(name);
+  }
+  if let Some(password) = password {
+    builder = builder.password(password);
+  }
+  builder.connect().await
+}
+
+fn switch_entity(key: u32) -> api::ListEntitiesSwitchResponse {
+  api::ListEntitiesSwitchResponse {
+    object_id: format!("switch_{key}"),
+    key,
+    name: format!("Switch {key}"),
+    ..Default::default()
+  }
+}
+
+#[tokio::test]
+async fn plain_connection_reads_device_info() {
+  let device = MockDevice::builder()
+    .name("plain-device")
+    .mac_address("AA:BB:CC:DD:EE:FF")
+    .start()
+    .await
+    .unwrap();
+
+  let client = connect(&device, None, Some("plain-device")).await.unwrap();
+  let info = client.device_info().await.unwrap();
+
+  assert_eq!(info.name, "plain-device");
+  assert_eq!(info.mac_address, "AA:BB:CC:DD:EE:FF");
+
+  let hello: api::HelloRequest = device.next_received().await.unwrap();
+  assert_eq!(hello.api_version_major, 1);
+}
+
+#[tokio::test]
+async fn noise_connection_reads_device_info() {
+  let device = MockDevice::builder()
+    .name("noise-device")
+    .psk(PSK)
+    .start()
+    .await
+    .unwrap();
+
+  let client = connect(&device, Some(PSK), None).await.unwrap();
+  let info = client.device_info().await.unwrap();
+
+  assert_eq!(info.name, "noise-device");
+}
+
+#[tokio::test]
+async fn noise_connection_rejects_wrong_psk() {
+  let device = MockDevice::builder().psk(PSK).start().await.unwrap();
+
+  let result = connect(
+    &device,
+    Some("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA="),
+    None,
+  )
+  .await;
+
+  assert!(matches!(result, Err(Error::Handshake(_))));
+}
+
+#[tokio::test]
+async fn name_mismatch_is_rejected() {
+  let device = MockDevice::builder().name("actual").start().await.unwrap();
+
+  let result = connect(&device, None, Some("expected")).await;
+
+  assert!(matches!(
+    result,
+    Err(Error::NameMismatch { expected, actual }) if expected == "expected" && actual == "actual"
+  ));
+}
+
+#[tokio::test]
+async fn wrong_password_is_rejected() {
+  let device = MockDevice::builder()
+    .password("secret")
+    .start()
+    .await
+    .unwrap();
+
+  assert!(connect_with_password(&device, None, None, Some("secret"))
+    .await
+    .is_ok());
+
+  let result = connect_with_password(&device, None, None, Some("wrong")).await;
+  assert!(matches!(result, Err(Error::Authentication)));
+}
+
+#[tokio::test]
+async fn refused_connection_is_reported() {
+  let device = MockDevice::builder().start().await.unwrap();
+  let (host, port) = (device.host(), device.port());
+  drop(device);
+
+  let result = Client::builder(host).port(port).connect().await;
+
+  assert!(matches!(result, Err(Error::Connect { .. })));
+}
+
+#[tokio::test]
+async fn list_entities_returns_scripted_entities() {
+  let device = MockDevice::builder()
+    .entity(&switch_entity(1))
+    .entity(&switch_entity(2))
+    .entity(&api::ListEntitiesServicesResponse {
+      name: "reboot".to_string(),
+      key: 10,
+      ..Default::default()
+    })
+    .start()
+    .await
+    .unwrap();
+
+  let client = connect(&device, None, None).await.unwrap();
+  let (entities, services) = client.list_entities_services().await.unwrap();
+
+  assert_eq!(entities.len(), 2);
+  assert!(matches!(&entities[0], EntityInfo::Switch(info) if info.entity_info.key == 1));
+  assert_eq!(services.len(), 1);
+  assert_eq!(services[0].name, "reboot");
+}
+
+#[tokio::test]
+async fn siren_water_heater_and_infrared_are_listed() {
+  let device = MockDevice::builder()
+    .entity(&api::ListEntitiesSirenResponse {
+      key: 1,
+      tones: vec!["beep".to_string()],
+      supports_volume: true,
+      ..Default::default()
+    })
+    .entity(&api::ListEntitiesWaterHeaterResponse {
+      key: 2,
+      max_temperature: 65.0,
+      supported_modes: vec![api::WaterHeaterMode::WATER_HEATER_MODE_ECO.into()],
+      ..Default::default()
+    })
+    .entity(&api::ListEntitiesInfraredResponse {
+      key: 3,
+      capabilities: 0b11,
+      ..Default::default()
+    })
+    .state(&api::WaterHeaterStateResponse {
+      key: 2,
+      target_temperature: 50.0,
+      state: 0b10,
+      ..Default::default()
+    })
+    .start()
+    .await
+    .unwrap();
+
+  let client = connect(&device, None, None).await.unwrap();
+  let (entities, _) = client.list_entities_services().await.unwrap();
+
+  assert_eq!(entities.len(), 3);
+  assert!(
+    matches!(&entities[0], EntityInfo::Siren(info) if info.tones == ["beep"] && info.supports_volume)
+  );
+  assert!(matches!(
+    &entities[1],
+    EntityInfo::WaterHeater(info) if info.supported_modes == [WaterHeaterMode::Eco]
+  ));
+  assert!(matches!(
+    &entities[2],
+    EntityInfo::Infrared(info) if info.capabilities.contains(InfraredCapability::Receiver)
+  ));
+
+  let mut states = client.states_receiver();
+  client.request_states().await.unwrap();
+  let state = tokio::time::timeout(Duration::from_secs(5), states.recv())
+    .await
+    .unwrap()
+    .unwrap();
+  assert!(matches!(
+    state,
+    EntityState::WaterHeater(state)
+      if state.target_temperature == 50.0 && state.state.contains(WaterHeaterStateFlag::On)
+  ));
+}
+
+#[tokio::test]
+async fn states_are_sent_on_subscribe_and_on_demand() {
+  let device = MockDevice::builder()
+    .entity(&switch_entity(1))
+    .state(&api::SwitchStateResponse {
+      key: 1,
+      state: true,
+      ..Default::default()
+    })
+    .start()
+    .await
+    .unwrap();
+
+  let client = connect(&device, None, None).await.unwrap();
+  let mut states = client.states_receiver();
+  client.request_states().await.unwrap();
+
+  let state = tokio::time::timeout(Duration::from_secs(5), states.recv())
+    .await
+    .unwrap()
+    .unwrap();
+  assert!(matches!(state, EntityState::Switch(state) if state.state));
+
+  device
+    .send(&api::SwitchStateResponse {
+      key: 1,
+      state: false,
+      ..Default::default()
+    })
+    .unwrap();
+
+  let state = tokio::time::timeout(Duration::from_secs(5), states.recv())
+    .await
+    .unwrap()
+    .unwrap();
+  assert!(matches!(state, EntityState::Switch(state) if !state.state));
+}
+
+#[tokio::test]
+async fn commands_are_recorded() {
+  let device = MockDevice::builder()
+    .entity(&switch_entity(7))
+    .start()
+    .await
+    .unwrap();
+
+  let client = connect(&device, None, None).await.unwrap();
+  client.switch_command(7, true).await.unwrap();
+  client.switch_command(7, false).await.unwrap();
+
+  let first: api::SwitchCommandRequest = device.next_received().await.unwrap();
+  let second: api::SwitchCommandRequest = device.next_received().await.unwrap();
+
+  assert_eq!(first.key, 7);
+  assert!(first.state);
+  assert!(!second.state);
+  assert_eq!(device.received_of::<api::SwitchCommandRequest>().len(), 2);
+}
+
+#[tokio::test]
+async fn device_disconnect_request_is_reported() {
+  let device = MockDevice::builder().start().await.unwrap();
+
+  let client = connect(&device, None, None).await.unwrap();
+  let mut disconnected = client.on_device_disconnect();
+
+  device.request_disconnect().unwrap();
+
+  tokio::time::timeout(Duration::from_secs(5), disconnected.recv())
+    .await
+    .unwrap()
+    .unwrap();
+  let _: api::DisconnectResponse = device.next_received().await.unwrap();
+}
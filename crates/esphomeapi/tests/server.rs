@@ -0,0 +1,188 @@
+use std::time::Duration;
+
+use esphomeapi::model::{
+  BaseEntityInfo, BaseEntityState, DeviceInfo, EntityCategory, EntityInfo, EntityState, SwitchInfo,
+  SwitchState,
+};
+use esphomeapi::server::{DeviceHandle, VirtualDevice, VirtualDeviceBuilder};
+use esphomeapi::time::TimeSource;
+use esphomeapi::{api, BufferSizes, Client};
+use tokio::time::timeout;
+
+const PSK: &str = "px7tsbK3C7bpXHr2OevEV2ZMg/FrNBw2+O2pNPbedtA=";
+
+fn relay(key: u32) -> EntityInfo {
+  EntityInfo::Switch(SwitchInfo {
+    entity_info: BaseEntityInfo {
+      object_id: format!("relay_{key}"),
+      key,
+      device_id: 0,
+      name: format!("Relay {key}"),
+      disabled_by_default: false,
+      icon: String::new(),
+      entity_category: EntityCategory::None,
+    },
+    assumed_state: false,
+    device_class: String::new(),
+  })
+}
+
+fn relay_state(key: u32, state: bool) -> EntityState {
+  EntityState::Switch(SwitchState {
+    entity_state: BaseEntityState { key, device_id: 0 },
+    state,
+  })
+}
+
+fn gateway() -> VirtualDeviceBuilder {
+  VirtualDevice::builder("gateway")
+    .entity(relay(1))
+    .state(relay_state(1, false))
+}
+
+async fn start(builder: VirtualDeviceBuilder) -> VirtualDevice {
+  builder.bind("127.0.0.1:0").await.unwrap()
+}
+
+fn client(device: &VirtualDevice) -> esphomeapi::ClientBuilder {
+  let addr = device.local_addr();
+  Client::builder(addr.ip().to_string()).port(addr.port().into())
+}
+
+async fn next_state(states: &mut tokio::sync::broadcast::Receiver<EntityState>) -> EntityState {
+  timeout(Duration::from_secs(5), states.recv())
+    .await
+    .unwrap()
+    .unwrap()
+}
+
+#[tokio::test]
+async fn client_sees_device_info_and_entities() {
+  let device = start(gateway().device_info(DeviceInfo {
+    mac_address: "AA:BB:CC:DD:EE:FF".to_string(),
+    model: "Relay bridge".to_string(),
+    ..Default::default()
+  }))
+  .await;
+
+  let client = client(&device).connect().await.unwrap();
+  let info = client.device_info().await.unwrap();
+  let (entities, _) = client.list_entities_services().await.unwrap();
+
+  assert_eq!(info.name, "gateway");
+  assert_eq!(info.model, "Relay bridge");
+  assert_eq!(client.server_info().name, "gateway");
+  assert_eq!(entities, [relay(1)]);
+}
+
+#[tokio::test]
+async fn states_are_sent_on_subscribe_and_pushed_on_change() {
+  let device = start(gateway()).await;
+  let client = client(&device).connect().await.unwrap();
+  let mut states = client.states_receiver();
+
+  client.request_states().await.unwrap();
+  let initial = next_state(&mut states).await;
+  device.set_state(relay_state(1, false)).unwrap();
+  device.set_state(relay_state(1, true)).unwrap();
+  let pushed = next_state(&mut states).await;
+
+  assert_eq!(initial, relay_state(1, false));
+  // The unchanged state is not sent again
+  assert_eq!(pushed, relay_state(1, true));
+}
+
+#[tokio::test]
+async fn lagging_client_is_sent_every_state_again() {
+  let device = start(gateway().entity(relay(2)).state(relay_state(2, false))).await;
+  let client = client(&device)
+    .buffer_sizes(BufferSizes {
+      states: 1024,
+      ..Default::default()
+    })
+    .connect()
+    .await
+    .unwrap();
+  let mut states = client.states_receiver();
+  client.request_states().await.unwrap();
+  next_state(&mut states).await;
+  next_state(&mut states).await;
+
+  // Nothing yields in between, so the connection falls behind the updates
+  device.set_state(relay_state(1, true)).unwrap();
+  for i in 0..600 {
+    device.set_state(relay_state(2, i % 2 == 0)).unwrap();
+  }
+
+  // The first update was dropped with the backlog; the resent snapshot has it
+  while next_state(&mut states).await != relay_state(1, true) {}
+}
+
+#[tokio::test]
+async fn commands_reach_the_callback() {
+  let device = start(gateway().on_command(
+    |command: api::SwitchCommandRequest, device: &DeviceHandle| {
+      device.set_state(relay_state(command.key, command.state))
+    },
+  ))
+  .await;
+  let client = client(&device).connect().await.unwrap();
+  let mut states = client.states_receiver();
+  client.request_states().await.unwrap();
+  next_state(&mut states).await;
+
+  client.switch_command(1, true).await.unwrap();
+
+  assert_eq!(next_state(&mut states).await, relay_state(1, true));
+  assert_eq!(device.handle().state(0, 1), Some(relay_state(1, true)));
+}
+
+#[tokio::test]
+async fn encrypted_device_accepts_only_the_right_key() {
+  let device = start(gateway().psk(PSK)).await;
+
+  let client = client(&device).psk(PSK).connect().await.unwrap();
+  let refused = self::client(&device).connect().await;
+
+  assert_eq!(client.device_info().await.unwrap().name, "gateway");
+  assert!(refused.is_err());
+}
+
+#[tokio::test]
+async fn client_time_is_requested_on_subscribe() {
+  let device = start(gateway()).await;
+  let mut time = device.handle().time_receiver();
+  let client = client(&device)
+    .time_source(TimeSource::posix("EST5EDT,M3.2.0,M11.1.0"))
+    .connect()
+    .await
+    .unwrap();
+
+  client.request_states().await.unwrap();
+  timeout(Duration::from_secs(5), time.wait_for(Option::is_some))
+    .await
+    .unwrap()
+    .unwrap();
+
+  assert_eq!(
+    device.handle().time().unwrap().timezone,
+    "EST5EDT,M3.2.0,M11.1.0"
+  );
+}
+
+#[tokio::test]
+async fn disconnect_request_is_answered() {
+  let device = start(gateway()).await;
+  let client = client(&device).connect().await.unwrap();
+  let mut disconnected = client.on_device_disconnect();
+
+  timeout(Duration::from_secs(5), client.disconnect())
+    .await
+    .unwrap()
+    .unwrap();
+
+  timeout(Duration::from_secs(5), disconnected.recv())
+    .await
+    .unwrap()
+    .unwrap();
+}
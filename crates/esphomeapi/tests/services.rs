@@ -0,0 +1,164 @@
+use esphomeapi::model::{SupportsResponseType, UserServiceArgValue};
+use esphomeapi::testing::{frame, MockDevice};
+use esphomeapi::{api, Client, Error};
+
+fn service(supports_response: api::SupportsResponseType) -> api::ListEntitiesServicesResponse {
+  let arg = |name: &str, type_: api::ServiceArgType| api::ListEntitiesServicesArgument {
+    name: name.to_string(),
+    type_: type_.into(),
+    ..Default::default()
+  };
+  api::ListEntitiesServicesResponse {
+    name: "play_rtttl".to_string(),
+    key: 9,
+    args: vec![
+      arg("song", api::ServiceArgType::SERVICE_ARG_TYPE_STRING),
+      arg("volumes", api::ServiceArgType::SERVICE_ARG_TYPE_INT_ARRAY),
+    ],
+    supports_response: supports_response.into(),
+    ..Default::default()
+  }
+}
+
+async fn connect(device: &MockDevice) -> Client {
+  let client = device.client().await.unwrap();
+  client.list_entities_services().await.unwrap();
+  client
+}
+
+fn args() -> Vec<UserServiceArgValue> {
+  vec![
+    UserServiceArgValue::String("scale".to_string()),
+    UserServiceArgValue::IntArray(vec![1, 2, 3]),
+  ]
+}
+
+#[tokio::test]
+async fn service_response_type_is_parsed() {
+  let device = MockDevice::builder()
+    .entity(&service(
+      api::SupportsResponseType::SUPPORTS_RESPONSE_OPTIONAL,
+    ))
+    .start()
+    .await
+    .unwrap();
+  let client = device.client().await.unwrap();
+
+  let (_, services) = client.list_entities_services().await.unwrap();
+  assert_eq!(
+    services[0].supports_response,
+    SupportsResponseType::Optional
+  );
+}
+
+#[tokio::test]
+async fn execute_service_sends_typed_arguments() {
+  let device = MockDevice::builder()
+    .entity(&service(api::SupportsResponseType::SUPPORTS_RESPONSE_NONE))
+    .start()
+    .await
+    .unwrap();
+  let client = connect(&device).await;
+
+  client.execute_service(9, args()).await.unwrap();
+
+  let request: api::ExecuteServiceRequest = device.next_received().await.unwrap();
+  assert_eq!(request.key, 9);
+  assert_eq!(request.args[0].string_, "scale");
+  assert_eq!(request.args[1].int_array, vec![1, 2, 3]);
+  assert!(!request.return_response);
+  assert_eq!(request.call_id, 0);
+}
+
+#[tokio::test]
+async fn execute_service_rejects_mismatched_arguments() {
+  let device = MockDevice::builder()
+    .entity(&service(api::SupportsResponseType::SUPPORTS_RESPONSE_NONE))
+    .start()
+    .await
+    .unwrap();
+  let client = connect(&device).await;
+
+  let wrong_type = client
+    .execute_service(
+      9,
+      vec![
+        UserServiceArgValue::String("scale".to_string()),
+        UserServiceArgValue::Int(1),
+      ],
+    )
+    .await;
+  assert!(matches!(
+    wrong_type,
+    Err(Error::InvalidServiceArguments { .. })
+  ));
+
+  let too_few = client.execute_service(9, args()[..1].to_vec()).await;
+  assert!(matches!(
+    too_few,
+    Err(Error::InvalidServiceArguments { .. })
+  ));
+
+  let unknown = client.execute_service(10, args()).await;
+  assert!(matches!(unknown, Err(Error::UnknownService(10))));
+
+  let no_response = client.execute_service_with_response(9, args()).await;
+  assert!(matches!(
+    no_response,
+    Err(Error::InvalidServiceArguments { .. })
+  ));
+}
+
+#[tokio::test]
+async fn response_only_service_is_not_executed_without_a_response() {
+  let device = MockDevice::builder()
+    .entity(&service(api::SupportsResponseType::SUPPORTS_RESPONSE_ONLY))
+    .start()
+    .await
+    .unwrap();
+  let client = connect(&device).await;
+
+  let result = client.execute_service(9, args()).await;
+
+  assert!(matches!(result, Err(Error::InvalidServiceArguments { .. })));
+  assert!(device
+    .received_of::<api::ExecuteServiceRequest>()
+    .is_empty());
+}
+
+#[tokio::test]
+async fn execute_service_with_response_awaits_matching_call_id() {
+  let device = MockDevice::builder()
+    .entity(&service(api::SupportsResponseType::SUPPORTS_RESPONSE_ONLY))
+    .on::<api::ExecuteServiceRequest, _>(|request| {
+      // An unrelated answer first; the client must skip it
+      let other = api::ExecuteServiceResponse {
+        call_id: request.call_id + 100,
+        success: false,
+        ..Default::default()
+      };
+      let answer = api::ExecuteServiceResponse {
+        call_id: request.call_id,
+        success: true,
+        response_data: br#"{"notes":3}"#.to_vec(),
+        ..Default::default()
+      };
+      vec![frame(&other), frame(&answer)]
+    })
+    .start()
+    .await
+    .unwrap();
+  let client = connect(&device).await;
+
+  let response = client
+    .execute_service_with_response(9, args())
+    .await
+    .unwrap();
+
+  let request: api::ExecuteServiceRequest = device.next_received().await.unwrap();
+  assert!(request.return_response);
+  assert_ne!(request.call_id, 0);
+  assert_eq!(response.call_id, request.call_id);
+  assert!(response.success);
+  assert_eq!(response.response_json(), Some(r#"{"notes":3}"#));
+}
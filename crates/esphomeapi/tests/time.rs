@@ -0,0 +1,96 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use esphomeapi::testing::MockDevice;
+use esphomeapi::time::{DeviceTime, TimeSource};
+use esphomeapi::{api, Client, Error};
+
+async fn ask_time(time_source: Option<TimeSource>) -> api::GetTimeResponse {
+  let device = MockDevice::builder().start().await.unwrap();
+  let mut builder = Client::builder(device.host()).port(device.port());
+  if let Some(time_source) = time_source {
+    builder = builder.time_source(time_source);
+  }
+  let _client = builder.connect().await.unwrap();
+
+  device.send(&api::GetTimeRequest::default()).unwrap();
+  device.next_received().await.unwrap()
+}
+
+#[tokio::test]
+async fn system_time_is_sent_without_timezone_by_default() {
+  let before = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+
+  let response = ask_time(None).await;
+
+  assert!(u64::from(response.epoch_seconds) >= before.as_secs());
+  assert!(response.timezone.is_empty());
+}
+
+#[tokio::test]
+async fn posix_timezone_is_sent_with_the_time() {
+  let response = ask_time(Some(TimeSource::posix("EST5EDT,M3.2.0,M11.1.0"))).await;
+
+  assert_eq!(response.timezone, "EST5EDT,M3.2.0,M11.1.0");
+}
+
+#[tokio::test]
+async fn custom_hook_is_asked_on_every_request() {
+  // One second before the 2025 spring-forward in Berlin
+  let clock = Arc::new(AtomicU32::new(1_743_296_399));
+  let hook_clock = Arc::clone(&clock);
+  let device = MockDevice::builder().start().await.unwrap();
+  let _client = Client::builder(device.host())
+    .port(device.port())
+    .time_source(TimeSource::custom(move || DeviceTime {
+      epoch_seconds: hook_clock.load(Ordering::SeqCst),
+      timezone: "CET-1CEST,M3.5.0,M10.5.0/3".to_string(),
+    }))
+    .connect()
+    .await
+    .unwrap();
+
+  device.send(&api::GetTimeRequest::default()).unwrap();
+  let first: api::GetTimeResponse = device.next_received().await.unwrap();
+  clock.store(1_743_296_401, Ordering::SeqCst);
+  device.send(&api::GetTimeRequest::default()).unwrap();
+  let second: api::GetTimeResponse = device.next_received().await.unwrap();
+
+  assert_eq!(first.epoch_seconds, 1_743_296_399);
+  assert_eq!(second.epoch_seconds, 1_743_296_401);
+  assert_eq!(second.timezone, "CET-1CEST,M3.5.0,M10.5.0/3");
+}
+
+#[test]
+fn iana_zone_is_converted_to_posix() {
+  let time_source = TimeSource::iana("Europe/Berlin").unwrap();
+
+  assert_eq!(time_source.now().timezone, "CET-1CEST,M3.5.0,M10.5.0/3");
+}
+
+#[test]
+fn iana_zone_is_looked_up_in_tzdir_first() {
+  let tzdir = std::env::temp_dir().join(format!("esphomeapi-tzdir-{}", std::process::id()));
+  std::fs::create_dir_all(tzdir.join("Test")).unwrap();
+  std::fs::write(tzdir.join("Test/Kolkata"), b"TZif2\0\0\0\n<+0530>-5:30\n").unwrap();
+  std::env::set_var("TZDIR", &tzdir);
+
+  let custom = TimeSource::iana("Test/Kolkata").unwrap();
+  // Zones missing from TZDIR still come from the system database
+  let system = TimeSource::iana("Europe/Berlin").unwrap();
+
+  assert_eq!(custom.now().timezone, "<+0530>-5:30");
+  assert_eq!(system.now().timezone, "CET-1CEST,M3.5.0,M10.5.0/3");
+  std::fs::remove_dir_all(tzdir).unwrap();
+}
+
+#[test]
+fn unknown_iana_zone_is_rejected() {
+  for zone in ["Mars/Olympus_Mons", "../../etc/passwd", ""] {
+    assert!(matches!(
+      TimeSource::iana(zone),
+      Err(Error::InvalidTimezone(_))
+    ));
+  }
+}
@@ -0,0 +1,100 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use esphomeapi::testing::MockDevice;
+use esphomeapi::transport::{self, Tcp, Transport as _, UnixSocket};
+use esphomeapi::{api, Client, Error, ReconnectPolicy};
+use tokio::io::{duplex, AsyncRead, AsyncWrite};
+use tokio::net::{TcpListener, TcpStream, UnixListener};
+
+/// Pipes `stream` to the mock device, standing in for a tunnel.
+fn bridge(device: &MockDevice, mut stream: impl AsyncRead + AsyncWrite + Send + Unpin + 'static) {
+  let addr = device.addr();
+  tokio::spawn(async move {
+    let mut device = TcpStream::connect(addr).await.unwrap();
+    let _ = tokio::io::copy_bidirectional(&mut stream, &mut device).await;
+  });
+}
+
+#[tokio::test]
+async fn custom_dialer_is_used_for_connects_and_reconnects() {
+  let device = Arc::new(MockDevice::builder().name("node").start().await.unwrap());
+  let dials = Arc::new(AtomicUsize::new(0));
+
+  let dialer_device = Arc::clone(&device);
+  let dialer_dials = Arc::clone(&dials);
+  let client = Client::builder("node")
+    .reconnect_policy(ReconnectPolicy {
+      initial_delay: Duration::from_millis(20),
+      ..Default::default()
+    })
+    .transport(transport::from_fn(move |host, port| {
+      assert_eq!((host.as_str(), port), ("node", 6053));
+      dialer_dials.fetch_add(1, Ordering::SeqCst);
+      let (client_end, device_end) = duplex(4096);
+      bridge(&dialer_device, device_end);
+      async move { Ok(client_end) }
+    }))
+    .connect()
+    .await
+    .unwrap();
+  let mut reconnected = client.on_reconnect();
+
+  device.drop_connection();
+  tokio::time::timeout(Duration::from_secs(5), reconnected.recv())
+    .await
+    .unwrap()
+    .unwrap();
+
+  assert_eq!(dials.load(Ordering::SeqCst), 2);
+  assert_eq!(device.received_of::<api::HelloRequest>().len(), 2);
+}
+
+#[tokio::test]
+async fn unix_socket_reaches_the_device() {
+  let device = MockDevice::builder().name("node").start().await.unwrap();
+  let dir = std::env::temp_dir().join(format!("esphomeapi-{}", std::process::id()));
+  std::fs::create_dir_all(&dir).unwrap();
+  let path = dir.join("node.sock");
+  let _ = std::fs::remove_file(&path);
+  let listener = UnixListener::bind(&path).unwrap();
+
+  let client = Client::builder("node").transport(UnixSocket(path.clone()));
+  let (client, ()) = tokio::join!(client.connect(), async {
+    let (stream, _) = listener.accept().await.unwrap();
+    bridge(&device, stream);
+  });
+
+  assert_eq!(client.unwrap().server_info().name, "node");
+  std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn dial_failures_name_the_transport_address() {
+  let path = std::env::temp_dir().join("esphomeapi-missing.sock");
+
+  let result = Client::builder("node")
+    .transport(UnixSocket(path.clone()))
+    .connect()
+    .await;
+
+  assert!(matches!(
+    result,
+    Err(Error::Connect { address, .. }) if address == path.display().to_string()
+  ));
+}
+
+#[tokio::test]
+async fn tcp_dials_ipv6_literals() {
+  let listener = TcpListener::bind("[::1]:0").await.unwrap();
+  let port = listener.local_addr().unwrap().port().into();
+
+  for host in ["::1", "[::1]"] {
+    let dialed = Tcp.dial(host, port).await;
+
+    assert!(dialed.is_ok(), "{host}: {:?}", dialed.err());
+  }
+  assert_eq!(Tcp.address("::1", 6053), "[::1]:6053");
+  assert_eq!(Tcp.address("10.0.0.2", 6053), "10.0.0.2:6053");
+}
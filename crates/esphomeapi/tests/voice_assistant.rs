@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use esphomeapi::api;
+use esphomeapi::model::{
+  VoiceAssistantAnnounce, VoiceAssistantAudioData, VoiceAssistantCommandFlag,
+  VoiceAssistantEventType, VoiceAssistantExternalWakeWord, VoiceAssistantMessage,
+  VoiceAssistantResponse, VoiceAssistantSubscriptionFlag, VoiceAssistantTimerEvent,
+  VoiceAssistantTimerEventType,
+};
+use esphomeapi::testing::{frame, MockDevice};
+
+#[tokio::test]
+async fn pipeline_requests_and_audio_arrive_in_order() {
+  let device = MockDevice::builder()
+    .on::<api::SubscribeVoiceAssistantRequest, _>(|_| {
+      let audio = |data: Vec<u8>, end| {
+        frame(&api::VoiceAssistantAudio {
+          data,
+          end,
+          ..Default::default()
+        })
+      };
+      vec![
+        frame(&api::VoiceAssistantRequest {
+          start: true,
+          conversation_id: "abc".to_string(),
+          flags: 3,
+          wake_word_phrase: "okay nabu".to_string(),
+          ..Default::default()
+        }),
+        audio(vec![1, 2], false),
+        audio(vec![], true),
+      ]
+    })
+    .start()
+    .await
+    .unwrap();
+  let client = device.client().await.unwrap();
+
+  let mut messages = client
+    .subscribe_voice_assistant(VoiceAssistantSubscriptionFlag::APIAudio.into())
+    .await
+    .unwrap();
+
+  let request: api::SubscribeVoiceAssistantRequest = device.next_received().await.unwrap();
+  assert!(request.subscribe);
+  assert_eq!(request.flags, 1);
+
+  let VoiceAssistantMessage::Command(command) = messages.recv().await.unwrap() else {
+    panic!("expected a pipeline request first");
+  };
+  assert!(command.start);
+  assert_eq!(command.conversation_id, "abc");
+  assert!(command
+    .flags
+    .contains(VoiceAssistantCommandFlag::UseWakeWord));
+  assert_eq!(command.wake_word_phrase, "okay nabu");
+
+  assert_eq!(
+    messages.recv().await.unwrap(),
+    VoiceAssistantMessage::Audio(VoiceAssistantAudioData {
+      data: vec![1, 2],
+      end: false
+    })
+  );
+  assert!(matches!(
+    messages.recv().await.unwrap(),
+    VoiceAssistantMessage::Audio(VoiceAssistantAudioData { end: true, .. })
+  ));
+}
+
+#[tokio::test]
+async fn pipeline_host_replies_with_response_events_and_audio() {
+  let device = MockDevice::builder().start().await.unwrap();
+  let client = device.client().await.unwrap();
+
+  client
+    .send_voice_assistant_response(VoiceAssistantResponse::Udp { port: 12345 })
+    .await
+    .unwrap();
+  client
+    .send_voice_assistant_response(VoiceAssistantResponse::ApiAudio)
+    .await
+    .unwrap();
+  client
+    .send_voice_assistant_event(
+      VoiceAssistantEventType::STTEnd,
+      HashMap::from([("text".to_string(), "turn on the light".to_string())]),
+    )
+    .await
+    .unwrap();
+  client
+    .send_voice_assistant_audio(VoiceAssistantAudioData {
+      data: vec![9; 4],
+      end: true,
+    })
+    .await
+    .unwrap();
+  client.unsubscribe_voice_assistant().await.unwrap();
+
+  let udp: api::VoiceAssistantResponse = device.next_received().await.unwrap();
+  assert_eq!(udp.port, 12345);
+  assert!(!udp.error);
+  let api_audio: api::VoiceAssistantResponse = device.next_received().await.unwrap();
+  assert_eq!(api_audio.port, 0);
+
+  let event: api::VoiceAssistantEventResponse = device.next_received().await.unwrap();
+  assert_eq!(
+    event.event_type.enum_value(),
+    Ok(api::VoiceAssistantEvent::VOICE_ASSISTANT_STT_END)
+  );
+  assert_eq!(event.data[0].name, "text");
+  assert_eq!(event.data[0].value, "turn on the light");
+
+  let audio: api::VoiceAssistantAudio = device.next_received().await.unwrap();
+  assert_eq!(audio.data, vec![9; 4]);
+  assert!(audio.end);
+
+  let unsubscribe: api::SubscribeVoiceAssistantRequest = device.next_received().await.unwrap();
+  assert!(!unsubscribe.subscribe);
+}
+
+#[tokio::test]
+async fn wake_words_are_read_and_replaced() {
+  let device = MockDevice::builder()
+    .on::<api::VoiceAssistantConfigurationRequest, _>(|_| {
+      vec![frame(&api::VoiceAssistantConfigurationResponse {
+        available_wake_words: vec![api::VoiceAssistantWakeWord {
+          id: "okay_nabu".to_string(),
+          wake_word: "Okay Nabu".to_string(),
+          trained_languages: vec!["en".to_string()],
+          ..Default::default()
+        }],
+        active_wake_words: vec!["okay_nabu".to_string()],
+        max_active_wake_words: 2,
+        ..Default::default()
+      })]
+    })
+    .start()
+    .await
+    .unwrap();
+  let client = device.client().await.unwrap();
+
+  let configuration = client
+    .voice_assistant_get_configuration(vec![VoiceAssistantExternalWakeWord {
+      id: "hey_jarvis".to_string(),
+      wake_word: "Hey Jarvis".to_string(),
+      url: "http://example.com/hey_jarvis.json".to_string(),
+      ..Default::default()
+    }])
+    .await
+    .unwrap();
+  assert_eq!(configuration.available_wake_words[0].id, "okay_nabu");
+  assert_eq!(configuration.active_wake_words, vec!["okay_nabu"]);
+  assert_eq!(configuration.max_active_wake_words, 2);
+
+  let request: api::VoiceAssistantConfigurationRequest = device.next_received().await.unwrap();
+  assert_eq!(request.external_wake_words[0].id, "hey_jarvis");
+
+  client
+    .voice_assistant_set_configuration(vec!["hey_jarvis".to_string()])
+    .await
+    .unwrap();
+  let set: api::VoiceAssistantSetConfiguration = device.next_received().await.unwrap();
+  assert_eq!(set.active_wake_words, vec!["hey_jarvis"]);
+}
+
+#[tokio::test]
+async fn announcement_waits_for_playback_to_finish() {
+  let device = MockDevice::builder()
+    .on::<api::VoiceAssistantAnnounceRequest, _>(|_| {
+      vec![frame(&api::VoiceAssistantAnnounceFinished {
+        success: true,
+        ..Default::default()
+      })]
+    })
+    .start()
+    .await
+    .unwrap();
+  let client = device.client().await.unwrap();
+
+  let finished = client
+    .voice_assistant_announce(
+      VoiceAssistantAnnounce {
+        media_id: "http://example.com/tts.mp3".to_string(),
+        text: "Dinner is ready".to_string(),
+        ..Default::default()
+      },
+      Duration::from_secs(5),
+    )
+    .await
+    .unwrap();
+  assert!(finished.success);
+
+  let request: api::VoiceAssistantAnnounceRequest = device.next_received().await.unwrap();
+  assert_eq!(request.media_id, "http://example.com/tts.mp3");
+  assert_eq!(request.text, "Dinner is ready");
+  assert!(!request.start_conversation);
+}
+
+#[tokio::test]
+async fn timer_events_are_pushed() {
+  let device = MockDevice::builder().start().await.unwrap();
+  let client = device.client().await.unwrap();
+
+  client
+    .send_voice_assistant_timer_event(VoiceAssistantTimerEvent {
+      event_type: VoiceAssistantTimerEventType::TimerStarted,
+      timer_id: "t1".to_string(),
+      name: "pasta".to_string(),
+      total_seconds: 600,
+      seconds_left: 600,
+      is_active: true,
+    })
+    .await
+    .unwrap();
+
+  let event: api::VoiceAssistantTimerEventResponse = device.next_received().await.unwrap();
+  assert_eq!(
+    event.event_type.enum_value(),
+    Ok(api::VoiceAssistantTimerEvent::VOICE_ASSISTANT_TIMER_STARTED)
+  );
+  assert_eq!(event.timer_id, "t1");
+  assert_eq!(event.name, "pasta");
+  assert_eq!(event.seconds_left, 600);
+  assert!(event.is_active);
+}
@@ -0,0 +1,130 @@
+use std::time::Duration;
+
+use esphomeapi::testing::{frame, MockDevice};
+use esphomeapi::{api, zwave};
+use tokio::io::{AsyncReadExt as _, AsyncWriteExt as _};
+use tokio::net::{TcpListener, TcpStream};
+
+const HOME_ID: u32 = 0xC0FF_EE01;
+
+async fn proxy_device() -> MockDevice {
+  MockDevice::builder()
+    .device_info(api::DeviceInfoResponse {
+      name: "zwave-proxy".to_string(),
+      zwave_proxy_feature_flags: 1,
+      zwave_home_id: HOME_ID,
+      ..Default::default()
+    })
+    .on::<api::ZWaveProxyRequest, _>(|request| {
+      let subscribe = request.type_.enum_value()
+        == Ok(api::ZWaveProxyRequestType::ZWAVE_PROXY_REQUEST_TYPE_SUBSCRIBE);
+      if subscribe {
+        // An ACK from the controller
+        vec![frame(&api::ZWaveProxyFrame {
+          data: vec![0x06],
+          ..Default::default()
+        })]
+      } else {
+        vec![]
+      }
+    })
+    .start()
+    .await
+    .unwrap()
+}
+
+fn request_type(request: &api::ZWaveProxyRequest) -> api::ZWaveProxyRequestType {
+  request.type_.enum_value().unwrap()
+}
+
+#[tokio::test]
+async fn proxy_streams_controller_bytes_both_ways() {
+  let device = proxy_device().await;
+  let client = device.client().await.unwrap();
+
+  let mut proxy = client.zwave_proxy().await.unwrap();
+  let subscribe = device
+    .next_received::<api::ZWaveProxyRequest>()
+    .await
+    .unwrap();
+  assert_eq!(
+    request_type(&subscribe),
+    api::ZWaveProxyRequestType::ZWAVE_PROXY_REQUEST_TYPE_SUBSCRIBE
+  );
+  assert_eq!(proxy.home_id(), Some(HOME_ID));
+
+  let mut buf = [0; 1];
+  proxy.read_exact(&mut buf).await.unwrap();
+  assert_eq!(buf, [0x06]);
+
+  proxy
+    .write_all(&[0x01, 0x03, 0x00, 0x15, 0xE9])
+    .await
+    .unwrap();
+  let sent = device
+    .next_received::<api::ZWaveProxyFrame>()
+    .await
+    .unwrap();
+  assert_eq!(sent.data, vec![0x01, 0x03, 0x00, 0x15, 0xE9]);
+
+  drop(proxy);
+  let unsubscribe = device
+    .next_received::<api::ZWaveProxyRequest>()
+    .await
+    .unwrap();
+  assert_eq!(
+    request_type(&unsubscribe),
+    api::ZWaveProxyRequestType::ZWAVE_PROXY_REQUEST_TYPE_UNSUBSCRIBE
+  );
+}
+
+#[tokio::test]
+async fn proxy_reports_home_id_changes() {
+  let device = proxy_device().await;
+  let client = device.client().await.unwrap();
+
+  let proxy = client.zwave_proxy().await.unwrap();
+  let mut home_id = proxy.home_id_receiver();
+
+  device
+    .send(&api::ZWaveProxyRequest {
+      type_: api::ZWaveProxyRequestType::ZWAVE_PROXY_REQUEST_TYPE_HOME_ID_CHANGE.into(),
+      data: 0xDEAD_BEEF_u32.to_be_bytes().to_vec(),
+      ..Default::default()
+    })
+    .unwrap();
+
+  tokio::time::timeout(Duration::from_secs(2), home_id.changed())
+    .await
+    .unwrap()
+    .unwrap();
+  assert_eq!(*home_id.borrow(), Some(0xDEAD_BEEF));
+  assert_eq!(proxy.home_id(), Some(0xDEAD_BEEF));
+}
+
+#[tokio::test]
+async fn serve_bridges_a_tcp_serial_port() {
+  let device = proxy_device().await;
+  let client = device.client().await.unwrap();
+
+  let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let addr = listener.local_addr().unwrap();
+  let server = tokio::spawn(async move { zwave::serve(&client, listener).await });
+
+  let mut serial = TcpStream::connect(addr).await.unwrap();
+  let mut buf = [0; 1];
+  tokio::time::timeout(Duration::from_secs(2), serial.read_exact(&mut buf))
+    .await
+    .unwrap()
+    .unwrap();
+  assert_eq!(buf, [0x06]);
+
+  serial.write_all(&[0x15]).await.unwrap();
+  let sent = device
+    .next_received::<api::ZWaveProxyFrame>()
+    .await
+    .unwrap();
+  assert_eq!(sent.data, vec![0x15]);
+
+  server.abort();
+}